@@ -1,25 +1,261 @@
-use anyhow::Result;
+use crate::OutputFormat;
+use anyhow::{bail, Context, Result};
 use colored::Colorize;
-use std::path::PathBuf;
-
-#[allow(clippy::unused_async)] // Will be async when implemented
-pub async fn run(
-    _config_path: Option<PathBuf>,
-    _project_name: Option<String>,
-    base: String,
-    head: String,
+use graphql_config::{find_config, load_config};
+use graphql_project::{
+    diff_schemas, introspection_to_sdl, ChangeCriticality, GraphQLProject, SchemaChange,
+    SchemaIndex,
+};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+
+/// Compare each project's schema at `base` with the schema at `head` and report the changes
+///
+/// `base` and `head` are each a schema file or a git ref. Exits with an error if any change
+/// is breaking.
+pub fn run(
+    config_path: Option<PathBuf>,
+    project_name: Option<String>,
+    format: OutputFormat,
+    base: &str,
+    head: &str,
 ) -> Result<()> {
-    println!(
-        "{}",
-        format!("Breaking change detection not yet implemented (comparing {base} -> {head})")
-            .yellow()
-    );
-
-    // TODO: Implement breaking change detection
-    // - Load schema from base ref
-    // - Load schema from head ref
-    // - Compare schemas for breaking changes
-    // - Report breaking changes
+    if matches!(format, OutputFormat::Rdjson | OutputFormat::Sarif) {
+        bail!("Schema changes have no location in a document; use --format human or json");
+    }
+
+    // Find config
+    let config_path = if let Some(path) = config_path {
+        path
+    } else {
+        let current_dir = std::env::current_dir()?;
+        find_config(&current_dir)
+            .context("Failed to search for config")?
+            .context("No GraphQL config file found")?
+    };
+
+    let config = load_config(&config_path).context("Failed to load config")?;
+    let base_dir = config_path
+        .parent()
+        .context("Failed to get config directory")?
+        .to_path_buf();
+
+    let projects = GraphQLProject::from_config_with_base(&config, &base_dir)?;
+    let projects_to_check: Vec<_> = if let Some(ref name) = project_name {
+        projects.into_iter().filter(|(n, _)| n == name).collect()
+    } else {
+        projects
+    };
+
+    if projects_to_check.is_empty() {
+        if let Some(name) = project_name {
+            eprintln!("{}", format!("Project '{name}' not found").red());
+            process::exit(1);
+        }
+    }
+
+    let mut total_breaking = 0;
+    let mut json_changes = Vec::new();
+
+    for (name, project) in &projects_to_check {
+        if projects_to_check.len() > 1 && matches!(format, OutputFormat::Human) {
+            println!("\n{}", format!("=== Project: {name} ===").bold().cyan());
+        }
+
+        let old = load_schema_at(project, &base_dir, base)?;
+        let new = load_schema_at(project, &base_dir, head)?;
+        let changes = diff_schemas(&old, &new);
+
+        total_breaking += changes
+            .iter()
+            .filter(|change| change.criticality == ChangeCriticality::Breaking)
+            .count();
+
+        match format {
+            OutputFormat::Human => print_changes(&changes),
+            _ => json_changes.extend(changes.iter().map(|change| {
+                serde_json::json!({
+                    "project": name,
+                    "type": change.kind.as_str(),
+                    "criticality": change.criticality.as_str(),
+                    "path": change.path,
+                    "message": change.message,
+                })
+            })),
+        }
+    }
+
+    let failed = total_breaking > 0;
+
+    if matches!(format, OutputFormat::Human) {
+        println!();
+        if failed {
+            println!(
+                "{}",
+                format!("✗ Found {total_breaking} breaking change(s) ({base} -> {head})").red()
+            );
+        } else {
+            println!(
+                "{}",
+                format!("✓ No breaking changes ({base} -> {head})")
+                    .green()
+                    .bold()
+            );
+        }
+    } else {
+        let count = |criticality: &str| {
+            json_changes
+                .iter()
+                .filter(|change| change["criticality"] == criticality)
+                .count()
+        };
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": if failed { "failed" } else { "ok" },
+                "changes": json_changes,
+                "summary": {
+                    "breaking": count(ChangeCriticality::Breaking.as_str()),
+                    "dangerous": count(ChangeCriticality::Dangerous.as_str()),
+                    "nonBreaking": count(ChangeCriticality::NonBreaking.as_str()),
+                },
+            })
+        );
+    }
+
+    if failed {
+        process::exit(1);
+    }
 
     Ok(())
 }
+
+/// Print changes grouped by criticality, breaking changes first
+fn print_changes(changes: &[SchemaChange]) {
+    if changes.is_empty() {
+        println!("{}", "No schema changes".dimmed());
+        return;
+    }
+
+    for (criticality, heading) in [
+        (ChangeCriticality::Breaking, "Breaking changes".red().bold()),
+        (
+            ChangeCriticality::Dangerous,
+            "Dangerous changes".yellow().bold(),
+        ),
+        (
+            ChangeCriticality::NonBreaking,
+            "Non-breaking changes".green().bold(),
+        ),
+    ] {
+        let group: Vec<_> = changes
+            .iter()
+            .filter(|change| change.criticality == criticality)
+            .collect();
+        if group.is_empty() {
+            continue;
+        }
+
+        println!("\n{heading} ({})", group.len());
+        for change in group {
+            let marker = match criticality {
+                ChangeCriticality::Breaking => "✗".red(),
+                ChangeCriticality::Dangerous => "⚠".yellow(),
+                ChangeCriticality::NonBreaking => "✓".green(),
+            };
+            println!("  {marker} {}", change.message);
+        }
+    }
+}
+
+/// Load a project's schema as it is at `revision`
+///
+/// A `revision` naming a file is read as the whole schema. Anything else is a git ref, and
+/// each of the project's local schema files is read as it was at that ref; files that didn't
+/// exist then are skipped. Remote schemas aren't compared.
+fn load_schema_at(
+    project: &GraphQLProject,
+    base_dir: &Path,
+    revision: &str,
+) -> Result<SchemaIndex> {
+    let revision_path = Path::new(revision);
+
+    let files = if revision_path.is_file() {
+        let contents = std::fs::read_to_string(revision_path)
+            .with_context(|| format!("Failed to read {revision}"))?;
+        vec![(revision.to_string(), schema_sdl(revision_path, contents)?)]
+    } else {
+        if !is_git_ref(base_dir, revision)? {
+            bail!("'{revision}' is neither a schema file nor a git ref");
+        }
+
+        let mut files = Vec::new();
+        for path in project.schema_files()? {
+            if let Some(contents) = git_show(revision, &path)? {
+                files.push((path.display().to_string(), schema_sdl(&path, contents)?));
+            }
+        }
+        files
+    };
+
+    if files.is_empty() {
+        bail!("No schema files found at '{revision}'");
+    }
+
+    SchemaIndex::try_from_schema_files(files)
+        .with_context(|| format!("The schema at '{revision}' has errors"))
+}
+
+/// The SDL of a schema file, converting `.json` introspection results
+fn schema_sdl(path: &Path, contents: String) -> Result<String> {
+    if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+        return Ok(contents);
+    }
+
+    let introspection: serde_json::Value = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    Ok(introspection_to_sdl(&introspection)?)
+}
+
+/// Whether `revision` names a commit in the git repository containing `dir`
+fn is_git_ref(dir: &Path, revision: &str) -> Result<bool> {
+    let status = Command::new("git")
+        .current_dir(non_empty_dir(dir))
+        .args(["rev-parse", "--verify", "--quiet"])
+        .arg(format!("{revision}^{{commit}}"))
+        .output()
+        .context("Failed to run git")?
+        .status;
+    Ok(status.success())
+}
+
+/// Read a file as it was at `revision`, or `None` if it didn't exist then
+fn git_show(revision: &str, path: &Path) -> Result<Option<String>> {
+    let (Some(dir), Some(file_name)) = (path.parent(), path.file_name()) else {
+        return Ok(None);
+    };
+
+    // `./` makes the path relative to the file's directory rather than the repository root
+    let output = Command::new("git")
+        .current_dir(non_empty_dir(dir))
+        .arg("show")
+        .arg(format!("{revision}:./{}", file_name.to_string_lossy()))
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    Ok(Some(String::from_utf8(output.stdout).with_context(
+        || format!("{} isn't valid UTF-8 at {revision}", path.display()),
+    )?))
+}
+
+/// `dir`, or `.` if it's empty, as the parent of a bare file name is
+fn non_empty_dir(dir: &Path) -> &Path {
+    if dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        dir
+    }
+}
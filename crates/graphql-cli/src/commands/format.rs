@@ -0,0 +1,253 @@
+use crate::OutputFormat;
+use anyhow::{Context, Result};
+use colored::Colorize;
+use graphql_config::{find_config, load_config};
+use graphql_project::{format_document, GraphQLProject};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process;
+
+/// What to do with files whose formatting differs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatMode {
+    /// Report the files that would change
+    List,
+    /// Report the files that would change and fail if there are any
+    Check,
+    /// Rewrite the files in place
+    Write,
+}
+
+/// A file whose formatted content differs from what's on disk
+struct ChangedFile {
+    path: PathBuf,
+    added: usize,
+    removed: usize,
+}
+
+#[allow(clippy::too_many_lines)]
+pub fn run(
+    config_path: Option<PathBuf>,
+    project_name: Option<String>,
+    format: OutputFormat,
+    mode: FormatMode,
+    include_schema: bool,
+) -> Result<()> {
+    // Find and load config
+    let config_path = if let Some(path) = config_path {
+        path
+    } else {
+        let current_dir = std::env::current_dir()?;
+        find_config(&current_dir)
+            .context("Failed to search for config")?
+            .context("No GraphQL config file found")?
+    };
+
+    let config = load_config(&config_path).context("Failed to load config")?;
+
+    // Get the base directory from the config path
+    let base_dir = config_path
+        .parent()
+        .context("Failed to get config directory")?
+        .to_path_buf();
+
+    // Get projects with base directory
+    let projects = GraphQLProject::from_config_with_base(&config, &base_dir)?;
+
+    // Filter by project name if specified
+    let projects_to_format: Vec<_> = if let Some(ref name) = project_name {
+        projects.into_iter().filter(|(n, _)| n == name).collect()
+    } else {
+        projects
+    };
+
+    if projects_to_format.is_empty() {
+        if let Some(name) = project_name {
+            eprintln!("{}", format!("Project '{name}' not found").red());
+            process::exit(1);
+        }
+    }
+
+    // A file can belong to several projects; format it once
+    let mut files = BTreeSet::new();
+    for (_, project) in &projects_to_format {
+        files.extend(project.document_files()?);
+        if include_schema {
+            files.extend(project.schema_files()?);
+        }
+    }
+
+    let mut changed = Vec::new();
+    let mut skipped = Vec::new();
+
+    // Embedded GraphQL in TypeScript/JavaScript is left to the host language's formatter
+    for path in files.into_iter().filter(|path| is_graphql_file(path)) {
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+
+        let Some(formatted) = format_document(&content) else {
+            skipped.push(path);
+            continue;
+        };
+
+        if formatted == content {
+            continue;
+        }
+
+        if mode == FormatMode::Write {
+            std::fs::write(&path, &formatted)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+
+        let (added, removed) = line_changes(&content, &formatted);
+        changed.push(ChangedFile {
+            path,
+            added,
+            removed,
+        });
+    }
+
+    match format {
+        OutputFormat::Human => {
+            for file in &skipped {
+                eprintln!(
+                    "{} {} (syntax errors)",
+                    "⚠ Skipped".yellow(),
+                    file.display()
+                );
+            }
+
+            for file in &changed {
+                println!(
+                    "{} {}",
+                    file.path.display(),
+                    format!("+{} -{}", file.added, file.removed).dimmed()
+                );
+            }
+
+            println!();
+            if changed.is_empty() {
+                println!("{}", "✓ All files are formatted".green().bold());
+            } else if mode == FormatMode::Write {
+                println!(
+                    "{}",
+                    format!("✓ Formatted {} file(s)", changed.len()).green()
+                );
+            } else {
+                println!(
+                    "{}",
+                    format!("✗ {} file(s) need formatting", changed.len()).red()
+                );
+            }
+        }
+        OutputFormat::Json => {
+            let changed: Vec<_> = changed
+                .iter()
+                .map(|file| file.path.display().to_string())
+                .collect();
+            let skipped: Vec<_> = skipped
+                .iter()
+                .map(|file| file.display().to_string())
+                .collect();
+            println!(
+                "{}",
+                serde_json::json!({
+                    "changed": changed,
+                    "skipped": skipped,
+                })
+            );
+        }
+        OutputFormat::Rdjson | OutputFormat::Sarif => {
+            let to_diagnostic = if matches!(format, OutputFormat::Sarif) {
+                super::sarif_result_entry
+            } else {
+                super::rdjson_diagnostic
+            };
+            let severity = if mode == FormatMode::Check {
+                "error"
+            } else {
+                "warning"
+            };
+            // Files rewritten in place are already fixed, so only the rest are reported
+            let diagnostics = changed
+                .iter()
+                .filter(|_| mode != FormatMode::Write)
+                .map(|file| {
+                    to_diagnostic(
+                        &file.path.display().to_string(),
+                        None,
+                        "File is not formatted",
+                        severity,
+                        None,
+                    )
+                })
+                .chain(skipped.iter().map(|file| {
+                    to_diagnostic(
+                        &file.display().to_string(),
+                        None,
+                        "File was not formatted because it has syntax errors",
+                        "warning",
+                        None,
+                    )
+                }))
+                .collect();
+            let result = if matches!(format, OutputFormat::Sarif) {
+                super::sarif_result(diagnostics)
+            } else {
+                super::rdjson_result(diagnostics)
+            };
+            println!("{result}");
+        }
+    }
+
+    if mode == FormatMode::Check && !changed.is_empty() {
+        process::exit(1);
+    }
+
+    Ok(())
+}
+
+fn is_graphql_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| matches!(ext, "graphql" | "gql" | "graphqls"))
+}
+
+/// Count the lines added and removed between two versions of a file
+///
+/// Lines are matched with a longest common subsequence, like a unified diff.
+fn line_changes(before: &str, after: &str) -> (usize, usize) {
+    let before: Vec<&str> = before.lines().collect();
+    let after: Vec<&str> = after.lines().collect();
+
+    // lengths[j] is the LCS length of the lines seen so far and `after[..j]`
+    let mut lengths = vec![0; after.len() + 1];
+    for before_line in &before {
+        let mut diagonal = 0;
+        for (j, after_line) in after.iter().enumerate() {
+            let above = lengths[j + 1];
+            lengths[j + 1] = if before_line == after_line {
+                diagonal + 1
+            } else {
+                above.max(lengths[j])
+            };
+            diagonal = above;
+        }
+    }
+
+    let common = lengths[after.len()];
+    (after.len() - common, before.len() - common)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_changes() {
+        assert_eq!(line_changes("a\nb\nc", "a\nb\nc"), (0, 0));
+        assert_eq!(line_changes("a\nb\nc", "a\nc"), (0, 1));
+        assert_eq!(line_changes("a { b }", "a {\n  b\n}"), (3, 1));
+        assert_eq!(line_changes("a\nb\nc", "a\nx\nc\nd"), (2, 1));
+    }
+}
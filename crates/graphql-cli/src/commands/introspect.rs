@@ -0,0 +1,84 @@
+use crate::SchemaFormat;
+use anyhow::{Context, Result};
+use colored::Colorize;
+use graphql_config::SchemaConfig;
+use graphql_project::{introspection_to_sdl, RemoteSchemaConfig, SchemaLoader};
+use std::path::Path;
+
+/// Parse a `--header "NAME: VALUE"` entry
+pub fn parse_header(value: &str) -> std::result::Result<(String, String), String> {
+    let Some((name, header_value)) = value.split_once(':') else {
+        return Err(format!("expected NAME: VALUE, got '{value}'"));
+    };
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(format!("missing header name in '{value}'"));
+    }
+    Ok((name.to_string(), header_value.trim().to_string()))
+}
+
+/// Introspect the schema at `url` and write it to `output`, or stdout
+///
+/// The request is sent the way remote schemas in a config are loaded, so headers and
+/// timeouts behave the same.
+pub async fn run(
+    url: &str,
+    headers: Vec<(String, String)>,
+    output: Option<&Path>,
+    format: SchemaFormat,
+    timeout: u64,
+) -> Result<()> {
+    let loader = SchemaLoader::new(SchemaConfig::Path(url.to_string())).with_remote_config(
+        RemoteSchemaConfig {
+            headers: headers.into_iter().collect(),
+            timeout,
+        },
+    );
+
+    let response = loader
+        .fetch_introspection(url)
+        .await
+        .with_context(|| format!("Failed to introspect {url}"))?;
+
+    let mut schema = match format {
+        SchemaFormat::Sdl => introspection_to_sdl(&response)
+            .context("The response isn't a valid introspection result")?,
+        SchemaFormat::Json => serde_json::to_string_pretty(&response)?,
+    };
+    if !schema.ends_with('\n') {
+        schema.push('\n');
+    }
+
+    let Some(output) = output else {
+        print!("{schema}");
+        return Ok(());
+    };
+
+    std::fs::write(output, schema)
+        .with_context(|| format!("Failed to write {}", output.display()))?;
+    println!(
+        "{}",
+        format!("✓ Wrote schema from {url} to {}", output.display()).green()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_header() {
+        assert_eq!(
+            parse_header("Authorization: Bearer abc:123"),
+            Ok(("Authorization".to_string(), "Bearer abc:123".to_string()))
+        );
+        assert_eq!(
+            parse_header("X-Empty:"),
+            Ok(("X-Empty".to_string(), String::new()))
+        );
+        assert!(parse_header("Authorization").is_err());
+        assert!(parse_header(": value").is_err());
+    }
+}
@@ -2,29 +2,170 @@ use crate::OutputFormat;
 use anyhow::{Context, Result};
 use colored::Colorize;
 use graphql_config::{find_config, load_config};
-use graphql_project::{GraphQLProject, Linter, Severity};
+use graphql_project::{GraphQLProject, LintSeverity, Linter, Severity};
 use std::path::PathBuf;
 use std::process;
 
+/// Rule name used when grouping diagnostics that have no code
+const NO_RULE: &str = "(no rule)";
+
+/// Diagnostic output structure for collecting warnings and errors
+struct DiagnosticOutput {
+    file_path: String,
+    line: usize,
+    column: usize,
+    end_line: usize,
+    end_column: usize,
+    message: String,
+    severity: String,
+    rule: Option<String>,
+}
+
+impl DiagnosticOutput {
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "file": self.file_path,
+            "severity": self.severity,
+            "rule": self.rule,
+            "message": self.message,
+            "location": {
+                "start": {
+                    "line": self.line,
+                    "column": self.column
+                },
+                "end": {
+                    "line": self.end_line,
+                    "column": self.end_column
+                }
+            }
+        })
+    }
+
+    /// Key that orders diagnostics by file, then position, then rule
+    fn sort_key(&self) -> (&str, usize, usize, Option<&str>) {
+        (
+            &self.file_path,
+            self.line,
+            self.column,
+            self.rule.as_deref(),
+        )
+    }
+
+    fn to_rdjson(&self) -> serde_json::Value {
+        super::rdjson_diagnostic(
+            &self.file_path,
+            Some(((self.line, self.column), (self.end_line, self.end_column))),
+            &self.message,
+            &self.severity,
+            self.rule.as_deref(),
+        )
+    }
+
+    fn to_sarif(&self) -> serde_json::Value {
+        super::sarif_result_entry(
+            &self.file_path,
+            Some(((self.line, self.column), (self.end_line, self.end_column))),
+            &self.message,
+            &self.severity,
+            self.rule.as_deref(),
+        )
+    }
+}
+
+/// Sort diagnostics so output is stable across runs
+///
+/// Files are read in no particular order, so without sorting the order diagnostics are
+/// printed in can change from one run to the next.
+fn sort_diagnostics(diagnostics: &mut [DiagnosticOutput]) {
+    diagnostics.sort_by(|a, b| a.sort_key().cmp(&b.sort_key()));
+}
+
+/// Group diagnostics by rule name
+///
+/// Groups are ordered by descending diagnostic count, then by rule name, so the rules
+/// that fire most come first.
+fn group_by_rule<'a>(
+    diagnostics: impl IntoIterator<Item = &'a DiagnosticOutput>,
+) -> Vec<(String, Vec<&'a DiagnosticOutput>)> {
+    let mut groups: std::collections::BTreeMap<String, Vec<&DiagnosticOutput>> =
+        std::collections::BTreeMap::new();
+    for diag in diagnostics {
+        let rule = diag.rule.clone().unwrap_or_else(|| NO_RULE.to_string());
+        groups.entry(rule).or_default().push(diag);
+    }
+
+    let mut groups: Vec<_> = groups.into_iter().collect();
+    // Stable sort keeps the alphabetical order from the BTreeMap for equal counts
+    groups.sort_by(|(_, a), (_, b)| b.len().cmp(&a.len()));
+    groups
+}
+
+/// Print diagnostics grouped by rule, with per-rule counts
+fn print_grouped_by_rule(all_warnings: &[DiagnosticOutput], all_errors: &[DiagnosticOutput]) {
+    for (rule, diagnostics) in group_by_rule(all_errors.iter().chain(all_warnings)) {
+        println!(
+            "\n{} {}",
+            rule.bold(),
+            format!("({})", diagnostics.len()).dimmed()
+        );
+        for diag in diagnostics {
+            let label = if diag.severity == "error" {
+                "error:".red().bold()
+            } else {
+                "warning:".yellow().bold()
+            };
+            println!(
+                "  {}:{}:{}: {} {}",
+                diag.file_path, diag.line, diag.column, label, diag.message
+            );
+        }
+    }
+}
+
+/// Diagnostics grouped by rule as JSON, with per-rule counts
+fn rules_json(diagnostics: &[DiagnosticOutput]) -> Vec<serde_json::Value> {
+    group_by_rule(diagnostics)
+        .into_iter()
+        .map(|(rule, diagnostics)| {
+            let errors = diagnostics
+                .iter()
+                .filter(|diag| diag.severity == "error")
+                .count();
+            serde_json::json!({
+                "rule": rule,
+                "count": diagnostics.len(),
+                "errors": errors,
+                "warnings": diagnostics.len() - errors,
+                "diagnostics": diagnostics
+                    .iter()
+                    .map(|diag| diag.to_json())
+                    .collect::<Vec<_>>(),
+            })
+        })
+        .collect()
+}
+
+/// Parse a `--rule NAME=SEVERITY` override
+pub fn parse_rule_override(value: &str) -> std::result::Result<(String, LintSeverity), String> {
+    let Some((rule, severity)) = value.split_once('=') else {
+        return Err(format!("expected NAME=SEVERITY, got '{value}'"));
+    };
+    if rule.is_empty() {
+        return Err(format!("missing rule name in '{value}'"));
+    }
+    Ok((rule.to_string(), severity.parse()?))
+}
+
 #[allow(clippy::too_many_lines)]
 pub async fn run(
     config_path: Option<PathBuf>,
     project_name: Option<String>,
     format: OutputFormat,
+    group_by_rule: bool,
     _watch: bool,
+    fail_on_unused: bool,
+    rule_overrides: &[(String, LintSeverity)],
 ) -> Result<()> {
-    // Define diagnostic output structure for collecting warnings and errors
-    struct DiagnosticOutput {
-        file_path: String,
-        line: usize,
-        column: usize,
-        end_line: usize,
-        end_column: usize,
-        message: String,
-        severity: String,
-        rule: Option<String>,
-    }
-
     // Find and load config
     let config_path = if let Some(path) = config_path {
         path
@@ -62,9 +203,14 @@ pub async fn run(
 
     let mut total_errors = 0;
     let mut total_warnings = 0;
+    let mut total_unused = 0;
+    // JSON, RDJSON, and SARIF are single documents, so diagnostics from every project are
+    // printed together
+    let mut json_diagnostics = Vec::new();
+    let mut report_diagnostics = Vec::new();
 
     for (name, project) in &projects_to_lint {
-        if projects_to_lint.len() > 1 {
+        if projects_to_lint.len() > 1 && matches!(format, OutputFormat::Human) {
             println!("\n{}", format!("=== Project: {name} ===").bold().cyan());
         }
 
@@ -110,9 +256,12 @@ pub async fn run(
             }
         }
 
-        // Get lint config and create linter
-        let lint_config = project.get_lint_config();
-        let linter = Linter::new(lint_config);
+        // Get lint config, with the command line's overrides, and create linter
+        let mut lint_config = project.get_lint_config();
+        for (rule, severity) in rule_overrides {
+            lint_config.set_severity(rule, *severity);
+        }
+        let linter = Linter::new(lint_config.clone());
 
         // Get extract config
         let extract_config = project.get_extract_config();
@@ -209,10 +358,44 @@ pub async fn run(
                     }
                 }
             }
+
+            // Unused fragments are reported by validation rather than a lint rule, so
+            // they are only included when they can fail the run
+            if fail_on_unused {
+                let diagnostics = project.validate_extracted_documents(&extracted, file_path);
+                for diag in diagnostics {
+                    if !super::is_unused_code(diag.code.as_deref()) {
+                        continue;
+                    }
+
+                    let diag_output = DiagnosticOutput {
+                        file_path: file_path.clone(),
+                        // Positions are already relative to the file; convert to 1-based
+                        line: diag.range.start.line + 1,
+                        column: diag.range.start.character + 1,
+                        end_line: diag.range.end.line + 1,
+                        end_column: diag.range.end.character + 1,
+                        message: diag.message,
+                        severity: match diag.severity {
+                            Severity::Error => "error".to_string(),
+                            Severity::Warning => "warning".to_string(),
+                            Severity::Information => "info".to_string(),
+                            Severity::Hint => "hint".to_string(),
+                        },
+                        rule: diag.code.clone(),
+                    };
+
+                    if diag.severity == Severity::Error {
+                        all_errors.push(diag_output);
+                    } else {
+                        all_warnings.push(diag_output);
+                    }
+                }
+            }
         }
 
         // Run project-wide lint rules (e.g., unused_fields, unique_names)
-        let project_diagnostics = project.lint_project();
+        let project_diagnostics = project.lint_project_with_config(lint_config);
         for diag in project_diagnostics {
             // Extract file path from diagnostic source field (format: "graphql-linter:path")
             let file_path = if diag.source.starts_with("graphql-linter:") {
@@ -251,9 +434,23 @@ pub async fn run(
             }
         }
 
+        sort_diagnostics(&mut all_warnings);
+        sort_diagnostics(&mut all_errors);
+
         // Display results
-        total_warnings = all_warnings.len();
-        total_errors = all_errors.len();
+        total_warnings += all_warnings.len();
+        total_errors += all_errors.len();
+        if fail_on_unused {
+            total_unused += all_warnings
+                .iter()
+                .filter(|diag| super::is_unused_code(diag.rule.as_deref()))
+                .count();
+        }
+
+        if group_by_rule && matches!(format, OutputFormat::Human) {
+            print_grouped_by_rule(&all_warnings, &all_errors);
+            continue;
+        }
 
         match format {
             OutputFormat::Human => {
@@ -288,52 +485,49 @@ pub async fn run(
                 }
             }
             OutputFormat::Json => {
-                // Print all diagnostics as JSON
-                for warning in &all_warnings {
-                    println!(
-                        "{}",
-                        serde_json::json!({
-                            "file": warning.file_path,
-                            "severity": warning.severity,
-                            "rule": warning.rule,
-                            "message": warning.message,
-                            "location": {
-                                "start": {
-                                    "line": warning.line,
-                                    "column": warning.column
-                                },
-                                "end": {
-                                    "line": warning.end_line,
-                                    "column": warning.end_column
-                                }
-                            }
-                        })
-                    );
-                }
+                json_diagnostics.extend(all_warnings);
+                json_diagnostics.extend(all_errors);
+            }
+            OutputFormat::Rdjson => {
+                report_diagnostics.extend(
+                    all_errors
+                        .iter()
+                        .chain(&all_warnings)
+                        .map(DiagnosticOutput::to_rdjson),
+                );
+            }
+            OutputFormat::Sarif => {
+                report_diagnostics.extend(
+                    all_errors
+                        .iter()
+                        .chain(&all_warnings)
+                        .map(DiagnosticOutput::to_sarif),
+                );
+            }
+        }
+    }
 
-                for error in &all_errors {
-                    println!(
-                        "{}",
-                        serde_json::json!({
-                            "file": error.file_path,
-                            "severity": error.severity,
-                            "rule": error.rule,
-                            "message": error.message,
-                            "location": {
-                                "start": {
-                                    "line": error.line,
-                                    "column": error.column
-                                },
-                                "end": {
-                                    "line": error.end_line,
-                                    "column": error.end_column
-                                }
-                            }
-                        })
-                    );
-                }
+    let failed = total_errors > 0 || total_unused > 0;
+
+    match format {
+        OutputFormat::Json => {
+            let mut result = super::json_result(
+                json_diagnostics
+                    .iter()
+                    .map(DiagnosticOutput::to_json)
+                    .collect(),
+                total_errors,
+                total_warnings,
+                failed,
+            );
+            if group_by_rule {
+                result["rules"] = serde_json::json!(rules_json(&json_diagnostics));
             }
+            println!("{result}");
         }
+        OutputFormat::Rdjson => println!("{}", super::rdjson_result(report_diagnostics)),
+        OutputFormat::Sarif => println!("{}", super::sarif_result(report_diagnostics)),
+        OutputFormat::Human => {}
     }
 
     // Summary
@@ -356,11 +550,120 @@ pub async fn run(
                 format!("✗ Found {total_errors} error(s) and {total_warnings} warning(s)").red()
             );
         }
+        if total_unused > 0 {
+            println!(
+                "{}",
+                format!("✗ Found {total_unused} unused fragment(s) or field(s) (--fail-on-unused)")
+                    .red()
+            );
+        }
     }
 
-    if total_errors > 0 {
+    if failed {
         process::exit(1);
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnostic(file_path: &str, severity: &str, rule: Option<&str>) -> DiagnosticOutput {
+        DiagnosticOutput {
+            file_path: file_path.to_string(),
+            line: 1,
+            column: 1,
+            end_line: 1,
+            end_column: 2,
+            message: "message".to_string(),
+            severity: severity.to_string(),
+            rule: rule.map(ToString::to_string),
+        }
+    }
+
+    #[test]
+    fn test_sort_diagnostics_orders_by_file_position_and_rule() {
+        let at =
+            |file_path: &str, line: usize, column: usize, rule: Option<&str>| DiagnosticOutput {
+                line,
+                column,
+                ..diagnostic(file_path, "warning", rule)
+            };
+        let mut diagnostics = vec![
+            at("b.graphql", 1, 1, Some("deprecated_field")),
+            at("a.graphql", 3, 5, Some("unused_fields")),
+            at("a.graphql", 3, 5, Some("deprecated_field")),
+            at("a.graphql", 1, 9, None),
+            at("a.graphql", 2, 1, Some("unique_names")),
+            at("a.graphql", 1, 2, Some("unique_names")),
+        ];
+
+        sort_diagnostics(&mut diagnostics);
+        let order: Vec<_> = diagnostics.iter().map(DiagnosticOutput::sort_key).collect();
+
+        assert_eq!(
+            order,
+            vec![
+                ("a.graphql", 1, 2, Some("unique_names")),
+                ("a.graphql", 1, 9, None),
+                ("a.graphql", 2, 1, Some("unique_names")),
+                ("a.graphql", 3, 5, Some("deprecated_field")),
+                ("a.graphql", 3, 5, Some("unused_fields")),
+                ("b.graphql", 1, 1, Some("deprecated_field")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_rule_override() {
+        assert_eq!(
+            parse_rule_override("max-depth=error"),
+            Ok(("max-depth".to_string(), LintSeverity::Error))
+        );
+        assert_eq!(
+            parse_rule_override("deprecated_field=off"),
+            Ok(("deprecated_field".to_string(), LintSeverity::Off))
+        );
+        assert!(parse_rule_override("max-depth").is_err());
+        assert!(parse_rule_override("=warn").is_err());
+        assert!(parse_rule_override("max-depth=fatal").is_err());
+    }
+
+    #[test]
+    fn test_group_by_rule_counts_and_orders_by_frequency() {
+        let diagnostics = vec![
+            diagnostic("a.graphql", "warning", Some("deprecated_field")),
+            diagnostic("a.graphql", "error", Some("unique_names")),
+            diagnostic("b.graphql", "warning", Some("deprecated_field")),
+            diagnostic("b.graphql", "warning", Some("unused_fields")),
+            diagnostic("c.graphql", "warning", Some("deprecated_field")),
+            diagnostic("c.graphql", "error", Some("unique_names")),
+            diagnostic("c.graphql", "warning", None),
+        ];
+
+        let groups = group_by_rule(&diagnostics);
+        let summary: Vec<(&str, usize)> = groups
+            .iter()
+            .map(|(rule, diags)| (rule.as_str(), diags.len()))
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("deprecated_field", 3),
+                ("unique_names", 2),
+                (NO_RULE, 1),
+                ("unused_fields", 1),
+            ]
+        );
+
+        let files: Vec<&str> = groups[0]
+            .1
+            .iter()
+            .map(|diag| diag.file_path.as_str())
+            .collect();
+        assert_eq!(files, vec!["a.graphql", "b.graphql", "c.graphql"]);
+    }
+}
@@ -1,3 +1,310 @@
 pub mod check;
+pub mod format;
+pub mod introspect;
 pub mod lint;
 pub mod validate;
+
+/// Codes of the diagnostics `--fail-on-unused` turns into failures
+const UNUSED_CODES: &[&str] = &["unused-fragment", "unused_field"];
+
+/// Whether a diagnostic reports an unused fragment or an unused schema field
+fn is_unused_code(code: Option<&str>) -> bool {
+    code.is_some_and(|code| UNUSED_CODES.contains(&code))
+}
+
+/// Whether a diagnostic should fail the run
+///
+/// Errors always fail. With `--fail-on-unused`, unused fragments and fields fail too, even
+/// when they are configured as warnings.
+fn is_failure(diag: &graphql_project::Diagnostic, fail_on_unused: bool) -> bool {
+    diag.severity == graphql_project::Severity::Error
+        || (fail_on_unused && is_unused_code(diag.code.as_deref()))
+}
+
+/// Build the result document printed by `--format json`
+///
+/// The document is printed even when there are no diagnostics, with `status: "ok"`, so
+/// tooling can tell a clean run from one that failed before printing anything. `failed`
+/// is whether the run exits with an error.
+fn json_result(
+    diagnostics: Vec<serde_json::Value>,
+    errors: usize,
+    warnings: usize,
+    failed: bool,
+) -> serde_json::Value {
+    serde_json::json!({
+        "status": if failed { "failed" } else { "ok" },
+        "diagnostics": diagnostics,
+        "summary": {
+            "errors": errors,
+            "warnings": warnings,
+        },
+    })
+}
+
+/// Name reviewdog shows as the tool that reported a diagnostic
+const RDJSON_SOURCE: &str = "graphql";
+
+/// Build one diagnostic in reviewdog's RDJSON format
+///
+/// `range` is the 1-based start and end `(line, column)`; without it the diagnostic is
+/// attached to the whole file. `severity` is the name used in JSON output (`error`,
+/// `warning`, `info`, or `hint`).
+fn rdjson_diagnostic(
+    file_path: &str,
+    range: Option<((usize, usize), (usize, usize))>,
+    message: &str,
+    severity: &str,
+    code: Option<&str>,
+) -> serde_json::Value {
+    let mut location = serde_json::json!({ "path": file_path });
+    if let Some(((line, column), (end_line, end_column))) = range {
+        location["range"] = serde_json::json!({
+            "start": { "line": line, "column": column },
+            "end": { "line": end_line, "column": end_column },
+        });
+    }
+
+    let mut diagnostic = serde_json::json!({
+        "message": message,
+        "location": location,
+        "severity": match severity {
+            "error" => "ERROR",
+            "warning" => "WARNING",
+            _ => "INFO",
+        },
+        "source": { "name": RDJSON_SOURCE },
+    });
+    if let Some(code) = code {
+        diagnostic["code"] = serde_json::json!({ "value": code });
+    }
+    diagnostic
+}
+
+/// Wrap RDJSON diagnostics in the result document reviewdog reads
+fn rdjson_result(diagnostics: Vec<serde_json::Value>) -> serde_json::Value {
+    serde_json::json!({
+        "source": { "name": RDJSON_SOURCE },
+        "diagnostics": diagnostics,
+    })
+}
+
+/// SARIF version the `--format sarif` report follows
+const SARIF_VERSION: &str = "2.1.0";
+
+/// JSON schema of the SARIF version the report follows
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Build one result of a SARIF report, as read by GitHub code scanning
+///
+/// Takes the same arguments as [`rdjson_diagnostic`]. The diagnostic code becomes the
+/// result's `ruleId`, and relative paths are kept relative so code scanning resolves them
+/// against the repository root.
+fn sarif_result_entry(
+    file_path: &str,
+    range: Option<((usize, usize), (usize, usize))>,
+    message: &str,
+    severity: &str,
+    code: Option<&str>,
+) -> serde_json::Value {
+    let uri = if file_path.starts_with('/') {
+        format!("file://{file_path}")
+    } else {
+        file_path.to_string()
+    };
+    let mut physical_location = serde_json::json!({
+        "artifactLocation": { "uri": uri },
+    });
+    if let Some(((line, column), (end_line, end_column))) = range {
+        physical_location["region"] = serde_json::json!({
+            "startLine": line,
+            "startColumn": column,
+            "endLine": end_line,
+            "endColumn": end_column,
+        });
+    }
+
+    let mut result = serde_json::json!({
+        "level": match severity {
+            "error" => "error",
+            "warning" => "warning",
+            _ => "note",
+        },
+        "message": { "text": message },
+        "locations": [{ "physicalLocation": physical_location }],
+    });
+    if let Some(code) = code {
+        result["ruleId"] = serde_json::json!(code);
+    }
+    result
+}
+
+/// Wrap SARIF results in a report with a single run
+///
+/// Every rule id the results use is listed on the tool, so viewers can group results by
+/// rule.
+fn sarif_result(results: Vec<serde_json::Value>) -> serde_json::Value {
+    let rules: std::collections::BTreeSet<&str> = results
+        .iter()
+        .filter_map(|result| result["ruleId"].as_str())
+        .collect();
+    let rules: Vec<_> = rules
+        .into_iter()
+        .map(|rule| serde_json::json!({ "id": rule }))
+        .collect();
+
+    serde_json::json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": RDJSON_SOURCE,
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rdjson_diagnostic_shape() {
+        let diagnostic = rdjson_diagnostic(
+            "src/queries.graphql",
+            Some(((3, 5), (3, 12))),
+            "Cannot query field \"fulName\" on type \"User\"",
+            "error",
+            Some("unknown-field"),
+        );
+
+        assert_eq!(
+            rdjson_result(vec![diagnostic]),
+            serde_json::json!({
+                "source": { "name": "graphql" },
+                "diagnostics": [{
+                    "message": "Cannot query field \"fulName\" on type \"User\"",
+                    "location": {
+                        "path": "src/queries.graphql",
+                        "range": {
+                            "start": { "line": 3, "column": 5 },
+                            "end": { "line": 3, "column": 12 },
+                        },
+                    },
+                    "severity": "ERROR",
+                    "source": { "name": "graphql" },
+                    "code": { "value": "unknown-field" },
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn test_sarif_result_shape() {
+        let result = sarif_result_entry(
+            "src/queries.graphql",
+            Some(((3, 5), (3, 12))),
+            "Cannot query field \"fulName\" on type \"User\"",
+            "error",
+            Some("unknown-field"),
+        );
+        let unlocated = sarif_result_entry(
+            "/abs/schema.graphql",
+            None,
+            "Type 'Query' has no description",
+            "info",
+            None,
+        );
+
+        assert_eq!(
+            sarif_result(vec![result, unlocated]),
+            serde_json::json!({
+                "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+                "version": "2.1.0",
+                "runs": [{
+                    "tool": {
+                        "driver": {
+                            "name": "graphql",
+                            "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                            "rules": [{ "id": "unknown-field" }],
+                        },
+                    },
+                    "results": [
+                        {
+                            "ruleId": "unknown-field",
+                            "level": "error",
+                            "message": { "text": "Cannot query field \"fulName\" on type \"User\"" },
+                            "locations": [{
+                                "physicalLocation": {
+                                    "artifactLocation": { "uri": "src/queries.graphql" },
+                                    "region": {
+                                        "startLine": 3,
+                                        "startColumn": 5,
+                                        "endLine": 3,
+                                        "endColumn": 12,
+                                    },
+                                },
+                            }],
+                        },
+                        {
+                            "level": "note",
+                            "message": { "text": "Type 'Query' has no description" },
+                            "locations": [{
+                                "physicalLocation": {
+                                    "artifactLocation": { "uri": "file:///abs/schema.graphql" },
+                                },
+                            }],
+                        },
+                    ],
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn test_json_result_for_clean_run() {
+        assert_eq!(
+            json_result(Vec::new(), 0, 0, false),
+            serde_json::json!({
+                "status": "ok",
+                "diagnostics": [],
+                "summary": { "errors": 0, "warnings": 0 },
+            })
+        );
+    }
+
+    use graphql_config::{ProjectConfig, SchemaConfig};
+    use graphql_project::GraphQLProject;
+
+    #[test]
+    fn test_unused_fragment_fails_only_with_flag() {
+        let project = GraphQLProject::new(ProjectConfig {
+            schema: SchemaConfig::Path("schema.graphql".to_string()),
+            documents: None,
+            include: None,
+            exclude: None,
+            extensions: None,
+        });
+
+        let source = "query GetUser { user { id } }\n\nfragment UserFields on User { name }\n";
+        project
+            .update_document_index("/virtual/queries.graphql", source)
+            .unwrap();
+        let diagnostics = project.validate_document_source(source, "/virtual/queries.graphql");
+
+        let unused: Vec<_> = diagnostics
+            .iter()
+            .filter(|diag| is_unused_code(diag.code.as_deref()))
+            .collect();
+        assert_eq!(unused.len(), 1);
+        assert!(unused[0].message.contains("UserFields"));
+
+        assert!(!is_failure(unused[0], false));
+        assert!(is_failure(unused[0], true));
+    }
+}
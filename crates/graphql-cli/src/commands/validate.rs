@@ -2,31 +2,23 @@ use crate::OutputFormat;
 use anyhow::{Context, Result};
 use colored::Colorize;
 use graphql_config::{find_config, load_config};
-use graphql_project::GraphQLProject;
-use std::path::PathBuf;
+use graphql_project::{GraphQLProject, Severity};
+use std::path::{Path, PathBuf};
 use std::process;
+use tokio::sync::mpsc::UnboundedReceiver;
+
+/// How long to wait after a change before re-validating, so a burst of changes (like a
+/// save through a temporary file) triggers a single run
+const WATCH_DEBOUNCE_MS: u64 = 200;
 
-#[allow(clippy::too_many_lines)]
 pub async fn run(
     config_path: Option<PathBuf>,
     project_name: Option<String>,
     format: OutputFormat,
     watch: bool,
+    fail_on_unused: bool,
 ) -> Result<()> {
-    // Define diagnostic output structure for collecting errors
-    struct DiagnosticOutput {
-        file_path: String,
-        line: usize,
-        column: usize,
-        message: String,
-    }
-
-    if watch {
-        println!("{}", "Watch mode not yet implemented".yellow());
-        return Ok(());
-    }
-
-    // Find and load config
+    // Find config
     let config_path = if let Some(path) = config_path {
         path
     } else {
@@ -36,7 +28,128 @@ pub async fn run(
             .context("No GraphQL config file found")?
     };
 
-    let config = load_config(&config_path).context("Failed to load config")?;
+    if watch {
+        return watch_and_validate(
+            &config_path,
+            project_name.as_deref(),
+            format,
+            fail_on_unused,
+        )
+        .await;
+    }
+
+    if validate(
+        &config_path,
+        project_name.as_deref(),
+        format,
+        fail_on_unused,
+    )
+    .await?
+    {
+        process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Validate once, then again whenever the config or a file it covers changes
+///
+/// The config is re-read on every run, so edits to it take effect without a restart.
+async fn watch_and_validate(
+    config_path: &Path,
+    project_name: Option<&str>,
+    format: OutputFormat,
+    fail_on_unused: bool,
+) -> Result<()> {
+    let base_dir = config_path
+        .parent()
+        .context("Failed to get config directory")?
+        .to_path_buf();
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if let Ok(event) = event {
+            let _ = tx.send(event);
+        }
+    })
+    .context("Failed to start file watcher")?;
+    notify::Watcher::watch(&mut watcher, &base_dir, notify::RecursiveMode::Recursive)
+        .context("Failed to watch config directory")?;
+
+    loop {
+        if let Err(e) = validate(config_path, project_name, format, fail_on_unused).await {
+            eprintln!("{} {:#}", "✗".red(), e);
+        }
+
+        if matches!(format, OutputFormat::Human) {
+            println!("\n{}", "Watching for changes...".dimmed());
+        }
+        if !wait_for_change(&mut rx, config_path, &base_dir).await {
+            return Ok(());
+        }
+    }
+}
+
+/// Wait until the config, or a schema or document file of one of its projects, changes
+///
+/// Returns `false` if the watcher stopped.
+async fn wait_for_change(
+    rx: &mut UnboundedReceiver<notify::Event>,
+    config_path: &Path,
+    base_dir: &Path,
+) -> bool {
+    // Read from the config as it is now, which may not be valid
+    let projects = load_config(config_path)
+        .ok()
+        .and_then(|config| GraphQLProject::from_config_with_base(&config, base_dir).ok())
+        .unwrap_or_default();
+    let is_relevant = |event: &notify::Event| {
+        !event.kind.is_access()
+            && event.paths.iter().any(|path| {
+                path == config_path
+                    || projects.iter().any(|(_, project)| {
+                        project.is_schema_file(path) || project.is_document_file(path)
+                    })
+            })
+    };
+
+    loop {
+        let Some(event) = rx.recv().await else {
+            return false;
+        };
+        if is_relevant(&event) {
+            break;
+        }
+    }
+
+    tokio::time::sleep(std::time::Duration::from_millis(WATCH_DEBOUNCE_MS)).await;
+    while rx.try_recv().is_ok() {}
+    true
+}
+
+/// Load the config and validate its projects, printing the results
+///
+/// Returns whether validation failed.
+#[allow(clippy::too_many_lines)]
+async fn validate(
+    config_path: &Path,
+    project_name: Option<&str>,
+    format: OutputFormat,
+    fail_on_unused: bool,
+) -> Result<bool> {
+    // Define diagnostic output structure for collecting errors
+    struct DiagnosticOutput {
+        file_path: String,
+        line: usize,
+        column: usize,
+        end_line: usize,
+        end_column: usize,
+        message: String,
+        severity: Severity,
+        code: Option<String>,
+    }
+
+    let config = load_config(config_path).context("Failed to load config")?;
 
     // Get the base directory from the config path
     let base_dir = config_path
@@ -48,7 +161,7 @@ pub async fn run(
     let projects = GraphQLProject::from_config_with_base(&config, &base_dir)?;
 
     // Filter by project name if specified
-    let projects_to_validate: Vec<_> = if let Some(ref name) = project_name {
+    let projects_to_validate: Vec<_> = if let Some(name) = project_name {
         projects.into_iter().filter(|(n, _)| n == name).collect()
     } else {
         projects
@@ -57,14 +170,19 @@ pub async fn run(
     if projects_to_validate.is_empty() {
         if let Some(name) = project_name {
             eprintln!("{}", format!("Project '{name}' not found").red());
-            process::exit(1);
+            return Ok(true);
         }
     }
 
     let mut total_errors = 0;
+    let mut total_warnings = 0;
+    // JSON, RDJSON, and SARIF are single documents, so diagnostics from every project are
+    // printed together
+    let mut json_diagnostics = Vec::new();
+    let mut report_diagnostics = Vec::new();
 
     for (name, project) in &projects_to_validate {
-        if projects_to_validate.len() > 1 {
+        if projects_to_validate.len() > 1 && matches!(format, OutputFormat::Human) {
             println!("\n{}", format!("=== Project: {name} ===").bold().cyan());
         }
 
@@ -81,7 +199,7 @@ pub async fn run(
                 } else {
                     eprintln!("{}", serde_json::json!({ "error": e.to_string() }));
                 }
-                process::exit(1);
+                return Ok(true);
             }
         }
 
@@ -106,7 +224,7 @@ pub async fn run(
                 } else {
                     eprintln!("{}", serde_json::json!({ "error": e.to_string() }));
                 }
-                process::exit(1);
+                return Ok(true);
             }
         }
 
@@ -159,16 +277,19 @@ pub async fn run(
 
             // Convert diagnostics to CLI output format
             for diag in diagnostics {
-                use graphql_project::Severity;
-
-                // Only process errors (Apollo compiler validation)
-                if diag.severity == Severity::Error {
+                // Only process errors (Apollo compiler validation), plus unused fragments
+                // when they should fail the run
+                if super::is_failure(&diag, fail_on_unused) {
                     let diag_output = DiagnosticOutput {
                         file_path: file_path.clone(),
                         // graphql-project uses 0-based, CLI output uses 1-based
                         line: diag.range.start.line + 1,
                         column: diag.range.start.character + 1,
+                        end_line: diag.range.end.line + 1,
+                        end_column: diag.range.end.character + 1,
                         message: diag.message,
+                        severity: diag.severity,
+                        code: diag.code,
                     };
 
                     all_errors.push(diag_output);
@@ -176,20 +297,40 @@ pub async fn run(
             }
         }
 
+        // Files are read in no particular order; sort so output is stable across runs
+        all_errors.sort_by(|a, b| {
+            (&a.file_path, a.line, a.column, &a.code).cmp(&(
+                &b.file_path,
+                b.line,
+                b.column,
+                &b.code,
+            ))
+        });
+
         // Display errors
-        total_errors = all_errors.len();
+        let warnings = all_errors
+            .iter()
+            .filter(|error| error.severity != Severity::Error)
+            .count();
+        total_errors += all_errors.len() - warnings;
+        total_warnings += warnings;
 
         match format {
             OutputFormat::Human => {
                 // Print all errors
                 for error in &all_errors {
+                    let label = if error.severity == Severity::Error {
+                        "error:".red().bold()
+                    } else {
+                        "warning:".yellow().bold()
+                    };
                     if error.line > 0 {
                         println!(
                             "\n{}:{}:{}: {} {}",
                             error.file_path,
                             error.line,
                             error.column,
-                            "error:".red().bold(),
+                            label,
                             error.message.red()
                         );
                     } else {
@@ -199,7 +340,6 @@ pub async fn run(
                 }
             }
             OutputFormat::Json => {
-                // Print all errors as JSON
                 for error in &all_errors {
                     let location = if error.line > 0 {
                         Some(serde_json::json!({
@@ -210,33 +350,70 @@ pub async fn run(
                         None
                     };
 
-                    println!(
-                        "{}",
-                        serde_json::json!({
-                            "file": error.file_path,
-                            "severity": "error",
-                            "message": error.message,
-                            "location": location
-                        })
-                    );
+                    json_diagnostics.push(serde_json::json!({
+                        "file": error.file_path,
+                        "severity": if error.severity == Severity::Error {
+                            "error"
+                        } else {
+                            "warning"
+                        },
+                        "message": error.message,
+                        "location": location
+                    }));
+                }
+            }
+            OutputFormat::Rdjson | OutputFormat::Sarif => {
+                let to_diagnostic = if matches!(format, OutputFormat::Sarif) {
+                    super::sarif_result_entry
+                } else {
+                    super::rdjson_diagnostic
+                };
+                for error in &all_errors {
+                    let range = (error.line > 0).then_some((
+                        (error.line, error.column),
+                        (error.end_line, error.end_column),
+                    ));
+                    report_diagnostics.push(to_diagnostic(
+                        &error.file_path,
+                        range,
+                        &error.message,
+                        if error.severity == Severity::Error {
+                            "error"
+                        } else {
+                            "warning"
+                        },
+                        error.code.as_deref(),
+                    ));
                 }
             }
         }
     }
 
+    // Only failures are collected: errors, plus unused fragments with --fail-on-unused
+    let failed = total_errors + total_warnings > 0;
+
+    match format {
+        OutputFormat::Json => println!(
+            "{}",
+            super::json_result(json_diagnostics, total_errors, total_warnings, failed)
+        ),
+        OutputFormat::Rdjson => println!("{}", super::rdjson_result(report_diagnostics)),
+        OutputFormat::Sarif => println!("{}", super::sarif_result(report_diagnostics)),
+        OutputFormat::Human => {}
+    }
+
     // Summary
     if matches!(format, OutputFormat::Human) {
         println!();
-        if total_errors == 0 {
-            println!("{}", "✓ All validations passed!".green().bold());
+        if failed {
+            println!(
+                "{}",
+                format!("✗ Found {} error(s)", total_errors + total_warnings).red()
+            );
         } else {
-            println!("{}", format!("✗ Found {total_errors} error(s)").red());
+            println!("{}", "✓ All validations passed!".green().bold());
         }
     }
 
-    if total_errors > 0 {
-        process::exit(1);
-    }
-
-    Ok(())
+    Ok(failed)
 }
@@ -31,6 +31,10 @@ enum Commands {
         /// Watch mode - re-validate on file changes
         #[arg(short, long)]
         watch: bool,
+
+        /// Fail if any fragment is unused, even though it is only a warning
+        #[arg(long)]
+        fail_on_unused: bool,
     },
 
     /// Run custom lint rules on GraphQL documents
@@ -39,18 +43,85 @@ enum Commands {
         #[arg(short, long, value_enum, default_value = "human")]
         format: OutputFormat,
 
+        /// Group results by lint rule, with per-rule counts, instead of listing by file
+        #[arg(long)]
+        group_by_rule: bool,
+
         /// Watch mode - re-lint on file changes
         #[arg(short, long)]
         watch: bool,
+
+        /// Fail if any fragment or schema field is unused, whatever the rule's severity
+        #[arg(long)]
+        fail_on_unused: bool,
+
+        /// Override a rule's configured severity, like `--rule max-depth=error`. Repeatable
+        #[arg(
+            long = "rule",
+            value_name = "NAME=SEVERITY",
+            value_parser = commands::lint::parse_rule_override
+        )]
+        rules: Vec<(String, graphql_project::LintSeverity)>,
+    },
+
+    /// Format GraphQL documents
+    Format {
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "human")]
+        format: OutputFormat,
+
+        /// Exit with an error if any file is not formatted
+        #[arg(long, conflicts_with = "write")]
+        check: bool,
+
+        /// Rewrite files in place
+        #[arg(long)]
+        write: bool,
+
+        /// Also format the project's schema files
+        #[arg(long)]
+        include_schema: bool,
     },
 
-    /// Check for breaking changes between schemas
+    /// Download a schema from a GraphQL endpoint by introspection
+    Introspect {
+        /// URL of the GraphQL endpoint
+        #[arg(long)]
+        url: String,
+
+        /// Header sent with the request, like `--header "Authorization: Bearer $TOKEN"`.
+        /// Repeatable
+        #[arg(
+            long = "header",
+            value_name = "NAME: VALUE",
+            value_parser = commands::introspect::parse_header
+        )]
+        headers: Vec<(String, String)>,
+
+        /// File to write the schema to; printed to stdout if omitted
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+
+        /// Schema format to write
+        #[arg(short, long, value_enum, default_value = "sdl")]
+        format: SchemaFormat,
+
+        /// Request timeout in seconds
+        #[arg(long, default_value_t = 30)]
+        timeout: u64,
+    },
+
+    /// Check for breaking changes between two versions of the schema
     Check {
-        /// Base branch/ref to compare against
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "human")]
+        format: OutputFormat,
+
+        /// Base schema to compare against: a git ref, or a schema file
         #[arg(long)]
         base: String,
 
-        /// Head branch/ref to compare
+        /// Head schema to compare: a git ref, or a schema file
         #[arg(long)]
         head: String,
     },
@@ -62,6 +133,18 @@ enum OutputFormat {
     Human,
     /// JSON output for tooling
     Json,
+    /// Reviewdog diagnostic format (RDJSON), for inline review comments
+    Rdjson,
+    /// SARIF 2.1.0 report, for GitHub code scanning
+    Sarif,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum SchemaFormat {
+    /// GraphQL SDL
+    Sdl,
+    /// The introspection result as JSON, which can be used as a `.json` schema file
+    Json,
 }
 
 #[tokio::main]
@@ -69,14 +152,57 @@ async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Validate { format, watch } => {
-            commands::validate::run(cli.config, cli.project, format, watch).await?;
+        Commands::Validate {
+            format,
+            watch,
+            fail_on_unused,
+        } => {
+            commands::validate::run(cli.config, cli.project, format, watch, fail_on_unused).await?;
+        }
+        Commands::Lint {
+            format,
+            group_by_rule,
+            watch,
+            fail_on_unused,
+            rules,
+        } => {
+            commands::lint::run(
+                cli.config,
+                cli.project,
+                format,
+                group_by_rule,
+                watch,
+                fail_on_unused,
+                &rules,
+            )
+            .await?;
+        }
+        Commands::Format {
+            format,
+            check,
+            write,
+            include_schema,
+        } => {
+            let mode = if write {
+                commands::format::FormatMode::Write
+            } else if check {
+                commands::format::FormatMode::Check
+            } else {
+                commands::format::FormatMode::List
+            };
+            commands::format::run(cli.config, cli.project, format, mode, include_schema)?;
         }
-        Commands::Lint { format, watch } => {
-            commands::lint::run(cli.config, cli.project, format, watch).await?;
+        Commands::Introspect {
+            url,
+            headers,
+            output,
+            format,
+            timeout,
+        } => {
+            commands::introspect::run(&url, headers, output.as_deref(), format, timeout).await?;
         }
-        Commands::Check { base, head } => {
-            commands::check::run(cli.config, cli.project, base, head).await?;
+        Commands::Check { format, base, head } => {
+            commands::check::run(cli.config, cli.project, format, &base, &head)?;
         }
     }
 
@@ -11,6 +11,9 @@ pub enum GraphQLConfig {
     /// Multi-project configuration
     Multi {
         projects: HashMap<String, ProjectConfig>,
+        /// Tool-specific extensions shared by all projects
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        extensions: Option<HashMap<String, serde_json::Value>>,
     },
 }
 
@@ -21,7 +24,7 @@ impl GraphQLConfig {
     pub fn projects(&self) -> Box<dyn Iterator<Item = (&str, &ProjectConfig)> + '_> {
         match self {
             Self::Single(config) => Box::new(std::iter::once(("default", config))),
-            Self::Multi { projects } => Box::new(
+            Self::Multi { projects, .. } => Box::new(
                 projects
                     .iter()
                     .map(|(name, config)| (name.as_str(), config)),
@@ -36,7 +39,7 @@ impl GraphQLConfig {
         match self {
             Self::Single(config) if name == "default" => Some(config),
             Self::Single(_) => None,
-            Self::Multi { projects } => projects.get(name),
+            Self::Multi { projects, .. } => projects.get(name),
         }
     }
 
@@ -46,12 +49,23 @@ impl GraphQLConfig {
         matches!(self, Self::Multi { .. })
     }
 
+    /// Get the root-level extensions of a multi-project configuration
+    ///
+    /// Single project configs have no root level; their extensions belong to the project.
+    #[must_use]
+    pub const fn root_extensions(&self) -> Option<&HashMap<String, serde_json::Value>> {
+        match self {
+            Self::Single(_) => None,
+            Self::Multi { extensions, .. } => extensions.as_ref(),
+        }
+    }
+
     /// Get the number of projects
     #[must_use]
     pub fn project_count(&self) -> usize {
         match self {
             Self::Single(_) => 1,
-            Self::Multi { projects } => projects.len(),
+            Self::Multi { projects, .. } => projects.len(),
         }
     }
 }
@@ -174,7 +188,10 @@ mod tests {
             },
         );
 
-        let config = GraphQLConfig::Multi { projects };
+        let config = GraphQLConfig::Multi {
+            projects,
+            extensions: None,
+        };
 
         assert!(config.is_multi_project());
         assert_eq!(config.project_count(), 2);
@@ -238,4 +255,21 @@ extensions:
         assert!(extensions.contains_key("extractConfig"));
         assert!(extensions.contains_key("otherExtension"));
     }
+
+    #[test]
+    fn test_root_extensions_field() {
+        let yaml = r#"
+projects:
+  app:
+    schema: schema.graphql
+extensions:
+  extractConfig:
+    tagIdentifiers: ["gql", "myTag"]
+"#;
+        let config: GraphQLConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.is_multi_project());
+        assert!(config
+            .root_extensions()
+            .is_some_and(|extensions| extensions.contains_key("extractConfig")));
+    }
 }
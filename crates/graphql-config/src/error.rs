@@ -24,6 +24,25 @@ pub enum ConfigError {
     #[error("Unsupported config file format: {0}")]
     UnsupportedFormat(PathBuf),
 
+    #[error(
+        "Conflicting configuration sources, keep only one: {}",
+        .0.iter().map(|path| path.display().to_string()).collect::<Vec<_>>().join(", ")
+    )]
+    Conflicting(Vec<PathBuf>),
+
+    #[error("Environment variable {variable} used in {field} at {path} is not set")]
+    MissingEnvVar {
+        path: PathBuf,
+        variable: String,
+        field: String,
+    },
+
+    #[error(
+        "Circular extends in configuration: {}",
+        .0.iter().map(|path| path.display().to_string()).collect::<Vec<_>>().join(" -> ")
+    )]
+    CircularExtends(Vec<PathBuf>),
+
     #[error("Configuration validation error: {0}")]
     Validation(String),
 }
@@ -4,4 +4,4 @@ mod loader;
 
 pub use config::{DocumentsConfig, GraphQLConfig, ProjectConfig, SchemaConfig};
 pub use error::{ConfigError, Result};
-pub use loader::{find_config, load_config, load_config_from_str};
+pub use loader::{config_file_names, find_config, load_config, load_config_from_str};
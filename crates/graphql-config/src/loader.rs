@@ -13,17 +13,57 @@ const CONFIG_FILES: &[&str] = &[
     "graphql.config.json",
 ];
 
+/// Config files that have to be executed to be read, which isn't supported. They're still
+/// discovered, so that loading reports them rather than picking up a parent directory's config
+const SCRIPT_CONFIG_FILES: &[&str] = &[
+    "graphql.config.ts",
+    "graphql.config.js",
+    "graphql.config.cjs",
+    "graphql.config.mjs",
+];
+
+/// Field of `package.json` holding the GraphQL config
+const PACKAGE_JSON_FIELD: &str = "graphql";
+
+/// Names of every file a GraphQL config can be found in, including `package.json`
+///
+/// Useful for watching for config changes; a `package.json` only holds a config when it has
+/// a `graphql` field.
+pub fn config_file_names() -> impl Iterator<Item = &'static str> {
+    CONFIG_FILES
+        .iter()
+        .chain(SCRIPT_CONFIG_FILES)
+        .copied()
+        .chain(std::iter::once("package.json"))
+}
+
 /// Find a GraphQL config file by walking up the directory tree from the given start directory.
 /// Returns the path to the config file if found.
+///
+/// A `package.json` counts as a config file when it has a `graphql` field. Returns
+/// [`ConfigError::Conflicting`] if a directory has both a config file and such a
+/// `package.json`.
 pub fn find_config(start_dir: &Path) -> Result<Option<PathBuf>> {
     let mut current_dir = start_dir.to_path_buf();
 
     loop {
-        for file_name in CONFIG_FILES {
-            let config_path = current_dir.join(file_name);
-            if config_path.exists() && config_path.is_file() {
+        let config_file = CONFIG_FILES
+            .iter()
+            .chain(SCRIPT_CONFIG_FILES)
+            .map(|file_name| current_dir.join(file_name))
+            .find(|config_path| config_path.is_file());
+
+        let package_json = current_dir.join("package.json");
+        let package_json = package_json_has_config(&package_json).then_some(package_json);
+
+        match (config_file, package_json) {
+            (Some(config_path), Some(package_json)) => {
+                return Err(ConfigError::Conflicting(vec![config_path, package_json]));
+            }
+            (Some(config_path), None) | (None, Some(config_path)) => {
                 return Ok(Some(config_path));
             }
+            (None, None) => {}
         }
 
         // Move to parent directory
@@ -36,6 +76,16 @@ pub fn find_config(start_dir: &Path) -> Result<Option<PathBuf>> {
     Ok(None)
 }
 
+/// Whether a `package.json` has a `graphql` field
+///
+/// Missing, unreadable, and invalid files don't, since `package.json` belongs to other tools.
+fn package_json_has_config(path: &Path) -> bool {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str::<serde_json::Value>(&contents).ok())
+        .is_some_and(|package| package.get(PACKAGE_JSON_FIELD).is_some())
+}
+
 /// Load a GraphQL config from the specified path.
 /// Automatically detects the format based on file extension.
 pub fn load_config(path: &Path) -> Result<GraphQLConfig> {
@@ -45,7 +95,37 @@ pub fn load_config(path: &Path) -> Result<GraphQLConfig> {
 
 /// Load a GraphQL config from a string.
 /// The path is used for error messages and format detection.
+///
+/// `${VAR}` and `${VAR:-default}` in string values are replaced with environment variables
+/// before the config is deserialized.
+///
+/// A top-level `extends` key, holding a path or an array of paths relative to the config
+/// file, loads those configs as bases. The config is deep-merged over its bases, with later
+/// bases overriding earlier ones. Returns [`ConfigError::CircularExtends`] if a config
+/// extends itself, directly or through its bases.
 pub fn load_config_from_str(contents: &str, path: &Path) -> Result<GraphQLConfig> {
+    let value = parse_config_value(contents, path, &mut vec![config_identity(path)])?;
+
+    let config = serde_json::from_value(value).map_err(|e| ConfigError::Invalid {
+        path: path.to_path_buf(),
+        message: format!("Invalid configuration: {e}"),
+    })?;
+
+    validate_config(&config, path)?;
+
+    Ok(config)
+}
+
+/// Parse a config file into a value, with environment variables interpolated and `extends`
+/// resolved
+///
+/// `extending` holds the configs whose bases are being loaded, ending with this one, to
+/// detect cycles.
+fn parse_config_value(
+    contents: &str,
+    path: &Path,
+    extending: &mut Vec<PathBuf>,
+) -> Result<serde_json::Value> {
     let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
 
     let file_name = path
@@ -53,9 +133,18 @@ pub fn load_config_from_str(contents: &str, path: &Path) -> Result<GraphQLConfig
         .and_then(|name| name.to_str())
         .unwrap_or("");
 
-    let config = match extension {
+    let mut value = match extension {
         "yml" | "yaml" => parse_yaml(contents, path)?,
+        "json" if file_name == "package.json" => parse_package_json(contents, path)?,
         "json" => parse_json(contents, path)?,
+        "ts" | "js" | "cjs" | "mjs" => {
+            return Err(ConfigError::Invalid {
+                path: path.to_path_buf(),
+                message: "JavaScript and TypeScript config files can't be loaded; move the \
+                          config to .graphqlrc.yml or the \"graphql\" field of package.json"
+                    .to_string(),
+            })
+        }
         "" if file_name == ".graphqlrc" => {
             // .graphqlrc without extension - try YAML first, then JSON
             parse_yaml(contents, path).or_else(|_| parse_json(contents, path))?
@@ -63,13 +152,96 @@ pub fn load_config_from_str(contents: &str, path: &Path) -> Result<GraphQLConfig
         _ => return Err(ConfigError::UnsupportedFormat(path.to_path_buf())),
     };
 
-    validate_config(&config, path)?;
+    interpolate_env_vars(&mut value, "", path)?;
 
-    Ok(config)
+    resolve_extends(value, path, extending)
+}
+
+/// Path identifying a config file, so the same file reached through different relative
+/// paths is recognized
+fn config_identity(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Load the bases named by a config value's `extends` key and merge the value over them
+fn resolve_extends(
+    mut value: serde_json::Value,
+    path: &Path,
+    extending: &mut Vec<PathBuf>,
+) -> Result<serde_json::Value> {
+    let Some(extends) = value
+        .as_object_mut()
+        .and_then(|entries| entries.remove("extends"))
+    else {
+        return Ok(value);
+    };
+
+    let invalid = || ConfigError::Invalid {
+        path: path.to_path_buf(),
+        message: "\"extends\" must be a path or an array of paths".to_string(),
+    };
+    let base_paths = match extends {
+        serde_json::Value::String(base_path) => vec![base_path],
+        serde_json::Value::Array(items) => items
+            .into_iter()
+            .map(|item| match item {
+                serde_json::Value::String(base_path) => Ok(base_path),
+                _ => Err(invalid()),
+            })
+            .collect::<Result<_>>()?,
+        _ => return Err(invalid()),
+    };
+
+    let config_dir = path.parent().unwrap_or_else(|| Path::new(""));
+    let mut merged = serde_json::Value::Object(serde_json::Map::new());
+
+    for base_path in base_paths {
+        let base_path = config_dir.join(base_path);
+        let identity = config_identity(&base_path);
+        if extending.contains(&identity) {
+            let mut cycle = extending.clone();
+            cycle.push(identity);
+            return Err(ConfigError::CircularExtends(cycle));
+        }
+
+        let contents = fs::read_to_string(&base_path).map_err(|e| ConfigError::Invalid {
+            path: path.to_path_buf(),
+            message: format!("Can't read extended config {}: {e}", base_path.display()),
+        })?;
+
+        extending.push(identity);
+        let base = parse_config_value(&contents, &base_path, extending)?;
+        extending.pop();
+
+        merge_values(&mut merged, base);
+    }
+
+    merge_values(&mut merged, value);
+    Ok(merged)
+}
+
+/// Deep-merge `overlay` into `base`
+///
+/// Objects are merged key by key; any other value, including an array, replaces the base
+/// value.
+fn merge_values(base: &mut serde_json::Value, overlay: serde_json::Value) {
+    match (base, overlay) {
+        (serde_json::Value::Object(base_entries), serde_json::Value::Object(overlay_entries)) => {
+            for (key, overlay_entry) in overlay_entries {
+                match base_entries.get_mut(&key) {
+                    Some(base_entry) => merge_values(base_entry, overlay_entry),
+                    None => {
+                        base_entries.insert(key, overlay_entry);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
 }
 
 /// Parse YAML configuration
-fn parse_yaml(contents: &str, path: &Path) -> Result<GraphQLConfig> {
+fn parse_yaml(contents: &str, path: &Path) -> Result<serde_json::Value> {
     serde_yaml::from_str(contents).map_err(|e| ConfigError::Invalid {
         path: path.to_path_buf(),
         message: format!("YAML parse error: {e}"),
@@ -77,13 +249,100 @@ fn parse_yaml(contents: &str, path: &Path) -> Result<GraphQLConfig> {
 }
 
 /// Parse JSON configuration
-fn parse_json(contents: &str, path: &Path) -> Result<GraphQLConfig> {
+fn parse_json(contents: &str, path: &Path) -> Result<serde_json::Value> {
     serde_json::from_str(contents).map_err(|e| ConfigError::Invalid {
         path: path.to_path_buf(),
         message: format!("JSON parse error: {e}"),
     })
 }
 
+/// Parse the `graphql` field of a `package.json`
+fn parse_package_json(contents: &str, path: &Path) -> Result<serde_json::Value> {
+    let mut package = parse_json(contents, path)?;
+
+    package
+        .get_mut(PACKAGE_JSON_FIELD)
+        .map(serde_json::Value::take)
+        .ok_or_else(|| ConfigError::Invalid {
+            path: path.to_path_buf(),
+            message: format!("No \"{PACKAGE_JSON_FIELD}\" field in package.json"),
+        })
+}
+
+/// Replace `${VAR}` and `${VAR:-default}` in every string within a config value
+///
+/// `field` is the dotted path of `value` within the config, like `projects.api.schema[0]`,
+/// and is used to report variables that aren't set.
+fn interpolate_env_vars(value: &mut serde_json::Value, field: &str, path: &Path) -> Result<()> {
+    match value {
+        serde_json::Value::String(string) => {
+            *string =
+                expand_env_vars(string, |name| std::env::var(name).ok()).map_err(|variable| {
+                    ConfigError::MissingEnvVar {
+                        path: path.to_path_buf(),
+                        variable,
+                        field: field.to_string(),
+                    }
+                })?;
+        }
+        serde_json::Value::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                interpolate_env_vars(item, &format!("{field}[{index}]"), path)?;
+            }
+        }
+        serde_json::Value::Object(entries) => {
+            for (key, entry) in entries {
+                let entry_field = if field.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{field}.{key}")
+                };
+                interpolate_env_vars(entry, &entry_field, path)?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Expand `${VAR}` and `${VAR:-default}` references using `lookup`
+///
+/// As in a shell, the default is used when the variable is unset or empty. An unterminated
+/// `${` is left as is. Returns the name of the first variable that's unset and has no
+/// default.
+fn expand_env_vars(
+    input: &str,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> std::result::Result<String, String> {
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(start) = rest.find("${") {
+        let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+            break;
+        };
+
+        output.push_str(&rest[..start]);
+        let reference = &rest[start + 2..end];
+        let (name, default) = reference
+            .split_once(":-")
+            .map_or((reference, None), |(name, default)| (name, Some(default)));
+
+        match (lookup(name), default) {
+            (Some(value), Some(default)) if value.is_empty() => output.push_str(default),
+            (Some(value), _) => output.push_str(&value),
+            (None, Some(default)) => output.push_str(default),
+            (None, None) => return Err(name.to_string()),
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    output.push_str(rest);
+    Ok(output)
+}
+
 /// Validate the loaded configuration
 fn validate_config(config: &GraphQLConfig, path: &Path) -> Result<()> {
     for (project_name, project_config) in config.projects() {
@@ -255,4 +514,211 @@ schema: ""
         // Should prefer .graphqlrc.yml over graphql.config.json
         assert_eq!(found.file_name().unwrap(), ".graphqlrc.yml");
     }
+
+    #[test]
+    fn test_find_config_in_package_json() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let package_json = temp_dir.path().join("package.json");
+        fs::write(
+            &package_json,
+            r#"{"name": "app", "graphql": {"schema": "schema.graphql"}}"#,
+        )
+        .unwrap();
+
+        let found = find_config(temp_dir.path()).unwrap();
+        assert_eq!(found, Some(package_json.clone()));
+
+        let config = load_config(&package_json).unwrap();
+        assert_eq!(config.project_count(), 1);
+    }
+
+    #[test]
+    fn test_package_json_without_graphql_field_is_skipped() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config_path = temp_dir.path().join(".graphqlrc.yml");
+        fs::write(&config_path, "schema: schema.graphql").unwrap();
+
+        let sub_dir = temp_dir.path().join("app");
+        fs::create_dir(&sub_dir).unwrap();
+        fs::write(sub_dir.join("package.json"), r#"{"name": "app"}"#).unwrap();
+
+        let found = find_config(&sub_dir).unwrap();
+        assert_eq!(found, Some(config_path));
+    }
+
+    #[test]
+    fn test_config_file_and_package_json_conflict() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            temp_dir.path().join(".graphqlrc.yml"),
+            "schema: schema.graphql",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("package.json"),
+            r#"{"graphql": {"schema": "schema.graphql"}}"#,
+        )
+        .unwrap();
+
+        let result = find_config(temp_dir.path());
+        assert!(matches!(result, Err(ConfigError::Conflicting(paths)) if paths.len() == 2));
+    }
+
+    #[test]
+    fn test_config_file_names_cover_every_discovered_file() {
+        let names: Vec<_> = config_file_names().collect();
+
+        for name in CONFIG_FILES.iter().chain(SCRIPT_CONFIG_FILES) {
+            assert!(names.contains(name), "missing {name}");
+        }
+        assert!(names.contains(&"package.json"));
+    }
+
+    #[test]
+    fn test_script_config_is_found_but_not_loaded() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config_path = temp_dir.path().join("graphql.config.ts");
+        fs::write(&config_path, "export default { schema: 'schema.graphql' };").unwrap();
+
+        let found = find_config(temp_dir.path()).unwrap();
+        assert_eq!(found, Some(config_path.clone()));
+
+        let result = load_config(&config_path);
+        assert!(matches!(result, Err(ConfigError::Invalid { .. })));
+    }
+
+    #[test]
+    fn test_extends_merges_over_base() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::create_dir(temp_dir.path().join("shared")).unwrap();
+        fs::write(
+            temp_dir.path().join("shared/base.graphqlrc.yml"),
+            r"
+schema: shared/schema.graphql
+documents: src/**/*.graphql
+extensions:
+  lint:
+    recommended: error
+  scalars:
+    DateTime: string
+",
+        )
+        .unwrap();
+        let config_path = temp_dir.path().join(".graphqlrc.yml");
+        fs::write(
+            &config_path,
+            r"
+extends: ./shared/base.graphqlrc.yml
+schema: app/schema.graphql
+extensions:
+  scalars:
+    JSON: unknown
+",
+        )
+        .unwrap();
+
+        let config = load_config(&config_path).unwrap();
+        let project = config.get_project("default").unwrap();
+        assert_eq!(project.schema.paths(), vec!["app/schema.graphql"]);
+        assert_eq!(
+            project.documents.as_ref().unwrap().patterns(),
+            vec!["src/**/*.graphql"]
+        );
+
+        let extensions = project.extensions.as_ref().unwrap();
+        assert_eq!(
+            extensions["lint"],
+            serde_json::json!({ "recommended": "error" })
+        );
+        assert_eq!(
+            extensions["scalars"],
+            serde_json::json!({ "DateTime": "string", "JSON": "unknown" })
+        );
+    }
+
+    #[test]
+    fn test_circular_extends_is_an_error() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            temp_dir.path().join("a.yml"),
+            "extends: b.yml\nschema: a.graphql\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("b.yml"),
+            "extends: [a.yml]\nschema: b.graphql\n",
+        )
+        .unwrap();
+
+        let result = load_config(&temp_dir.path().join("a.yml"));
+        assert!(matches!(
+            result,
+            Err(ConfigError::CircularExtends(cycle)) if cycle.len() == 3
+        ));
+    }
+
+    #[test]
+    fn test_expand_env_vars() {
+        let lookup = |name: &str| match name {
+            "TOKEN" => Some("secret".to_string()),
+            "EMPTY" => Some(String::new()),
+            _ => None,
+        };
+
+        assert_eq!(
+            expand_env_vars("Bearer ${TOKEN}", lookup),
+            Ok("Bearer secret".to_string())
+        );
+        assert_eq!(
+            expand_env_vars("${API_URL:-http://localhost:4000}/graphql", lookup),
+            Ok("http://localhost:4000/graphql".to_string())
+        );
+        assert_eq!(
+            expand_env_vars("${EMPTY:-fallback}|${EMPTY}", lookup),
+            Ok("fallback|".to_string())
+        );
+        assert_eq!(
+            expand_env_vars("${unterminated", lookup),
+            Ok("${unterminated".to_string())
+        );
+        assert_eq!(
+            expand_env_vars("${TOKEN}/${MISSING}", lookup),
+            Err("MISSING".to_string())
+        );
+    }
+
+    #[test]
+    fn test_load_config_interpolates_env_vars() {
+        std::env::set_var("GRAPHQL_CONFIG_TEST_SCHEMA_DIR", "generated");
+        let yaml = r"
+projects:
+  api:
+    schema: ${GRAPHQL_CONFIG_TEST_SCHEMA_DIR}/schema.graphql
+    documents: ${GRAPHQL_CONFIG_TEST_UNSET_DOCUMENTS:-src/**/*.graphql}
+";
+
+        let config = load_config_from_str(yaml, Path::new(".graphqlrc.yml")).unwrap();
+        let project = config.get_project("api").unwrap();
+        assert_eq!(project.schema.paths(), vec!["generated/schema.graphql"]);
+        assert_eq!(
+            project.documents.as_ref().unwrap().patterns(),
+            vec!["src/**/*.graphql"]
+        );
+    }
+
+    #[test]
+    fn test_load_config_missing_env_var() {
+        let yaml = r"
+schema:
+  - schema.graphql
+  - https://api.example.com/graphql?token=${GRAPHQL_CONFIG_TEST_UNSET_TOKEN}
+";
+
+        let result = load_config_from_str(yaml, Path::new(".graphqlrc.yml"));
+        assert!(matches!(
+            result,
+            Err(ConfigError::MissingEnvVar { variable, field, .. })
+                if variable == "GRAPHQL_CONFIG_TEST_UNSET_TOKEN" && field == "schema[1]"
+        ));
+    }
 }
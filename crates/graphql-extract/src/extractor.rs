@@ -13,7 +13,9 @@ pub struct ExtractConfig {
     pub magic_comment: String,
 
     /// Tag identifiers to extract (default: `["gql", "graphql"]`)
-    /// Matches: `gql`query { ... }`\` or `graphql`query { ... }`\`
+    /// Matches: `gql`query { ... }`\` or `graphql`query { ... }`\`, and calls like
+    /// `gql(`query { ... }`)`. Dotted entries like `"client.gql"` match that member
+    /// expression, and need no import since they name the object themselves.
     #[serde(default = "default_tag_identifiers")]
     pub tag_identifiers: Vec<String>,
 
@@ -25,6 +27,13 @@ pub struct ExtractConfig {
     /// Allow extraction without imports (global identifiers)
     #[serde(default)]
     pub allow_global_identifiers: bool,
+
+    /// Fail on files with syntax errors instead of extracting on a best-effort basis
+    ///
+    /// By default, a file that can't be parsed is scanned for tagged templates instead, so
+    /// that a syntax error elsewhere in the file doesn't drop its GraphQL.
+    #[serde(default)]
+    pub strict: bool,
 }
 
 fn default_magic_comment() -> String {
@@ -53,6 +62,7 @@ impl Default for ExtractConfig {
             tag_identifiers: default_tag_identifiers(),
             modules: default_modules(),
             allow_global_identifiers: false,
+            strict: false,
         }
     }
 }
@@ -68,6 +78,29 @@ pub struct ExtractedGraphQL {
 
     /// The tag name used (e.g., "gql", "graphql"), if any
     pub tag_name: Option<String>,
+
+    /// The `${...}` interpolations of the template literal, in order
+    ///
+    /// Each one is replaced with whitespace of the same length in `source` (keeping its
+    /// newlines), so the surrounding GraphQL still parses and positions stay accurate.
+    pub interpolations: Vec<Interpolation>,
+}
+
+/// A `${...}` interpolation blanked out of an extracted template literal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interpolation {
+    /// Byte offset of the blanked span in the extracted source
+    pub offset: usize,
+    /// Length of the blanked span in bytes
+    pub length: usize,
+    /// Source of the interpolated expression, without `${` and `}`
+    pub expression: String,
+    /// Whether the interpolation refers to a fragment
+    ///
+    /// That's the case between definitions, as in `` gql`query { ... } ${UserFragment}` ``,
+    /// and after a spread, as in `` gql`{ user { ...${UserFragment} } }` ``. The `...` of a
+    /// spread is blanked along with the interpolation.
+    pub is_fragment: bool,
 }
 
 /// Extract GraphQL from a file
@@ -99,11 +132,13 @@ pub fn extract_from_source(
                     ),
                 ),
                 tag_name: None,
+                interpolations: Vec::new(),
             }])
         }
         Language::TypeScript | Language::JavaScript => {
             extract_from_js_family(source, language, config)
         }
+        Language::Svelte => extract_from_svelte(source, config),
         _ => Err(ExtractError::UnsupportedLanguage(language)),
     }
 }
@@ -144,11 +179,17 @@ fn extract_from_js_family(
     };
 
     // Parse the module
-    let module = parse_file_as_module(&source_file, syntax, EsVersion::EsNext, None, &mut vec![])
-        .map_err(|e| ExtractError::Parse {
-        path: std::path::PathBuf::from("input"),
-        message: format!("SWC parse error: {e:?}"),
-    })?;
+    let module =
+        match parse_file_as_module(&source_file, syntax, EsVersion::EsNext, None, &mut vec![]) {
+            Ok(module) => module,
+            Err(_) if !config.strict => return Ok(extract_leniently(source, config)),
+            Err(e) => {
+                return Err(ExtractError::Parse {
+                    path: std::path::PathBuf::from("input"),
+                    message: format!("SWC parse error: {e:?}"),
+                })
+            }
+        };
 
     // Create visitor to collect GraphQL
     let mut visitor = GraphQLVisitor::new(source, config);
@@ -157,6 +198,319 @@ fn extract_from_js_family(
     Ok(visitor.extracted)
 }
 
+/// Extract GraphQL from the `<script>` blocks of a Svelte component
+///
+/// Instance scripts and `context="module"` scripts are both scanned, as TypeScript when
+/// they have `lang="ts"` and as JavaScript otherwise. Locations are in the component,
+/// not the script block.
+fn extract_from_svelte(source: &str, config: &ExtractConfig) -> Result<Vec<ExtractedGraphQL>> {
+    let mut extracted = Vec::new();
+
+    for (content_start, content, language) in script_blocks(source) {
+        for mut item in extract_from_js_family(content, language, config)? {
+            let offset = content_start + item.location.offset;
+            let length = item.location.length;
+            item.location = SourceLocation::new(
+                offset,
+                length,
+                Range::new(
+                    position_from_offset(source, offset),
+                    position_from_offset(source, offset + length),
+                ),
+            );
+            extracted.push(item);
+        }
+    }
+
+    Ok(extracted)
+}
+
+/// Byte offset, content, and language of each `<script>` block in a component
+fn script_blocks(source: &str) -> Vec<(usize, &str, Language)> {
+    let mut blocks = Vec::new();
+    let mut search_from = 0;
+
+    while let Some(open) = source[search_from..]
+        .find("<script")
+        .map(|i| search_from + i)
+    {
+        let attributes_start = open + "<script".len();
+        search_from = attributes_start;
+        // Skip other tags that start the same way, like `<scripts>`
+        if !source[attributes_start..].starts_with(|c: char| c.is_whitespace() || c == '>') {
+            continue;
+        }
+
+        let Some(tag_end) = source[attributes_start..]
+            .find('>')
+            .map(|i| attributes_start + i)
+        else {
+            break;
+        };
+        let content_start = tag_end + 1;
+        let Some(content_end) = source[content_start..]
+            .find("</script")
+            .map(|i| content_start + i)
+        else {
+            break;
+        };
+        search_from = content_end;
+
+        let is_typescript = source[attributes_start..tag_end]
+            .split_whitespace()
+            .any(|attribute| {
+                matches!(
+                    attribute.trim_end_matches('/'),
+                    "lang=\"ts\"" | "lang='ts'" | "lang=\"typescript\"" | "lang='typescript'"
+                )
+            });
+        let language = if is_typescript {
+            Language::TypeScript
+        } else {
+            Language::JavaScript
+        };
+
+        blocks.push((content_start, &source[content_start..content_end], language));
+    }
+
+    blocks
+}
+
+/// Best-effort extraction from JavaScript/TypeScript that doesn't parse
+///
+/// Scans the text for template literals tagged with a configured identifier, as a tag or
+/// as the only argument of a call, or preceded by the magic comment. Tags are checked
+/// against imports found the same way. Strings and comments outside the templates aren't
+/// tracked, so a stray backtick in one can hide the templates that follow it.
+fn extract_leniently(source: &str, config: &ExtractConfig) -> Vec<ExtractedGraphQL> {
+    let imported = scan_imports(source, config);
+    let mut extracted = Vec::new();
+    let mut search_from = 0;
+
+    while let Some(open) = source[search_from..].find('`').map(|i| search_from + i) {
+        let Some(close) = template_end(source, open + 1) else {
+            break;
+        };
+        search_from = close + 1;
+
+        let content = &source[open + 1..close];
+
+        let before = source[..open].trim_end();
+        let tag_name = if let Some(comment) = before.strip_suffix("*/") {
+            let is_magic_comment = comment
+                .rfind("/*")
+                .is_some_and(|start| comment[start + 2..].trim() == config.magic_comment);
+            if !is_magic_comment {
+                continue;
+            }
+            None
+        } else {
+            let before = before.strip_suffix('(').unwrap_or(before).trim_end();
+            let path = &before[before
+                .trim_end_matches(|c: char| c.is_alphanumeric() || c == '_' || c == '$' || c == '.')
+                .len()..];
+            let Some(tag) = match_tag(path, config, |name| imported.iter().any(|i| i == name))
+            else {
+                continue;
+            };
+            Some(tag)
+        };
+
+        let start_pos = position_from_offset(source, open + 1);
+        let end_pos = position_from_offset(source, close);
+        let (graphql, interpolations) =
+            blank_interpolations(content, &interpolation_spans(content));
+        extracted.push(ExtractedGraphQL {
+            source: graphql,
+            location: SourceLocation::new(open + 1, content.len(), Range::new(start_pos, end_pos)),
+            tag_name,
+            interpolations,
+        });
+    }
+
+    extracted
+}
+
+/// The configured tag that a tag like `gql` or `client.gql` matches, if any
+///
+/// `path` is the tag's identifier, or its member expression in dotted form. A dotted entry
+/// of `tag_identifiers` matches the same path. Other entries match an identifier, or the
+/// object of a member expression like `graphql.experimental`, when `is_imported` from a
+/// configured module or when global identifiers are allowed.
+fn match_tag(
+    path: &str,
+    config: &ExtractConfig,
+    is_imported: impl Fn(&str) -> bool,
+) -> Option<String> {
+    if path.contains('.') && config.tag_identifiers.iter().any(|tag| tag == path) {
+        return Some(path.to_string());
+    }
+
+    let root = path.split('.').next()?;
+    let is_configured = config.tag_identifiers.iter().any(|tag| tag == root);
+    (is_configured && (config.allow_global_identifiers || is_imported(root)))
+        .then(|| root.to_string())
+}
+
+/// The dotted path of a tag or callee, like `gql` or `client.gql`
+///
+/// Only identifiers and chains of non-computed member accesses on them have a path.
+fn tag_path(expr: &swc_core::ecma::ast::Expr) -> Option<String> {
+    use swc_core::ecma::ast::{Expr, MemberProp};
+
+    match expr {
+        Expr::Ident(ident) => Some(String::from_utf8_lossy(ident.sym.as_bytes()).to_string()),
+        Expr::Member(member) => {
+            let MemberProp::Ident(prop) = &member.prop else {
+                return None;
+            };
+            let object = tag_path(&member.obj)?;
+            Some(format!(
+                "{object}.{}",
+                String::from_utf8_lossy(prop.sym.as_bytes())
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Byte ranges of the `${...}` interpolations in the content of a template literal
+///
+/// Braces are matched without tracking strings or nested templates in the expressions.
+fn interpolation_spans(content: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut search_from = 0;
+
+    while let Some(start) = content[search_from..].find("${").map(|i| search_from + i) {
+        let mut depth = 0;
+        let mut end = None;
+        for (i, ch) in content[start + 2..].char_indices() {
+            match ch {
+                '{' => depth += 1,
+                '}' if depth == 0 => {
+                    end = Some(start + 2 + i + 1);
+                    break;
+                }
+                '}' => depth -= 1,
+                _ => {}
+            }
+        }
+        let Some(end) = end else {
+            break;
+        };
+        spans.push((start, end));
+        search_from = end;
+    }
+
+    spans
+}
+
+/// Replace the `${...}` interpolations at `spans` in a template's content with whitespace
+///
+/// Newlines inside an interpolation are kept, and everything else becomes spaces, so the
+/// GraphQL around it keeps its byte offsets and line/column positions. An interpolation
+/// right after `...` is blanked along with the `...`, since a spread without a name
+/// doesn't parse.
+fn blank_interpolations(content: &str, spans: &[(usize, usize)]) -> (String, Vec<Interpolation>) {
+    let blank = |text: &str| -> String {
+        text.chars()
+            .map(|ch| {
+                if ch == '\n' || ch == '\r' {
+                    ch.to_string()
+                } else {
+                    " ".repeat(ch.len_utf8())
+                }
+            })
+            .collect()
+    };
+
+    let mut graphql = String::with_capacity(content.len());
+    let mut interpolations = Vec::new();
+    let mut copied_to = 0;
+
+    for &(start, end) in spans {
+        let before = content[copied_to..start].trim_end();
+        let (blank_start, is_spread) = before
+            .strip_suffix("...")
+            .map_or((start, false), |rest| (copied_to + rest.len(), true));
+
+        graphql.push_str(&content[copied_to..blank_start]);
+        // Outside every selection set, between definitions
+        let is_top_level = graphql.matches('{').count() <= graphql.matches('}').count();
+        graphql.push_str(&blank(&content[blank_start..end]));
+
+        interpolations.push(Interpolation {
+            offset: blank_start,
+            length: end - blank_start,
+            expression: content[start + 2..end - 1].trim().to_string(),
+            is_fragment: is_spread || is_top_level,
+        });
+        copied_to = end;
+    }
+    graphql.push_str(&content[copied_to..]);
+
+    (graphql, interpolations)
+}
+
+/// Byte offset of the backtick closing a template literal whose content starts at `start`
+fn template_end(source: &str, start: usize) -> Option<usize> {
+    let mut chars = source[start..].char_indices();
+    while let Some((i, ch)) = chars.next() {
+        match ch {
+            '\\' => {
+                chars.next();
+            }
+            '`' => return Some(start + i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Names bound by `import ... from '<module>'` statements for the configured modules
+fn scan_imports(source: &str, config: &ExtractConfig) -> Vec<String> {
+    let is_identifier_char = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    let mut names = Vec::new();
+
+    for (start, _) in source.match_indices("import") {
+        if source[..start]
+            .chars()
+            .next_back()
+            .is_some_and(is_identifier_char)
+        {
+            continue;
+        }
+        let statement = &source[start + "import".len()..];
+        let Some(from) = statement.find("from") else {
+            continue;
+        };
+        let specifiers = &statement[..from];
+        if specifiers.contains(';') {
+            continue;
+        }
+
+        let module = statement[from + "from".len()..].trim_start();
+        let Some(quote) = module.chars().next().filter(|c| *c == '\'' || *c == '"') else {
+            continue;
+        };
+        let Some(module) = module[1..].split(quote).next() else {
+            continue;
+        };
+        if !config.modules.iter().any(|name| name == module) {
+            continue;
+        }
+
+        names.extend(
+            specifiers
+                .split(|c: char| !is_identifier_char(c))
+                .filter(|name| !name.is_empty() && !matches!(*name, "type" | "as"))
+                .map(ToString::to_string),
+        );
+    }
+
+    names
+}
+
 /// Visitor to extract GraphQL from JavaScript/TypeScript AST
 struct GraphQLVisitor<'a> {
     source: &'a str,
@@ -181,17 +535,13 @@ impl<'a> GraphQLVisitor<'a> {
         }
     }
 
-    /// Check if a tag identifier is valid (imported or global allowed)
-    fn is_valid_tag(&self, tag_name: &str) -> bool {
-        if self.config.allow_global_identifiers {
-            return true;
-        }
-
-        if let Some(module_source) = self.imports.get(tag_name) {
-            return self.config.modules.contains(module_source);
-        }
-
-        false
+    /// The configured tag that a tag or callee path matches, checking imports
+    fn match_tag(&self, path: &str) -> Option<String> {
+        match_tag(path, self.config, |name| {
+            self.imports
+                .get(name)
+                .is_some_and(|module_source| self.config.modules.contains(module_source))
+        })
     }
 
     /// Extract string content from a template literal
@@ -200,30 +550,36 @@ impl<'a> GraphQLVisitor<'a> {
         tpl: &swc_core::ecma::ast::Tpl,
         tag_name: Option<String>,
     ) -> Option<ExtractedGraphQL> {
-        if tpl.quasis.is_empty() {
-            return None;
-        }
-
-        // For now, only support templates without expressions
-        if tpl.exprs.is_empty() && tpl.quasis.len() == 1 {
-            let quasi = &tpl.quasis[0];
-            let raw_str = String::from_utf8_lossy(quasi.raw.as_bytes());
-
-            // Calculate positions
-            let start_offset = quasi.span.lo.0 as usize - 1; // -1 to account for SWC byte offset
-            let length = raw_str.len();
-
-            let start_pos = position_from_offset(self.source, start_offset);
-            let end_pos = position_from_offset(self.source, start_offset + length);
-
-            return Some(ExtractedGraphQL {
-                source: raw_str.to_string(),
-                location: SourceLocation::new(start_offset, length, Range::new(start_pos, end_pos)),
-                tag_name,
-            });
-        }
-
-        None
+        let (first, last) = (tpl.quasis.first()?, tpl.quasis.last()?);
+
+        // Calculate positions (-1 to account for SWC byte offsets starting at 1)
+        let start_offset = first.span.lo.0 as usize - 1;
+        let end_offset = last.span.hi.0 as usize - 1;
+        let content = self.source.get(start_offset..end_offset)?;
+
+        // Expressions sit between consecutive quasis
+        let spans: Vec<(usize, usize)> = tpl
+            .quasis
+            .windows(2)
+            .map(|pair| {
+                (
+                    pair[0].span.hi.0 as usize - 1 - start_offset,
+                    pair[1].span.lo.0 as usize - 1 - start_offset,
+                )
+            })
+            .collect();
+        let (graphql, interpolations) = blank_interpolations(content, &spans);
+
+        let length = content.len();
+        let start_pos = position_from_offset(self.source, start_offset);
+        let end_pos = position_from_offset(self.source, start_offset + length);
+
+        Some(ExtractedGraphQL {
+            source: graphql,
+            location: SourceLocation::new(start_offset, length, Range::new(start_pos, end_pos)),
+            tag_name,
+            interpolations,
+        })
     }
 
     /// Check if there's a magic comment before this position
@@ -272,37 +628,14 @@ impl swc_core::ecma::visit::Visit for GraphQLVisitor<'_> {
 
     /// Visit tagged template expressions (e.g., gql`query { ... }`)
     fn visit_tagged_tpl(&mut self, tagged: &swc_core::ecma::ast::TaggedTpl) {
-        use swc_core::ecma::ast::Expr;
         use swc_core::ecma::visit::VisitWith;
-        // Extract tag identifier
-        let tag_name = match &*tagged.tag {
-            Expr::Ident(ident) => String::from_utf8_lossy(ident.sym.as_bytes()).to_string(),
-            Expr::Member(member) => {
-                // Handle member expressions like `graphql.default`
-                if let Expr::Ident(obj) = &*member.obj {
-                    String::from_utf8_lossy(obj.sym.as_bytes()).to_string()
-                } else {
-                    tagged.visit_children_with(self);
-                    return;
-                }
-            }
-            _ => {
-                tagged.visit_children_with(self);
-                return;
-            }
-        };
 
-        // Check if this is a configured tag identifier
-        if !self.config.tag_identifiers.contains(&tag_name) {
+        // Check if this is a configured tag that's imported (or global allowed). Member
+        // expressions like `graphql.default` or a dotted tag like `client.gql` count too.
+        let Some(tag_name) = tag_path(&tagged.tag).and_then(|path| self.match_tag(&path)) else {
             tagged.visit_children_with(self);
             return;
-        }
-
-        // Check if the tag is valid (imported or global allowed)
-        if !self.is_valid_tag(&tag_name) {
-            tagged.visit_children_with(self);
-            return;
-        }
+        };
 
         // Extract the template literal content
         if let Some(extracted) = self.extract_template_literal(&tagged.tpl, Some(tag_name)) {
@@ -320,32 +653,10 @@ impl swc_core::ecma::visit::Visit for GraphQLVisitor<'_> {
         use swc_core::ecma::ast::{Callee, Expr, Lit};
         use swc_core::ecma::visit::VisitWith;
 
-        // Check if this is a call to a configured tag identifier (e.g., gql(...) or graphql(...))
+        // Check if this is a call to a configured tag (e.g., gql(...), graphql.default(...),
+        // or client.gql(...) with a dotted tag)
         let tag_name = match &call.callee {
-            Callee::Expr(expr) => match &**expr {
-                Expr::Ident(ident) => {
-                    let name = String::from_utf8_lossy(ident.sym.as_bytes()).to_string();
-                    if self.config.tag_identifiers.contains(&name) && self.is_valid_tag(&name) {
-                        Some(name)
-                    } else {
-                        None
-                    }
-                }
-                Expr::Member(member) => {
-                    // Handle member expressions like `graphql.default`
-                    if let Expr::Ident(obj) = &*member.obj {
-                        let name = String::from_utf8_lossy(obj.sym.as_bytes()).to_string();
-                        if self.config.tag_identifiers.contains(&name) && self.is_valid_tag(&name) {
-                            Some(name)
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                }
-                _ => None,
-            },
+            Callee::Expr(expr) => tag_path(expr).and_then(|path| self.match_tag(&path)),
             _ => None,
         };
 
@@ -379,6 +690,7 @@ impl swc_core::ecma::visit::Visit for GraphQLVisitor<'_> {
                                     Range::new(start_pos, end_pos),
                                 ),
                                 tag_name: None,
+                                interpolations: Vec::new(),
                             });
                         }
                     }
@@ -406,6 +718,7 @@ impl swc_core::ecma::visit::Visit for GraphQLVisitor<'_> {
                                 Range::new(start_pos, end_pos),
                             ),
                             tag_name: None,
+                            interpolations: Vec::new(),
                         });
                     }
                 }
@@ -440,6 +753,7 @@ impl swc_core::ecma::visit::Visit for GraphQLVisitor<'_> {
                                 Range::new(start_pos, end_pos),
                             ),
                             tag_name: None,
+                            interpolations: Vec::new(),
                         });
                     }
                 }
@@ -523,6 +837,52 @@ query GetUser {
         assert_eq!(pos, Position::new(2, 0));
     }
 
+    mod svelte_tests {
+        use super::*;
+
+        #[test]
+        fn test_extract_from_instance_and_module_scripts() {
+            let source = r#"<script context="module" lang="ts">
+  import { gql } from '@apollo/client';
+  export const USER_FIELDS = gql`fragment UserFields on User { id }`;
+</script>
+
+<script>
+  import { gql } from '@apollo/client';
+  const QUERY = gql`query GetUser { user { ...UserFields } }`;
+</script>
+
+<h1>{title}</h1>
+"#;
+            let config = ExtractConfig::default();
+            let result = extract_from_source(source, Language::Svelte, &config).unwrap();
+
+            assert_eq!(result.len(), 2);
+            assert_eq!(result[0].source, "fragment UserFields on User { id }");
+            assert_eq!(result[1].source, "query GetUser { user { ...UserFields } }");
+
+            // Locations point into the component
+            for item in &result {
+                let offset = item.location.offset;
+                assert_eq!(&source[offset..offset + item.location.length], item.source);
+            }
+            assert_eq!(result[0].location.range.start, Position::new(2, 33));
+            assert_eq!(result[1].location.range.start, Position::new(7, 20));
+        }
+
+        #[test]
+        fn test_markup_outside_scripts_is_ignored() {
+            let source = "<p>gql`query { ignored }`</p>\n<script>const x = 1;</script>\n";
+            let config = ExtractConfig {
+                allow_global_identifiers: true,
+                ..Default::default()
+            };
+            let result = extract_from_source(source, Language::Svelte, &config).unwrap();
+
+            assert!(result.is_empty());
+        }
+    }
+
     mod typescript_tests {
         use super::*;
 
@@ -713,6 +1073,49 @@ const query = customGql`query Custom { field }`;
             assert_eq!(result[0].tag_name, Some("customGql".to_string()));
         }
 
+        #[test]
+        fn test_extract_dotted_member_tag() {
+            let source = r"
+const client = createClient();
+
+const QUERY = client.gql`query FromClient { field }`;
+const OTHER = other.gql`query FromOther { field }`;
+";
+            let mut config = ExtractConfig::default();
+            config.tag_identifiers.push("client.gql".to_string());
+            let result = extract_from_source(source, Language::TypeScript, &config).unwrap();
+
+            // Only the configured object matches, without an import
+            assert_eq!(result.len(), 1);
+            assert_eq!(result[0].source, "query FromClient { field }");
+            assert_eq!(result[0].tag_name, Some("client.gql".to_string()));
+
+            // Scanning a file that doesn't parse matches the same tags
+            let broken = format!("{source}const broken = %%%invalid%%%;\n");
+            let lenient = extract_from_source(&broken, Language::TypeScript, &config).unwrap();
+            assert_eq!(lenient, result);
+        }
+
+        #[test]
+        fn test_extract_call_expression_tags() {
+            let source = r"
+import { parse } from 'graphql-tag';
+
+const QUERY = parse(`query Parsed { field }`);
+const CLIENT_QUERY = apollo.gql(`query FromClient { field }`);
+const IGNORED = unrelated(`query Ignored { field }`);
+";
+            let mut config = ExtractConfig::default();
+            config.tag_identifiers = vec!["parse".to_string(), "apollo.gql".to_string()];
+            let result = extract_from_source(source, Language::TypeScript, &config).unwrap();
+
+            assert_eq!(result.len(), 2);
+            assert_eq!(result[0].source, "query Parsed { field }");
+            assert_eq!(result[0].tag_name, Some("parse".to_string()));
+            assert_eq!(result[1].source, "query FromClient { field }");
+            assert_eq!(result[1].tag_name, Some("apollo.gql".to_string()));
+        }
+
         #[test]
         fn test_import_from_unknown_module() {
             let source = r"
@@ -777,7 +1180,10 @@ class UserQuery {
         fn test_parse_error_handling() {
             let source = "import { gql } from 'graphql-tag'; const x = %%%invalid%%%";
 
-            let config = ExtractConfig::default();
+            let config = ExtractConfig {
+                strict: true,
+                ..Default::default()
+            };
             let result = extract_from_source(source, Language::TypeScript, &config);
 
             assert!(result.is_err());
@@ -788,6 +1194,96 @@ class UserQuery {
             }
         }
 
+        #[test]
+        fn test_lenient_extraction_with_unrelated_syntax_error() {
+            let valid = r"
+import { gql } from '@apollo/client';
+
+export const GET_USER = gql`
+  query GetUser {
+    user { id }
+  }
+`;
+";
+            let source = format!(
+                "{valid}
+const other = notGql`query Other {{ field }}`;
+const broken = %%%invalid%%%;
+"
+            );
+
+            let config = ExtractConfig::default();
+            let result = extract_from_source(&source, Language::TypeScript, &config).unwrap();
+
+            // Same block, at the same location, as when the file parses
+            let expected = extract_from_source(valid, Language::TypeScript, &config).unwrap();
+            assert_eq!(result, expected);
+            assert_eq!(result[0].tag_name, Some("gql".to_string()));
+
+            let strict = ExtractConfig {
+                strict: true,
+                ..Default::default()
+            };
+            assert!(extract_from_source(&source, Language::TypeScript, &strict).is_err());
+        }
+
+        #[test]
+        fn test_interpolations_are_blanked() {
+            let source = r"
+import { gql } from '@apollo/client';
+
+const GET_USER = gql`
+  query GetUser {
+    user {
+      ...UserFields
+      ...${POST_FRAGMENT}
+    }
+    users(first: ${PAGE_SIZE}) { id }
+  }
+  ${USER_FRAGMENT}
+`;
+";
+            let config = ExtractConfig::default();
+            let result = extract_from_source(source, Language::TypeScript, &config).unwrap();
+
+            assert_eq!(result.len(), 1);
+            let item = &result[0];
+            assert!(!item.source.contains("${"));
+            assert!(item
+                .source
+                .contains(&format!("...UserFields\n{}\n", " ".repeat(25))));
+
+            // Blanking keeps the length, so positions in the GraphQL match the file
+            let original = &source[item.location.offset..][..item.location.length];
+            assert_eq!(item.source.len(), original.len());
+            assert_eq!(item.source.lines().count(), original.lines().count());
+
+            let blanked: Vec<_> = item
+                .interpolations
+                .iter()
+                .map(|interpolation| {
+                    (
+                        &original[interpolation.offset..][..interpolation.length],
+                        interpolation.expression.as_str(),
+                        interpolation.is_fragment,
+                    )
+                })
+                .collect();
+            assert_eq!(
+                blanked,
+                vec![
+                    ("...${POST_FRAGMENT}", "POST_FRAGMENT", true),
+                    ("${PAGE_SIZE}", "PAGE_SIZE", false),
+                    ("${USER_FRAGMENT}", "USER_FRAGMENT", true),
+                ]
+            );
+
+            // Scanning a file that doesn't parse finds the same interpolations
+            let broken = format!("{source}const broken = %%%invalid%%%;\n");
+            let lenient = extract_from_source(&broken, Language::TypeScript, &config).unwrap();
+            assert_eq!(lenient, result);
+        }
+
         #[test]
         fn test_multiline_query_formatting() {
             let source = r"
@@ -958,5 +1454,84 @@ const document = gql(`
             assert!(result[0].source.contains("query GetPosts"));
             assert_eq!(result[0].tag_name, Some("gql".to_string()));
         }
+
+        #[test]
+        fn test_extract_tagged_template_inside_hook_call() {
+            let source = r"
+import { gql } from '@apollo/client';
+
+export function useUser() {
+  return useQuery(gql`
+    query GetUser {
+      user {
+        id
+      }
+    }
+  `);
+}
+";
+            let config = ExtractConfig::default();
+            let result = extract_from_source(source, Language::TypeScript, &config).unwrap();
+
+            assert_eq!(result.len(), 1);
+            assert!(result[0].source.contains("query GetUser"));
+            assert_eq!(result[0].tag_name, Some("gql".to_string()));
+        }
+
+        #[test]
+        fn test_extract_tagged_template_inside_object_argument() {
+            let source = r"
+import { gql } from '@apollo/client';
+
+async function load() {
+  const { data } = await client.query({
+    query: gql`
+      query GetPosts {
+        posts {
+          id
+        }
+      }
+    `,
+    variables: { first: 10 },
+  });
+  return data;
+}
+";
+            let config = ExtractConfig::default();
+            let result = extract_from_source(source, Language::TypeScript, &config).unwrap();
+
+            assert_eq!(result.len(), 1);
+            assert!(result[0].source.contains("query GetPosts"));
+            assert_eq!(result[0].tag_name, Some("gql".to_string()));
+        }
+
+        #[test]
+        fn test_extract_tagged_template_inside_jsx() {
+            let source = r"
+import { gql } from '@apollo/client';
+
+export const UserName = () => (
+  <Query query={gql`
+    query GetUserName {
+      user {
+        name
+      }
+    }
+  `}>
+    {({ data }) => <span>{data.user.name}</span>}
+  </Query>
+);
+";
+            let config = ExtractConfig::default();
+            let result = extract_from_source(source, Language::TypeScript, &config).unwrap();
+
+            assert_eq!(result.len(), 1);
+            assert!(result[0].source.contains("query GetUserName"));
+            let offset = result[0].location.offset;
+            assert_eq!(
+                &source[offset..offset + result[0].location.length],
+                result[0].source
+            );
+        }
     }
 }
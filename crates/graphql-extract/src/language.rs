@@ -78,6 +78,10 @@ mod tests {
             Language::from_path(&PathBuf::from("component.vue")),
             Some(Language::Vue)
         );
+        assert_eq!(
+            Language::from_path(&PathBuf::from("Component.svelte")),
+            Some(Language::Svelte)
+        );
         assert_eq!(Language::from_path(&PathBuf::from("README.md")), None);
     }
 
@@ -4,6 +4,8 @@ mod language;
 mod source_location;
 
 pub use error::{ExtractError, Result};
-pub use extractor::{extract_from_file, extract_from_source, ExtractConfig, ExtractedGraphQL};
+pub use extractor::{
+    extract_from_file, extract_from_source, ExtractConfig, ExtractedGraphQL, Interpolation,
+};
 pub use language::Language;
 pub use source_location::{Position, Range, SourceLocation};
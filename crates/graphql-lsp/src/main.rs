@@ -1,27 +1,43 @@
 mod server;
+mod trace;
 
 use server::GraphQLLanguageServer;
 use tower_lsp_server::{LspService, Server};
+use trace::TraceLevel;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
 
 #[tokio::main]
 async fn main() {
+    // The filter can be changed at runtime by the client through `$/setTrace`
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"));
+    let default_directives = filter.to_string();
+    let (filter, filter_handle) = tracing_subscriber::reload::Layer::new(filter);
+
     // IMPORTANT: LSP uses stdin/stdout for JSON-RPC communication
     // All logs MUST go to stderr to avoid breaking the protocol
-    tracing_subscriber::fmt()
-        .with_writer(std::io::stderr)
-        .with_ansi(false) // Disable ANSI colors since LSP output doesn't support them
-        .with_target(true) // Include module target in logs for better filtering
-        .with_thread_ids(true) // Include thread IDs for async debugging
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"))
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(std::io::stderr)
+                .with_ansi(false) // Disable ANSI colors since LSP output doesn't support them
+                .with_target(true) // Include module target in logs for better filtering
+                .with_thread_ids(true), // Include thread IDs for async debugging
         )
         .init();
 
+    let trace_level = TraceLevel::new(filter_handle, default_directives);
+
     let stdin = tokio::io::stdin();
     let stdout = tokio::io::stdout();
 
-    let (service, socket) = LspService::new(GraphQLLanguageServer::new);
+    let (service, socket) = LspService::build(|client| {
+        GraphQLLanguageServer::new(client).with_trace_level(trace_level)
+    })
+    .custom_method("$/setTrace", GraphQLLanguageServer::set_trace)
+    .finish();
 
     Server::new(stdin, stdout, socket).serve(service).await;
 }
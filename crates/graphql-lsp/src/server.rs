@@ -1,17 +1,37 @@
+use crate::trace::TraceLevel;
 use dashmap::DashMap;
-use graphql_config::{find_config, load_config};
-use graphql_project::GraphQLProject;
+use graphql_config::{config_file_names, find_config, load_config};
+use graphql_project::{
+    DocumentSymbolKind, GraphQLProject, LineIndex, OperationType, PositionEncoding,
+    SemanticTokenKind, TypeKind, WorkspaceSymbolKind,
+};
 use lsp_types::{
-    CompletionOptions, CompletionParams, CompletionResponse, Diagnostic, DiagnosticSeverity,
-    DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
-    DidSaveTextDocumentParams, DocumentSymbolParams, DocumentSymbolResponse, GotoDefinitionParams,
-    GotoDefinitionResponse, Hover, HoverParams, HoverProviderCapability, InitializeParams,
-    InitializeResult, InitializedParams, Location, MessageType, OneOf, Position, Range,
-    ReferenceParams, ServerCapabilities, ServerInfo, SymbolInformation, TextDocumentSyncCapability,
-    TextDocumentSyncKind, Uri, WorkspaceSymbol, WorkspaceSymbolParams,
+    CodeAction, CodeActionKind, CodeActionOptions, CodeActionOrCommand, CodeActionParams,
+    CodeActionProviderCapability, CodeActionResponse, CompletionOptions, CompletionParams,
+    CompletionResponse, CreateFile, CreateFileOptions, Diagnostic, DiagnosticSeverity,
+    DidChangeTextDocumentParams, DidChangeWatchedFilesParams,
+    DidChangeWatchedFilesRegistrationOptions, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, DidSaveTextDocumentParams, DocumentChangeOperation, DocumentChanges,
+    DocumentFormattingParams, DocumentHighlight, DocumentHighlightKind, DocumentHighlightParams,
+    DocumentSymbol, DocumentSymbolParams, DocumentSymbolResponse, Documentation, FileSystemWatcher,
+    FoldingRange, FoldingRangeKind, FoldingRangeParams, FoldingRangeProviderCapability,
+    GlobPattern, GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverParams,
+    HoverProviderCapability, ImplementationProviderCapability, InitializeParams, InitializeResult,
+    InitializedParams, InlayHint, InlayHintKind, InlayHintLabel, InlayHintParams, Location,
+    MessageType, OneOf, OptionalVersionedTextDocumentIdentifier, ParameterInformation,
+    ParameterLabel, Position, PositionEncodingKind, PrepareRenameResponse, Range, ReferenceParams,
+    Registration, RenameOptions, RenameParams, ResourceOp, SemanticToken, SemanticTokenType,
+    SemanticTokens, SemanticTokensFullOptions, SemanticTokensLegend, SemanticTokensOptions,
+    SemanticTokensParams, SemanticTokensResult, SemanticTokensServerCapabilities,
+    ServerCapabilities, ServerInfo, SetTraceParams, SignatureHelp, SignatureHelpOptions,
+    SignatureHelpParams, SignatureInformation, SymbolInformation, SymbolKind,
+    TextDocumentContentChangeEvent, TextDocumentEdit, TextDocumentPositionParams,
+    TextDocumentSyncCapability, TextDocumentSyncKind, TextEdit, Unregistration, Uri,
+    WorkDoneProgressOptions, WorkspaceEdit, WorkspaceSymbol, WorkspaceSymbolParams,
 };
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
 use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
 use tower_lsp_server::jsonrpc::Result;
@@ -20,8 +40,49 @@ use tower_lsp_server::{Client, LanguageServer, UriExt};
 /// Debounce delay for validation in milliseconds
 const VALIDATION_DEBOUNCE_MS: u64 = 200;
 
+/// Code action kind of the source action that splits operations into files
+const SPLIT_OPERATIONS_KIND: &str = "source.splitOperations";
+
+/// Registration ID for watching config and schema files
+const WATCHED_FILES_REGISTRATION_ID: &str = "graphql-watched-files";
+
+/// LSP method of the watched files notification
+const DID_CHANGE_WATCHED_FILES: &str = "workspace/didChangeWatchedFiles";
+
+/// Semantic token types advertised in `initialize`, indexed by `semantic_token_type`
+const SEMANTIC_TOKEN_TYPES: [SemanticTokenType; 7] = [
+    SemanticTokenType::TYPE,
+    SemanticTokenType::PROPERTY,
+    SemanticTokenType::PARAMETER,
+    SemanticTokenType::VARIABLE,
+    SemanticTokenType::CLASS,
+    SemanticTokenType::DECORATOR,
+    SemanticTokenType::ENUM_MEMBER,
+];
+
+/// Sort diagnostics by start position, then code, so they are published in a stable order
+fn sort_diagnostics(diagnostics: &mut [Diagnostic]) {
+    let code = |diagnostic: &Diagnostic| match &diagnostic.code {
+        Some(lsp_types::NumberOrString::String(code)) => code.clone(),
+        Some(lsp_types::NumberOrString::Number(code)) => code.to_string(),
+        None => String::new(),
+    };
+    diagnostics.sort_by(|a, b| {
+        (a.range.start.line, a.range.start.character)
+            .cmp(&(b.range.start.line, b.range.start.character))
+            .then_with(|| code(a).cmp(&code(b)))
+    });
+}
+
+/// Debounced validation of a document, waiting to run or running
+struct PendingValidation {
+    /// Distinguishes this validation from those scheduled later for the same document
+    id: u64,
+    handle: JoinHandle<()>,
+}
+
 /// Type alias for validation task handle
-type ValidationTask = Arc<Mutex<Option<JoinHandle<()>>>>;
+type ValidationTask = Arc<Mutex<Option<PendingValidation>>>;
 
 pub struct GraphQLLanguageServer {
     client: Client,
@@ -36,6 +97,12 @@ pub struct GraphQLLanguageServer {
     /// Pending validation tasks (URI -> `JoinHandle`) for debouncing
     /// Each document can have at most one pending validation task
     validation_tasks: Arc<DashMap<String, ValidationTask>>,
+    /// Source of `PendingValidation` ids
+    next_validation_id: Arc<AtomicU64>,
+    /// Log filter the client can adjust through `$/setTrace`
+    trace_level: Option<TraceLevel>,
+    /// Position encoding negotiated with the client in `initialize`
+    position_encoding: Arc<OnceLock<PositionEncoding>>,
 }
 
 impl GraphQLLanguageServer {
@@ -47,7 +114,400 @@ impl GraphQLLanguageServer {
             projects: Arc::new(DashMap::new()),
             document_cache: Arc::new(DashMap::new()),
             validation_tasks: Arc::new(DashMap::new()),
+            next_validation_id: Arc::new(AtomicU64::new(0)),
+            trace_level: None,
+            position_encoding: Arc::new(OnceLock::new()),
+        }
+    }
+
+    /// Let the client adjust log verbosity through `$/setTrace`
+    #[must_use]
+    pub fn with_trace_level(mut self, trace_level: TraceLevel) -> Self {
+        self.trace_level = Some(trace_level);
+        self
+    }
+
+    /// Handle `$/setTrace`, switching the log filter to match the client's trace level
+    // Custom methods are registered with tower-lsp as async handlers
+    #[allow(clippy::unused_async)]
+    pub async fn set_trace(&self, params: SetTraceParams) {
+        let Some(ref trace_level) = self.trace_level else {
+            return;
+        };
+
+        tracing::info!(value = ?params.value, "Setting trace level");
+        if let Err(e) = trace_level.set(params.value) {
+            tracing::warn!("Failed to set trace level: {e}");
+        }
+    }
+
+    /// Position encoding negotiated in `initialize`, or UTF-16 before then
+    fn position_encoding(&self) -> PositionEncoding {
+        self.position_encoding.get().copied().unwrap_or_default()
+    }
+
+    /// Pick the position encoding to use for this session
+    ///
+    /// Takes the first encoding in the client's preference list that we support, falling
+    /// back to UTF-16 which every client must support.
+    fn negotiate_position_encoding(params: &InitializeParams) -> PositionEncoding {
+        params
+            .capabilities
+            .general
+            .as_ref()
+            .and_then(|general| general.position_encodings.as_ref())
+            .and_then(|encodings| {
+                encodings.iter().find_map(|kind| match kind.as_str() {
+                    "utf-8" => Some(PositionEncoding::Utf8),
+                    "utf-16" => Some(PositionEncoding::Utf16),
+                    "utf-32" => Some(PositionEncoding::Utf32),
+                    _ => None,
+                })
+            })
+            .unwrap_or_default()
+    }
+
+    /// Build a goto-definition response from resolved locations
+    ///
+    /// A single location is returned as a scalar so clients jump straight to it instead of
+    /// showing a one-item peek list.
+    fn definition_response(mut locations: Vec<Location>) -> Option<GotoDefinitionResponse> {
+        match locations.len() {
+            0 => None,
+            1 => locations.pop().map(GotoDefinitionResponse::Scalar),
+            _ => Some(GotoDefinitionResponse::Array(locations)),
+        }
+    }
+
+    /// Message explaining a goto-definition miss on a name from a remote schema
+    ///
+    /// Remote schemas have no file to navigate to, so instead of returning a location that
+    /// doesn't exist we point the user at the hover, which still has the definition's
+    /// details. Returns `None` when the miss has nothing to do with a remote schema.
+    fn remote_definition_message(
+        project: &GraphQLProject,
+        uri: &Uri,
+        position: Position,
+        content: &str,
+    ) -> Option<String> {
+        if !project.has_remote_schema() {
+            return None;
+        }
+
+        let file_path = uri
+            .to_file_path()
+            .map_or_else(|| uri.to_string(), |path| path.display().to_string());
+        let position = graphql_project::Position {
+            line: position.line as usize,
+            character: position.character as usize,
+        };
+        project.hover_info_at_position(&file_path, position, content)?;
+
+        Some(
+            "This is defined in a remote schema, which has no local file to open. Hover it to \
+             see its definition."
+                .to_string(),
+        )
+    }
+
+    /// Convert a document symbol from graphql-project to its LSP representation
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(deprecated)]
+    fn convert_document_symbol(symbol: graphql_project::DocumentSymbol) -> DocumentSymbol {
+        let convert_range = |range: graphql_project::Range| Range {
+            start: Position {
+                line: range.start.line as u32,
+                character: range.start.character as u32,
+            },
+            end: Position {
+                line: range.end.line as u32,
+                character: range.end.character as u32,
+            },
+        };
+
+        let kind = match symbol.kind {
+            DocumentSymbolKind::Operation(OperationType::Query) => SymbolKind::FUNCTION,
+            DocumentSymbolKind::Operation(OperationType::Mutation) => SymbolKind::METHOD,
+            DocumentSymbolKind::Operation(OperationType::Subscription) => SymbolKind::EVENT,
+            DocumentSymbolKind::Fragment => SymbolKind::CLASS,
+            DocumentSymbolKind::Field => SymbolKind::FIELD,
+        };
+
+        let children: Vec<DocumentSymbol> = symbol
+            .children
+            .into_iter()
+            .map(Self::convert_document_symbol)
+            .collect();
+
+        DocumentSymbol {
+            name: symbol.name,
+            detail: symbol.detail,
+            kind,
+            tags: None,
+            deprecated: None,
+            range: convert_range(symbol.range),
+            selection_range: convert_range(symbol.selection_range),
+            children: (!children.is_empty()).then_some(children),
+        }
+    }
+
+    /// Index of a semantic token kind in `SEMANTIC_TOKEN_TYPES`
+    const fn semantic_token_type(kind: SemanticTokenKind) -> u32 {
+        match kind {
+            SemanticTokenKind::Type => 0,
+            SemanticTokenKind::Field => 1,
+            SemanticTokenKind::Argument => 2,
+            SemanticTokenKind::Variable => 3,
+            SemanticTokenKind::Fragment => 4,
+            SemanticTokenKind::Directive => 5,
+            SemanticTokenKind::EnumValue => 6,
+        }
+    }
+
+    /// Encode semantic tokens from graphql-project as LSP relative deltas
+    ///
+    /// Each token's line is relative to the previous token, and so is its start character
+    /// when both are on the same line. Tokens must be in document order.
+    #[allow(clippy::cast_possible_truncation)]
+    fn encode_semantic_tokens(tokens: &[graphql_project::SemanticToken]) -> Vec<SemanticToken> {
+        let mut previous = graphql_project::Position {
+            line: 0,
+            character: 0,
+        };
+
+        tokens
+            .iter()
+            .map(|token| {
+                let delta_line = token.start.line - previous.line;
+                let delta_start = if delta_line == 0 {
+                    token.start.character - previous.character
+                } else {
+                    token.start.character
+                };
+                previous = token.start;
+
+                SemanticToken {
+                    delta_line: delta_line as u32,
+                    delta_start: delta_start as u32,
+                    length: token.length as u32,
+                    token_type: Self::semantic_token_type(token.kind),
+                    token_modifiers_bitset: 0,
+                }
+            })
+            .collect()
+    }
+
+    /// Resolve a file path from a project index to a URI
+    ///
+    /// Paths may already be URIs, absolute paths, or paths relative to the workspace root.
+    fn resolve_file_uri(workspace_uri: &str, file_path: &str) -> Option<Uri> {
+        if file_path.starts_with("file://") {
+            return file_path.parse::<Uri>().ok();
+        }
+
+        let path = if std::path::Path::new(file_path).is_absolute() {
+            std::path::PathBuf::from(file_path)
+        } else {
+            let workspace_path: Uri = workspace_uri.parse().ok()?;
+            workspace_path.to_file_path()?.join(file_path)
+        };
+
+        Uri::from_file_path(&path)
+    }
+
+    /// Open documents that belong to a project, keyed by file path
+    ///
+    /// The keys match the paths in the project's `DocumentIndex`, so an open document and
+    /// its indexed copy are recognized as the same file.
+    fn project_documents(&self, workspace_uri: &str, project_idx: usize) -> Vec<(String, String)> {
+        self.document_cache
+            .iter()
+            .filter_map(|entry| {
+                let uri: Uri = entry.key().parse().ok()?;
+                let (document_workspace, document_project) =
+                    self.find_workspace_and_project(&uri)?;
+                if document_workspace != workspace_uri || document_project != project_idx {
+                    return None;
+                }
+                let path = uri.to_file_path()?;
+                Some((path.display().to_string(), entry.value().clone()))
+            })
+            .collect()
+    }
+
+    /// Convert a range from graphql-project to its LSP representation
+    #[allow(clippy::cast_possible_truncation)]
+    const fn convert_range(range: graphql_project::Range) -> Range {
+        Range {
+            start: Position {
+                line: range.start.line as u32,
+                character: range.start.character as u32,
+            },
+            end: Position {
+                line: range.end.line as u32,
+                character: range.end.character as u32,
+            },
+        }
+    }
+
+    /// Build the source action that moves a document's operations into files of their own
+    ///
+    /// Each new file is created next to the document, named after its operation and with
+    /// the document's extension. Files are never overwritten, so the edit fails rather than
+    /// clobbering an existing file of the same name.
+    fn split_operations_action(
+        uri: &Uri,
+        split: graphql_project::SplitOperations,
+    ) -> Option<CodeAction> {
+        let path = uri.to_file_path()?;
+        let extension = path.extension().map_or_else(
+            || "graphql".to_string(),
+            |ext| ext.to_string_lossy().to_string(),
+        );
+
+        let mut operations = Vec::new();
+        for operation in split.operations {
+            let new_uri = Uri::from_file_path(
+                path.with_file_name(format!("{}.{extension}", operation.name)),
+            )?;
+            operations.push(DocumentChangeOperation::Op(ResourceOp::Create(
+                CreateFile {
+                    uri: new_uri.clone(),
+                    options: Some(CreateFileOptions {
+                        overwrite: Some(false),
+                        ignore_if_exists: Some(false),
+                    }),
+                    annotation_id: None,
+                },
+            )));
+            operations.push(DocumentChangeOperation::Edit(TextDocumentEdit {
+                text_document: OptionalVersionedTextDocumentIdentifier {
+                    uri: new_uri,
+                    version: None,
+                },
+                edits: vec![OneOf::Left(TextEdit {
+                    range: Range::default(),
+                    new_text: operation.content,
+                })],
+            }));
+        }
+
+        operations.push(DocumentChangeOperation::Edit(TextDocumentEdit {
+            text_document: OptionalVersionedTextDocumentIdentifier {
+                uri: uri.clone(),
+                version: None,
+            },
+            edits: split
+                .edits
+                .into_iter()
+                .map(|edit| {
+                    OneOf::Left(TextEdit {
+                        range: Self::convert_range(edit.range),
+                        new_text: edit.new_text,
+                    })
+                })
+                .collect(),
+        }));
+
+        Some(CodeAction {
+            title: "Split operations into files".to_string(),
+            kind: Some(CodeActionKind::new(SPLIT_OPERATIONS_KIND)),
+            edit: Some(WorkspaceEdit {
+                document_changes: Some(DocumentChanges::Operations(operations)),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+    }
+
+    /// Convert a workspace symbol from graphql-project to its LSP representation
+    #[allow(clippy::cast_possible_truncation)]
+    fn convert_workspace_symbol(
+        symbol: graphql_project::WorkspaceSymbol,
+        uri: Uri,
+    ) -> WorkspaceSymbol {
+        let (kind, container_name) = match symbol.kind {
+            WorkspaceSymbolKind::Operation(OperationType::Query) => (SymbolKind::FUNCTION, "query"),
+            WorkspaceSymbolKind::Operation(OperationType::Mutation) => {
+                (SymbolKind::METHOD, "mutation")
+            }
+            WorkspaceSymbolKind::Operation(OperationType::Subscription) => {
+                (SymbolKind::EVENT, "subscription")
+            }
+            WorkspaceSymbolKind::Fragment => (SymbolKind::CLASS, "fragment"),
+            WorkspaceSymbolKind::Type(TypeKind::Object) => (SymbolKind::STRUCT, "type"),
+            WorkspaceSymbolKind::Type(TypeKind::Interface) => (SymbolKind::INTERFACE, "interface"),
+            WorkspaceSymbolKind::Type(TypeKind::Union) => (SymbolKind::ENUM, "union"),
+            WorkspaceSymbolKind::Type(TypeKind::Enum) => (SymbolKind::ENUM, "enum"),
+            WorkspaceSymbolKind::Type(TypeKind::InputObject) => (SymbolKind::STRUCT, "input"),
+            WorkspaceSymbolKind::Type(TypeKind::Scalar) => (SymbolKind::TYPE_PARAMETER, "scalar"),
+            WorkspaceSymbolKind::Field { ref type_name } => (SymbolKind::FIELD, type_name.as_str()),
+            WorkspaceSymbolKind::Directive => (SymbolKind::FUNCTION, "directive"),
+        };
+
+        WorkspaceSymbol {
+            name: symbol.name,
+            kind,
+            tags: None,
+            container_name: Some(container_name.to_string()),
+            location: OneOf::Left(Location {
+                uri,
+                range: Range {
+                    start: Position {
+                        line: symbol.range.start.line as u32,
+                        character: symbol.range.start.character as u32,
+                    },
+                    end: Position {
+                        line: symbol.range.end.line as u32,
+                        character: symbol.range.end.character as u32,
+                    },
+                },
+            }),
+            data: None,
+        }
+    }
+
+    /// Apply a content change from `did_change` to a cached document
+    ///
+    /// Changes with a range replace just that range. Changes without one replace the whole
+    /// document, as in full sync mode.
+    fn apply_content_change(
+        text: &mut String,
+        change: TextDocumentContentChangeEvent,
+        encoding: PositionEncoding,
+    ) {
+        let Some(range) = change.range else {
+            *text = change.text;
+            return;
+        };
+
+        let line_index = LineIndex::with_encoding(text, encoding);
+        let start = Self::clamped_offset(text, &line_index, range.start);
+        let end = Self::clamped_offset(text, &line_index, range.end).max(start);
+        text.replace_range(start..end, &change.text);
+    }
+
+    /// Convert an LSP position to a byte offset, clamped to the end of its line
+    ///
+    /// Clients may send a character past the end of a line to mean the line end, and a
+    /// line past the end of the document to mean the document end.
+    fn clamped_offset(text: &str, line_index: &LineIndex, position: Position) -> usize {
+        let line = position.line as usize;
+        if line_index.line_start(line).is_none() {
+            return text.len();
         }
+
+        // Exclude the line's newline so an overlong character can't spill onto the next line
+        let line_end = line_index
+            .line_start(line + 1)
+            .map_or(text.len(), |next_line_start| next_line_start - 1);
+
+        line_index
+            .position_to_offset(graphql_project::Position {
+                line,
+                character: position.character as usize,
+            })
+            .map_or(line_end, |offset| offset.min(line_end))
     }
 
     /// Load GraphQL config from a workspace folder
@@ -67,6 +527,14 @@ impl GraphQLLanguageServer {
                         match GraphQLProject::from_config_with_base(&config, workspace_path) {
                             Ok(projects) => {
                                 tracing::info!(count = projects.len(), "Loaded GraphQL projects");
+                                let projects: Vec<_> = projects
+                                    .into_iter()
+                                    .map(|(name, project)| {
+                                        let project = project
+                                            .with_position_encoding(self.position_encoding());
+                                        (name, project)
+                                    })
+                                    .collect();
 
                                 // Load schemas and documents for all projects
                                 for (name, project) in &projects {
@@ -76,8 +544,10 @@ impl GraphQLLanguageServer {
                                             error = %e,
                                             "Failed to load schema"
                                         );
+                                        // Shown rather than logged, since every document
+                                        // fails to validate without a schema
                                         self.client
-                                            .log_message(
+                                            .show_message(
                                                 MessageType::ERROR,
                                                 format!("Failed to load schema for project '{name}': {e}"),
                                             )
@@ -159,6 +629,102 @@ impl GraphQLLanguageServer {
         }
     }
 
+    /// Ask the client to watch the config and schema files of every workspace
+    ///
+    /// Replaces any earlier registration, since the schema files can change when the
+    /// config does.
+    async fn register_file_watchers(&self) {
+        let config_names: Vec<_> = config_file_names().collect();
+        let mut watchers = vec![FileSystemWatcher {
+            glob_pattern: GlobPattern::String(format!("**/{{{}}}", config_names.join(","))),
+            kind: None,
+        }];
+        for workspace_projects in self.projects.iter() {
+            for (_, project) in workspace_projects.value() {
+                watchers.extend(
+                    project
+                        .schema_globs()
+                        .into_iter()
+                        .map(|glob| FileSystemWatcher {
+                            glob_pattern: GlobPattern::String(glob),
+                            kind: None,
+                        }),
+                );
+            }
+        }
+
+        // Fails when nothing was registered yet, which is fine
+        let _ = self
+            .client
+            .unregister_capability(vec![Unregistration {
+                id: WATCHED_FILES_REGISTRATION_ID.to_string(),
+                method: DID_CHANGE_WATCHED_FILES.to_string(),
+            }])
+            .await;
+
+        let registration = Registration {
+            id: WATCHED_FILES_REGISTRATION_ID.to_string(),
+            method: DID_CHANGE_WATCHED_FILES.to_string(),
+            register_options: serde_json::to_value(DidChangeWatchedFilesRegistrationOptions {
+                watchers,
+            })
+            .ok(),
+        };
+        if let Err(e) = self.client.register_capability(vec![registration]).await {
+            tracing::warn!("Failed to register file watchers: {e}");
+        }
+    }
+
+    /// Reload the config and projects of the workspaces containing the changed files
+    ///
+    /// New projects are fully loaded before they replace the old ones, so requests handled
+    /// meanwhile see either the old schema and document indexes or the new ones, never a
+    /// half-loaded state. Diagnostics are then re-published for every open document.
+    async fn reload_workspaces(&self, changed_paths: &[PathBuf]) {
+        let workspaces: Vec<(String, PathBuf)> = self
+            .init_workspace_folders
+            .iter()
+            .filter(|entry| {
+                changed_paths
+                    .iter()
+                    .any(|path| path.starts_with(entry.value()))
+            })
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect();
+        if workspaces.is_empty() {
+            return;
+        }
+
+        for (uri, path) in &workspaces {
+            tracing::info!(workspace = %uri, "Reloading GraphQL config");
+            self.load_workspace_config(uri, path).await;
+        }
+        self.register_file_watchers().await;
+
+        // Open schema files go first, so their unsaved content is in the schema the other
+        // documents are validated against
+        let open_schema_files: Vec<(Uri, String)> = self
+            .document_cache
+            .iter()
+            .filter_map(|entry| {
+                let uri = serde_json::from_str::<Uri>(&format!("\"{}\"", entry.key())).ok()?;
+                let path = uri.to_file_path()?.into_owned();
+                let is_schema = self.projects.iter().any(|workspace_projects| {
+                    workspace_projects
+                        .value()
+                        .iter()
+                        .any(|(_, project)| project.is_schema_file(&path))
+                });
+                is_schema.then(|| (uri, entry.value().clone()))
+            })
+            .collect();
+        for (uri, content) in open_schema_files {
+            self.validate_document_impl(uri, &content, false).await;
+        }
+
+        self.revalidate_all_documents().await;
+    }
+
     /// Find the workspace and project for a given document URI
     fn find_workspace_and_project(&self, document_uri: &Uri) -> Option<(String, usize)> {
         let doc_path = document_uri.to_file_path()?;
@@ -169,9 +735,23 @@ impl GraphQLLanguageServer {
             let workspace_path = workspace_entry.value();
 
             if doc_path.as_ref().starts_with(workspace_path.as_path()) {
-                // Found the workspace, return the workspace URI and project index (0 for now)
-                // TODO: Match document to correct project based on includes/excludes
-                return Some((workspace_uri.clone(), 0));
+                // Use the project that most specifically claims this file through its
+                // documents, schema, or include patterns, so fragments and types resolve
+                // against that project. Ties go to the project listed first, and files no
+                // project claims fall back to the first project.
+                let mut best: Option<(usize, usize)> = None;
+                if let Some(projects) = self.projects.get(workspace_uri) {
+                    for (idx, (_, project)) in projects.iter().enumerate() {
+                        let Some(specificity) = project.match_specificity(doc_path.as_ref()) else {
+                            continue;
+                        };
+                        if best.is_none_or(|(_, best_specificity)| specificity > best_specificity) {
+                            best = Some((idx, specificity));
+                        }
+                    }
+                }
+                let project_idx = best.map_or(0, |(idx, _)| idx);
+                return Some((workspace_uri.clone(), project_idx));
             }
         }
 
@@ -464,7 +1044,7 @@ impl GraphQLLanguageServer {
 
                 // Publish project-wide lint diagnostics for the schema file
                 // This includes unused_fields warnings
-                let schema_diagnostics = {
+                let mut schema_diagnostics = {
                     let Some(projects) = self.projects.get(&workspace_uri) else {
                         tracing::warn!("No projects loaded for workspace: {workspace_uri}");
                         return;
@@ -479,10 +1059,20 @@ impl GraphQLLanguageServer {
 
                     file_path.as_ref().map_or_else(Vec::new, |path| {
                         let file_path_str = path.display().to_string();
-                        self.get_project_wide_diagnostics(&file_path_str, project)
+                        let mut diagnostics =
+                            self.get_project_wide_diagnostics(&file_path_str, project);
+                        // Schema build and validation errors found in this file
+                        diagnostics.extend(
+                            project
+                                .validate_schema_source(&file_path_str)
+                                .into_iter()
+                                .map(|diag| self.convert_project_diagnostic(diag)),
+                        );
+                        diagnostics
                     })
                 };
 
+                sort_diagnostics(&mut schema_diagnostics);
                 self.client
                     .publish_diagnostics(uri.clone(), schema_diagnostics, None)
                     .await;
@@ -517,7 +1107,7 @@ impl GraphQLLanguageServer {
             .and_then(|path| {
                 path.extension()
                     .and_then(|ext| ext.to_str())
-                    .map(|ext| matches!(ext, "ts" | "tsx" | "js" | "jsx"))
+                    .map(|ext| matches!(ext, "ts" | "tsx" | "js" | "jsx" | "svelte"))
             })
             .unwrap_or(false);
 
@@ -539,7 +1129,7 @@ impl GraphQLLanguageServer {
             if is_ts_js {
                 self.validate_typescript_document(&uri, content, project)
             } else {
-                self.validate_graphql_document(content, project)
+                self.validate_graphql_document(&uri, content, project)
             }
         }; // Drop the read lock here
 
@@ -586,6 +1176,12 @@ impl GraphQLLanguageServer {
             }
         });
 
+        if self.is_superseded(&uri, content) {
+            tracing::debug!("Document changed during validation, dropping stale diagnostics");
+            return;
+        }
+
+        sort_diagnostics(&mut diagnostics);
         self.client
             .publish_diagnostics(uri.clone(), diagnostics.clone(), None)
             .await;
@@ -742,13 +1338,13 @@ impl GraphQLLanguageServer {
             let is_ts_js = std::path::Path::new(&file_path)
                 .extension()
                 .and_then(|ext| ext.to_str())
-                .is_some_and(|ext| matches!(ext, "ts" | "tsx" | "js" | "jsx"));
+                .is_some_and(|ext| matches!(ext, "ts" | "tsx" | "js" | "jsx" | "svelte"));
 
             // Get document-specific diagnostics (type errors, etc.)
             let mut diagnostics = if is_ts_js {
                 self.validate_typescript_document(&file_uri, &content, project)
             } else {
-                self.validate_graphql_document(&content, project)
+                self.validate_graphql_document(&file_uri, &content, project)
             };
 
             // Add project-wide duplicate name diagnostics for this file
@@ -774,6 +1370,7 @@ impl GraphQLLanguageServer {
             });
 
             // Publish diagnostics for the affected file
+            sort_diagnostics(&mut diagnostics);
             tracing::debug!(
                 "Refreshing diagnostics for affected file: {} ({} diagnostics)",
                 file_path,
@@ -790,11 +1387,18 @@ impl GraphQLLanguageServer {
     #[allow(clippy::cast_possible_truncation)]
     fn validate_graphql_document(
         &self,
+        uri: &Uri,
         content: &str,
         project: &GraphQLProject,
     ) -> Vec<Diagnostic> {
+        // The file's path is needed to resolve its `#import` comments
+        let file_name = uri.to_file_path().map_or_else(
+            || "document.graphql".to_string(),
+            |path| path.display().to_string(),
+        );
+
         // Use the centralized validation logic from graphql-project
-        let project_diagnostics = project.validate_document_source(content, "document.graphql");
+        let project_diagnostics = project.validate_document_source(content, &file_name);
 
         // Convert graphql-project diagnostics to LSP diagnostics
         project_diagnostics
@@ -864,7 +1468,8 @@ impl GraphQLLanguageServer {
 
         // Run custom lints (if configured)
         let lint_config = project.get_lint_config();
-        let linter = graphql_project::Linter::new(lint_config);
+        let linter = graphql_project::Linter::new(lint_config)
+            .with_position_encoding(project.position_encoding());
         let schema_index = project.get_schema_index();
 
         for block in &extracted {
@@ -923,15 +1528,27 @@ impl GraphQLLanguageServer {
             code: diag.code.map(lsp_types::NumberOrString::String),
             source: Some(diag.source),
             message: diag.message,
+            data: diag.data.and_then(|data| serde_json::to_value(data).ok()),
             ..Default::default()
         }
     }
 
+    /// Whether `content` has been replaced by a later change to the open document at `uri`
+    ///
+    /// Validation results for superseded content are dropped rather than published, since
+    /// the validation of the newer content will publish its own.
+    fn is_superseded(&self, uri: &Uri, content: &str) -> bool {
+        self.document_cache
+            .get(&uri.to_string())
+            .is_some_and(|latest| latest.as_str() != content)
+    }
+
     /// Schedule a debounced validation for a document
     ///
     /// Cancels any pending validation for the same document and schedules a new one
-    /// after `VALIDATION_DEBOUNCE_MS` milliseconds. This prevents validation spam during
-    /// rapid typing.
+    /// after `VALIDATION_DEBOUNCE_MS` milliseconds, so a burst of changes is validated once
+    /// the user pauses. Fragment files are only revalidated if the document hasn't changed
+    /// again by the time its own validation finishes.
     async fn schedule_debounced_validation(&self, uri: Uri, content: String) {
         let uri_string = uri.to_string();
 
@@ -942,13 +1559,13 @@ impl GraphQLLanguageServer {
             .or_insert_with(|| Arc::new(Mutex::new(None)))
             .clone();
 
+        // Hold the slot while replacing its task, so concurrent changes can't both keep one
+        let mut task_guard = task_slot.lock().await;
+
         // Cancel any existing pending validation for this document
-        {
-            let mut task_guard = task_slot.lock().await;
-            if let Some(existing_task) = task_guard.take() {
-                existing_task.abort();
-                tracing::debug!(uri = ?uri, "Cancelled previous validation task");
-            }
+        if let Some(existing) = task_guard.take() {
+            existing.handle.abort();
+            tracing::debug!(uri = ?uri, "Cancelled previous validation task");
         }
 
         // Clone necessary data for the async task
@@ -959,13 +1576,18 @@ impl GraphQLLanguageServer {
             projects: self.projects.clone(),
             document_cache: self.document_cache.clone(),
             validation_tasks: self.validation_tasks.clone(),
+            next_validation_id: self.next_validation_id.clone(),
+            trace_level: self.trace_level.clone(),
+            position_encoding: self.position_encoding.clone(),
         };
 
+        let id = self.next_validation_id.fetch_add(1, Ordering::Relaxed);
+
         // Clone uri for the closure
         let uri_for_task = uri.clone();
 
         // Spawn a new debounced validation task
-        let task = tokio::spawn(async move {
+        let handle = tokio::spawn(async move {
             // Wait for the debounce period
             tokio::time::sleep(tokio::time::Duration::from_millis(VALIDATION_DEBOUNCE_MS)).await;
 
@@ -977,6 +1599,12 @@ impl GraphQLLanguageServer {
                 .await;
             tracing::debug!(uri = ?uri_for_task, elapsed_ms = validate_start.elapsed().as_millis(), "Main validation completed");
 
+            // The validation scheduled for the newer content revalidates fragment files instead
+            if server.is_superseded(&uri_for_task, &content) {
+                tracing::debug!(uri = ?uri_for_task, "Document changed during validation, skipping fragment revalidation");
+                return;
+            }
+
             // Re-validate all fragment definition files to update unused fragment warnings
             // This ensures that when fragment usage changes in one file, warnings in
             // fragment files are immediately updated
@@ -988,18 +1616,22 @@ impl GraphQLLanguageServer {
                 "Fragment revalidation completed"
             );
 
-            // Clear the task slot once validation completes
-            if let Some(slot) = server.validation_tasks.get(&uri_for_task.to_string()) {
+            // Clear the task slot once validation completes, unless a later change has
+            // already replaced this task with its own
+            let slot = server
+                .validation_tasks
+                .get(&uri_for_task.to_string())
+                .map(|slot| Arc::clone(&slot));
+            if let Some(slot) = slot {
                 let mut task_guard = slot.lock().await;
-                *task_guard = None;
+                if task_guard.as_ref().is_some_and(|pending| pending.id == id) {
+                    *task_guard = None;
+                }
             }
         });
 
         // Store the new task
-        {
-            let mut task_guard = task_slot.lock().await;
-            *task_guard = Some(task);
-        }
+        *task_guard = Some(PendingValidation { id, handle });
 
         tracing::debug!(uri = ?uri, delay_ms = VALIDATION_DEBOUNCE_MS, "Scheduled debounced validation");
     }
@@ -1021,20 +1653,71 @@ impl LanguageServer for GraphQLLanguageServer {
             }
         }
 
+        let position_encoding = Self::negotiate_position_encoding(&params);
+        if self.position_encoding.set(position_encoding).is_err() {
+            tracing::warn!("Ignoring position encoding of repeated initialize request");
+        }
+        tracing::info!(?position_encoding, "Negotiated position encoding");
+
         Ok(InitializeResult {
             capabilities: ServerCapabilities {
+                position_encoding: Some(match position_encoding {
+                    PositionEncoding::Utf8 => PositionEncodingKind::UTF8,
+                    PositionEncoding::Utf16 => PositionEncodingKind::UTF16,
+                    PositionEncoding::Utf32 => PositionEncodingKind::UTF32,
+                }),
                 text_document_sync: Some(TextDocumentSyncCapability::Kind(
-                    TextDocumentSyncKind::FULL,
+                    TextDocumentSyncKind::INCREMENTAL,
                 )),
                 completion_provider: Some(CompletionOptions {
-                    trigger_characters: Some(vec!["{".to_string(), "@".to_string()]),
+                    trigger_characters: Some(vec![
+                        "{".to_string(),
+                        "@".to_string(),
+                        "$".to_string(),
+                        ".".to_string(),
+                    ]),
                     ..Default::default()
                 }),
                 hover_provider: Some(HoverProviderCapability::Simple(true)),
+                code_action_provider: Some(CodeActionProviderCapability::Options(
+                    CodeActionOptions {
+                        code_action_kinds: Some(vec![
+                            CodeActionKind::QUICKFIX,
+                            CodeActionKind::new(SPLIT_OPERATIONS_KIND),
+                        ]),
+                        ..Default::default()
+                    },
+                )),
                 definition_provider: Some(OneOf::Left(true)),
+                implementation_provider: Some(ImplementationProviderCapability::Simple(true)),
                 references_provider: Some(OneOf::Left(true)),
+                rename_provider: Some(OneOf::Right(RenameOptions {
+                    prepare_provider: Some(true),
+                    work_done_progress_options: WorkDoneProgressOptions::default(),
+                })),
+                document_highlight_provider: Some(OneOf::Left(true)),
                 document_symbol_provider: Some(OneOf::Left(true)),
                 workspace_symbol_provider: Some(OneOf::Left(true)),
+                document_formatting_provider: Some(OneOf::Left(true)),
+                inlay_hint_provider: Some(OneOf::Left(true)),
+                folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
+                signature_help_provider: Some(SignatureHelpOptions {
+                    trigger_characters: Some(vec!["(".to_string(), ",".to_string()]),
+                    retrigger_characters: None,
+                    work_done_progress_options: WorkDoneProgressOptions::default(),
+                }),
+                semantic_tokens_provider: Some(
+                    SemanticTokensServerCapabilities::SemanticTokensOptions(
+                        SemanticTokensOptions {
+                            legend: SemanticTokensLegend {
+                                token_types: SEMANTIC_TOKEN_TYPES.to_vec(),
+                                token_modifiers: Vec::new(),
+                            },
+                            full: Some(SemanticTokensFullOptions::Bool(true)),
+                            ..Default::default()
+                        },
+                    ),
+                ),
                 ..Default::default()
             },
             server_info: Some(ServerInfo {
@@ -1060,6 +1743,8 @@ impl LanguageServer for GraphQLLanguageServer {
         for (uri, path) in folders {
             self.load_workspace_config(&uri, &path).await;
         }
+
+        self.register_file_watchers().await;
     }
 
     async fn shutdown(&self) -> Result<()> {
@@ -1085,16 +1770,18 @@ impl LanguageServer for GraphQLLanguageServer {
         let start = std::time::Instant::now();
         tracing::info!("Document changed");
 
-        // Get the latest content from changes (full sync mode)
-        for change in params.content_changes {
-            // Update the document cache
-            self.document_cache
-                .insert(uri.to_string(), change.text.clone());
+        // Apply the changes in order to the cached content (incremental sync mode)
+        let position_encoding = self.position_encoding();
+        let content = {
+            let mut entry = self.document_cache.entry(uri.to_string()).or_default();
+            for change in params.content_changes {
+                Self::apply_content_change(entry.value_mut(), change, position_encoding);
+            }
+            entry.value().clone()
+        };
 
-            // Schedule debounced validation instead of immediate validation
-            self.schedule_debounced_validation(uri.clone(), change.text.clone())
-                .await;
-        }
+        // Schedule debounced validation instead of immediate validation
+        self.schedule_debounced_validation(uri, content).await;
 
         tracing::debug!(
             elapsed_ms = start.elapsed().as_millis(),
@@ -1119,6 +1806,30 @@ impl LanguageServer for GraphQLLanguageServer {
         );
     }
 
+    async fn did_change_watched_files(&self, params: DidChangeWatchedFilesParams) {
+        // Open documents are kept up to date from the editor, so only config files and
+        // schema files changed outside the editor need a reload
+        let changed_paths: Vec<PathBuf> = params
+            .changes
+            .iter()
+            .filter_map(|change| {
+                let path = change.uri.to_file_path()?.into_owned();
+                let is_config = path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| config_file_names().any(|config| config == name));
+                let is_open = self.document_cache.contains_key(&change.uri.to_string());
+                (is_config || !is_open).then_some(path)
+            })
+            .collect();
+
+        if changed_paths.is_empty() {
+            return;
+        }
+        tracing::info!(count = changed_paths.len(), "Watched files changed");
+        self.reload_workspaces(&changed_paths).await;
+    }
+
     async fn did_close(&self, params: DidCloseTextDocumentParams) {
         tracing::info!("Document closed: {:?}", params.text_document.uri);
 
@@ -1196,6 +1907,9 @@ impl LanguageServer for GraphQLLanguageServer {
                     graphql_project::CompletionItemKind::Variable => {
                         Some(lsp_types::CompletionItemKind::VARIABLE)
                     }
+                    graphql_project::CompletionItemKind::Value => {
+                        Some(lsp_types::CompletionItemKind::VALUE)
+                    }
                 };
 
                 let documentation = item.documentation.map(|doc| {
@@ -1212,6 +1926,7 @@ impl LanguageServer for GraphQLLanguageServer {
                     documentation,
                     deprecated: Some(item.deprecated),
                     insert_text: item.insert_text,
+                    sort_text: item.sort_text,
                     ..Default::default()
                 }
             })
@@ -1338,6 +2053,7 @@ impl LanguageServer for GraphQLLanguageServer {
                         |ext| match ext {
                             "ts" | "tsx" => (true, graphql_extract::Language::TypeScript),
                             "js" | "jsx" => (true, graphql_extract::Language::JavaScript),
+                            "svelte" => (true, graphql_extract::Language::Svelte),
                             _ => (false, graphql_extract::Language::GraphQL),
                         },
                     )
@@ -1462,8 +2178,8 @@ impl LanguageServer for GraphQLLanguageServer {
                             })
                             .collect();
 
-                        if !lsp_locations.is_empty() {
-                            return Ok(Some(GotoDefinitionResponse::Array(lsp_locations)));
+                        if let Some(response) = Self::definition_response(lsp_locations) {
+                            return Ok(Some(response));
                         }
                     }
                 }
@@ -1596,13 +2312,19 @@ impl LanguageServer for GraphQLLanguageServer {
                             })
                             .collect();
 
-                        if !lsp_locations.is_empty() {
-                            return Ok(Some(GotoDefinitionResponse::Array(lsp_locations)));
+                        if let Some(response) = Self::definition_response(lsp_locations) {
+                            return Ok(Some(response));
                         }
                     }
                 }
             }
 
+            let message = Self::remote_definition_message(project, &uri, lsp_position, &content);
+            drop(projects);
+            drop(content);
+            if let Some(message) = message {
+                self.client.show_message(MessageType::INFO, message).await;
+            }
             return Ok(None);
         }
 
@@ -1624,6 +2346,12 @@ impl LanguageServer for GraphQLLanguageServer {
                 "project.goto_definition returned None at position {:?}",
                 position
             );
+            let message = Self::remote_definition_message(project, &uri, lsp_position, &content);
+            drop(projects);
+            drop(content);
+            if let Some(message) = message {
+                self.client.show_message(MessageType::INFO, message).await;
+            }
             return Ok(None);
         };
 
@@ -1692,40 +2420,33 @@ impl LanguageServer for GraphQLLanguageServer {
             lsp_locations.len()
         );
 
-        if lsp_locations.is_empty() {
-            Ok(None)
-        } else {
-            Ok(Some(GotoDefinitionResponse::Array(lsp_locations)))
-        }
+        Ok(Self::definition_response(lsp_locations))
     }
 
-    async fn references(&self, params: ReferenceParams) -> Result<Option<Vec<Location>>> {
-        let start = std::time::Instant::now();
-        let uri = params.text_document_position.text_document.uri;
-        let lsp_position = params.text_document_position.position;
-        let include_declaration = params.context.include_declaration;
+    async fn goto_implementation(
+        &self,
+        params: GotoDefinitionParams,
+    ) -> Result<Option<GotoDefinitionResponse>> {
+        let uri = params.text_document_position_params.text_document.uri;
+        let lsp_position = params.text_document_position_params.position;
 
         tracing::info!(
-            "Find references requested: {:?} at line={} char={} (include_declaration: {})",
+            "Go to implementation requested: {:?} at line={} char={}",
             uri,
             lsp_position.line,
-            lsp_position.character,
-            include_declaration
+            lsp_position.character
         );
 
-        // Get the cached document content
         let Some(content) = self.document_cache.get(&uri.to_string()) else {
             tracing::warn!("No cached content for document: {:?}", uri);
             return Ok(None);
         };
 
-        // Find the workspace and project for this document
         let Some((workspace_uri, project_idx)) = self.find_workspace_and_project(&uri) else {
             tracing::warn!("No project found for document: {:?}", uri);
             return Ok(None);
         };
 
-        // Get the project
         let Some(projects) = self.projects.get(&workspace_uri) else {
             tracing::warn!("No projects loaded for workspace: {workspace_uri}");
             return Ok(None);
@@ -1736,23 +2457,89 @@ impl LanguageServer for GraphQLLanguageServer {
             return Ok(None);
         };
 
-        // Collect all documents from the cache
-        let collect_start = std::time::Instant::now();
-        let all_documents: Vec<(String, String)> = self
-            .document_cache
-            .iter()
-            .map(|entry| {
-                let uri_string = entry.key().clone();
-                let content = entry.value().clone();
-                (uri_string, content)
-            })
-            .collect();
-
-        tracing::info!(
-            "Collected {} documents for reference search in {:?}",
-            all_documents.len(),
-            collect_start.elapsed()
-        );
+        let position = graphql_project::Position {
+            line: lsp_position.line as usize,
+            character: lsp_position.character as usize,
+        };
+
+        let Some(locations) = project.goto_implementation(&uri.to_string(), position, &content)
+        else {
+            tracing::debug!("No implementations found at position {:?}", position);
+            return Ok(None);
+        };
+
+        #[allow(clippy::cast_possible_truncation)]
+        let lsp_locations: Vec<Location> = locations
+            .into_iter()
+            .filter_map(|loc| {
+                Some(Location {
+                    uri: Self::resolve_file_uri(&workspace_uri, &loc.file_path)?,
+                    range: Range {
+                        start: Position {
+                            line: loc.range.start.line as u32,
+                            character: loc.range.start.character as u32,
+                        },
+                        end: Position {
+                            line: loc.range.end.line as u32,
+                            character: loc.range.end.character as u32,
+                        },
+                    },
+                })
+            })
+            .collect();
+
+        tracing::info!("Found {} implementation(s)", lsp_locations.len());
+
+        Ok(Self::definition_response(lsp_locations))
+    }
+
+    async fn references(&self, params: ReferenceParams) -> Result<Option<Vec<Location>>> {
+        let start = std::time::Instant::now();
+        let uri = params.text_document_position.text_document.uri;
+        let lsp_position = params.text_document_position.position;
+        let include_declaration = params.context.include_declaration;
+
+        tracing::info!(
+            "Find references requested: {:?} at line={} char={} (include_declaration: {})",
+            uri,
+            lsp_position.line,
+            lsp_position.character,
+            include_declaration
+        );
+
+        // Get the cached document content
+        let Some(content) = self.document_cache.get(&uri.to_string()) else {
+            tracing::warn!("No cached content for document: {:?}", uri);
+            return Ok(None);
+        };
+
+        // Find the workspace and project for this document
+        let Some((workspace_uri, project_idx)) = self.find_workspace_and_project(&uri) else {
+            tracing::warn!("No project found for document: {:?}", uri);
+            return Ok(None);
+        };
+
+        // Collect this project's open documents before the projects map is locked
+        let collect_start = std::time::Instant::now();
+        let all_documents = self.project_documents(&workspace_uri, project_idx);
+        let file_path = uri.to_file_path().map(|path| path.display().to_string());
+
+        tracing::info!(
+            "Collected {} documents for reference search in {:?}",
+            all_documents.len(),
+            collect_start.elapsed()
+        );
+
+        // Get the project
+        let Some(projects) = self.projects.get(&workspace_uri) else {
+            tracing::warn!("No projects loaded for workspace: {workspace_uri}");
+            return Ok(None);
+        };
+
+        let Some((_, project)) = projects.get(project_idx) else {
+            tracing::warn!("Project index {project_idx} not found in workspace {workspace_uri}");
+            return Ok(None);
+        };
 
         // For find_references optimization, we would parse all documents once here
         // However, since the documents are already cached in document_index via did_open/did_change,
@@ -1774,7 +2561,7 @@ impl LanguageServer for GraphQLLanguageServer {
             position,
             &all_documents,
             include_declaration,
-            Some(&uri.to_string()),
+            file_path.as_deref(),
             document_asts,
         ) else {
             tracing::info!("No references found at position {:?}", position);
@@ -1792,12 +2579,8 @@ impl LanguageServer for GraphQLLanguageServer {
         let lsp_locations: Vec<Location> = references
             .iter()
             .filter_map(|reference_loc| {
-                // The file_path in reference_loc is the URI string from the document cache
-                // Parse it as a URI
-                let file_uri: Uri = reference_loc.file_path.parse().ok()?;
-
                 Some(Location {
-                    uri: file_uri,
+                    uri: Self::resolve_file_uri(&workspace_uri, &reference_loc.file_path)?,
                     range: Range {
                         start: Position {
                             line: reference_loc.range.start.line as u32,
@@ -1825,13 +2608,559 @@ impl LanguageServer for GraphQLLanguageServer {
         }
     }
 
+    #[allow(clippy::cast_possible_truncation)]
+    async fn prepare_rename(
+        &self,
+        params: TextDocumentPositionParams,
+    ) -> Result<Option<PrepareRenameResponse>> {
+        let uri = params.text_document.uri;
+        let lsp_position = params.position;
+
+        tracing::debug!(
+            "Prepare rename requested: {:?} at line={} char={}",
+            uri,
+            lsp_position.line,
+            lsp_position.character
+        );
+
+        let Some(content) = self.document_cache.get(&uri.to_string()) else {
+            tracing::warn!("No cached content for document: {:?}", uri);
+            return Ok(None);
+        };
+
+        let Some((workspace_uri, project_idx)) = self.find_workspace_and_project(&uri) else {
+            tracing::warn!("No project found for document: {:?}", uri);
+            return Ok(None);
+        };
+
+        let Some(projects) = self.projects.get(&workspace_uri) else {
+            tracing::warn!("No projects loaded for workspace: {workspace_uri}");
+            return Ok(None);
+        };
+
+        let Some((_, project)) = projects.get(project_idx) else {
+            tracing::warn!("Project index {project_idx} not found in workspace {workspace_uri}");
+            return Ok(None);
+        };
+
+        let position = graphql_project::Position {
+            line: lsp_position.line as usize,
+            character: lsp_position.character as usize,
+        };
+
+        let file_path = uri.to_file_path().map(|path| path.display().to_string());
+        match project.prepare_rename(&content, position, file_path.as_deref()) {
+            Ok(Some(range)) => Ok(Some(PrepareRenameResponse::Range(Range {
+                start: Position {
+                    line: range.start.line as u32,
+                    character: range.start.character as u32,
+                },
+                end: Position {
+                    line: range.end.line as u32,
+                    character: range.end.character as u32,
+                },
+            }))),
+            Ok(None) => Ok(None),
+            Err(e) => {
+                tracing::debug!("Element cannot be renamed: {}", e);
+                Err(tower_lsp_server::jsonrpc::Error::invalid_params(
+                    e.to_string(),
+                ))
+            }
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    async fn rename(&self, params: RenameParams) -> Result<Option<WorkspaceEdit>> {
+        let uri = params.text_document_position.text_document.uri;
+        let lsp_position = params.text_document_position.position;
+        let new_name = params.new_name;
+
+        tracing::info!(
+            "Rename requested: {:?} at line={} char={} to '{}'",
+            uri,
+            lsp_position.line,
+            lsp_position.character,
+            new_name
+        );
+
+        let Some(content) = self.document_cache.get(&uri.to_string()) else {
+            tracing::warn!("No cached content for document: {:?}", uri);
+            return Ok(None);
+        };
+
+        let Some((workspace_uri, project_idx)) = self.find_workspace_and_project(&uri) else {
+            tracing::warn!("No project found for document: {:?}", uri);
+            return Ok(None);
+        };
+
+        // Only this project's documents, collected before the projects map is locked
+        let all_documents = self.project_documents(&workspace_uri, project_idx);
+        let file_path = uri.to_file_path().map(|path| path.display().to_string());
+
+        let Some(projects) = self.projects.get(&workspace_uri) else {
+            tracing::warn!("No projects loaded for workspace: {workspace_uri}");
+            return Ok(None);
+        };
+
+        let Some((_, project)) = projects.get(project_idx) else {
+            tracing::warn!("Project index {project_idx} not found in workspace {workspace_uri}");
+            return Ok(None);
+        };
+
+        let position = graphql_project::Position {
+            line: lsp_position.line as usize,
+            character: lsp_position.character as usize,
+        };
+
+        let locations = match project.rename(
+            &content,
+            position,
+            &new_name,
+            &all_documents,
+            file_path.as_deref(),
+        ) {
+            Ok(Some(locations)) => locations,
+            Ok(None) => {
+                tracing::info!("Nothing to rename at position {:?}", position);
+                return Ok(None);
+            }
+            Err(e) => {
+                tracing::warn!("Rejected rename: {}", e);
+                return Err(tower_lsp_server::jsonrpc::Error::invalid_params(
+                    e.to_string(),
+                ));
+            }
+        };
+
+        // Group edits by document so the whole rename is applied as one workspace edit
+        let mut changes: std::collections::HashMap<Uri, Vec<TextEdit>> =
+            std::collections::HashMap::new();
+        for location in locations {
+            let Some(file_uri) = Self::resolve_file_uri(&workspace_uri, &location.file_path) else {
+                continue;
+            };
+
+            changes.entry(file_uri).or_default().push(TextEdit {
+                range: Range {
+                    start: Position {
+                        line: location.range.start.line as u32,
+                        character: location.range.start.character as u32,
+                    },
+                    end: Position {
+                        line: location.range.end.line as u32,
+                        character: location.range.end.character as u32,
+                    },
+                },
+                new_text: new_name.clone(),
+            });
+        }
+
+        tracing::info!("Rename produced edits in {} document(s)", changes.len());
+
+        Ok(Some(WorkspaceEdit {
+            changes: Some(changes),
+            ..Default::default()
+        }))
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        let uri = params.text_document.uri;
+        tracing::debug!("Code actions requested: {:?}", uri);
+
+        let Some(content) = self.document_cache.get(&uri.to_string()) else {
+            tracing::warn!("No cached content for document: {:?}", uri);
+            return Ok(None);
+        };
+
+        let Some((workspace_uri, project_idx)) = self.find_workspace_and_project(&uri) else {
+            tracing::warn!("No project found for document: {:?}", uri);
+            return Ok(None);
+        };
+
+        let Some(projects) = self.projects.get(&workspace_uri) else {
+            tracing::warn!("No projects loaded for workspace: {workspace_uri}");
+            return Ok(None);
+        };
+
+        let Some((_, project)) = projects.get(project_idx) else {
+            tracing::warn!("Project index {project_idx} not found in workspace {workspace_uri}");
+            return Ok(None);
+        };
+
+        let mut actions = Vec::new();
+        for diagnostic in params.context.diagnostics {
+            let Some(lsp_types::NumberOrString::String(code)) = &diagnostic.code else {
+                continue;
+            };
+
+            let range = graphql_project::Range {
+                start: graphql_project::Position {
+                    line: diagnostic.range.start.line as usize,
+                    character: diagnostic.range.start.character as usize,
+                },
+                end: graphql_project::Position {
+                    line: diagnostic.range.end.line as usize,
+                    character: diagnostic.range.end.character as usize,
+                },
+            };
+
+            let data = diagnostic
+                .data
+                .clone()
+                .and_then(|value| serde_json::from_value(value).ok());
+
+            for action in
+                project.code_actions(&uri.to_string(), &content, code, range, data.as_ref())
+            {
+                let edits = action
+                    .edits
+                    .into_iter()
+                    .map(|edit| TextEdit {
+                        range: Self::convert_range(edit.range),
+                        new_text: edit.new_text,
+                    })
+                    .collect();
+
+                actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                    title: action.title,
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diagnostic.clone()]),
+                    edit: Some(WorkspaceEdit {
+                        changes: Some(std::collections::HashMap::from([(uri.clone(), edits)])),
+                        ..Default::default()
+                    }),
+                    is_preferred: Some(action.is_preferred),
+                    ..Default::default()
+                }));
+            }
+        }
+
+        let wants_source_actions = params.context.only.as_ref().is_none_or(|only| {
+            only.iter().any(|kind| {
+                *kind == CodeActionKind::SOURCE || kind.as_str() == SPLIT_OPERATIONS_KIND
+            })
+        });
+        if wants_source_actions {
+            if let Some(action) = project
+                .split_operations(&uri.to_string(), &content)
+                .and_then(|split| Self::split_operations_action(&uri, split))
+            {
+                actions.push(CodeActionOrCommand::CodeAction(action));
+            }
+        }
+
+        tracing::debug!("Returning {} code action(s)", actions.len());
+
+        Ok(Some(actions))
+    }
+
     async fn document_symbol(
         &self,
         params: DocumentSymbolParams,
     ) -> Result<Option<DocumentSymbolResponse>> {
-        tracing::debug!("Document symbols requested: {:?}", params.text_document.uri);
-        // TODO: Implement document symbols
-        Ok(None)
+        let uri = params.text_document.uri;
+        tracing::debug!("Document symbols requested: {:?}", uri);
+
+        let Some(content) = self.document_cache.get(&uri.to_string()) else {
+            tracing::warn!("No cached content for document: {:?}", uri);
+            return Ok(None);
+        };
+
+        let Some((workspace_uri, project_idx)) = self.find_workspace_and_project(&uri) else {
+            tracing::warn!("No project found for document: {:?}", uri);
+            return Ok(None);
+        };
+
+        let Some(projects) = self.projects.get(&workspace_uri) else {
+            tracing::warn!("No projects loaded for workspace: {workspace_uri}");
+            return Ok(None);
+        };
+
+        let Some((_, project)) = projects.get(project_idx) else {
+            tracing::warn!("Project index {project_idx} not found in workspace {workspace_uri}");
+            return Ok(None);
+        };
+
+        let symbols: Vec<DocumentSymbol> = project
+            .document_symbols(&uri.to_string(), &content)
+            .into_iter()
+            .map(Self::convert_document_symbol)
+            .collect();
+
+        tracing::debug!("Returning {} document symbol(s)", symbols.len());
+
+        Ok(Some(DocumentSymbolResponse::Nested(symbols)))
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    async fn signature_help(&self, params: SignatureHelpParams) -> Result<Option<SignatureHelp>> {
+        let uri = params.text_document_position_params.text_document.uri;
+        let lsp_position = params.text_document_position_params.position;
+        tracing::debug!("Signature help requested: {:?} at {:?}", uri, lsp_position);
+
+        let Some(content) = self.document_cache.get(&uri.to_string()) else {
+            tracing::warn!("No cached content for document: {:?}", uri);
+            return Ok(None);
+        };
+
+        let Some((workspace_uri, project_idx)) = self.find_workspace_and_project(&uri) else {
+            tracing::warn!("No project found for document: {:?}", uri);
+            return Ok(None);
+        };
+
+        let Some(projects) = self.projects.get(&workspace_uri) else {
+            tracing::warn!("No projects loaded for workspace: {workspace_uri}");
+            return Ok(None);
+        };
+
+        let Some((_, project)) = projects.get(project_idx) else {
+            tracing::warn!("Project index {project_idx} not found in workspace {workspace_uri}");
+            return Ok(None);
+        };
+
+        let position = graphql_project::Position {
+            line: lsp_position.line as usize,
+            character: lsp_position.character as usize,
+        };
+
+        let Some(help) = project.signature_help(&uri.to_string(), position, &content) else {
+            return Ok(None);
+        };
+
+        let markdown = |value: String| {
+            Documentation::MarkupContent(lsp_types::MarkupContent {
+                kind: lsp_types::MarkupKind::Markdown,
+                value,
+            })
+        };
+        let active_parameter = help.active_parameter.map(|index| index as u32);
+        let parameters = help
+            .parameters
+            .into_iter()
+            .map(|parameter| ParameterInformation {
+                label: ParameterLabel::Simple(parameter.label),
+                documentation: parameter.documentation.map(markdown),
+            })
+            .collect();
+
+        tracing::debug!("Returning signature help: {}", help.label);
+
+        Ok(Some(SignatureHelp {
+            signatures: vec![SignatureInformation {
+                label: help.label,
+                documentation: help.documentation.map(markdown),
+                parameters: Some(parameters),
+                active_parameter,
+            }],
+            active_signature: Some(0),
+            active_parameter,
+        }))
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    async fn document_highlight(
+        &self,
+        params: DocumentHighlightParams,
+    ) -> Result<Option<Vec<DocumentHighlight>>> {
+        let uri = params.text_document_position_params.text_document.uri;
+        let lsp_position = params.text_document_position_params.position;
+        tracing::debug!(
+            "Document highlight requested: {:?} at {:?}",
+            uri,
+            lsp_position
+        );
+
+        let Some(content) = self.document_cache.get(&uri.to_string()) else {
+            tracing::warn!("No cached content for document: {:?}", uri);
+            return Ok(None);
+        };
+
+        let Some((workspace_uri, project_idx)) = self.find_workspace_and_project(&uri) else {
+            tracing::warn!("No project found for document: {:?}", uri);
+            return Ok(None);
+        };
+
+        let Some(projects) = self.projects.get(&workspace_uri) else {
+            tracing::warn!("No projects loaded for workspace: {workspace_uri}");
+            return Ok(None);
+        };
+
+        let Some((_, project)) = projects.get(project_idx) else {
+            tracing::warn!("Project index {project_idx} not found in workspace {workspace_uri}");
+            return Ok(None);
+        };
+
+        let position = graphql_project::Position {
+            line: lsp_position.line as usize,
+            character: lsp_position.character as usize,
+        };
+
+        let highlights: Vec<DocumentHighlight> = project
+            .document_highlights(&uri.to_string(), position, &content)
+            .into_iter()
+            .map(|highlight| DocumentHighlight {
+                range: Range {
+                    start: Position {
+                        line: highlight.range.start.line as u32,
+                        character: highlight.range.start.character as u32,
+                    },
+                    end: Position {
+                        line: highlight.range.end.line as u32,
+                        character: highlight.range.end.character as u32,
+                    },
+                },
+                kind: Some(match highlight.kind {
+                    graphql_project::DocumentHighlightKind::Text => DocumentHighlightKind::TEXT,
+                    graphql_project::DocumentHighlightKind::Read => DocumentHighlightKind::READ,
+                    graphql_project::DocumentHighlightKind::Write => DocumentHighlightKind::WRITE,
+                }),
+            })
+            .collect();
+
+        tracing::debug!("Returning {} document highlight(s)", highlights.len());
+
+        if highlights.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(highlights))
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    async fn folding_range(&self, params: FoldingRangeParams) -> Result<Option<Vec<FoldingRange>>> {
+        let uri = params.text_document.uri;
+        tracing::debug!("Folding ranges requested: {:?}", uri);
+
+        let Some(content) = self.document_cache.get(&uri.to_string()) else {
+            tracing::warn!("No cached content for document: {:?}", uri);
+            return Ok(None);
+        };
+
+        let Some((workspace_uri, project_idx)) = self.find_workspace_and_project(&uri) else {
+            tracing::warn!("No project found for document: {:?}", uri);
+            return Ok(None);
+        };
+
+        let Some(projects) = self.projects.get(&workspace_uri) else {
+            tracing::warn!("No projects loaded for workspace: {workspace_uri}");
+            return Ok(None);
+        };
+
+        let Some((_, project)) = projects.get(project_idx) else {
+            tracing::warn!("Project index {project_idx} not found in workspace {workspace_uri}");
+            return Ok(None);
+        };
+
+        let ranges: Vec<FoldingRange> = project
+            .folding_ranges(&uri.to_string(), &content)
+            .into_iter()
+            .map(|range| FoldingRange {
+                start_line: range.start_line as u32,
+                start_character: None,
+                end_line: range.end_line as u32,
+                end_character: None,
+                kind: Some(match range.kind {
+                    graphql_project::FoldingRangeKind::Region => FoldingRangeKind::Region,
+                    graphql_project::FoldingRangeKind::Comment => FoldingRangeKind::Comment,
+                }),
+                collapsed_text: None,
+            })
+            .collect();
+
+        tracing::debug!("Returning {} folding range(s)", ranges.len());
+
+        Ok(Some(ranges))
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    async fn inlay_hint(&self, params: InlayHintParams) -> Result<Option<Vec<InlayHint>>> {
+        let uri = params.text_document.uri;
+        let range = params.range;
+        tracing::debug!("Inlay hints requested: {:?}", uri);
+
+        let Some(content) = self.document_cache.get(&uri.to_string()) else {
+            tracing::warn!("No cached content for document: {:?}", uri);
+            return Ok(None);
+        };
+
+        let Some((workspace_uri, project_idx)) = self.find_workspace_and_project(&uri) else {
+            tracing::warn!("No project found for document: {:?}", uri);
+            return Ok(None);
+        };
+
+        let Some(projects) = self.projects.get(&workspace_uri) else {
+            tracing::warn!("No projects loaded for workspace: {workspace_uri}");
+            return Ok(None);
+        };
+
+        let Some((_, project)) = projects.get(project_idx) else {
+            tracing::warn!("Project index {project_idx} not found in workspace {workspace_uri}");
+            return Ok(None);
+        };
+
+        let hints: Vec<InlayHint> = project
+            .inlay_hints(&uri.to_string(), &content)
+            .into_iter()
+            .map(|hint| InlayHint {
+                position: Position {
+                    line: hint.position.line as u32,
+                    character: hint.position.character as u32,
+                },
+                label: InlayHintLabel::String(hint.label),
+                kind: Some(match hint.kind {
+                    graphql_project::InlayHintKind::Type => InlayHintKind::TYPE,
+                    graphql_project::InlayHintKind::Parameter => InlayHintKind::PARAMETER,
+                }),
+                text_edits: None,
+                tooltip: None,
+                padding_left: None,
+                padding_right: None,
+                data: None,
+            })
+            .filter(|hint| range.start <= hint.position && hint.position <= range.end)
+            .collect();
+
+        tracing::debug!("Returning {} inlay hint(s)", hints.len());
+
+        Ok(Some(hints))
+    }
+
+    async fn semantic_tokens_full(
+        &self,
+        params: SemanticTokensParams,
+    ) -> Result<Option<SemanticTokensResult>> {
+        let uri = params.text_document.uri;
+        tracing::debug!("Semantic tokens requested: {:?}", uri);
+
+        let Some(content) = self.document_cache.get(&uri.to_string()) else {
+            tracing::warn!("No cached content for document: {:?}", uri);
+            return Ok(None);
+        };
+
+        let Some((workspace_uri, project_idx)) = self.find_workspace_and_project(&uri) else {
+            tracing::warn!("No project found for document: {:?}", uri);
+            return Ok(None);
+        };
+
+        let Some(projects) = self.projects.get(&workspace_uri) else {
+            tracing::warn!("No projects loaded for workspace: {workspace_uri}");
+            return Ok(None);
+        };
+
+        let Some((_, project)) = projects.get(project_idx) else {
+            tracing::warn!("Project index {project_idx} not found in workspace {workspace_uri}");
+            return Ok(None);
+        };
+
+        let tokens = project.semantic_tokens(&uri.to_string(), &content);
+        tracing::debug!("Returning {} semantic token(s)", tokens.len());
+
+        Ok(Some(SemanticTokensResult::Tokens(SemanticTokens {
+            result_id: None,
+            data: Self::encode_semantic_tokens(&tokens),
+        })))
     }
 
     async fn symbol(
@@ -1839,7 +3168,76 @@ impl LanguageServer for GraphQLLanguageServer {
         params: WorkspaceSymbolParams,
     ) -> Result<Option<OneOf<Vec<SymbolInformation>, Vec<WorkspaceSymbol>>>> {
         tracing::debug!("Workspace symbols requested: {}", params.query);
-        // TODO: Implement workspace symbols
-        Ok(None)
+
+        let mut symbols = Vec::new();
+        for workspace in self.projects.iter() {
+            let workspace_uri = workspace.key();
+            for (_, project) in workspace.value() {
+                for symbol in project.workspace_symbols(&params.query) {
+                    let Some(uri) = Self::resolve_file_uri(workspace_uri, &symbol.file_path) else {
+                        continue;
+                    };
+                    symbols.push(Self::convert_workspace_symbol(symbol, uri));
+                }
+            }
+        }
+
+        tracing::debug!("Returning {} workspace symbol(s)", symbols.len());
+
+        Ok(Some(OneOf::Right(symbols)))
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    async fn formatting(&self, params: DocumentFormattingParams) -> Result<Option<Vec<TextEdit>>> {
+        let uri = params.text_document.uri;
+        tracing::debug!("Formatting requested: {:?}", uri);
+
+        // Embedded GraphQL is left to the host language's formatter
+        let is_graphql_file = uri
+            .to_file_path()
+            .and_then(|path| {
+                path.extension()
+                    .and_then(|ext| ext.to_str())
+                    .map(|ext| matches!(ext, "graphql" | "gql"))
+            })
+            .unwrap_or(false);
+        if !is_graphql_file {
+            return Ok(None);
+        }
+
+        let Some(content) = self.document_cache.get(&uri.to_string()) else {
+            tracing::warn!("No cached content for document: {:?}", uri);
+            return Ok(None);
+        };
+
+        let Some(formatted) = graphql_project::format_document(&content) else {
+            tracing::debug!("Not formatting document with syntax errors: {:?}", uri);
+            return Ok(None);
+        };
+
+        if formatted == *content {
+            return Ok(Some(Vec::new()));
+        }
+
+        let Some(end) = self
+            .position_encoding()
+            .offset_to_position(&content, content.len())
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(vec![TextEdit {
+            range: Range {
+                start: Position {
+                    line: 0,
+                    character: 0,
+                },
+                end: Position {
+                    line: end.line as u32,
+                    character: end.character as u32,
+                },
+            },
+            new_text: formatted,
+        }]))
     }
 }
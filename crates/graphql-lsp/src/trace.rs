@@ -0,0 +1,64 @@
+use lsp_types::TraceValue;
+use tracing_subscriber::{reload, EnvFilter, Registry};
+
+/// Handle for swapping the log filter while the server runs
+pub type FilterHandle = reload::Handle<EnvFilter, Registry>;
+
+/// Adjusts the log filter to the trace level a client asks for with `$/setTrace`
+#[derive(Clone)]
+pub struct TraceLevel {
+    handle: FilterHandle,
+    /// Filter directives from startup (`RUST_LOG`, or `info`), restored when tracing is off
+    default_directives: String,
+}
+
+impl TraceLevel {
+    #[must_use]
+    pub fn new(handle: FilterHandle, default_directives: impl Into<String>) -> Self {
+        Self {
+            handle,
+            default_directives: default_directives.into(),
+        }
+    }
+
+    /// Switch the log filter to the one for `value`
+    pub fn set(&self, value: TraceValue) -> Result<(), reload::Error> {
+        let directives = directives_for(value, &self.default_directives);
+        self.handle.reload(EnvFilter::new(directives))
+    }
+}
+
+/// Filter directives for an LSP trace level
+///
+/// `off` keeps the startup filter, so errors and warnings are still logged; `messages`
+/// logs at debug level and `verbose` at trace level.
+fn directives_for(value: TraceValue, default_directives: &str) -> String {
+    match value {
+        TraceValue::Off => default_directives.to_string(),
+        TraceValue::Messages => "debug".to_string(),
+        TraceValue::Verbose => "trace".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_trace_level_changes_filter() {
+        let (filter, handle) = reload::Layer::new(EnvFilter::new("info"));
+        let _subscriber = tracing_subscriber::registry().with(filter);
+        let trace_level = TraceLevel::new(handle.clone(), "info");
+        let current = || handle.with_current(ToString::to_string).unwrap();
+
+        trace_level.set(TraceValue::Verbose).unwrap();
+        assert_eq!(current(), "trace");
+
+        trace_level.set(TraceValue::Messages).unwrap();
+        assert_eq!(current(), "debug");
+
+        trace_level.set(TraceValue::Off).unwrap();
+        assert_eq!(current(), "info");
+    }
+}
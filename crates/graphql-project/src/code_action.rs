@@ -0,0 +1,958 @@
+use crate::{
+    index::FieldInfo, DiagnosticData, OperationType, Position, PositionEncoding, Range,
+    SchemaIndex, TypeKind,
+};
+use apollo_parser::{
+    cst::{self, CstNode},
+    Parser,
+};
+
+/// Replacement of a range of text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub range: Range,
+    pub new_text: String,
+}
+
+/// Quick fix for a diagnostic
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeAction {
+    /// Title shown to the user
+    pub title: String,
+    /// Edits to apply to the document the diagnostic was reported in
+    pub edits: Vec<TextEdit>,
+    /// Whether this is the fix to apply by default, which only the closest of several
+    /// spelling suggestions is
+    pub is_preferred: bool,
+}
+
+/// Operation moved out of a document by [`CodeActionProvider::split_operations`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MovedOperation {
+    /// Name of the operation, for naming the file it moves to
+    pub name: String,
+    /// Contents of that file
+    pub content: String,
+}
+
+/// Split of a multi-operation document into one operation per file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitOperations {
+    /// Operations that move to files of their own, in document order
+    pub operations: Vec<MovedOperation>,
+    /// Edits removing the moved operations from the original document
+    pub edits: Vec<TextEdit>,
+}
+
+/// Code action provider
+///
+/// Computes quick fixes for diagnostics reported by the validator, identified by their
+/// diagnostic code.
+pub struct CodeActionProvider {
+    /// Unit `Position::character` columns are counted in
+    position_encoding: PositionEncoding,
+}
+
+impl CodeActionProvider {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            position_encoding: PositionEncoding::Utf16,
+        }
+    }
+
+    /// Count `Position::character` columns in `encoding` instead of UTF-16 code units
+    #[must_use]
+    pub const fn with_position_encoding(mut self, encoding: PositionEncoding) -> Self {
+        self.position_encoding = encoding;
+        self
+    }
+
+    /// Get the quick fixes for a diagnostic with the given code and range
+    ///
+    /// `data` is the structured context recorded on the diagnostic, if any; spelling
+    /// suggestions for unknown fields and types need it to know what was looked up where.
+    #[must_use]
+    pub fn code_actions(
+        &self,
+        source: &str,
+        code: &str,
+        range: Range,
+        data: Option<&DiagnosticData>,
+        schema_index: &SchemaIndex,
+    ) -> Vec<CodeAction> {
+        match (code, data) {
+            ("unused-fragment", _) => self
+                .remove_fragment(source, range.start)
+                .into_iter()
+                .collect(),
+            ("missing-subselection", _) => self
+                .add_subselection(source, range.start, schema_index)
+                .into_iter()
+                .collect(),
+            ("missing-required-argument", _) => self
+                .add_required_arguments(source, range.start, schema_index)
+                .into_iter()
+                .collect(),
+            (
+                "unknown-field",
+                Some(DiagnosticData::UnknownField {
+                    parent_type,
+                    field_name,
+                }),
+            ) => {
+                let candidates = schema_index
+                    .get_fields(parent_type)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|field| field.name);
+                self.suggest_names(source, range, field_name, candidates)
+            }
+            ("unknown-type", Some(DiagnosticData::UnknownType { type_name })) => {
+                let candidates = schema_index
+                    .all_types()
+                    .into_iter()
+                    .map(|type_info| type_info.name)
+                    .filter(|name| !name.starts_with("__"));
+                self.suggest_names(source, range, type_name, candidates)
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Split a document with several operations so each is in a file of its own
+    ///
+    /// The first operation stays in the document, along with any fragment definitions, and
+    /// every other operation moves out. Fragment spreads are left intact, since fragments
+    /// resolve across the project's documents. Returns `None` unless the document parses and
+    /// has more than one operation, all of them named.
+    #[must_use]
+    pub fn split_operations(&self, source: &str) -> Option<SplitOperations> {
+        let tree = Parser::new(source).parse();
+        if tree.errors().count() > 0 {
+            return None;
+        }
+
+        let operations = tree
+            .document()
+            .definitions()
+            .filter_map(|definition| match definition {
+                cst::Definition::OperationDefinition(operation) => Some(operation),
+                _ => None,
+            })
+            .map(|operation| {
+                let name = operation.name()?.text().to_string();
+                Some((name, operation))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        if operations.len() < 2 {
+            return None;
+        }
+
+        let mut moved = Vec::new();
+        let mut removals: Vec<(usize, usize)> = Vec::new();
+        for (name, operation) in operations.into_iter().skip(1) {
+            let start: usize = operation.syntax().text_range().start().into();
+            let end = trimmed_end(source, operation.syntax());
+            moved.push(MovedOperation {
+                name,
+                content: format!("{}\n", &source[start..end]),
+            });
+
+            // Take the whitespace up to the next definition too, merging with the previous
+            // removal when the two operations are adjacent
+            let removal_end = source[end..]
+                .find(|ch: char| !ch.is_whitespace())
+                .map_or(end, |index| end + index);
+            match removals.last_mut() {
+                Some(last) if last.1 >= start => last.1 = removal_end,
+                _ => removals.push((start, removal_end)),
+            }
+        }
+
+        // Nothing follows the last removal, so take the whitespace before it instead, leaving
+        // the document's trailing newline in place
+        if let Some(last) = removals.last_mut() {
+            if source[last.1..].trim().is_empty() {
+                last.0 = source[..last.0].trim_end().len();
+            }
+        }
+
+        let encoding = self.position_encoding;
+        let edits = removals
+            .into_iter()
+            .map(|(start, end)| {
+                Some(TextEdit {
+                    range: Range {
+                        start: encoding.offset_to_position(source, start)?,
+                        end: encoding.offset_to_position(source, end)?,
+                    },
+                    new_text: String::new(),
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(SplitOperations {
+            operations: moved,
+            edits,
+        })
+    }
+
+    /// Remove the fragment definition whose name is at `position`
+    ///
+    /// The deletion also takes the whitespace that separates the fragment from the next
+    /// definition, or from the previous one if the fragment is last, so no blank lines are
+    /// left behind.
+    fn remove_fragment(&self, source: &str, position: Position) -> Option<CodeAction> {
+        let tree = Parser::new(source).parse();
+        if tree.errors().count() > 0 {
+            return None;
+        }
+
+        let encoding = self.position_encoding;
+        let offset = encoding.position_to_offset(source, position)?;
+
+        let fragment = tree.document().definitions().find_map(|definition| {
+            let cst::Definition::FragmentDefinition(fragment) = definition else {
+                return None;
+            };
+            let name = fragment.fragment_name()?.name()?;
+            let name_range = name.syntax().text_range();
+            (usize::from(name_range.start()) <= offset && offset <= usize::from(name_range.end()))
+                .then_some((fragment, name.text().to_string()))
+        });
+        let (fragment, name) = fragment?;
+
+        let text_range = fragment.syntax().text_range();
+        let start: usize = text_range.start().into();
+        let end: usize = text_range.end().into();
+
+        let next_content = source[end..]
+            .find(|ch: char| !ch.is_whitespace())
+            .map(|index| end + index);
+        let (start, end) = if let Some(next_content) = next_content {
+            (start, next_content)
+        } else {
+            let previous_content = source[..start].trim_end().len();
+            (previous_content, end)
+        };
+
+        Some(CodeAction {
+            title: format!("Remove unused fragment '{name}'"),
+            edits: vec![TextEdit {
+                range: Range {
+                    start: encoding.offset_to_position(source, start)?,
+                    end: encoding.offset_to_position(source, end)?,
+                },
+                new_text: String::new(),
+            }],
+            is_preferred: true,
+        })
+    }
+
+    /// Add a selection set to the field at `position`
+    ///
+    /// The selection is `{ id }` if the field's type has an `id` field, and
+    /// `{ __typename }` otherwise, since every composite type has it.
+    fn add_subselection(
+        &self,
+        source: &str,
+        position: Position,
+        schema_index: &SchemaIndex,
+    ) -> Option<CodeAction> {
+        let tree = Parser::new(source).parse();
+        let encoding = self.position_encoding;
+        let offset = encoding.position_to_offset(source, position)?;
+
+        let (field, field_info) = find_field_at(&tree.document(), offset, schema_index)?;
+        if field.selection_set().is_some() {
+            return None;
+        }
+
+        let type_name = base_type_name(&field_info.type_name);
+        let has_id = schema_index
+            .get_fields(type_name)
+            .is_some_and(|fields| fields.iter().any(|f| f.name == "id"));
+        let selection = if has_id { "id" } else { "__typename" };
+
+        let end = trimmed_end(source, field.syntax());
+        let end_position = encoding.offset_to_position(source, end)?;
+
+        Some(CodeAction {
+            title: "Add required subselection".to_string(),
+            edits: vec![TextEdit {
+                range: Range {
+                    start: end_position,
+                    end: end_position,
+                },
+                new_text: format!(" {{ {selection} }}"),
+            }],
+            is_preferred: true,
+        })
+    }
+
+    /// Add the non-null arguments without a default value that the field at `position` is
+    /// missing
+    ///
+    /// Each argument gets a placeholder value of its type for the user to fill in.
+    fn add_required_arguments(
+        &self,
+        source: &str,
+        position: Position,
+        schema_index: &SchemaIndex,
+    ) -> Option<CodeAction> {
+        let tree = Parser::new(source).parse();
+        let encoding = self.position_encoding;
+        let offset = encoding.position_to_offset(source, position)?;
+
+        let (field, field_info) = find_field_at(&tree.document(), offset, schema_index)?;
+
+        let provided: Vec<String> = field
+            .arguments()
+            .iter()
+            .flat_map(cst::Arguments::arguments)
+            .filter_map(|argument| argument.name().map(|name| name.text().to_string()))
+            .collect();
+        let missing: Vec<_> = field_info
+            .arguments
+            .iter()
+            .filter(|arg| arg.type_name.ends_with('!') && arg.default_value.is_none())
+            .filter(|arg| !provided.contains(&arg.name))
+            .collect();
+        if missing.is_empty() {
+            return None;
+        }
+
+        let new_arguments = missing
+            .iter()
+            .map(|arg| {
+                format!(
+                    "{}: {}",
+                    arg.name,
+                    placeholder_value(&arg.type_name, schema_index)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let (insert_at, new_text) = if let Some(arguments) = field.arguments() {
+            let r_paren = arguments.r_paren_token()?;
+            let separator = if provided.is_empty() { "" } else { ", " };
+            (
+                usize::from(r_paren.text_range().start()),
+                format!("{separator}{new_arguments}"),
+            )
+        } else {
+            let name = field.name()?;
+            (
+                trimmed_end(source, name.syntax()),
+                format!("({new_arguments})"),
+            )
+        };
+        let insert_position = encoding.offset_to_position(source, insert_at)?;
+
+        let title = if let [arg] = missing.as_slice() {
+            format!("Add missing required argument '{}'", arg.name)
+        } else {
+            "Add missing required arguments".to_string()
+        };
+
+        Some(CodeAction {
+            title,
+            edits: vec![TextEdit {
+                range: Range {
+                    start: insert_position,
+                    end: insert_position,
+                },
+                new_text,
+            }],
+            is_preferred: true,
+        })
+    }
+
+    /// Offer to replace a misspelled `name` within `range` with the closest candidates
+    ///
+    /// Only candidates within a small edit distance are suggested (2 for short names, 3
+    /// for longer ones), closest first, so unrelated names are never proposed.
+    /// Only the closest suggestion is preferred.
+    fn suggest_names(
+        &self,
+        source: &str,
+        range: Range,
+        name: &str,
+        candidates: impl Iterator<Item = String>,
+    ) -> Vec<CodeAction> {
+        const MAX_SUGGESTIONS: usize = 3;
+
+        let encoding = self.position_encoding;
+        let Some(start) = encoding.position_to_offset(source, range.start) else {
+            return Vec::new();
+        };
+        let end = encoding
+            .position_to_offset(source, range.end)
+            .filter(|&end| end > start)
+            .unwrap_or_else(|| {
+                source[start..]
+                    .find('\n')
+                    .map_or(source.len(), |index| start + index)
+            });
+        let Some(name_start) = find_name(source, start, end, name) else {
+            return Vec::new();
+        };
+        let (Some(name_start), Some(name_end)) = (
+            encoding.offset_to_position(source, name_start),
+            encoding.offset_to_position(source, name_start + name.len()),
+        ) else {
+            return Vec::new();
+        };
+
+        let max_distance = if name.chars().count() <= 5 { 2 } else { 3 };
+        let mut suggestions: Vec<(usize, String)> = candidates
+            .filter(|candidate| candidate != name)
+            .map(|candidate| (levenshtein(name, &candidate), candidate))
+            .filter(|(distance, _)| *distance <= max_distance)
+            .collect();
+        suggestions.sort();
+
+        suggestions
+            .into_iter()
+            .take(MAX_SUGGESTIONS)
+            .enumerate()
+            .map(|(index, (_, candidate))| CodeAction {
+                title: format!("Did you mean '{candidate}'?"),
+                edits: vec![TextEdit {
+                    range: Range {
+                        start: name_start,
+                        end: name_end,
+                    },
+                    new_text: candidate,
+                }],
+                is_preferred: index == 0,
+            })
+            .collect()
+    }
+}
+
+impl Default for CodeActionProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Find the innermost field containing `offset`, along with its schema definition
+fn find_field_at(
+    document: &cst::Document,
+    offset: usize,
+    schema_index: &SchemaIndex,
+) -> Option<(cst::Field, FieldInfo)> {
+    document.definitions().find_map(|definition| {
+        let (type_name, selection_set) = match definition {
+            cst::Definition::OperationDefinition(operation) => (
+                schema_index
+                    .root_type(OperationType::of(&operation))
+                    .map(ToString::to_string),
+                operation.selection_set(),
+            ),
+            cst::Definition::FragmentDefinition(fragment) => (
+                fragment
+                    .type_condition()
+                    .and_then(|tc| tc.named_type())
+                    .and_then(|nt| nt.name())
+                    .map(|name| name.text().to_string()),
+                fragment.selection_set(),
+            ),
+            _ => return None,
+        };
+        find_field_in_selection_set(&selection_set?, &type_name?, offset, schema_index)
+    })
+}
+
+fn find_field_in_selection_set(
+    selection_set: &cst::SelectionSet,
+    parent_type_name: &str,
+    offset: usize,
+    schema_index: &SchemaIndex,
+) -> Option<(cst::Field, FieldInfo)> {
+    selection_set
+        .selections()
+        .find_map(|selection| match selection {
+            cst::Selection::Field(field) => {
+                if !contains_offset(field.syntax(), offset) {
+                    return None;
+                }
+                let field_name = field.name()?;
+                let field_info = schema_index
+                    .get_fields(parent_type_name)?
+                    .into_iter()
+                    .find(|f| f.name == field_name.text())?;
+
+                let nested = field.selection_set().and_then(|nested| {
+                    find_field_in_selection_set(
+                        &nested,
+                        base_type_name(&field_info.type_name),
+                        offset,
+                        schema_index,
+                    )
+                });
+                Some(nested.unwrap_or((field, field_info)))
+            }
+            cst::Selection::InlineFragment(inline) => {
+                if !contains_offset(inline.syntax(), offset) {
+                    return None;
+                }
+                let type_name = inline
+                    .type_condition()
+                    .and_then(|tc| tc.named_type())
+                    .and_then(|nt| nt.name())
+                    .map_or_else(|| parent_type_name.to_string(), |n| n.text().to_string());
+                find_field_in_selection_set(
+                    &inline.selection_set()?,
+                    &type_name,
+                    offset,
+                    schema_index,
+                )
+            }
+            cst::Selection::FragmentSpread(_) => None,
+        })
+}
+
+fn contains_offset(node: &apollo_parser::SyntaxNode, offset: usize) -> bool {
+    let range = node.text_range();
+    usize::from(range.start()) <= offset && offset <= usize::from(range.end())
+}
+
+/// End offset of a node, excluding the trailing whitespace the parser attaches to it
+fn trimmed_end(source: &str, node: &apollo_parser::SyntaxNode) -> usize {
+    let range = node.text_range();
+    let start: usize = range.start().into();
+    let end: usize = range.end().into();
+    start + source[start..end].trim_end().len()
+}
+
+/// Offset of `name` as a whole identifier within `source[start..end]`
+fn find_name(source: &str, start: usize, end: usize, name: &str) -> Option<usize> {
+    let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    source[start..end]
+        .match_indices(name)
+        .map(|(index, _)| start + index)
+        .find(|&offset| {
+            let before = source[..offset].chars().next_back();
+            let after = source[offset + name.len()..].chars().next();
+            !before.is_some_and(is_name_char) && !after.is_some_and(is_name_char)
+        })
+}
+
+/// Number of single-character insertions, deletions, and substitutions between two strings
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+/// Strip list and non-null wrappers from a type reference
+fn base_type_name(type_name: &str) -> &str {
+    type_name.trim_matches(|c| c == '[' || c == ']' || c == '!')
+}
+
+/// Placeholder literal for an argument of the given type
+fn placeholder_value(type_name: &str, schema_index: &SchemaIndex) -> String {
+    if type_name.starts_with('[') {
+        return "[]".to_string();
+    }
+
+    let base = base_type_name(type_name);
+    match base {
+        "Int" => "0".to_string(),
+        "Float" => "0.0".to_string(),
+        "Boolean" => "false".to_string(),
+        _ => match schema_index.get_type(base).map(|type_info| type_info.kind) {
+            Some(TypeKind::Enum) => schema_index
+                .get_enum_values(base)
+                .into_iter()
+                .next()
+                .map_or_else(|| "\"\"".to_string(), |value| value.name),
+            Some(TypeKind::InputObject) => "{}".to_string(),
+            _ => "\"\"".to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply_edits(source: &str, edits: &[TextEdit]) -> String {
+        let encoding = PositionEncoding::Utf16;
+        let mut result = source.to_string();
+        for edit in edits.iter().rev() {
+            let start = encoding
+                .position_to_offset(source, edit.range.start)
+                .unwrap();
+            let end = encoding.position_to_offset(source, edit.range.end).unwrap();
+            result.replace_range(start..end, &edit.new_text);
+        }
+        result
+    }
+
+    #[test]
+    fn test_remove_unused_fragment_leaves_parseable_document() {
+        let source = r"query GetUser {
+  user {
+    ...UsedFields
+  }
+}
+
+fragment UnusedFields on User {
+  name
+}
+
+fragment UsedFields on User {
+  id
+}
+";
+
+        let actions = CodeActionProvider::new().code_actions(
+            source,
+            "unused-fragment",
+            Range {
+                start: Position {
+                    line: 6,
+                    character: 9,
+                },
+                end: Position {
+                    line: 6,
+                    character: 21,
+                },
+            },
+            None,
+            &SchemaIndex::new(),
+        );
+
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].title, "Remove unused fragment 'UnusedFields'");
+
+        let result = apply_edits(source, &actions[0].edits);
+        assert_eq!(Parser::new(&result).parse().errors().count(), 0);
+        assert!(!result.contains("UnusedFields"));
+        assert!(result.contains("\n\nfragment UsedFields on User {"));
+    }
+
+    #[test]
+    fn test_remove_last_fragment_trims_preceding_whitespace() {
+        let source = "query GetUser {\n  user {\n    id\n  }\n}\n\nfragment UnusedFields on User {\n  name\n}\n";
+
+        let actions = CodeActionProvider::new().code_actions(
+            source,
+            "unused-fragment",
+            Range {
+                start: Position {
+                    line: 6,
+                    character: 9,
+                },
+                end: Position {
+                    line: 6,
+                    character: 21,
+                },
+            },
+            None,
+            &SchemaIndex::new(),
+        );
+
+        let result = apply_edits(source, &actions[0].edits);
+        assert_eq!(result, "query GetUser {\n  user {\n    id\n  }\n}\n");
+    }
+
+    #[test]
+    fn test_no_action_for_other_codes() {
+        let actions = CodeActionProvider::new().code_actions(
+            "fragment F on User { id }",
+            "deprecated-field",
+            Range {
+                start: Position {
+                    line: 0,
+                    character: 9,
+                },
+                end: Position {
+                    line: 0,
+                    character: 10,
+                },
+            },
+            None,
+            &SchemaIndex::new(),
+        );
+
+        assert!(actions.is_empty());
+    }
+
+    fn create_schema_index() -> SchemaIndex {
+        SchemaIndex::from_schema(
+            r"
+            type Query {
+                user(id: ID!, includePosts: Boolean = false): User
+                search(term: String!, kind: SearchKind!, limit: Int): [SearchResult!]!
+            }
+
+            type User {
+                id: ID!
+                name: String
+            }
+
+            enum SearchKind {
+                USER
+                POST
+            }
+
+            type SearchResult {
+                title: String
+            }
+            ",
+        )
+    }
+
+    fn at(line: usize, character: usize) -> Range {
+        let position = Position { line, character };
+        Range {
+            start: position,
+            end: position,
+        }
+    }
+
+    #[test]
+    fn test_add_subselection_prefers_id() {
+        let source = "query GetUser {\n  user(id: \"1\")\n}\n";
+        let schema_index = create_schema_index();
+
+        let actions = CodeActionProvider::new().code_actions(
+            source,
+            "missing-subselection",
+            at(1, 2),
+            None,
+            &schema_index,
+        );
+
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].title, "Add required subselection");
+        let result = apply_edits(source, &actions[0].edits);
+        assert_eq!(result, "query GetUser {\n  user(id: \"1\") { id }\n}\n");
+    }
+
+    #[test]
+    fn test_add_subselection_falls_back_to_typename() {
+        let source = "query Search {\n  search(term: \"a\", kind: USER)\n}\n";
+        let schema_index = create_schema_index();
+
+        let actions = CodeActionProvider::new().code_actions(
+            source,
+            "missing-subselection",
+            at(1, 2),
+            None,
+            &schema_index,
+        );
+
+        let result = apply_edits(source, &actions[0].edits);
+        assert_eq!(
+            result,
+            "query Search {\n  search(term: \"a\", kind: USER) { __typename }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_add_missing_required_arguments() {
+        let schema_index = create_schema_index();
+
+        let source = "query GetUser {\n  user {\n    name\n  }\n}\n";
+        let actions = CodeActionProvider::new().code_actions(
+            source,
+            "missing-required-argument",
+            at(1, 2),
+            None,
+            &schema_index,
+        );
+        assert_eq!(actions[0].title, "Add missing required argument 'id'");
+        assert_eq!(
+            apply_edits(source, &actions[0].edits),
+            "query GetUser {\n  user(id: \"\") {\n    name\n  }\n}\n"
+        );
+
+        let source = "query Search {\n  search(limit: 5) {\n    title\n  }\n}\n";
+        let actions = CodeActionProvider::new().code_actions(
+            source,
+            "missing-required-argument",
+            at(1, 2),
+            None,
+            &schema_index,
+        );
+        assert_eq!(actions[0].title, "Add missing required arguments");
+        assert_eq!(
+            apply_edits(source, &actions[0].edits),
+            "query Search {\n  search(limit: 5, term: \"\", kind: USER) {\n    title\n  }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_suggest_closest_field_name() {
+        let source = "query GetUser {\n  user(id: \"1\") {\n    nmae\n  }\n}\n";
+        let schema_index = create_schema_index();
+        let data = DiagnosticData::UnknownField {
+            parent_type: "User".to_string(),
+            field_name: "nmae".to_string(),
+        };
+
+        let actions = CodeActionProvider::new().code_actions(
+            source,
+            "unknown-field",
+            Range {
+                start: Position {
+                    line: 2,
+                    character: 4,
+                },
+                end: Position {
+                    line: 2,
+                    character: 8,
+                },
+            },
+            Some(&data),
+            &schema_index,
+        );
+
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].title, "Did you mean 'name'?");
+        assert_eq!(
+            apply_edits(source, &actions[0].edits),
+            "query GetUser {\n  user(id: \"1\") {\n    name\n  }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_only_closest_suggestion_is_preferred() {
+        let source = "query GetUser {\n  user {\n    nme\n  }\n}\n";
+        let schema_index = SchemaIndex::from_schema(
+            "type Query {\n  user: User\n}\n\ntype User {\n  name: String\n  game: String\n  names: String\n}\n",
+        );
+        let data = DiagnosticData::UnknownField {
+            parent_type: "User".to_string(),
+            field_name: "nme".to_string(),
+        };
+
+        let actions = CodeActionProvider::new().code_actions(
+            source,
+            "unknown-field",
+            at(2, 4),
+            Some(&data),
+            &schema_index,
+        );
+
+        let titles: Vec<_> = actions.iter().map(|action| action.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            [
+                "Did you mean 'name'?",
+                "Did you mean 'game'?",
+                "Did you mean 'names'?"
+            ]
+        );
+        let preferred: Vec<_> = actions.iter().map(|action| action.is_preferred).collect();
+        assert_eq!(preferred, [true, false, false]);
+    }
+
+    #[test]
+    fn test_suggest_type_name_skips_distant_candidates() {
+        let source = "fragment F on Usr {\n  id\n}\n";
+        let schema_index = create_schema_index();
+
+        let actions = CodeActionProvider::new().code_actions(
+            source,
+            "unknown-type",
+            at(0, 14),
+            Some(&DiagnosticData::UnknownType {
+                type_name: "Usr".to_string(),
+            }),
+            &schema_index,
+        );
+        assert_eq!(actions.len(), 1);
+        assert_eq!(
+            apply_edits(source, &actions[0].edits),
+            "fragment F on User {\n  id\n}\n"
+        );
+
+        let actions = CodeActionProvider::new().code_actions(
+            "fragment F on Nonsense {\n  id\n}\n",
+            "unknown-type",
+            at(0, 14),
+            Some(&DiagnosticData::UnknownType {
+                type_name: "Nonsense".to_string(),
+            }),
+            &schema_index,
+        );
+        assert!(actions.is_empty());
+    }
+
+    #[test]
+    fn test_split_two_operations() {
+        let source = r"query GetUser {
+  user {
+    ...UserFields
+  }
+}
+
+mutation UpdateUser {
+  updateUser {
+    ...UserFields
+  }
+}
+
+fragment UserFields on User {
+  id
+}
+";
+
+        let split = CodeActionProvider::new().split_operations(source).unwrap();
+
+        assert_eq!(
+            split.operations,
+            vec![MovedOperation {
+                name: "UpdateUser".to_string(),
+                content: "mutation UpdateUser {\n  updateUser {\n    ...UserFields\n  }\n}\n"
+                    .to_string(),
+            }]
+        );
+        assert_eq!(
+            apply_edits(source, &split.edits),
+            "query GetUser {\n  user {\n    ...UserFields\n  }\n}\n\nfragment UserFields on User {\n  id\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_split_trailing_operations() {
+        let source = "query A { a }\n\nquery B { b }\n\nquery C { c }\n";
+
+        let split = CodeActionProvider::new().split_operations(source).unwrap();
+
+        let names: Vec<_> = split.operations.iter().map(|op| op.name.as_str()).collect();
+        assert_eq!(names, vec!["B", "C"]);
+        assert_eq!(split.edits.len(), 1);
+        assert_eq!(apply_edits(source, &split.edits), "query A { a }\n");
+
+        assert!(CodeActionProvider::new()
+            .split_operations("query A { a }\n\nfragment F on Query { a }\n")
+            .is_none());
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("name", "name"), 0);
+        assert_eq!(levenshtein("nmae", "name"), 2);
+        assert_eq!(levenshtein("fulName", "fullName"), 1);
+        assert_eq!(levenshtein("", "abc"), 3);
+    }
+}
@@ -1,10 +1,32 @@
 #![allow(clippy::too_many_lines)]
 
-use crate::{DocumentIndex, Position, SchemaIndex};
+use crate::{ArgumentInfo, DocumentIndex, OperationType, Position, SchemaIndex, TypeKind};
 use apollo_parser::{
     cst::{self, CstNode},
-    Parser,
+    Parser, SyntaxKind,
 };
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, RwLock,
+};
+
+/// Completion configuration, read from `extensions.completion`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CompletionConfig {
+    /// Offer deprecated fields and enum values, flagged as deprecated
+    pub include_deprecated: bool,
+}
+
+impl Default for CompletionConfig {
+    fn default() -> Self {
+        Self {
+            include_deprecated: true,
+        }
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CompletionItemKind {
@@ -16,6 +38,8 @@ pub enum CompletionItemKind {
     EnumValue,
     Argument,
     Variable,
+    /// Literal values like `true` and `null`
+    Value,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -26,6 +50,8 @@ pub struct CompletionItem {
     pub documentation: Option<String>,
     pub deprecated: bool,
     pub insert_text: Option<String>,
+    /// Key clients sort by instead of the label
+    pub sort_text: Option<String>,
 }
 
 impl CompletionItem {
@@ -45,8 +71,15 @@ impl CompletionItem {
             documentation,
             deprecated,
             insert_text,
+            sort_text: None,
         }
     }
+
+    #[must_use]
+    pub fn with_sort_text(mut self, sort_text: impl Into<String>) -> Self {
+        self.sort_text = Some(sort_text.into());
+        self
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -57,7 +90,9 @@ enum CompletionContext {
         already_selected_fields: Vec<String>,
         is_in_alias: bool,
     },
-    FragmentSpread,
+    FragmentSpread {
+        parent_type: String,
+    },
     TypeCondition,
     Directive {
         location: DirectiveLocation,
@@ -66,35 +101,113 @@ enum CompletionContext {
         parent_type: String,
         field_name: String,
     },
-    EnumValue {
-        enum_type: String,
+    /// Argument name inside a directive's argument list
+    DirectiveArgument {
+        directive_name: String,
+        already_provided: Vec<String>,
+    },
+    /// Value of an argument or input object field of type `value_type`
+    Value {
+        value_type: String,
+    },
+    /// Field name inside an object value of input type `input_type`
+    InputObjectField {
+        input_type: String,
+        already_provided: Vec<String>,
     },
     VariableDefinition,
     FieldType,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-#[allow(dead_code)]
+/// Executable location a directive is being written at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum DirectiveLocation {
     Query,
+    Mutation,
+    Subscription,
     Field,
     FragmentDefinition,
     FragmentSpread,
     InlineFragment,
 }
 
-pub struct CompletionProvider;
+impl DirectiveLocation {
+    /// Name of the location as stored in `DirectiveInfo::locations`
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Query => "Query",
+            Self::Mutation => "Mutation",
+            Self::Subscription => "Subscription",
+            Self::Field => "Field",
+            Self::FragmentDefinition => "FragmentDefinition",
+            Self::FragmentSpread => "FragmentSpread",
+            Self::InlineFragment => "InlineFragment",
+        }
+    }
+}
+
+/// Field completion items by parent type name
+///
+/// Building the items for a type with many fields is repeated on every keystroke inside
+/// its selection sets, so they are computed once per type. Each `SchemaIndex` owns one,
+/// which is dropped along with the index when the schema changes.
+#[derive(Debug, Default)]
+pub(crate) struct FieldCompletionCache {
+    items: RwLock<HashMap<String, Arc<Vec<CompletionItem>>>>,
+    /// Number of times items were computed rather than served from the cache
+    misses: AtomicUsize,
+}
+
+impl FieldCompletionCache {
+    /// Get the items for `parent_type`, computing them with `compute` on first use
+    fn get_or_insert_with(
+        &self,
+        parent_type: &str,
+        compute: impl FnOnce() -> Vec<CompletionItem>,
+    ) -> Arc<Vec<CompletionItem>> {
+        if let Some(items) = self.items.read().unwrap().get(parent_type) {
+            return Arc::clone(items);
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let items = Arc::new(compute());
+        self.items
+            .write()
+            .unwrap()
+            .insert(parent_type.to_string(), Arc::clone(&items));
+        items
+    }
+
+    #[cfg(test)]
+    fn misses(&self) -> usize {
+        self.misses.load(Ordering::Relaxed)
+    }
+}
+
+pub struct CompletionProvider {
+    config: CompletionConfig,
+}
 
 impl Default for CompletionProvider {
     fn default() -> Self {
-        Self
+        Self::new()
     }
 }
 
 impl CompletionProvider {
     #[must_use]
     pub const fn new() -> Self {
-        Self
+        Self {
+            config: CompletionConfig {
+                include_deprecated: true,
+            },
+        }
+    }
+
+    /// Create a provider that completes according to `config`
+    #[must_use]
+    pub const fn with_config(config: CompletionConfig) -> Self {
+        Self { config }
     }
 
     #[must_use]
@@ -135,61 +248,113 @@ impl CompletionProvider {
 
         let doc = tree.document();
 
-        // Try to use cached LineIndex for O(1) position-to-offset conversion
-        // Fall back to O(N) character iteration if not available
-        let byte_offset = file_path
-            .and_then(|path| document_index.get_line_index(path))
-            .and_then(|line_index| Self::position_to_offset_with_index(&line_index, position))
-            .or_else(|| Self::position_to_offset(source, position))?;
+        let byte_offset = document_index
+            .line_index_for(file_path, source)
+            .position_to_offset(position)?;
+
+        if Self::is_after_dollar(source, byte_offset) {
+            return Self::complete_variables(&doc, byte_offset, source, schema_index);
+        }
 
         let context = Self::determine_completion_context(&doc, byte_offset, source, schema_index)?;
 
-        Some(Self::generate_completions(
-            context,
-            document_index,
-            schema_index,
-        ))
+        let mut items = Self::generate_completions(context, document_index, schema_index);
+        if !self.config.include_deprecated {
+            items.retain(|item| !item.deprecated);
+        }
+        Some(items)
     }
 
-    /// Convert a line/column position to a byte offset using a cached `LineIndex`
-    ///
-    /// This is the fast path with O(1) complexity instead of O(N) character iteration.
-    fn position_to_offset_with_index(
-        line_index: &crate::LineIndex,
-        position: Position,
-    ) -> Option<usize> {
-        line_index.position_to_offset(position)
+    /// Whether the cursor is in a variable name, i.e. after a `$` and any name characters
+    fn is_after_dollar(source: &str, byte_offset: usize) -> bool {
+        let Some(before) = source.get(..byte_offset) else {
+            return false;
+        };
+        before
+            .trim_end_matches(|c: char| c.is_ascii_alphanumeric() || c == '_')
+            .ends_with('$')
     }
 
-    /// Convert a line/column position to a byte offset (fallback O(N) implementation)
+    /// Complete the variables declared by the operation around the cursor
     ///
-    /// This is the slow path used when no cached `LineIndex` is available.
-    /// Prefers `position_to_offset_with_index` when possible.
-    fn position_to_offset(source: &str, position: Position) -> Option<usize> {
-        let mut current_line = 0;
-        let mut current_col = 0;
-        let mut offset = 0;
-
-        for ch in source.chars() {
-            if current_line == position.line && current_col == position.character {
-                return Some(offset);
-            }
-
-            if ch == '\n' {
-                current_line += 1;
-                current_col = 0;
-            } else {
-                current_col += 1;
+    /// When the variable is an argument or input field value of a known type, only the
+    /// variables that may be used there are offered. Nothing is offered inside the
+    /// variable definitions themselves, or outside of an operation.
+    fn complete_variables(
+        doc: &cst::Document,
+        byte_offset: usize,
+        source: &str,
+        schema_index: &SchemaIndex,
+    ) -> Option<Vec<CompletionItem>> {
+        let operation = doc.definitions().find_map(|def| match def {
+            cst::Definition::OperationDefinition(op) => {
+                let range = op.syntax().text_range();
+                Self::range_contains(range.start().into(), range.end().into(), byte_offset)
+                    .then_some(op)
             }
+            _ => None,
+        })?;
+        let variable_definitions = operation.variable_definitions()?;
 
-            offset += ch.len_utf8();
+        let definitions_range = variable_definitions.syntax().text_range();
+        if Self::range_contains(
+            definitions_range.start().into(),
+            definitions_range.end().into(),
+            byte_offset,
+        ) {
+            return None;
         }
 
-        if current_line == position.line && current_col == position.character {
-            Some(offset)
-        } else {
-            None
-        }
+        let expected_type = match Self::check_operation_for_context(
+            &operation,
+            byte_offset,
+            source,
+            schema_index,
+        ) {
+            Some(CompletionContext::Value { value_type }) => Some(value_type),
+            _ => None,
+        };
+
+        let items = variable_definitions
+            .variable_definitions()
+            .filter_map(|definition| {
+                let name = definition.variable()?.name()?.text().to_string();
+                let variable_type: String = definition
+                    .ty()?
+                    .syntax()
+                    .text()
+                    .to_string()
+                    .chars()
+                    .filter(|c| !c.is_whitespace() && *c != ',')
+                    .collect();
+                let has_default = definition
+                    .default_value()
+                    .and_then(|default| default.value())
+                    .is_some_and(|value| !matches!(value, cst::Value::NullValue(_)));
+
+                let is_allowed = expected_type.as_deref().is_none_or(|expected_type| {
+                    crate::validation::is_variable_usage_allowed(
+                        &variable_type,
+                        has_default,
+                        expected_type,
+                        false,
+                    )
+                });
+
+                is_allowed.then(|| {
+                    CompletionItem::new(
+                        name,
+                        CompletionItemKind::Variable,
+                        Some(variable_type),
+                        None,
+                        false,
+                        None,
+                    )
+                })
+            })
+            .collect();
+
+        Some(items)
     }
 
     fn determine_completion_context(
@@ -237,17 +402,9 @@ impl CompletionProvider {
         }
 
         if let Some(selection_set) = op.selection_set() {
-            let op_type = op.operation_type().map_or("Query", |op_type_node| {
-                if op_type_node.query_token().is_some() {
-                    "Query"
-                } else if op_type_node.mutation_token().is_some() {
-                    "Mutation"
-                } else if op_type_node.subscription_token().is_some() {
-                    "Subscription"
-                } else {
-                    "Query"
-                }
-            });
+            let op_type = schema_index
+                .root_type(OperationType::of(op))
+                .unwrap_or("Query");
 
             if let Some(context) = Self::check_selection_set_for_context(
                 &selection_set,
@@ -262,9 +419,18 @@ impl CompletionProvider {
 
         if let Some(directives) = op.directives() {
             if Self::is_in_directives(&directives, byte_offset) {
-                return Some(CompletionContext::Directive {
-                    location: DirectiveLocation::Query,
-                });
+                let location = match OperationType::of(op) {
+                    OperationType::Query => DirectiveLocation::Query,
+                    OperationType::Mutation => DirectiveLocation::Mutation,
+                    OperationType::Subscription => DirectiveLocation::Subscription,
+                };
+                return Self::check_directives_for_context(
+                    &directives,
+                    byte_offset,
+                    source,
+                    schema_index,
+                    location,
+                );
             }
         }
 
@@ -306,9 +472,13 @@ impl CompletionProvider {
 
         if let Some(directives) = frag.directives() {
             if Self::is_in_directives(&directives, byte_offset) {
-                return Some(CompletionContext::Directive {
-                    location: DirectiveLocation::FragmentDefinition,
-                });
+                return Self::check_directives_for_context(
+                    &directives,
+                    byte_offset,
+                    source,
+                    schema_index,
+                    DirectiveLocation::FragmentDefinition,
+                );
             }
         }
 
@@ -392,7 +562,21 @@ impl CompletionProvider {
                         spread_range.end().into(),
                         byte_offset,
                     ) {
-                        return Some(CompletionContext::FragmentSpread);
+                        if let Some(directives) = spread.directives() {
+                            if Self::is_in_directives(&directives, byte_offset) {
+                                return Self::check_directives_for_context(
+                                    &directives,
+                                    byte_offset,
+                                    source,
+                                    schema_index,
+                                    DirectiveLocation::FragmentSpread,
+                                );
+                            }
+                        }
+
+                        return Some(CompletionContext::FragmentSpread {
+                            parent_type: parent_type.to_string(),
+                        });
                     }
                 }
                 cst::Selection::InlineFragment(inline_frag) => {
@@ -421,7 +605,9 @@ impl CompletionProvider {
             if trimmed.ends_with('.') || trimmed.ends_with("..") || trimmed.ends_with("...") {
                 // User typed one or more dots, they're starting a fragment spread
                 // Don't suggest field names - return FragmentSpread context
-                return Some(CompletionContext::FragmentSpread);
+                return Some(CompletionContext::FragmentSpread {
+                    parent_type: parent_type.to_string(),
+                });
             }
         }
 
@@ -475,6 +661,36 @@ impl CompletionProvider {
 
         if let Some(arguments) = field.arguments() {
             if Self::is_in_arguments(&arguments, byte_offset) {
+                let argument_defs = schema_index
+                    .get_fields(parent_type)
+                    .and_then(|fields| fields.into_iter().find(|f| f.name == field_name))
+                    .map(|f| f.arguments)
+                    .unwrap_or_default();
+                if let Some(value_type) = Self::argument_value_type(
+                    &arguments,
+                    byte_offset,
+                    source,
+                    &argument_defs,
+                    schema_index,
+                ) {
+                    return Some(CompletionContext::Value { value_type });
+                }
+
+                if let Some(context) = Self::argument_input_object_context(
+                    &arguments,
+                    byte_offset,
+                    source,
+                    &argument_defs,
+                    schema_index,
+                ) {
+                    return Some(context);
+                }
+
+                // Argument names don't belong inside an input object
+                if Self::is_in_object_value(&arguments, byte_offset) {
+                    return None;
+                }
+
                 return Some(CompletionContext::Argument {
                     parent_type: parent_type.to_string(),
                     field_name: field_name.to_string(),
@@ -484,9 +700,13 @@ impl CompletionProvider {
 
         if let Some(directives) = field.directives() {
             if Self::is_in_directives(&directives, byte_offset) {
-                return Some(CompletionContext::Directive {
-                    location: DirectiveLocation::Field,
-                });
+                return Self::check_directives_for_context(
+                    &directives,
+                    byte_offset,
+                    source,
+                    schema_index,
+                    DirectiveLocation::Field,
+                );
             }
         }
 
@@ -537,9 +757,13 @@ impl CompletionProvider {
 
         if let Some(directives) = inline_frag.directives() {
             if Self::is_in_directives(&directives, byte_offset) {
-                return Some(CompletionContext::Directive {
-                    location: DirectiveLocation::InlineFragment,
-                });
+                return Self::check_directives_for_context(
+                    &directives,
+                    byte_offset,
+                    source,
+                    schema_index,
+                    DirectiveLocation::InlineFragment,
+                );
             }
         }
 
@@ -567,6 +791,71 @@ impl CompletionProvider {
         )
     }
 
+    /// Context inside a list of directives: a directive's argument names or values when the
+    /// cursor is in its parentheses, otherwise the directive names valid at `location`
+    fn check_directives_for_context(
+        directives: &cst::Directives,
+        byte_offset: usize,
+        source: &str,
+        schema_index: &SchemaIndex,
+        location: DirectiveLocation,
+    ) -> Option<CompletionContext> {
+        for directive in directives.directives() {
+            let Some(arguments) = directive.arguments() else {
+                continue;
+            };
+            if !Self::is_in_arguments(&arguments, byte_offset) {
+                continue;
+            }
+
+            let directive_name = directive.name()?.text().to_string();
+            let argument_defs = schema_index
+                .get_directive(&directive_name)
+                .map(|d| d.arguments)
+                .unwrap_or_default();
+            if let Some(value_type) = Self::argument_value_type(
+                &arguments,
+                byte_offset,
+                source,
+                &argument_defs,
+                schema_index,
+            ) {
+                return Some(CompletionContext::Value { value_type });
+            }
+
+            if let Some(context) = Self::argument_input_object_context(
+                &arguments,
+                byte_offset,
+                source,
+                &argument_defs,
+                schema_index,
+            ) {
+                return Some(context);
+            }
+
+            if Self::is_in_object_value(&arguments, byte_offset) {
+                return None;
+            }
+
+            // Arguments other than the one being typed are already provided
+            let already_provided = arguments
+                .arguments()
+                .filter(|arg| {
+                    let range = arg.syntax().text_range();
+                    !Self::range_contains(range.start().into(), range.end().into(), byte_offset)
+                })
+                .filter_map(|arg| arg.name().map(|name| name.text().to_string()))
+                .collect();
+
+            return Some(CompletionContext::DirectiveArgument {
+                directive_name,
+                already_provided,
+            });
+        }
+
+        Some(CompletionContext::Directive { location })
+    }
+
     fn is_in_arguments(arguments: &cst::Arguments, byte_offset: usize) -> bool {
         let args_range = arguments.syntax().text_range();
         Self::range_contains(
@@ -576,6 +865,228 @@ impl CompletionProvider {
         )
     }
 
+    /// Type of the value being written at the cursor, if it's after an argument's `:`
+    ///
+    /// Inside an input object value, this is the type of the input field being written.
+    fn argument_value_type(
+        arguments: &cst::Arguments,
+        byte_offset: usize,
+        source: &str,
+        argument_defs: &[ArgumentInfo],
+        schema_index: &SchemaIndex,
+    ) -> Option<String> {
+        let argument = arguments
+            .arguments()
+            .take_while(|arg| usize::from(arg.syntax().text_range().start()) < byte_offset)
+            .last()?;
+        let argument_name = argument.name()?.text().to_string();
+
+        let argument_type = &argument_defs
+            .iter()
+            .find(|arg| arg.name == argument_name)?
+            .type_name;
+
+        Self::value_type_at(
+            argument.syntax(),
+            argument.value(),
+            argument_type,
+            byte_offset,
+            source,
+            schema_index,
+        )
+    }
+
+    /// Type of the value being written after the `:` of an argument or object field
+    ///
+    /// `node` is the argument or object field, and `value_type` is its type.
+    fn value_type_at(
+        node: &apollo_parser::SyntaxNode,
+        value: Option<cst::Value>,
+        value_type: &str,
+        byte_offset: usize,
+        source: &str,
+        schema_index: &SchemaIndex,
+    ) -> Option<String> {
+        let colon = node
+            .children_with_tokens()
+            .filter_map(|child| child.as_token().cloned())
+            .find(|token| token.kind() == SyntaxKind::COLON)?;
+        let colon_end: usize = colon.text_range().end().into();
+        if byte_offset < colon_end {
+            return None;
+        }
+
+        match value {
+            Some(cst::Value::ObjectValue(object)) => {
+                let start: usize = object.syntax().text_range().start().into();
+                let is_after_object = object
+                    .r_curly_token()
+                    .is_some_and(|close| byte_offset > usize::from(close.text_range().start()));
+                if byte_offset <= start || is_after_object {
+                    return None;
+                }
+
+                let object_field = object
+                    .object_fields()
+                    .take_while(|field| {
+                        usize::from(field.syntax().text_range().start()) < byte_offset
+                    })
+                    .last()?;
+                let object_field_name = object_field.name()?.text().to_string();
+                let object_field_type = schema_index
+                    .get_fields(&Self::extract_base_type(value_type))?
+                    .into_iter()
+                    .find(|f| f.name == object_field_name)?
+                    .type_name;
+
+                Self::value_type_at(
+                    object_field.syntax(),
+                    object_field.value(),
+                    &object_field_type,
+                    byte_offset,
+                    source,
+                    schema_index,
+                )
+            }
+            Some(value) => (byte_offset <= Self::trimmed_end(source, value.syntax()))
+                .then(|| value_type.to_string()),
+            // Nothing typed after the colon yet
+            None => source
+                .get(colon_end..byte_offset)?
+                .trim()
+                .is_empty()
+                .then(|| value_type.to_string()),
+        }
+    }
+
+    /// Input object field context, if the cursor is between the fields of an argument's
+    /// object value
+    fn argument_input_object_context(
+        arguments: &cst::Arguments,
+        byte_offset: usize,
+        source: &str,
+        argument_defs: &[ArgumentInfo],
+        schema_index: &SchemaIndex,
+    ) -> Option<CompletionContext> {
+        let argument = arguments
+            .arguments()
+            .take_while(|arg| usize::from(arg.syntax().text_range().start()) < byte_offset)
+            .last()?;
+        let argument_name = argument.name()?.text().to_string();
+
+        let argument_type = &argument_defs
+            .iter()
+            .find(|arg| arg.name == argument_name)?
+            .type_name;
+
+        Self::input_object_context_at(
+            argument.syntax(),
+            argument.value(),
+            argument_type,
+            byte_offset,
+            source,
+            schema_index,
+        )
+    }
+
+    /// Input object field context inside the object value of an argument or object field
+    ///
+    /// `node` is the argument or object field, and `value_type` is its type. When the
+    /// cursor is inside a nested object value, the context is that of the innermost one.
+    fn input_object_context_at(
+        node: &apollo_parser::SyntaxNode,
+        value: Option<cst::Value>,
+        value_type: &str,
+        byte_offset: usize,
+        source: &str,
+        schema_index: &SchemaIndex,
+    ) -> Option<CompletionContext> {
+        let colon = node
+            .children_with_tokens()
+            .filter_map(|child| child.as_token().cloned())
+            .find(|token| token.kind() == SyntaxKind::COLON)?;
+        if byte_offset < usize::from(colon.text_range().end()) {
+            return None;
+        }
+
+        let Some(cst::Value::ObjectValue(object)) = value else {
+            return None;
+        };
+        let start: usize = object.syntax().text_range().start().into();
+        let is_after_object = object
+            .r_curly_token()
+            .is_some_and(|close| byte_offset > usize::from(close.text_range().start()));
+        if byte_offset <= start || is_after_object {
+            return None;
+        }
+
+        let input_type = Self::extract_base_type(value_type);
+
+        let nested = object
+            .object_fields()
+            .take_while(|field| usize::from(field.syntax().text_range().start()) < byte_offset)
+            .last()
+            .and_then(|object_field| {
+                let object_field_name = object_field.name()?.text().to_string();
+                let object_field_type = schema_index
+                    .get_fields(&input_type)?
+                    .into_iter()
+                    .find(|f| f.name == object_field_name)?
+                    .type_name;
+                Self::input_object_context_at(
+                    object_field.syntax(),
+                    object_field.value(),
+                    &object_field_type,
+                    byte_offset,
+                    source,
+                    schema_index,
+                )
+            });
+        if nested.is_some() {
+            return nested;
+        }
+
+        // Fields other than the one whose name is being typed are already provided
+        let already_provided = object
+            .object_fields()
+            .filter_map(|field| field.name())
+            .filter(|name| {
+                let start: usize = name.syntax().text_range().start().into();
+                !Self::range_contains(start, Self::trimmed_end(source, name.syntax()), byte_offset)
+            })
+            .map(|name| name.text().to_string())
+            .collect();
+
+        Some(CompletionContext::InputObjectField {
+            input_type,
+            already_provided,
+        })
+    }
+
+    fn is_in_object_value(arguments: &cst::Arguments, byte_offset: usize) -> bool {
+        arguments
+            .syntax()
+            .descendants()
+            .filter(|node| node.kind() == SyntaxKind::OBJECT_VALUE)
+            .any(|node| {
+                let range = node.text_range();
+                usize::from(range.start()) < byte_offset && byte_offset < usize::from(range.end())
+            })
+    }
+
+    /// End offset of a node, excluding the whitespace and commas the parser attaches after it
+    fn trimmed_end(source: &str, node: &apollo_parser::SyntaxNode) -> usize {
+        let range = node.text_range();
+        let start: usize = range.start().into();
+        let end: usize = range.end().into();
+        start
+            + source
+                .get(start..end)
+                .unwrap_or_default()
+                .trim_end_matches(|c: char| c.is_whitespace() || c == ',')
+                .len()
+    }
+
     const fn range_contains(start: usize, end: usize, offset: usize) -> bool {
         offset >= start && offset <= end
     }
@@ -605,18 +1116,35 @@ impl CompletionProvider {
                 &already_selected_fields,
                 is_in_alias,
             ),
-            CompletionContext::FragmentSpread => Self::complete_fragments(document_index),
+            CompletionContext::FragmentSpread { parent_type } => {
+                let mut items =
+                    Self::complete_fragments(&parent_type, document_index, schema_index);
+                items.extend(Self::complete_inline_fragments(&parent_type, schema_index));
+                items
+            }
             CompletionContext::TypeCondition | CompletionContext::FieldType => {
                 Self::complete_types(schema_index)
             }
-            CompletionContext::Directive { .. } => Self::complete_directives(schema_index),
+            CompletionContext::Directive { location } => {
+                Self::complete_directives(location, schema_index)
+            }
             CompletionContext::Argument {
                 parent_type,
                 field_name,
             } => Self::complete_arguments(&parent_type, &field_name, schema_index),
-            CompletionContext::EnumValue { enum_type } => {
-                Self::complete_enum_values(&enum_type, schema_index)
+            CompletionContext::DirectiveArgument {
+                directive_name,
+                already_provided,
+            } => {
+                Self::complete_directive_arguments(&directive_name, &already_provided, schema_index)
+            }
+            CompletionContext::Value { value_type } => {
+                Self::complete_values(&value_type, schema_index)
             }
+            CompletionContext::InputObjectField {
+                input_type,
+                already_provided,
+            } => Self::complete_input_fields(&input_type, &already_provided, schema_index),
             CompletionContext::VariableDefinition => Vec::new(),
         }
     }
@@ -627,17 +1155,64 @@ impl CompletionProvider {
         already_selected_fields: &[String],
         is_in_alias: bool,
     ) -> Vec<CompletionItem> {
+        let items = schema_index
+            .field_completions()
+            .get_or_insert_with(parent_type, || Self::field_items(parent_type, schema_index));
+
+        items
+            .iter()
+            .filter(|item| is_in_alias || !already_selected_fields.contains(&item.label))
+            .cloned()
+            .collect()
+    }
+
+    /// Build the completion items for every field of `parent_type`
+    ///
+    /// `__typename` is offered on every object, interface, and union. On unions it is the
+    /// only field, and on interfaces it's how to tell the possible types apart, so for
+    /// abstract types it's sorted before the other fields.
+    fn field_items(parent_type: &str, schema_index: &SchemaIndex) -> Vec<CompletionItem> {
         let mut items = Vec::new();
 
-        let Some(fields) = schema_index.get_fields(parent_type) else {
+        let Some(type_info) = schema_index.get_type(parent_type) else {
             return items;
         };
 
-        for field in fields {
-            if !is_in_alias && already_selected_fields.contains(&field.name) {
-                continue;
-            }
+        let (documentation, sort_text) = match type_info.kind {
+            TypeKind::Union | TypeKind::Interface => (
+                format!(
+                    "The name of the concrete object type, one of the types of `{parent_type}`"
+                ),
+                Some("0__typename"),
+            ),
+            TypeKind::Object => (
+                format!("The name of the object type, `{parent_type}`"),
+                None,
+            ),
+            TypeKind::Enum | TypeKind::InputObject | TypeKind::Scalar => return items,
+        };
+
+        let typename = CompletionItem::new(
+            "__typename".to_string(),
+            CompletionItemKind::Field,
+            Some("String!".to_string()),
+            Some(documentation),
+            false,
+            None,
+        );
+        items.push(match sort_text {
+            Some(sort_text) => typename.with_sort_text(sort_text),
+            None => typename,
+        });
 
+        let Some(fields) = schema_index.get_fields(parent_type) else {
+            return items;
+        };
+
+        for field in fields
+            .into_iter()
+            .filter(|field| field.name != "__typename")
+        {
             let detail = Some(field.type_name.clone());
             let documentation = field.description.clone();
             let deprecated = field.deprecated.is_some();
@@ -655,11 +1230,32 @@ impl CompletionProvider {
         items
     }
 
-    fn complete_fragments(document_index: &DocumentIndex) -> Vec<CompletionItem> {
+    /// Suggest the fragments that can be spread where `parent_type` is expected
+    ///
+    /// A fragment can be spread when its type condition and the parent type share a
+    /// possible object type. Fragments on unknown types are left out; when the parent type
+    /// itself is unknown, every fragment is offered.
+    fn complete_fragments(
+        parent_type: &str,
+        document_index: &DocumentIndex,
+        schema_index: &SchemaIndex,
+    ) -> Vec<CompletionItem> {
         let mut items = Vec::new();
+        let parent_possible_types = schema_index.possible_object_types(parent_type);
+
+        let mut fragments: Vec<_> = document_index.fragments.iter().collect();
+        fragments.sort_by(|(a, _), (b, _)| a.cmp(b));
 
-        for (fragment_name, fragment_infos) in &document_index.fragments {
+        for (fragment_name, fragment_infos) in fragments {
             if let Some(first) = fragment_infos.first() {
+                let is_applicable = parent_possible_types.is_empty()
+                    || !schema_index
+                        .possible_object_types(&first.type_condition)
+                        .is_disjoint(&parent_possible_types);
+                if !is_applicable {
+                    continue;
+                }
+
                 let detail = Some(format!("on {}", first.type_condition));
 
                 items.push(CompletionItem::new(
@@ -676,6 +1272,34 @@ impl CompletionProvider {
         items
     }
 
+    /// Suggest `... on <Type>` for each possible type of an abstract parent type
+    ///
+    /// Inline fragments on a concrete object type are redundant, so nothing is suggested
+    /// unless the parent is a union or an interface.
+    fn complete_inline_fragments(
+        parent_type: &str,
+        schema_index: &SchemaIndex,
+    ) -> Vec<CompletionItem> {
+        schema_index
+            .implementation_names(parent_type)
+            .into_iter()
+            .map(|type_name| {
+                let documentation = schema_index
+                    .get_type(&type_name)
+                    .and_then(|type_info| type_info.description);
+
+                CompletionItem::new(
+                    format!("... on {type_name}"),
+                    CompletionItemKind::Type,
+                    Some(format!("Inline fragment on {parent_type}")),
+                    documentation,
+                    false,
+                    Some(format!("on {type_name}")),
+                )
+            })
+            .collect()
+    }
+
     fn complete_types(schema_index: &SchemaIndex) -> Vec<CompletionItem> {
         let mut items = Vec::new();
 
@@ -696,10 +1320,18 @@ impl CompletionProvider {
         items
     }
 
-    fn complete_directives(schema_index: &SchemaIndex) -> Vec<CompletionItem> {
+    /// Suggest the directives that can be used at `location`
+    fn complete_directives(
+        location: DirectiveLocation,
+        schema_index: &SchemaIndex,
+    ) -> Vec<CompletionItem> {
         let mut items = Vec::new();
 
-        for directive in schema_index.all_directives() {
+        for directive in schema_index
+            .all_directives()
+            .into_iter()
+            .filter(|directive| directive.locations.iter().any(|l| l == location.name()))
+        {
             let documentation = directive.description.clone();
 
             items.push(CompletionItem::new(
@@ -748,6 +1380,105 @@ impl CompletionProvider {
         items
     }
 
+    fn complete_directive_arguments(
+        directive_name: &str,
+        already_provided: &[String],
+        schema_index: &SchemaIndex,
+    ) -> Vec<CompletionItem> {
+        let Some(directive) = schema_index.get_directive(directive_name) else {
+            return Vec::new();
+        };
+
+        directive
+            .arguments
+            .into_iter()
+            .filter(|arg| !already_provided.contains(&arg.name))
+            .map(|arg| {
+                CompletionItem::new(
+                    arg.name,
+                    CompletionItemKind::Argument,
+                    Some(arg.type_name),
+                    arg.description,
+                    false,
+                    None,
+                )
+            })
+            .collect()
+    }
+
+    /// Complete the fields of an input object that haven't been provided yet
+    ///
+    /// Non-null fields are sorted before nullable ones, since they usually have to be set.
+    fn complete_input_fields(
+        input_type: &str,
+        already_provided: &[String],
+        schema_index: &SchemaIndex,
+    ) -> Vec<CompletionItem> {
+        let Some(fields) = schema_index.get_fields(input_type) else {
+            return Vec::new();
+        };
+
+        fields
+            .into_iter()
+            .filter(|field| !already_provided.contains(&field.name))
+            .map(|field| {
+                let rank = if field.type_name.ends_with('!') { 0 } else { 1 };
+                let sort_text = format!("{rank}{}", field.name);
+                CompletionItem::new(
+                    field.name,
+                    CompletionItemKind::Field,
+                    Some(field.type_name),
+                    field.description,
+                    field.deprecated.is_some(),
+                    None,
+                )
+                .with_sort_text(sort_text)
+            })
+            .collect()
+    }
+
+    /// Complete the literal values of a type: enum members, `true`/`false` for booleans,
+    /// and `null` unless the type is non-null
+    fn complete_values(value_type: &str, schema_index: &SchemaIndex) -> Vec<CompletionItem> {
+        let base_type = Self::extract_base_type(value_type);
+
+        let mut items = if base_type == "Boolean" {
+            ["true", "false"]
+                .into_iter()
+                .map(|value| {
+                    CompletionItem::new(
+                        value.to_string(),
+                        CompletionItemKind::Value,
+                        Some(base_type.clone()),
+                        None,
+                        false,
+                        None,
+                    )
+                })
+                .collect()
+        } else if schema_index
+            .get_type(&base_type)
+            .is_some_and(|type_info| type_info.kind == TypeKind::Enum)
+        {
+            Self::complete_enum_values(&base_type, schema_index)
+        } else {
+            Vec::new()
+        };
+
+        if !value_type.ends_with('!') {
+            items.push(CompletionItem::new(
+                "null".to_string(),
+                CompletionItemKind::Value,
+                Some(value_type.to_string()),
+                None,
+                false,
+                None,
+            ));
+        }
+
+        items
+    }
+
     fn complete_enum_values(enum_type: &str, schema_index: &SchemaIndex) -> Vec<CompletionItem> {
         let mut items = Vec::new();
 
@@ -768,3 +1499,456 @@ impl CompletionProvider {
         items
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_schema() -> SchemaIndex {
+        SchemaIndex::from_schema(
+            r#"
+            type Query {
+                search: [SearchResult!]!
+                node(id: ID!): Node
+                user: User
+                users(status: Status, active: Boolean!, filter: UserFilter): [User!]!
+            }
+
+            enum Status {
+                ACTIVE
+                INACTIVE
+                BANNED @deprecated(reason: "Use INACTIVE")
+            }
+
+            input UserFilter {
+                "Only users with this status"
+                status: Status!
+                verified: Boolean
+                name: NameFilter
+            }
+
+            input NameFilter {
+                prefix: String
+                exact: String!
+            }
+
+            interface Node {
+                id: ID!
+            }
+
+            union SearchResult = User | Post
+
+            type User implements Node {
+                id: ID!
+                name: String!
+            }
+
+            type Post implements Node {
+                id: ID!
+                title: String!
+            }
+
+            enum Role {
+                ADMIN
+                USER
+            }
+
+            directive @auth(
+                "The role required to access the field"
+                requires: Role!
+                "Whether to hide the field instead of failing"
+                silent: Boolean
+            ) on FIELD
+
+            "Stream updates to the operation's results"
+            directive @live on QUERY | SUBSCRIPTION
+            "#,
+        )
+    }
+
+    fn complete_at_end_of(source: &str, marker: &str) -> Vec<CompletionItem> {
+        let offset = source.find(marker).unwrap() + marker.len();
+        let line = source[..offset].matches('\n').count();
+        let line_start = source[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+        let position = Position {
+            line,
+            character: offset - line_start,
+        };
+
+        CompletionProvider::new()
+            .complete(
+                source,
+                position,
+                &DocumentIndex::new(),
+                &create_test_schema(),
+            )
+            .unwrap_or_default()
+    }
+
+    fn inline_fragment_labels(items: &[CompletionItem]) -> Vec<&str> {
+        items
+            .iter()
+            .filter(|item| item.label.starts_with("... on "))
+            .map(|item| item.label.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_inline_fragment_completion_on_union() {
+        let source = "query { search { ... o } }";
+        let items = complete_at_end_of(source, "... o");
+
+        assert_eq!(
+            inline_fragment_labels(&items),
+            vec!["... on User", "... on Post"]
+        );
+        let user = items
+            .iter()
+            .find(|item| item.label == "... on User")
+            .unwrap();
+        assert_eq!(user.insert_text.as_deref(), Some("on User"));
+    }
+
+    #[test]
+    fn test_inline_fragment_completion_on_interface() {
+        let source = r#"query { node(id: "1") { ... o } }"#;
+        let items = complete_at_end_of(source, "... o");
+
+        assert_eq!(
+            inline_fragment_labels(&items),
+            vec!["... on User", "... on Post"]
+        );
+    }
+
+    #[test]
+    fn test_no_inline_fragment_completion_on_object() {
+        let source = "query { user { ... o } }";
+        let items = complete_at_end_of(source, "... o");
+
+        assert!(inline_fragment_labels(&items).is_empty());
+    }
+
+    #[test]
+    fn test_typename_is_offered_first_on_union() {
+        let source = "query { search { } }";
+        let items = complete_at_end_of(source, "search { ");
+
+        let typename = items
+            .iter()
+            .find(|item| item.label == "__typename")
+            .unwrap();
+        assert_eq!(typename.kind, CompletionItemKind::Field);
+        assert_eq!(typename.sort_text.as_deref(), Some("0__typename"));
+        assert!(typename.documentation.is_some());
+    }
+
+    #[test]
+    fn test_typename_is_offered_on_object() {
+        let source = "query { user { } }";
+        let items = complete_at_end_of(source, "user { ");
+
+        let typename = items
+            .iter()
+            .find(|item| item.label == "__typename")
+            .unwrap();
+        assert_eq!(typename.sort_text, None);
+        assert!(items.iter().any(|item| item.label == "name"));
+    }
+
+    fn labels(items: &[CompletionItem]) -> Vec<&str> {
+        items.iter().map(|item| item.label.as_str()).collect()
+    }
+
+    #[test]
+    fn test_enum_argument_values() {
+        let items = complete_at_end_of("query { users(status: ) { id } }", "status: ");
+
+        assert_eq!(labels(&items), vec!["ACTIVE", "INACTIVE", "BANNED", "null"]);
+        let banned = items.iter().find(|item| item.label == "BANNED").unwrap();
+        assert!(banned.deprecated);
+        assert!(!items[0].deprecated);
+
+        // A partially typed value completes the same way
+        let items = complete_at_end_of("query { users(status: AC) { id } }", "status: AC");
+        assert_eq!(labels(&items), vec!["ACTIVE", "INACTIVE", "BANNED", "null"]);
+    }
+
+    #[test]
+    fn test_boolean_argument_values() {
+        let items = complete_at_end_of("query { users(active: ) { id } }", "active: ");
+
+        // Non-null, so no `null`
+        assert_eq!(labels(&items), vec!["true", "false"]);
+        assert!(items
+            .iter()
+            .all(|item| item.kind == CompletionItemKind::Value));
+    }
+
+    #[test]
+    fn test_input_object_field_values() {
+        let source = "query { users(filter: { verified: true, status: }) { id } }";
+
+        let items = complete_at_end_of(source, "status: ");
+        assert_eq!(labels(&items), vec!["ACTIVE", "INACTIVE", "BANNED"]);
+
+        let items = complete_at_end_of(source, "verified: ");
+        assert_eq!(labels(&items), vec!["true", "false", "null"]);
+    }
+
+    #[test]
+    fn test_input_object_field_names() {
+        let items = complete_at_end_of("query { users(filter: { }) { id } }", "filter: { ");
+
+        assert_eq!(labels(&items), vec!["status", "verified", "name"]);
+        assert!(items
+            .iter()
+            .all(|item| item.kind == CompletionItemKind::Field));
+        let status = &items[0];
+        assert_eq!(status.detail.as_deref(), Some("Status!"));
+        assert_eq!(
+            status.documentation.as_deref(),
+            Some("Only users with this status")
+        );
+        // Non-null fields sort first
+        assert!(status.sort_text < items[1].sort_text);
+
+        // Provided fields aren't offered again, but the one being typed is
+        let source = "query { users(filter: { verified: true, sta }) { id } }";
+        let items = complete_at_end_of(source, "sta");
+        assert_eq!(labels(&items), vec!["status", "name"]);
+    }
+
+    #[test]
+    fn test_nested_input_object_field_names() {
+        let source = "query { users(filter: { status: ACTIVE, name: { } }) { id } }";
+        let items = complete_at_end_of(source, "name: { ");
+
+        assert_eq!(labels(&items), vec!["prefix", "exact"]);
+        let exact = items.iter().find(|item| item.label == "exact").unwrap();
+        let prefix = items.iter().find(|item| item.label == "prefix").unwrap();
+        assert!(exact.sort_text < prefix.sort_text);
+    }
+
+    #[test]
+    fn test_argument_names_after_value() {
+        let items = complete_at_end_of("query { users(active: true, ) { id } }", "true, ");
+
+        assert!(items
+            .iter()
+            .all(|item| item.kind == CompletionItemKind::Argument));
+        assert!(items.iter().any(|item| item.label == "status"));
+    }
+
+    #[test]
+    fn test_directives_are_filtered_by_location() {
+        let items = complete_at_end_of("query { user @s { id } }", "@s");
+        let field_directives = labels(&items);
+        assert!(field_directives.contains(&"auth"));
+        assert!(field_directives.contains(&"skip"));
+        assert!(field_directives.contains(&"include"));
+        // Schema directives aren't offered in executable documents
+        assert!(!field_directives.contains(&"deprecated"));
+        assert!(!field_directives.contains(&"specifiedBy"));
+        assert!(!field_directives.contains(&"live"));
+
+        let items = complete_at_end_of("query Q @l { user { id } }", "@l");
+        assert_eq!(labels(&items), vec!["live"]);
+        assert_eq!(items[0].insert_text.as_deref(), Some("@live"));
+
+        let items = complete_at_end_of("mutation M @l { user { id } }", "@l");
+        assert!(items.is_empty());
+
+        let source = "fragment F on User { id }\nquery { user { ...F @s } }";
+        let spread_directives = complete_at_end_of(source, "@s");
+        let spread_directives = labels(&spread_directives);
+        assert!(spread_directives.contains(&"skip"));
+        assert!(spread_directives.contains(&"include"));
+        assert!(!spread_directives.contains(&"auth"));
+    }
+
+    #[test]
+    fn test_directive_argument_names() {
+        let items = complete_at_end_of("query { user @auth() { id } }", "@auth(");
+
+        assert_eq!(labels(&items), vec!["requires", "silent"]);
+        assert!(items
+            .iter()
+            .all(|item| item.kind == CompletionItemKind::Argument));
+        let requires = items.iter().find(|item| item.label == "requires").unwrap();
+        assert_eq!(requires.detail.as_deref(), Some("Role!"));
+        assert_eq!(
+            requires.documentation.as_deref(),
+            Some("The role required to access the field")
+        );
+
+        // Provided arguments aren't offered again, and values complete from the argument type
+        let source = "query { user @auth(requires: , ) { id } }";
+        assert_eq!(labels(&complete_at_end_of(source, ", ")), vec!["silent"]);
+        assert_eq!(
+            labels(&complete_at_end_of(source, "requires: ")),
+            vec!["ADMIN", "USER"]
+        );
+    }
+
+    #[test]
+    fn test_fragment_spread_completion_filters_by_type_condition() {
+        let mut document_index = DocumentIndex::new();
+        for (name, type_condition) in [
+            ("UserFields", "User"),
+            ("PostFields", "Post"),
+            ("NodeFields", "Node"),
+            ("SearchFields", "SearchResult"),
+        ] {
+            document_index.fragments.insert(
+                name.to_string(),
+                vec![crate::FragmentInfo {
+                    name: name.to_string(),
+                    type_condition: type_condition.to_string(),
+                    file_path: "fragments.graphql".to_string(),
+                    line: 0,
+                    column: 0,
+                }],
+            );
+        }
+
+        let complete = |source: &str| {
+            let offset = source.find("...").unwrap() + 3;
+            let position = Position {
+                line: 0,
+                character: offset,
+            };
+            CompletionProvider::new()
+                .complete(source, position, &document_index, &create_test_schema())
+                .unwrap_or_default()
+        };
+        let fragment_labels = |items: &[CompletionItem]| -> Vec<String> {
+            items
+                .iter()
+                .filter(|item| item.kind == CompletionItemKind::Fragment)
+                .map(|item| item.label.clone())
+                .collect()
+        };
+
+        // On an object type, fragments on its interfaces and unions apply too
+        let items = complete("query { user { ... } }");
+        assert_eq!(
+            fragment_labels(&items),
+            vec!["NodeFields", "SearchFields", "UserFields"]
+        );
+
+        // On a union, any fragment sharing a member type applies
+        let items = complete("query { search { ... } }");
+        assert_eq!(
+            fragment_labels(&items),
+            vec!["NodeFields", "PostFields", "SearchFields", "UserFields"]
+        );
+        assert_eq!(
+            inline_fragment_labels(&items),
+            vec!["... on User", "... on Post"]
+        );
+    }
+
+    #[test]
+    fn test_variable_completion_after_dollar() {
+        let source = "query Users($status: Status, $active: Boolean = true, $on: Boolean) {
+  users(status: $, active: $a) { id }
+}";
+
+        let items = complete_at_end_of(source, "status: $");
+        assert_eq!(labels(&items), vec!["status"]);
+        assert_eq!(items[0].kind, CompletionItemKind::Variable);
+        assert_eq!(items[0].detail.as_deref(), Some("Status"));
+
+        // `active` is non-null, so only the variable with a default fits
+        let items = complete_at_end_of(source, "active: $a");
+        assert_eq!(labels(&items), vec!["active"]);
+    }
+
+    #[test]
+    fn test_no_variable_completion_in_variable_definitions() {
+        let source = "query Users($status: Status, $) { users(status: ACTIVE) { id } }";
+
+        assert!(complete_at_end_of(source, "Status, $").is_empty());
+    }
+
+    #[test]
+    fn test_mutation_fields_with_root_from_schema_extension() {
+        let schema_index = SchemaIndex::from_schema(
+            "type Query { user: User }
+            type User { id: ID! }
+            type AppMutation { createUser(name: String!): User }
+            extend schema { mutation: AppMutation }",
+        );
+        let source = "mutation { }";
+        let position = Position {
+            line: 0,
+            character: 11,
+        };
+
+        let items = CompletionProvider::new()
+            .complete(source, position, &DocumentIndex::new(), &schema_index)
+            .unwrap_or_default();
+        assert!(items.iter().any(|item| item.label == "createUser"));
+    }
+
+    #[test]
+    fn test_deprecated_completions_can_be_excluded() {
+        let source = "query { users(status: ) { id } }";
+        let position = Position {
+            line: 0,
+            character: 22,
+        };
+        let complete = |provider: CompletionProvider| {
+            provider
+                .complete(
+                    source,
+                    position,
+                    &DocumentIndex::new(),
+                    &create_test_schema(),
+                )
+                .unwrap_or_default()
+        };
+
+        let items = complete(CompletionProvider::new());
+        assert_eq!(labels(&items), vec!["ACTIVE", "INACTIVE", "BANNED", "null"]);
+
+        let config: CompletionConfig =
+            serde_json::from_value(serde_json::json!({ "includeDeprecated": false })).unwrap();
+        let items = complete(CompletionProvider::with_config(config));
+        assert_eq!(labels(&items), vec!["ACTIVE", "INACTIVE", "null"]);
+    }
+
+    #[test]
+    fn test_field_completions_are_cached_per_schema() {
+        let schema_index = create_test_schema();
+        let source = "query { user { name } }";
+        let position = Position {
+            line: 0,
+            character: 15,
+        };
+
+        let complete = |schema_index: &SchemaIndex| {
+            CompletionProvider::new()
+                .complete(source, position, &DocumentIndex::new(), schema_index)
+                .unwrap_or_default()
+        };
+
+        let first = complete(&schema_index);
+        let second = complete(&schema_index);
+        assert_eq!(first, second);
+        assert!(first.iter().any(|item| item.label == "id"));
+        assert_eq!(schema_index.field_completions().misses(), 1);
+
+        // Clones of the index share its cache
+        complete(&schema_index.clone());
+        assert_eq!(schema_index.field_completions().misses(), 1);
+
+        // A changed schema is a new index with its own cache
+        let changed = SchemaIndex::from_schema(
+            "type Query { user: User }\ntype User { id: ID!\nemail: String }",
+        );
+        let items = complete(&changed);
+        assert!(items.iter().any(|item| item.label == "email"));
+        assert_eq!(changed.field_completions().misses(), 1);
+    }
+}
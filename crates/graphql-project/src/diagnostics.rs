@@ -37,6 +37,23 @@ pub struct Location {
     pub range: Range,
 }
 
+/// Structured context about what a diagnostic refers to
+///
+/// Carried alongside the message so code actions don't have to parse it back out.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum DiagnosticData {
+    /// A selected field that does not exist on its parent type
+    #[serde(rename_all = "camelCase")]
+    UnknownField {
+        parent_type: String,
+        field_name: String,
+    },
+    /// A reference to a type that is not defined in the schema
+    #[serde(rename_all = "camelCase")]
+    UnknownType { type_name: String },
+}
+
 /// A diagnostic message (error, warning, etc.)
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Diagnostic {
@@ -57,6 +74,9 @@ pub struct Diagnostic {
 
     /// Related information
     pub related_info: Vec<RelatedInfo>,
+
+    /// Structured context for code actions
+    pub data: Option<DiagnosticData>,
 }
 
 impl Diagnostic {
@@ -68,6 +88,7 @@ impl Diagnostic {
             code: None,
             source: "graphql-project".to_string(),
             related_info: Vec::new(),
+            data: None,
         }
     }
 
@@ -79,6 +100,7 @@ impl Diagnostic {
             code: None,
             source: "graphql-project".to_string(),
             related_info: Vec::new(),
+            data: None,
         }
     }
 
@@ -99,4 +121,10 @@ impl Diagnostic {
         self.related_info.push(info);
         self
     }
+
+    #[must_use]
+    pub fn with_data(mut self, data: DiagnosticData) -> Self {
+        self.data = Some(data);
+        self
+    }
 }
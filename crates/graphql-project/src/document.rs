@@ -1,4 +1,7 @@
-use crate::{DocumentIndex, FragmentInfo, OperationInfo, OperationType, ProjectError, Result};
+use crate::{
+    DocumentIndex, FragmentInfo, OperationInfo, OperationType, PositionEncoding, ProjectError,
+    Result,
+};
 use apollo_parser::{cst, Parser};
 use graphql_config::DocumentsConfig;
 use graphql_extract::{extract_from_file, ExtractConfig};
@@ -9,6 +12,10 @@ pub struct DocumentLoader {
     config: DocumentsConfig,
     base_path: Option<PathBuf>,
     extract_config: ExtractConfig,
+    /// Globs of files to skip even when a document pattern matches them
+    excluded_patterns: Vec<String>,
+    /// Unit the index counts `Position::character` columns in
+    position_encoding: PositionEncoding,
 }
 
 impl DocumentLoader {
@@ -18,6 +25,8 @@ impl DocumentLoader {
             config,
             base_path: None,
             extract_config: ExtractConfig::default(),
+            excluded_patterns: Vec::new(),
+            position_encoding: PositionEncoding::default(),
         }
     }
 
@@ -33,9 +42,26 @@ impl DocumentLoader {
         self
     }
 
+    /// Skip files matching any of these globs, relative to the base path
+    #[must_use]
+    pub fn with_excluded_patterns(mut self, patterns: Vec<String>) -> Self {
+        self.excluded_patterns = patterns;
+        self
+    }
+
+    /// Count the index's `Position::character` columns in `encoding`
+    #[must_use]
+    pub const fn with_position_encoding(mut self, encoding: PositionEncoding) -> Self {
+        self.position_encoding = encoding;
+        self
+    }
+
     /// Load all documents and build an index
     pub fn load(&self) -> Result<DocumentIndex> {
-        let mut index = DocumentIndex::new();
+        let mut index = DocumentIndex {
+            position_encoding: self.position_encoding,
+            ..DocumentIndex::new()
+        };
 
         for pattern in self.config.patterns() {
             let paths = self.find_files(pattern)?;
@@ -51,6 +77,21 @@ impl DocumentLoader {
         Ok(index)
     }
 
+    /// Find all files matching the configured document patterns
+    pub fn find_document_files(&self) -> Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+
+        for pattern in self.config.patterns() {
+            for path in self.find_files(pattern)? {
+                if !files.contains(&path) {
+                    files.push(path);
+                }
+            }
+        }
+
+        Ok(files)
+    }
+
     /// Find files matching a glob pattern
     fn find_files(&self, pattern: &str) -> Result<Vec<PathBuf>> {
         // Expand brace patterns like {ts,tsx} since glob crate doesn't support them
@@ -73,6 +114,9 @@ impl DocumentLoader {
                         if path.components().any(|c| c.as_os_str() == "node_modules") {
                             continue;
                         }
+                        if self.is_excluded(&path) {
+                            continue;
+                        }
                         if !files.contains(&path) {
                             files.push(path);
                         }
@@ -88,8 +132,23 @@ impl DocumentLoader {
         Ok(files)
     }
 
+    /// Check if a file matches one of the excluded patterns
+    fn is_excluded(&self, path: &Path) -> bool {
+        self.excluded_patterns
+            .iter()
+            .flat_map(|pattern| Self::expand_braces(pattern))
+            .any(|pattern| {
+                let normalized_pattern = pattern.strip_prefix("./").unwrap_or(&pattern);
+                let full_pattern = self.base_path.as_ref().map_or_else(
+                    || normalized_pattern.to_string(),
+                    |base| base.join(normalized_pattern).display().to_string(),
+                );
+                glob::Pattern::new(&full_pattern).is_ok_and(|pattern| pattern.matches_path(path))
+            })
+    }
+
     /// Expand brace patterns like {ts,tsx} into multiple patterns
-    fn expand_braces(pattern: &str) -> Vec<String> {
+    pub(crate) fn expand_braces(pattern: &str) -> Vec<String> {
         // Simple brace expansion for patterns like **/*.{ts,tsx}
         if let Some(start) = pattern.find('{') {
             if let Some(end) = pattern.find('}') {
@@ -183,22 +242,14 @@ impl DocumentLoader {
         for definition in document.definitions() {
             match definition {
                 cst::Definition::OperationDefinition(op) => {
-                    let operation_type = match op.operation_type() {
-                        Some(op_type) if op_type.query_token().is_some() => OperationType::Query,
-                        Some(op_type) if op_type.mutation_token().is_some() => {
-                            OperationType::Mutation
-                        }
-                        Some(op_type) if op_type.subscription_token().is_some() => {
-                            OperationType::Subscription
-                        }
-                        _ => OperationType::Query, // Default to query
-                    };
+                    let operation_type = OperationType::of(&op);
 
                     let (name, line, column) = op.name().map_or((None, 0, 0), |name_node| {
                         let name_str = name_node.text().to_string();
                         let syntax_node = name_node.syntax();
                         let offset: usize = syntax_node.text_range().start().into();
-                        let (rel_line, rel_col) = Self::offset_to_line_col(source, offset);
+                        let (rel_line, rel_col) =
+                            Self::offset_to_line_col(source, offset, index.position_encoding);
 
                         // Add the base position from the extracted block
                         let abs_line = base_line + rel_line;
@@ -237,7 +288,8 @@ impl DocumentLoader {
                         let (line, column) = name_node.name().map_or((0, 0), |name_token| {
                             let syntax_node = name_token.syntax();
                             let offset: usize = syntax_node.text_range().start().into();
-                            let (rel_line, rel_col) = Self::offset_to_line_col(source, offset);
+                            let (rel_line, rel_col) =
+                                Self::offset_to_line_col(source, offset, index.position_encoding);
 
                             // Add the base position from the extracted block
                             let abs_line = base_line + rel_line;
@@ -267,7 +319,11 @@ impl DocumentLoader {
     }
 
     /// Convert a byte offset to a line and column (0-indexed)
-    fn offset_to_line_col(document: &str, offset: usize) -> (usize, usize) {
+    fn offset_to_line_col(
+        document: &str,
+        offset: usize,
+        encoding: PositionEncoding,
+    ) -> (usize, usize) {
         let mut line = 0;
         let mut col = 0;
         let mut current_offset = 0;
@@ -281,7 +337,7 @@ impl DocumentLoader {
                 line += 1;
                 col = 0;
             } else {
-                col += 1;
+                col += encoding.char_len(ch);
             }
 
             current_offset += ch.len_utf8();
@@ -0,0 +1,419 @@
+use crate::{LineIndex, OperationType, Position, PositionEncoding, Range, SchemaIndex};
+use apollo_parser::{
+    cst::{self, CstNode},
+    Parser, SyntaxKind, SyntaxTree,
+};
+
+/// How an occurrence uses the highlighted symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocumentHighlightKind {
+    /// An occurrence that neither defines nor reads a value, like a selected field
+    Text,
+    /// A variable usage or fragment spread
+    Read,
+    /// A variable or fragment definition
+    Write,
+}
+
+/// One occurrence of the symbol under the cursor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DocumentHighlight {
+    /// Range of the occurrence's name
+    pub range: Range,
+    pub kind: DocumentHighlightKind,
+}
+
+impl DocumentHighlight {
+    /// Shift this highlight from a block-relative to a file range
+    ///
+    /// Used for GraphQL extracted from TypeScript/JavaScript, where the block starts at
+    /// `start_line`/`start_column` in the original file.
+    #[must_use]
+    pub const fn offset_by(mut self, start_line: usize, start_column: usize) -> Self {
+        if self.range.start.line == 0 {
+            self.range.start.character += start_column;
+        }
+        if self.range.end.line == 0 {
+            self.range.end.character += start_column;
+        }
+        self.range.start.line += start_line;
+        self.range.end.line += start_line;
+        self
+    }
+}
+
+/// Document highlight provider
+///
+/// Highlights every occurrence, within a single document, of the variable, fragment, or
+/// field under the cursor. Variables are matched within their operation, fragments across
+/// the document, and fields by their parent type and name.
+pub struct DocumentHighlightProvider {
+    /// Unit `Position::character` columns are counted in
+    position_encoding: PositionEncoding,
+}
+
+impl DocumentHighlightProvider {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            position_encoding: PositionEncoding::Utf16,
+        }
+    }
+
+    /// Count `Position::character` columns in `encoding` instead of UTF-16 code units
+    #[must_use]
+    pub const fn with_position_encoding(mut self, encoding: PositionEncoding) -> Self {
+        self.position_encoding = encoding;
+        self
+    }
+
+    /// Get the highlights for the symbol at a position in a GraphQL document
+    #[must_use]
+    pub fn document_highlights(
+        &self,
+        source: &str,
+        position: Position,
+        schema_index: &SchemaIndex,
+    ) -> Vec<DocumentHighlight> {
+        let tree = Parser::new(source).parse();
+        self.document_highlights_with_ast(source, &tree, position, schema_index)
+    }
+
+    /// Get the highlights for the symbol at a position using an already parsed AST
+    #[must_use]
+    pub fn document_highlights_with_ast(
+        &self,
+        source: &str,
+        tree: &SyntaxTree,
+        position: Position,
+        schema_index: &SchemaIndex,
+    ) -> Vec<DocumentHighlight> {
+        let line_index = LineIndex::with_encoding(source, self.position_encoding);
+        let Some(offset) = line_index.position_to_offset(position) else {
+            return Vec::new();
+        };
+
+        let document = tree.document();
+        let Some(name) = name_at(&document, offset) else {
+            return Vec::new();
+        };
+        let Some(parent) = name.syntax().parent() else {
+            return Vec::new();
+        };
+
+        let occurrences = match parent.kind() {
+            SyntaxKind::VARIABLE => variable_occurrences(&parent, &name.text()),
+            SyntaxKind::FRAGMENT_NAME => fragment_occurrences(&document, &name.text()),
+            SyntaxKind::FIELD => field_occurrences(&document, offset, schema_index),
+            _ => Vec::new(),
+        };
+
+        occurrences
+            .into_iter()
+            .map(|(name, kind)| {
+                let start = usize::from(name.syntax().text_range().start());
+                DocumentHighlight {
+                    range: Range {
+                        start: line_index.offset_to_position(start),
+                        end: line_index.offset_to_position(start + name.text().len()),
+                    },
+                    kind,
+                }
+            })
+            .collect()
+    }
+}
+
+impl Default for DocumentHighlightProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The name containing the offset, counting the end of the name as inside
+fn name_at(document: &cst::Document, offset: usize) -> Option<cst::Name> {
+    document
+        .syntax()
+        .descendants()
+        .filter_map(cst::Name::cast)
+        .find(|name| {
+            let start = usize::from(name.syntax().text_range().start());
+            start <= offset && offset <= start + name.text().len()
+        })
+}
+
+/// The definition and usages of a variable in the operation (or fragment) that uses it
+fn variable_occurrences(
+    variable: &apollo_parser::SyntaxNode,
+    name: &str,
+) -> Vec<(cst::Name, DocumentHighlightKind)> {
+    let Some(definition) = variable.ancestors().find(|node| {
+        matches!(
+            node.kind(),
+            SyntaxKind::OPERATION_DEFINITION | SyntaxKind::FRAGMENT_DEFINITION
+        )
+    }) else {
+        return Vec::new();
+    };
+
+    definition
+        .descendants()
+        .filter_map(cst::Variable::cast)
+        .filter_map(|variable| {
+            let variable_name = variable.name()?;
+            if variable_name.text() != name {
+                return None;
+            }
+            let is_definition = variable
+                .syntax()
+                .parent()
+                .is_some_and(|parent| parent.kind() == SyntaxKind::VARIABLE_DEFINITION);
+            let kind = if is_definition {
+                DocumentHighlightKind::Write
+            } else {
+                DocumentHighlightKind::Read
+            };
+            Some((variable_name, kind))
+        })
+        .collect()
+}
+
+/// The definition and spreads of a fragment in the document
+fn fragment_occurrences(
+    document: &cst::Document,
+    name: &str,
+) -> Vec<(cst::Name, DocumentHighlightKind)> {
+    document
+        .syntax()
+        .descendants()
+        .filter_map(cst::FragmentName::cast)
+        .filter_map(|fragment_name| {
+            let name_node = fragment_name.name()?;
+            if name_node.text() != name {
+                return None;
+            }
+            let is_definition = fragment_name
+                .syntax()
+                .parent()
+                .is_some_and(|parent| parent.kind() == SyntaxKind::FRAGMENT_DEFINITION);
+            let kind = if is_definition {
+                DocumentHighlightKind::Write
+            } else {
+                DocumentHighlightKind::Read
+            };
+            Some((name_node, kind))
+        })
+        .collect()
+}
+
+/// The selections of the field at the offset, matched by parent type and field name
+///
+/// Fields whose parent type can't be resolved against the schema are never highlighted.
+fn field_occurrences(
+    document: &cst::Document,
+    offset: usize,
+    schema_index: &SchemaIndex,
+) -> Vec<(cst::Name, DocumentHighlightKind)> {
+    let mut fields = Vec::new();
+    for definition in document.definitions() {
+        match definition {
+            cst::Definition::OperationDefinition(op) => {
+                if let (Some(selection_set), Some(root_type)) = (
+                    op.selection_set(),
+                    schema_index.root_type(OperationType::of(&op)),
+                ) {
+                    collect_fields(&selection_set, root_type, schema_index, &mut fields);
+                }
+            }
+            cst::Definition::FragmentDefinition(frag) => {
+                let type_name = frag
+                    .type_condition()
+                    .and_then(|condition| condition.named_type())
+                    .and_then(|named_type| named_type.name());
+                if let (Some(selection_set), Some(type_name)) = (frag.selection_set(), type_name) {
+                    collect_fields(&selection_set, &type_name.text(), schema_index, &mut fields);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let Some((target_type, target_name)) = fields.iter().find_map(|(parent_type, name)| {
+        let start = usize::from(name.syntax().text_range().start());
+        (start <= offset && offset <= start + name.text().len())
+            .then(|| (parent_type.clone(), name.text().to_string()))
+    }) else {
+        return Vec::new();
+    };
+
+    fields
+        .into_iter()
+        .filter(|(parent_type, name)| *parent_type == target_type && name.text() == target_name)
+        .map(|(_, name)| (name, DocumentHighlightKind::Text))
+        .collect()
+}
+
+/// Collect the name of every field selected in a selection set, with its parent type
+fn collect_fields(
+    selection_set: &cst::SelectionSet,
+    parent_type: &str,
+    schema_index: &SchemaIndex,
+    fields: &mut Vec<(String, cst::Name)>,
+) {
+    for selection in selection_set.selections() {
+        match selection {
+            cst::Selection::Field(field) => {
+                let Some(name) = field.name() else {
+                    continue;
+                };
+                let nested =
+                    field
+                        .selection_set()
+                        .zip(field_type(parent_type, &name.text(), schema_index));
+                fields.push((parent_type.to_string(), name));
+                if let Some((nested_selection_set, field_type)) = nested {
+                    collect_fields(&nested_selection_set, &field_type, schema_index, fields);
+                }
+            }
+            cst::Selection::InlineFragment(inline_frag) => {
+                let type_name = inline_frag
+                    .type_condition()
+                    .and_then(|condition| condition.named_type())
+                    .and_then(|named_type| named_type.name())
+                    .map_or_else(|| parent_type.to_string(), |name| name.text().to_string());
+                if let Some(nested_selection_set) = inline_frag.selection_set() {
+                    collect_fields(&nested_selection_set, &type_name, schema_index, fields);
+                }
+            }
+            cst::Selection::FragmentSpread(_) => {}
+        }
+    }
+}
+
+/// The named type of a field, without list and non-null wrappers
+fn field_type(parent_type: &str, field_name: &str, schema_index: &SchemaIndex) -> Option<String> {
+    let field = schema_index
+        .get_fields(parent_type)?
+        .into_iter()
+        .find(|field| field.name == field_name)?;
+    Some(
+        field
+            .type_name
+            .trim_matches(|c| c == '[' || c == ']' || c == '!')
+            .to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_schema() -> SchemaIndex {
+        SchemaIndex::from_schema(
+            r"
+            type Query {
+                user(id: ID!): User
+                users(first: Int): [User!]!
+            }
+
+            type User {
+                id: ID!
+                name: String
+                friends(first: Int): [User!]!
+            }
+            ",
+        )
+    }
+
+    fn highlights_at(source: &str, marker: &str) -> Vec<(usize, usize, DocumentHighlightKind)> {
+        let offset = source.find(marker).expect("marker should be in source");
+        let position = LineIndex::new(source).offset_to_position(offset);
+        DocumentHighlightProvider::new()
+            .document_highlights(source, position, &create_test_schema())
+            .into_iter()
+            .map(|highlight| {
+                (
+                    highlight.range.start.line,
+                    highlight.range.start.character,
+                    highlight.kind,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_variable_definition_and_usages() {
+        let source = "query A($id: ID!, $first: Int) {
+  user(id: $id) {
+    friends(first: $first) { id }
+  }
+  users(first: $first) { id }
+}
+
+query B($first: Int) {
+  users(first: $first) { id }
+}
+";
+
+        assert_eq!(
+            highlights_at(source, "first: Int)"),
+            vec![
+                (0, 19, DocumentHighlightKind::Write),
+                (2, 20, DocumentHighlightKind::Read),
+                (4, 16, DocumentHighlightKind::Read),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fragment_definition_and_spreads() {
+        let source = "query A {
+  user(id: 1) { ...UserFields }
+  users { ...UserFields }
+}
+
+fragment UserFields on User {
+  id
+}
+";
+
+        assert_eq!(
+            highlights_at(source, "UserFields on"),
+            vec![
+                (1, 19, DocumentHighlightKind::Read),
+                (2, 13, DocumentHighlightKind::Read),
+                (5, 9, DocumentHighlightKind::Write),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fields_matched_by_parent_type() {
+        let source = "query A {
+  user(id: 1) {
+    name
+    friends { name }
+  }
+}
+
+fragment UserFields on User {
+  name
+}
+";
+
+        // `name` is selected on `User` in all three places
+        assert_eq!(
+            highlights_at(source, "name\n    friends"),
+            vec![
+                (2, 4, DocumentHighlightKind::Text),
+                (3, 14, DocumentHighlightKind::Text),
+                (8, 2, DocumentHighlightKind::Text),
+            ]
+        );
+        // `user` on `Query` doesn't match `users` or anything on `User`
+        assert_eq!(
+            highlights_at(source, "user(id"),
+            vec![(1, 2, DocumentHighlightKind::Text)]
+        );
+    }
+}
@@ -0,0 +1,373 @@
+use crate::{LineIndex, OperationType, Position, PositionEncoding, Range};
+use apollo_parser::{
+    cst::{self, CstNode},
+    Parser, SyntaxTree,
+};
+
+/// Kind of element a document symbol represents
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocumentSymbolKind {
+    Operation(OperationType),
+    Fragment,
+    Field,
+}
+
+/// Symbol in the outline of a GraphQL document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentSymbol {
+    pub name: String,
+    /// Extra context shown next to the name, such as a fragment's type condition
+    pub detail: Option<String>,
+    pub kind: DocumentSymbolKind,
+    /// Range of the whole definition
+    pub range: Range,
+    /// Range of the name, selected when the symbol is clicked
+    pub selection_range: Range,
+    pub children: Vec<DocumentSymbol>,
+}
+
+impl DocumentSymbol {
+    /// Shift this symbol and its children from block-relative to file positions
+    ///
+    /// Used for GraphQL extracted from TypeScript/JavaScript, where the block starts at
+    /// `start_line`/`start_column` in the original file.
+    #[must_use]
+    pub fn offset_by(mut self, start_line: usize, start_column: usize) -> Self {
+        self.range = offset_range(self.range, start_line, start_column);
+        self.selection_range = offset_range(self.selection_range, start_line, start_column);
+        self.children = self
+            .children
+            .into_iter()
+            .map(|child| child.offset_by(start_line, start_column))
+            .collect();
+        self
+    }
+}
+
+const fn offset_range(range: Range, start_line: usize, start_column: usize) -> Range {
+    Range {
+        start: offset_position(range.start, start_line, start_column),
+        end: offset_position(range.end, start_line, start_column),
+    }
+}
+
+const fn offset_position(position: Position, start_line: usize, start_column: usize) -> Position {
+    Position {
+        line: position.line + start_line,
+        character: if position.line == 0 {
+            position.character + start_column
+        } else {
+            position.character
+        },
+    }
+}
+
+/// Document symbol provider
+///
+/// Builds an outline of the operations and fragments in a document, with their top-level
+/// fields as children. Anonymous operations are listed as `<anonymous query>` (or
+/// `mutation`/`subscription`).
+pub struct DocumentSymbolProvider {
+    /// Unit `Position::character` columns are counted in
+    position_encoding: PositionEncoding,
+}
+
+impl DocumentSymbolProvider {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            position_encoding: PositionEncoding::Utf16,
+        }
+    }
+
+    /// Count `Position::character` columns in `encoding` instead of UTF-16 code units
+    #[must_use]
+    pub const fn with_position_encoding(mut self, encoding: PositionEncoding) -> Self {
+        self.position_encoding = encoding;
+        self
+    }
+
+    /// Get the symbols for a GraphQL document
+    #[must_use]
+    pub fn document_symbols(&self, source: &str) -> Vec<DocumentSymbol> {
+        let tree = Parser::new(source).parse();
+        self.document_symbols_with_ast(source, &tree)
+    }
+
+    /// Get the symbols for a GraphQL document using an already parsed AST
+    ///
+    /// Symbols are still produced for documents with syntax errors, so the outline stays
+    /// useful while the user is typing.
+    #[must_use]
+    pub fn document_symbols_with_ast(
+        &self,
+        source: &str,
+        tree: &SyntaxTree,
+    ) -> Vec<DocumentSymbol> {
+        let line_index = LineIndex::with_encoding(source, self.position_encoding);
+        let mut symbols = Vec::new();
+
+        for definition in tree.document().definitions() {
+            match definition {
+                cst::Definition::OperationDefinition(op) => {
+                    let operation_type = OperationType::of(&op);
+
+                    let keyword = match operation_type {
+                        OperationType::Query => "query",
+                        OperationType::Mutation => "mutation",
+                        OperationType::Subscription => "subscription",
+                    };
+
+                    // Anonymous operations get a placeholder name so they still show up in
+                    // the outline, selected by their keyword (or the whole shorthand query)
+                    let (name, selection_range) = match op.name() {
+                        Some(name) => (
+                            name.text().to_string(),
+                            node_range(source, &line_index, name.syntax()),
+                        ),
+                        None => (
+                            format!("<anonymous {keyword}>"),
+                            op.operation_type().map_or_else(
+                                || node_range(source, &line_index, op.syntax()),
+                                |op_type| node_range(source, &line_index, op_type.syntax()),
+                            ),
+                        ),
+                    };
+
+                    symbols.push(DocumentSymbol {
+                        name,
+                        detail: Some(keyword.to_string()),
+                        kind: DocumentSymbolKind::Operation(operation_type),
+                        range: node_range(source, &line_index, op.syntax()),
+                        selection_range,
+                        children: op
+                            .selection_set()
+                            .map(|set| field_symbols(source, &line_index, &set))
+                            .unwrap_or_default(),
+                    });
+                }
+                cst::Definition::FragmentDefinition(frag) => {
+                    let Some(name) = frag.fragment_name().and_then(|n| n.name()) else {
+                        continue;
+                    };
+
+                    let type_condition = frag
+                        .type_condition()
+                        .and_then(|tc| tc.named_type())
+                        .and_then(|nt| nt.name())
+                        .map(|n| format!("on {}", n.text()));
+
+                    symbols.push(DocumentSymbol {
+                        name: name.text().to_string(),
+                        detail: type_condition,
+                        kind: DocumentSymbolKind::Fragment,
+                        range: node_range(source, &line_index, frag.syntax()),
+                        selection_range: node_range(source, &line_index, name.syntax()),
+                        children: frag
+                            .selection_set()
+                            .map(|set| field_symbols(source, &line_index, &set))
+                            .unwrap_or_default(),
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        symbols
+    }
+}
+
+impl Default for DocumentSymbolProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Symbols for the fields directly selected in a selection set
+///
+/// Aliased fields are listed under their alias, since that is the key in the response.
+fn field_symbols(
+    source: &str,
+    line_index: &LineIndex,
+    selection_set: &cst::SelectionSet,
+) -> Vec<DocumentSymbol> {
+    selection_set
+        .selections()
+        .filter_map(|selection| {
+            let cst::Selection::Field(field) = selection else {
+                return None;
+            };
+            let name = field.name()?;
+            let alias_name = field.alias().and_then(|alias| alias.name());
+            let display_name = alias_name.as_ref().unwrap_or(&name);
+
+            Some(DocumentSymbol {
+                name: display_name.text().to_string(),
+                detail: alias_name.is_some().then(|| name.text().to_string()),
+                kind: DocumentSymbolKind::Field,
+                range: node_range(source, line_index, field.syntax()),
+                selection_range: node_range(source, line_index, display_name.syntax()),
+                children: Vec::new(),
+            })
+        })
+        .collect()
+}
+
+/// Range of a node, excluding the whitespace and commas the parser attaches after it
+fn node_range(source: &str, line_index: &LineIndex, node: &apollo_parser::SyntaxNode) -> Range {
+    let range = node.text_range();
+    let start: usize = range.start().into();
+    let end: usize = range.end().into();
+    let end = start
+        + source[start..end]
+            .trim_end_matches(|c: char| c.is_whitespace() || c == ',')
+            .len();
+    Range {
+        start: line_index.offset_to_position(start),
+        end: line_index.offset_to_position(end),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operations_and_fragments_with_fields() {
+        let source = r"query GetUser {
+  user {
+    id
+  }
+  viewer: me {
+    name
+  }
+}
+
+mutation UpdateUser {
+  updateUser {
+    id
+  }
+}
+
+fragment UserFields on User {
+  id
+  ...OtherFields
+}
+";
+
+        let symbols = DocumentSymbolProvider::new().document_symbols(source);
+        assert_eq!(symbols.len(), 3);
+
+        let query = &symbols[0];
+        assert_eq!(query.name, "GetUser");
+        assert_eq!(
+            query.kind,
+            DocumentSymbolKind::Operation(OperationType::Query)
+        );
+        assert_eq!(
+            query.range,
+            Range {
+                start: Position {
+                    line: 0,
+                    character: 0
+                },
+                end: Position {
+                    line: 7,
+                    character: 1
+                },
+            }
+        );
+        assert_eq!(
+            query.selection_range,
+            Range {
+                start: Position {
+                    line: 0,
+                    character: 6
+                },
+                end: Position {
+                    line: 0,
+                    character: 13
+                },
+            }
+        );
+
+        let children: Vec<_> = query.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(children, vec!["user", "viewer"]);
+        assert_eq!(query.children[1].detail.as_deref(), Some("me"));
+
+        assert_eq!(
+            symbols[1].kind,
+            DocumentSymbolKind::Operation(OperationType::Mutation)
+        );
+
+        let fragment = &symbols[2];
+        assert_eq!(fragment.name, "UserFields");
+        assert_eq!(fragment.kind, DocumentSymbolKind::Fragment);
+        assert_eq!(fragment.detail.as_deref(), Some("on User"));
+        // Fragment spreads are not listed as fields
+        assert_eq!(fragment.children.len(), 1);
+    }
+
+    #[test]
+    fn test_offset_by_maps_block_positions() {
+        let symbols = DocumentSymbolProvider::new().document_symbols("query A { a }");
+        let symbol = symbols[0].clone().offset_by(4, 20);
+
+        assert_eq!(
+            symbol.selection_range.start,
+            Position {
+                line: 4,
+                character: 26
+            }
+        );
+        assert_eq!(
+            symbol.children[0].selection_range.start,
+            Position {
+                line: 4,
+                character: 30
+            }
+        );
+    }
+
+    #[test]
+    fn test_anonymous_operations_get_placeholder_names() {
+        let source = "query {\n  user {\n    id\n  }\n}\n\nmutation { logout }\n\n{ viewer }\n";
+
+        let symbols = DocumentSymbolProvider::new().document_symbols(source);
+        let names: Vec<_> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "<anonymous query>",
+                "<anonymous mutation>",
+                "<anonymous query>"
+            ]
+        );
+
+        let query = &symbols[0];
+        assert_eq!(
+            query.kind,
+            DocumentSymbolKind::Operation(OperationType::Query)
+        );
+        assert_eq!(
+            query.selection_range,
+            Range {
+                start: Position {
+                    line: 0,
+                    character: 0
+                },
+                end: Position {
+                    line: 0,
+                    character: 5
+                },
+            }
+        );
+        assert_eq!(query.children[0].name, "user");
+
+        assert_eq!(
+            symbols[1].kind,
+            DocumentSymbolKind::Operation(OperationType::Mutation)
+        );
+        assert_eq!(symbols[2].selection_range.start.line, 8);
+    }
+}
@@ -1,6 +1,8 @@
 #![allow(clippy::too_many_lines)]
 
-use crate::{DocumentIndex, Position, Range, SchemaIndex};
+use crate::{
+    index::uri_to_file_path, DocumentIndex, LineIndex, OperationType, Position, Range, SchemaIndex,
+};
 use apollo_parser::{
     cst::{self, CstNode},
     Parser, SyntaxTree,
@@ -67,7 +69,6 @@ impl FindReferencesProvider {
     /// - `file_path`: Source file path for accessing cached `LineIndex` (O(1) position conversion)
     #[must_use]
     #[allow(clippy::too_many_arguments)]
-    #[allow(clippy::option_if_let_else)]
     pub fn find_references_with_asts(
         &self,
         source: &str,
@@ -80,6 +81,50 @@ impl FindReferencesProvider {
         document_asts: Option<&HashMap<String, SyntaxTree>>,
         file_path: Option<&str>,
     ) -> Option<Vec<ReferenceLocation>> {
+        let mut references = Vec::new();
+        self.find_references_streaming(
+            source,
+            position,
+            document_index,
+            schema_index,
+            all_documents,
+            include_declaration,
+            source_ast,
+            document_asts,
+            file_path,
+            |reference| references.push(reference),
+        );
+
+        tracing::info!(count = references.len(), "Found references");
+        if references.is_empty() {
+            None
+        } else {
+            Some(references)
+        }
+    }
+
+    /// Find all references, calling `on_reference` with each one as it's found
+    ///
+    /// Takes the same arguments as [`Self::find_references_with_asts`], but never holds
+    /// more than one document's references at a time, which keeps memory flat for
+    /// symbols used across very large workspaces. References are reported in the order
+    /// the batch API returns them. Nothing is reported when the source has syntax errors
+    /// or the position isn't on a fragment, type, or field.
+    #[allow(clippy::too_many_arguments)]
+    #[allow(clippy::option_if_let_else)]
+    pub fn find_references_streaming(
+        &self,
+        source: &str,
+        position: Position,
+        document_index: &DocumentIndex,
+        schema_index: &SchemaIndex,
+        all_documents: &[(String, String)],
+        include_declaration: bool,
+        source_ast: Option<&SyntaxTree>,
+        document_asts: Option<&HashMap<String, SyntaxTree>>,
+        file_path: Option<&str>,
+        mut on_reference: impl FnMut(ReferenceLocation),
+    ) {
         tracing::info!(
             line = position.line,
             character = position.character,
@@ -98,104 +143,47 @@ impl FindReferencesProvider {
 
         let error_count = tree.errors().count();
         if error_count > 0 {
-            tracing::debug!(error_count, "Parser errors, returning None");
-            return None;
+            tracing::debug!(error_count, "Parser errors, returning no references");
+            return;
         }
 
         let doc = tree.document();
 
-        // Try to use cached LineIndex for O(1) position-to-offset conversion
-        // Fall back to O(N) character iteration if not available
-        let byte_offset = file_path
-            .and_then(|path| document_index.get_line_index(path))
-            .and_then(|line_index| Self::position_to_offset_with_index(&line_index, position))
-            .or_else(|| Self::position_to_offset(source, position))?;
-        let element_type = Self::find_element_at_position(&doc, byte_offset, source, schema_index)?;
+        let Some(byte_offset) = document_index
+            .line_index_for(file_path, source)
+            .position_to_offset(position)
+        else {
+            return;
+        };
+        let Some(element_type) =
+            Self::find_element_at_position(&doc, byte_offset, source, schema_index)
+        else {
+            return;
+        };
 
         tracing::debug!(element_type = ?element_type, "Finding references for element");
 
-        let references = Self::find_all_references_with_asts(
+        Self::find_all_references_with_asts(
             &element_type,
             document_index,
             schema_index,
             all_documents,
             include_declaration,
             document_asts,
-        )?;
-
-        tracing::info!(count = references.len(), "Found references");
-        Some(references)
-    }
-
-    /// Convert a line/column position to a byte offset using a cached `LineIndex`
-    ///
-    /// This is the fast path with O(1) complexity instead of O(N) character iteration.
-    fn position_to_offset_with_index(
-        line_index: &crate::LineIndex,
-        position: Position,
-    ) -> Option<usize> {
-        line_index.position_to_offset(position)
-    }
-
-    /// Convert a line/column position to a byte offset (fallback O(N) implementation)
-    ///
-    /// This is the slow path used when no cached `LineIndex` is available.
-    /// Prefers `position_to_offset_with_index` when possible.
-    fn position_to_offset(source: &str, position: Position) -> Option<usize> {
-        let mut current_line = 0;
-        let mut current_col = 0;
-        let mut offset = 0;
-
-        for ch in source.chars() {
-            if current_line == position.line && current_col == position.character {
-                return Some(offset);
-            }
-
-            if ch == '\n' {
-                current_line += 1;
-                current_col = 0;
-            } else {
-                current_col += 1;
-            }
-
-            offset += ch.len_utf8();
-        }
-
-        if current_line == position.line && current_col == position.character {
-            Some(offset)
-        } else {
-            None
-        }
+            &mut on_reference,
+        );
     }
 
-    fn offset_to_position(source: &str, offset: usize) -> (usize, usize) {
-        let mut line = 0;
-        let mut column = 0;
-        let mut current_offset = 0;
-
-        for ch in source.chars() {
-            if current_offset >= offset {
-                break;
-            }
-
-            if ch == '\n' {
-                line += 1;
-                column = 0;
-            } else {
-                column += 1;
-            }
-
-            current_offset += ch.len_utf8();
-        }
-
-        (line, column)
+    fn offset_to_position(line_index: &LineIndex, offset: usize) -> (usize, usize) {
+        let position = line_index.offset_to_position(offset);
+        (position.line, position.character)
     }
 
     fn find_element_at_position(
         doc: &cst::Document,
         byte_offset: usize,
         _source: &str,
-        _schema_index: &SchemaIndex,
+        schema_index: &SchemaIndex,
     ) -> Option<ElementType> {
         for definition in doc.definitions() {
             match definition {
@@ -314,9 +302,172 @@ impl FindReferencesProvider {
             }
         }
 
+        if let Some(element) = Self::field_at_position(doc, byte_offset, schema_index) {
+            return Some(element);
+        }
+
+        // Any other type reference: field and argument types, implements clauses, union
+        // members, type conditions, and variable types
+        doc.syntax()
+            .descendants()
+            .filter_map(cst::NamedType::cast)
+            .find_map(|named_type| {
+                let name = named_type.name()?;
+                Self::name_contains(&name, byte_offset).then(|| ElementType::TypeDefinition {
+                    type_name: name.text().to_string(),
+                })
+            })
+    }
+
+    /// Find the schema field under the cursor, at its definition or at a selection of it
+    fn field_at_position(
+        doc: &cst::Document,
+        byte_offset: usize,
+        schema_index: &SchemaIndex,
+    ) -> Option<ElementType> {
+        doc.definitions().find_map(|definition| match definition {
+            cst::Definition::ObjectTypeDefinition(obj) => {
+                Self::field_definition_at(obj.name(), obj.fields_definition(), byte_offset)
+            }
+            cst::Definition::InterfaceTypeDefinition(iface) => {
+                Self::field_definition_at(iface.name(), iface.fields_definition(), byte_offset)
+            }
+            cst::Definition::OperationDefinition(op) => Self::field_selection_at(
+                &op.selection_set()?,
+                &Self::operation_root_type(&op, schema_index)?,
+                byte_offset,
+                schema_index,
+            ),
+            cst::Definition::FragmentDefinition(frag) => Self::field_selection_at(
+                &frag.selection_set()?,
+                &frag.type_condition()?.named_type()?.name()?.text(),
+                byte_offset,
+                schema_index,
+            ),
+            _ => None,
+        })
+    }
+
+    /// Find the field definition whose name is under the cursor in a type definition
+    fn field_definition_at(
+        type_name: Option<cst::Name>,
+        fields: Option<cst::FieldsDefinition>,
+        byte_offset: usize,
+    ) -> Option<ElementType> {
+        let type_name = type_name?.text().to_string();
+        fields?.field_definitions().find_map(|field| {
+            let name = field.name()?;
+            Self::name_contains(&name, byte_offset).then(|| ElementType::FieldDefinition {
+                type_name: type_name.clone(),
+                field_name: name.text().to_string(),
+            })
+        })
+    }
+
+    /// Find the field selection whose name is under the cursor, with the type it's selected on
+    fn field_selection_at(
+        selection_set: &cst::SelectionSet,
+        parent_type: &str,
+        byte_offset: usize,
+        schema_index: &SchemaIndex,
+    ) -> Option<ElementType> {
+        for selection in selection_set.selections() {
+            match selection {
+                cst::Selection::Field(field) => {
+                    let Some(name) = field.name() else {
+                        continue;
+                    };
+                    if Self::name_contains(&name, byte_offset) {
+                        return Some(ElementType::FieldDefinition {
+                            type_name: parent_type.to_string(),
+                            field_name: name.text().to_string(),
+                        });
+                    }
+
+                    let nested = field.selection_set().zip(Self::field_type(
+                        parent_type,
+                        &name.text(),
+                        schema_index,
+                    ));
+                    if let Some((nested_selection_set, field_type)) = nested {
+                        if let Some(element) = Self::field_selection_at(
+                            &nested_selection_set,
+                            &field_type,
+                            byte_offset,
+                            schema_index,
+                        ) {
+                            return Some(element);
+                        }
+                    }
+                }
+                cst::Selection::InlineFragment(inline_frag) => {
+                    let Some(nested_selection_set) = inline_frag.selection_set() else {
+                        continue;
+                    };
+                    let type_name = Self::type_condition_name(&inline_frag)
+                        .unwrap_or_else(|| parent_type.to_string());
+                    if let Some(element) = Self::field_selection_at(
+                        &nested_selection_set,
+                        &type_name,
+                        byte_offset,
+                        schema_index,
+                    ) {
+                        return Some(element);
+                    }
+                }
+                cst::Selection::FragmentSpread(_) => {}
+            }
+        }
+
         None
     }
 
+    /// Whether the cursor is on a name
+    fn name_contains(name: &cst::Name, byte_offset: usize) -> bool {
+        let range = name.syntax().text_range();
+        byte_offset >= usize::from(range.start()) && byte_offset < usize::from(range.end())
+    }
+
+    /// The root type of an operation, as declared by the schema
+    fn operation_root_type(
+        op: &cst::OperationDefinition,
+        schema_index: &SchemaIndex,
+    ) -> Option<String> {
+        schema_index
+            .root_type(OperationType::of(op))
+            .map(ToString::to_string)
+    }
+
+    /// The named type of a field, without list and non-null wrappers
+    fn field_type(
+        parent_type: &str,
+        field_name: &str,
+        schema_index: &SchemaIndex,
+    ) -> Option<String> {
+        let field = schema_index
+            .get_fields(parent_type)?
+            .into_iter()
+            .find(|field| field.name == field_name)?;
+        Some(
+            field
+                .type_name
+                .trim_matches(|c| c == '[' || c == ']' || c == '!')
+                .to_string(),
+        )
+    }
+
+    /// The type named by an inline fragment's type condition, if it has one
+    fn type_condition_name(inline_frag: &cst::InlineFragment) -> Option<String> {
+        Some(
+            inline_frag
+                .type_condition()?
+                .named_type()?
+                .name()?
+                .text()
+                .to_string(),
+        )
+    }
+
     fn check_selection_set_for_spreads(
         selection_set: &cst::SelectionSet,
         byte_offset: usize,
@@ -364,19 +515,22 @@ impl FindReferencesProvider {
     fn find_all_references_with_asts(
         element_type: &ElementType,
         document_index: &DocumentIndex,
-        _schema_index: &SchemaIndex,
+        schema_index: &SchemaIndex,
         all_documents: &[(String, String)],
         include_declaration: bool,
         document_asts: Option<&HashMap<String, SyntaxTree>>,
-    ) -> Option<Vec<ReferenceLocation>> {
+        on_reference: &mut impl FnMut(ReferenceLocation),
+    ) {
         match element_type {
             ElementType::FragmentDefinition { fragment_name } => {
                 // Find all fragment spreads that use this fragment
-                let mut references = Self::find_fragment_spread_references_with_asts(
+                Self::find_fragment_spread_references_with_asts(
                     fragment_name,
+                    document_index,
                     all_documents,
                     document_asts,
-                )?;
+                    on_reference,
+                );
 
                 // Add fragment definitions if requested
                 if include_declaration {
@@ -392,41 +546,56 @@ impl FindReferencesProvider {
                                     character: frag.column + fragment_name.len(),
                                 },
                             };
-                            references.push(ReferenceLocation::new(frag.file_path.clone(), range));
+                            on_reference(ReferenceLocation::new(frag.file_path.clone(), range));
                         }
                     }
                 }
-
-                Some(references)
             }
             ElementType::FragmentSpread { fragment_name } => {
                 // When on a spread, find all spreads (same as definition)
                 Self::find_fragment_spread_references_with_asts(
                     fragment_name,
+                    document_index,
                     all_documents,
                     document_asts,
-                )
+                    on_reference,
+                );
             }
             ElementType::TypeDefinition { type_name } => {
                 // Find all type references
                 Self::find_type_references_with_asts(
                     type_name,
+                    document_index,
                     all_documents,
                     include_declaration,
                     document_asts,
-                )
+                    on_reference,
+                );
             }
+            ElementType::FieldDefinition {
+                type_name,
+                field_name,
+            } => Self::find_field_references_with_asts(
+                type_name,
+                field_name,
+                document_index,
+                schema_index,
+                all_documents,
+                include_declaration,
+                document_asts,
+                on_reference,
+            ),
         }
     }
 
     #[allow(clippy::option_if_let_else)]
     fn find_fragment_spread_references_with_asts(
         fragment_name: &str,
+        document_index: &DocumentIndex,
         all_documents: &[(String, String)],
         document_asts: Option<&HashMap<String, SyntaxTree>>,
-    ) -> Option<Vec<ReferenceLocation>> {
-        let mut references = Vec::new();
-
+        on_reference: &mut impl FnMut(ReferenceLocation),
+    ) {
         for (file_path, source) in all_documents {
             // Try to use cached AST first, otherwise parse
             let tree_holder;
@@ -451,13 +620,15 @@ impl FindReferencesProvider {
             }
 
             let doc = tree.document();
-            Self::collect_fragment_spreads(&doc, fragment_name, file_path, source, &mut references);
-        }
-
-        if references.is_empty() {
-            None
-        } else {
-            Some(references)
+            let line_index =
+                document_index.line_index_for(Some(uri_to_file_path(file_path)), source);
+            Self::collect_fragment_spreads(
+                &doc,
+                fragment_name,
+                file_path,
+                &line_index,
+                on_reference,
+            );
         }
     }
 
@@ -465,8 +636,8 @@ impl FindReferencesProvider {
         doc: &cst::Document,
         target_fragment: &str,
         file_path: &str,
-        source: &str,
-        references: &mut Vec<ReferenceLocation>,
+        line_index: &LineIndex,
+        on_reference: &mut impl FnMut(ReferenceLocation),
     ) {
         for definition in doc.definitions() {
             match definition {
@@ -476,8 +647,8 @@ impl FindReferencesProvider {
                             &selection_set,
                             target_fragment,
                             file_path,
-                            source,
-                            references,
+                            line_index,
+                            on_reference,
                         );
                     }
                 }
@@ -487,8 +658,8 @@ impl FindReferencesProvider {
                             &selection_set,
                             target_fragment,
                             file_path,
-                            source,
-                            references,
+                            line_index,
+                            on_reference,
                         );
                     }
                 }
@@ -501,8 +672,8 @@ impl FindReferencesProvider {
         selection_set: &cst::SelectionSet,
         target_fragment: &str,
         file_path: &str,
-        source: &str,
-        references: &mut Vec<ReferenceLocation>,
+        line_index: &LineIndex,
+        on_reference: &mut impl FnMut(ReferenceLocation),
     ) {
         for selection in selection_set.selections() {
             match selection {
@@ -512,8 +683,8 @@ impl FindReferencesProvider {
                             &nested_selection_set,
                             target_fragment,
                             file_path,
-                            source,
-                            references,
+                            line_index,
+                            on_reference,
                         );
                     }
                 }
@@ -522,7 +693,7 @@ impl FindReferencesProvider {
                         if frag_name.text() == target_fragment {
                             let range = frag_name.syntax().text_range();
                             let (line, column) =
-                                Self::offset_to_position(source, range.start().into());
+                                Self::offset_to_position(line_index, range.start().into());
                             let range = Range {
                                 start: Position {
                                     line,
@@ -533,7 +704,7 @@ impl FindReferencesProvider {
                                     character: column + target_fragment.len(),
                                 },
                             };
-                            references.push(ReferenceLocation::new(file_path.to_string(), range));
+                            on_reference(ReferenceLocation::new(file_path.to_string(), range));
                         }
                     }
                 }
@@ -543,8 +714,8 @@ impl FindReferencesProvider {
                             &selection_set,
                             target_fragment,
                             file_path,
-                            source,
-                            references,
+                            line_index,
+                            on_reference,
                         );
                     }
                 }
@@ -553,51 +724,247 @@ impl FindReferencesProvider {
     }
 
     /// Find all type references in all documents with optional pre-parsed ASTs
-    #[allow(clippy::option_if_let_else)]
     fn find_type_references_with_asts(
         type_name: &str,
+        document_index: &DocumentIndex,
         all_documents: &[(String, String)],
         include_declaration: bool,
         document_asts: Option<&HashMap<String, SyntaxTree>>,
-    ) -> Option<Vec<ReferenceLocation>> {
-        let mut references = Vec::new();
+        on_reference: &mut impl FnMut(ReferenceLocation),
+    ) {
+        Self::for_each_document(
+            all_documents,
+            document_index,
+            document_asts,
+            |file_path, source, tree| {
+                let line_index =
+                    document_index.line_index_for(Some(uri_to_file_path(file_path)), source);
+                Self::collect_type_references(
+                    &tree.document(),
+                    type_name,
+                    file_path,
+                    &line_index,
+                    on_reference,
+                    include_declaration,
+                );
+            },
+        );
+    }
+
+    /// Find all selections of a field on its owning type, with optional pre-parsed ASTs
+    ///
+    /// Selections are matched by the type they're made on, so `name` selected on `Post`
+    /// doesn't count as a reference to `User.name`. With `include_declaration`, the field's
+    /// definition is included when its schema file is among the documents searched.
+    #[allow(clippy::too_many_arguments)]
+    fn find_field_references_with_asts(
+        type_name: &str,
+        field_name: &str,
+        document_index: &DocumentIndex,
+        schema_index: &SchemaIndex,
+        all_documents: &[(String, String)],
+        include_declaration: bool,
+        document_asts: Option<&HashMap<String, SyntaxTree>>,
+        on_reference: &mut impl FnMut(ReferenceLocation),
+    ) {
+        Self::for_each_document(
+            all_documents,
+            document_index,
+            document_asts,
+            |file_path, source, tree| {
+                let mut names = Vec::new();
+                for definition in tree.document().definitions() {
+                    match definition {
+                        cst::Definition::OperationDefinition(op) => {
+                            let root_type = Self::operation_root_type(&op, schema_index);
+                            if let (Some(selection_set), Some(root_type)) =
+                                (op.selection_set(), root_type)
+                            {
+                                Self::collect_field_selections(
+                                    &selection_set,
+                                    &root_type,
+                                    (type_name, field_name),
+                                    schema_index,
+                                    &mut names,
+                                );
+                            }
+                        }
+                        cst::Definition::FragmentDefinition(frag) => {
+                            let type_condition = frag
+                                .type_condition()
+                                .and_then(|type_cond| type_cond.named_type())
+                                .and_then(|named_type| named_type.name());
+                            if let (Some(selection_set), Some(type_condition)) =
+                                (frag.selection_set(), type_condition)
+                            {
+                                Self::collect_field_selections(
+                                    &selection_set,
+                                    &type_condition.text(),
+                                    (type_name, field_name),
+                                    schema_index,
+                                    &mut names,
+                                );
+                            }
+                        }
+                        cst::Definition::ObjectTypeDefinition(obj) if include_declaration => {
+                            names.extend(Self::field_definition_name(
+                                obj.name(),
+                                obj.fields_definition(),
+                                (type_name, field_name),
+                            ));
+                        }
+                        cst::Definition::InterfaceTypeDefinition(iface) if include_declaration => {
+                            names.extend(Self::field_definition_name(
+                                iface.name(),
+                                iface.fields_definition(),
+                                (type_name, field_name),
+                            ));
+                        }
+                        _ => {}
+                    }
+                }
+
+                let line_index =
+                    document_index.line_index_for(Some(uri_to_file_path(file_path)), source);
+                for name in &names {
+                    on_reference(Self::name_location(name, file_path, &line_index));
+                }
+            },
+        );
+    }
+
+    /// Collect the names of the selections of the `(type, field)` target in a selection set
+    fn collect_field_selections(
+        selection_set: &cst::SelectionSet,
+        parent_type: &str,
+        target: (&str, &str),
+        schema_index: &SchemaIndex,
+        names: &mut Vec<cst::Name>,
+    ) {
+        for selection in selection_set.selections() {
+            match selection {
+                cst::Selection::Field(field) => {
+                    let Some(name) = field.name() else {
+                        continue;
+                    };
+                    if parent_type == target.0 && name.text() == target.1 {
+                        names.push(name.clone());
+                    }
+
+                    let nested = field.selection_set().zip(Self::field_type(
+                        parent_type,
+                        &name.text(),
+                        schema_index,
+                    ));
+                    if let Some((nested_selection_set, field_type)) = nested {
+                        Self::collect_field_selections(
+                            &nested_selection_set,
+                            &field_type,
+                            target,
+                            schema_index,
+                            names,
+                        );
+                    }
+                }
+                cst::Selection::InlineFragment(inline_frag) => {
+                    if let Some(nested_selection_set) = inline_frag.selection_set() {
+                        let type_name = Self::type_condition_name(&inline_frag)
+                            .unwrap_or_else(|| parent_type.to_string());
+                        Self::collect_field_selections(
+                            &nested_selection_set,
+                            &type_name,
+                            target,
+                            schema_index,
+                            names,
+                        );
+                    }
+                }
+                cst::Selection::FragmentSpread(_) => {}
+            }
+        }
+    }
 
+    /// The name of the `(type, field)` target's definition, if this type definition is its type
+    fn field_definition_name(
+        type_name: Option<cst::Name>,
+        fields: Option<cst::FieldsDefinition>,
+        target: (&str, &str),
+    ) -> Option<cst::Name> {
+        if type_name?.text() != target.0 {
+            return None;
+        }
+        fields?
+            .field_definitions()
+            .filter_map(|field| field.name())
+            .find(|name| name.text() == target.1)
+    }
+
+    /// Location of a name in a document
+    fn name_location(
+        name: &cst::Name,
+        file_path: &str,
+        line_index: &LineIndex,
+    ) -> ReferenceLocation {
+        let (line, column) =
+            Self::offset_to_position(line_index, name.syntax().text_range().start().into());
+        let range = Range {
+            start: Position {
+                line,
+                character: column,
+            },
+            end: Position {
+                line,
+                character: column + name.text().len(),
+            },
+        };
+        ReferenceLocation::new(file_path.to_string(), range)
+    }
+
+    /// Call `visit` with the path, source, and AST of each document to search
+    ///
+    /// These are `all_documents`, parsed unless `document_asts` has them, followed by any
+    /// other document whose AST is cached in `document_index`. Keys are compared as paths,
+    /// so an open document keyed by URI isn't visited again through its cached AST.
+    /// Documents with syntax errors are skipped.
+    #[allow(clippy::option_if_let_else)]
+    fn for_each_document(
+        all_documents: &[(String, String)],
+        document_index: &DocumentIndex,
+        document_asts: Option<&HashMap<String, SyntaxTree>>,
+        mut visit: impl FnMut(&str, &str, &SyntaxTree),
+    ) {
         for (file_path, source) in all_documents {
-            // Try to use cached AST first
             let tree_holder;
-            let tree = if let Some(asts) = document_asts {
-                if let Some(cached) = asts.get(file_path) {
-                    cached
-                } else {
-                    let parser = Parser::new(source);
-                    tree_holder = parser.parse();
-                    &tree_holder
-                }
+            let tree = if let Some(cached) = document_asts.and_then(|asts| asts.get(file_path)) {
+                cached
             } else {
-                let parser = Parser::new(source);
-                tree_holder = parser.parse();
+                tree_holder = Parser::new(source).parse();
                 &tree_holder
             };
 
-            if tree.errors().count() > 0 {
-                continue;
+            if tree.errors().count() == 0 {
+                visit(file_path, source, tree);
             }
-
-            let doc = tree.document();
-            Self::collect_type_references(
-                &doc,
-                type_name,
-                file_path,
-                source,
-                &mut references,
-                include_declaration,
-            );
         }
 
-        if references.is_empty() {
-            None
-        } else {
-            Some(references)
+        let mut cached_paths: Vec<&String> = document_index
+            .parsed_asts
+            .keys()
+            .filter(|path| {
+                !all_documents
+                    .iter()
+                    .any(|(file_path, _)| uri_to_file_path(file_path) == uri_to_file_path(path))
+            })
+            .collect();
+        cached_paths.sort();
+        for file_path in cached_paths {
+            let tree = &document_index.parsed_asts[file_path];
+            if tree.errors().count() > 0 {
+                continue;
+            }
+            // The tree is lossless, so its text is the source it was parsed from
+            let source = tree.document().syntax().text().to_string();
+            visit(file_path, &source, tree);
         }
     }
 
@@ -606,8 +973,8 @@ impl FindReferencesProvider {
         doc: &cst::Document,
         target_type: &str,
         file_path: &str,
-        source: &str,
-        references: &mut Vec<ReferenceLocation>,
+        line_index: &LineIndex,
+        on_reference: &mut impl FnMut(ReferenceLocation),
         include_declaration: bool,
     ) {
         for definition in doc.definitions() {
@@ -618,7 +985,7 @@ impl FindReferencesProvider {
                         if name.text() == target_type && include_declaration {
                             let range = name.syntax().text_range();
                             let (line, column) =
-                                Self::offset_to_position(source, range.start().into());
+                                Self::offset_to_position(line_index, range.start().into());
                             let range = Range {
                                 start: Position {
                                     line,
@@ -629,15 +996,15 @@ impl FindReferencesProvider {
                                     character: column + target_type.len(),
                                 },
                             };
-                            references.push(ReferenceLocation::new(file_path.to_string(), range));
+                            on_reference(ReferenceLocation::new(file_path.to_string(), range));
                         }
                     }
                     Self::collect_type_references_from_object_type(
                         &obj,
                         target_type,
                         file_path,
-                        source,
-                        references,
+                        line_index,
+                        on_reference,
                     );
                 }
                 cst::Definition::InterfaceTypeDefinition(iface) => {
@@ -645,7 +1012,7 @@ impl FindReferencesProvider {
                         if name.text() == target_type && include_declaration {
                             let range = name.syntax().text_range();
                             let (line, column) =
-                                Self::offset_to_position(source, range.start().into());
+                                Self::offset_to_position(line_index, range.start().into());
                             let range = Range {
                                 start: Position {
                                     line,
@@ -656,15 +1023,15 @@ impl FindReferencesProvider {
                                     character: column + target_type.len(),
                                 },
                             };
-                            references.push(ReferenceLocation::new(file_path.to_string(), range));
+                            on_reference(ReferenceLocation::new(file_path.to_string(), range));
                         }
                     }
                     Self::collect_type_references_from_interface_type(
                         &iface,
                         target_type,
                         file_path,
-                        source,
-                        references,
+                        line_index,
+                        on_reference,
                     );
                 }
                 cst::Definition::UnionTypeDefinition(union) => {
@@ -672,7 +1039,7 @@ impl FindReferencesProvider {
                         if name.text() == target_type && include_declaration {
                             let range = name.syntax().text_range();
                             let (line, column) =
-                                Self::offset_to_position(source, range.start().into());
+                                Self::offset_to_position(line_index, range.start().into());
                             let range = Range {
                                 start: Position {
                                     line,
@@ -683,15 +1050,15 @@ impl FindReferencesProvider {
                                     character: column + target_type.len(),
                                 },
                             };
-                            references.push(ReferenceLocation::new(file_path.to_string(), range));
+                            on_reference(ReferenceLocation::new(file_path.to_string(), range));
                         }
                     }
                     Self::collect_type_references_from_union_type(
                         &union,
                         target_type,
                         file_path,
-                        source,
-                        references,
+                        line_index,
+                        on_reference,
                     );
                 }
                 cst::Definition::InputObjectTypeDefinition(input) => {
@@ -699,7 +1066,7 @@ impl FindReferencesProvider {
                         if name.text() == target_type && include_declaration {
                             let range = name.syntax().text_range();
                             let (line, column) =
-                                Self::offset_to_position(source, range.start().into());
+                                Self::offset_to_position(line_index, range.start().into());
                             let range = Range {
                                 start: Position {
                                     line,
@@ -710,15 +1077,15 @@ impl FindReferencesProvider {
                                     character: column + target_type.len(),
                                 },
                             };
-                            references.push(ReferenceLocation::new(file_path.to_string(), range));
+                            on_reference(ReferenceLocation::new(file_path.to_string(), range));
                         }
                     }
                     Self::collect_type_references_from_input_object_type(
                         &input,
                         target_type,
                         file_path,
-                        source,
-                        references,
+                        line_index,
+                        on_reference,
                     );
                 }
                 cst::Definition::ScalarTypeDefinition(scalar) => {
@@ -726,7 +1093,7 @@ impl FindReferencesProvider {
                         if name.text() == target_type && include_declaration {
                             let range = name.syntax().text_range();
                             let (line, column) =
-                                Self::offset_to_position(source, range.start().into());
+                                Self::offset_to_position(line_index, range.start().into());
                             let range = Range {
                                 start: Position {
                                     line,
@@ -737,7 +1104,7 @@ impl FindReferencesProvider {
                                     character: column + target_type.len(),
                                 },
                             };
-                            references.push(ReferenceLocation::new(file_path.to_string(), range));
+                            on_reference(ReferenceLocation::new(file_path.to_string(), range));
                         }
                     }
                     // Scalars don't have fields, so no need to check for references within
@@ -747,7 +1114,7 @@ impl FindReferencesProvider {
                         if name.text() == target_type && include_declaration {
                             let range = name.syntax().text_range();
                             let (line, column) =
-                                Self::offset_to_position(source, range.start().into());
+                                Self::offset_to_position(line_index, range.start().into());
                             let range = Range {
                                 start: Position {
                                     line,
@@ -758,23 +1125,59 @@ impl FindReferencesProvider {
                                     character: column + target_type.len(),
                                 },
                             };
-                            references.push(ReferenceLocation::new(file_path.to_string(), range));
+                            on_reference(ReferenceLocation::new(file_path.to_string(), range));
                         }
                     }
                     // Enums don't have field types, so no need to check for references within
                 }
+                cst::Definition::OperationDefinition(op) => {
+                    // Variable types and inline fragment type conditions
+                    Self::collect_named_type_references(
+                        op.syntax(),
+                        target_type,
+                        file_path,
+                        line_index,
+                        on_reference,
+                    );
+                }
+                cst::Definition::FragmentDefinition(frag) => {
+                    // The fragment's type condition and inline fragment type conditions
+                    Self::collect_named_type_references(
+                        frag.syntax(),
+                        target_type,
+                        file_path,
+                        line_index,
+                        on_reference,
+                    );
+                }
                 _ => {}
             }
         }
     }
 
+    /// Collect every named type reference to the target type within a node
+    fn collect_named_type_references(
+        node: &apollo_parser::SyntaxNode,
+        target_type: &str,
+        file_path: &str,
+        line_index: &LineIndex,
+        on_reference: &mut impl FnMut(ReferenceLocation),
+    ) {
+        node.descendants()
+            .filter_map(cst::NamedType::cast)
+            .filter_map(|named_type| named_type.name())
+            .filter(|name| name.text() == target_type)
+            .map(|name| Self::name_location(&name, file_path, line_index))
+            .for_each(on_reference);
+    }
+
     /// Collect type references from an object type definition
     fn collect_type_references_from_object_type(
         obj: &cst::ObjectTypeDefinition,
         target_type: &str,
         file_path: &str,
-        source: &str,
-        references: &mut Vec<ReferenceLocation>,
+        line_index: &LineIndex,
+        on_reference: &mut impl FnMut(ReferenceLocation),
     ) {
         // Check field types
         if let Some(fields) = obj.fields_definition() {
@@ -784,8 +1187,8 @@ impl FindReferencesProvider {
                         &ty,
                         target_type,
                         file_path,
-                        source,
-                        references,
+                        line_index,
+                        on_reference,
                     );
                 }
                 // Check argument types
@@ -796,8 +1199,8 @@ impl FindReferencesProvider {
                                 &ty,
                                 target_type,
                                 file_path,
-                                source,
-                                references,
+                                line_index,
+                                on_reference,
                             );
                         }
                     }
@@ -811,7 +1214,8 @@ impl FindReferencesProvider {
                 if let Some(name) = named_type.name() {
                     if name.text() == target_type {
                         let range = name.syntax().text_range();
-                        let (line, column) = Self::offset_to_position(source, range.start().into());
+                        let (line, column) =
+                            Self::offset_to_position(line_index, range.start().into());
                         let range = Range {
                             start: Position {
                                 line,
@@ -822,7 +1226,7 @@ impl FindReferencesProvider {
                                 character: column + target_type.len(),
                             },
                         };
-                        references.push(ReferenceLocation::new(file_path.to_string(), range));
+                        on_reference(ReferenceLocation::new(file_path.to_string(), range));
                     }
                 }
             }
@@ -834,8 +1238,8 @@ impl FindReferencesProvider {
         iface: &cst::InterfaceTypeDefinition,
         target_type: &str,
         file_path: &str,
-        source: &str,
-        references: &mut Vec<ReferenceLocation>,
+        line_index: &LineIndex,
+        on_reference: &mut impl FnMut(ReferenceLocation),
     ) {
         // Check field types
         if let Some(fields) = iface.fields_definition() {
@@ -845,8 +1249,8 @@ impl FindReferencesProvider {
                         &ty,
                         target_type,
                         file_path,
-                        source,
-                        references,
+                        line_index,
+                        on_reference,
                     );
                 }
                 // Check argument types
@@ -857,8 +1261,8 @@ impl FindReferencesProvider {
                                 &ty,
                                 target_type,
                                 file_path,
-                                source,
-                                references,
+                                line_index,
+                                on_reference,
                             );
                         }
                     }
@@ -872,7 +1276,8 @@ impl FindReferencesProvider {
                 if let Some(name) = named_type.name() {
                     if name.text() == target_type {
                         let range = name.syntax().text_range();
-                        let (line, column) = Self::offset_to_position(source, range.start().into());
+                        let (line, column) =
+                            Self::offset_to_position(line_index, range.start().into());
                         let range = Range {
                             start: Position {
                                 line,
@@ -883,7 +1288,7 @@ impl FindReferencesProvider {
                                 character: column + target_type.len(),
                             },
                         };
-                        references.push(ReferenceLocation::new(file_path.to_string(), range));
+                        on_reference(ReferenceLocation::new(file_path.to_string(), range));
                     }
                 }
             }
@@ -895,8 +1300,8 @@ impl FindReferencesProvider {
         union: &cst::UnionTypeDefinition,
         target_type: &str,
         file_path: &str,
-        source: &str,
-        references: &mut Vec<ReferenceLocation>,
+        line_index: &LineIndex,
+        on_reference: &mut impl FnMut(ReferenceLocation),
     ) {
         // Check union members
         if let Some(members) = union.union_member_types() {
@@ -904,7 +1309,8 @@ impl FindReferencesProvider {
                 if let Some(name) = named_type.name() {
                     if name.text() == target_type {
                         let range = name.syntax().text_range();
-                        let (line, column) = Self::offset_to_position(source, range.start().into());
+                        let (line, column) =
+                            Self::offset_to_position(line_index, range.start().into());
                         let range = Range {
                             start: Position {
                                 line,
@@ -915,7 +1321,7 @@ impl FindReferencesProvider {
                                 character: column + target_type.len(),
                             },
                         };
-                        references.push(ReferenceLocation::new(file_path.to_string(), range));
+                        on_reference(ReferenceLocation::new(file_path.to_string(), range));
                     }
                 }
             }
@@ -927,8 +1333,8 @@ impl FindReferencesProvider {
         input: &cst::InputObjectTypeDefinition,
         target_type: &str,
         file_path: &str,
-        source: &str,
-        references: &mut Vec<ReferenceLocation>,
+        line_index: &LineIndex,
+        on_reference: &mut impl FnMut(ReferenceLocation),
     ) {
         // Check input field types
         if let Some(fields) = input.input_fields_definition() {
@@ -938,8 +1344,8 @@ impl FindReferencesProvider {
                         &ty,
                         target_type,
                         file_path,
-                        source,
-                        references,
+                        line_index,
+                        on_reference,
                     );
                 }
             }
@@ -951,15 +1357,16 @@ impl FindReferencesProvider {
         ty: &cst::Type,
         target_type: &str,
         file_path: &str,
-        source: &str,
-        references: &mut Vec<ReferenceLocation>,
+        line_index: &LineIndex,
+        on_reference: &mut impl FnMut(ReferenceLocation),
     ) {
         match ty {
             cst::Type::NamedType(named) => {
                 if let Some(name) = named.name() {
                     if name.text() == target_type {
                         let range = name.syntax().text_range();
-                        let (line, column) = Self::offset_to_position(source, range.start().into());
+                        let (line, column) =
+                            Self::offset_to_position(line_index, range.start().into());
                         let range = Range {
                             start: Position {
                                 line,
@@ -970,7 +1377,7 @@ impl FindReferencesProvider {
                                 character: column + target_type.len(),
                             },
                         };
-                        references.push(ReferenceLocation::new(file_path.to_string(), range));
+                        on_reference(ReferenceLocation::new(file_path.to_string(), range));
                     }
                 }
             }
@@ -980,8 +1387,8 @@ impl FindReferencesProvider {
                         &inner_ty,
                         target_type,
                         file_path,
-                        source,
-                        references,
+                        line_index,
+                        on_reference,
                     );
                 }
             }
@@ -992,7 +1399,7 @@ impl FindReferencesProvider {
                         if name.text() == target_type {
                             let range = name.syntax().text_range();
                             let (line, column) =
-                                Self::offset_to_position(source, range.start().into());
+                                Self::offset_to_position(line_index, range.start().into());
                             let range = Range {
                                 start: Position {
                                     line,
@@ -1003,7 +1410,7 @@ impl FindReferencesProvider {
                                     character: column + target_type.len(),
                                 },
                             };
-                            references.push(ReferenceLocation::new(file_path.to_string(), range));
+                            on_reference(ReferenceLocation::new(file_path.to_string(), range));
                         }
                     }
                 } else if let Some(list) = non_null.list_type() {
@@ -1012,8 +1419,8 @@ impl FindReferencesProvider {
                             &inner_ty,
                             target_type,
                             file_path,
-                            source,
-                            references,
+                            line_index,
+                            on_reference,
                         );
                     }
                 }
@@ -1030,9 +1437,20 @@ impl Default for FindReferencesProvider {
 
 #[derive(Debug, Clone, PartialEq)]
 enum ElementType {
-    FragmentSpread { fragment_name: String },
-    FragmentDefinition { fragment_name: String },
-    TypeDefinition { type_name: String },
+    FragmentSpread {
+        fragment_name: String,
+    },
+    FragmentDefinition {
+        fragment_name: String,
+    },
+    TypeDefinition {
+        type_name: String,
+    },
+    /// A schema field, from its definition or a selection of it
+    FieldDefinition {
+        type_name: String,
+        field_name: String,
+    },
 }
 
 #[cfg(test)]
@@ -1167,4 +1585,277 @@ query GetUser {
         // Should find the spread + the definition
         assert_eq!(references.len(), 2);
     }
+
+    const SCHEMA_DOC: &str = r"
+type Query {
+  user(filter: UserFilter): User
+  posts: [Post!]!
+}
+
+type User {
+  name: String
+}
+
+type Post {
+  name: String
+  author: User
+}
+
+input UserFilter {
+  name: String
+}
+union SearchResult = User | Post
+";
+
+    const QUERY_DOC: &str = r"
+query GetUser($filter: UserFilter) {
+  user(filter: $filter) {
+    name
+  }
+  posts {
+    name
+    author {
+      ...UserName
+    }
+  }
+}
+
+fragment UserName on User {
+  name
+}
+";
+
+    fn schema_and_query_documents() -> Vec<(String, String)> {
+        vec![
+            ("schema.graphql".to_string(), SCHEMA_DOC.to_string()),
+            ("query.graphql".to_string(), QUERY_DOC.to_string()),
+        ]
+    }
+
+    fn locations(references: &[ReferenceLocation]) -> Vec<(&str, usize, usize)> {
+        references
+            .iter()
+            .map(|reference| {
+                (
+                    reference.file_path.as_str(),
+                    reference.range.start.line,
+                    reference.range.start.character,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_find_type_references_across_schema_and_documents() {
+        let schema = SchemaIndex::from_schema(SCHEMA_DOC);
+        let provider = FindReferencesProvider::new();
+        let all_documents = schema_and_query_documents();
+
+        // On the `User` type definition name
+        let position = Position {
+            line: 6,
+            character: 5,
+        };
+        let references = provider
+            .find_references(
+                SCHEMA_DOC,
+                position,
+                &DocumentIndex::new(),
+                &schema,
+                &all_documents,
+                false,
+            )
+            .expect("Should find references");
+        assert_eq!(
+            locations(&references),
+            vec![
+                ("schema.graphql", 2, 28),
+                ("schema.graphql", 12, 10),
+                ("schema.graphql", 18, 21),
+                ("query.graphql", 13, 21),
+            ]
+        );
+
+        // On the variable type, with the declaration
+        let position = Position {
+            line: 1,
+            character: 25,
+        };
+        let references = provider
+            .find_references(
+                QUERY_DOC,
+                position,
+                &DocumentIndex::new(),
+                &schema,
+                &all_documents,
+                true,
+            )
+            .expect("Should find references");
+        assert_eq!(
+            locations(&references),
+            vec![
+                ("schema.graphql", 2, 15),
+                ("schema.graphql", 15, 6),
+                ("query.graphql", 1, 23),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_field_references_from_definition() {
+        let schema = SchemaIndex::from_schema(SCHEMA_DOC);
+        let provider = FindReferencesProvider::new();
+
+        // On `name` in `type User`; `Post.name` and `UserFilter.name` are other fields
+        let position = Position {
+            line: 7,
+            character: 2,
+        };
+        let references = provider
+            .find_references(
+                SCHEMA_DOC,
+                position,
+                &DocumentIndex::new(),
+                &schema,
+                &schema_and_query_documents(),
+                true,
+            )
+            .expect("Should find references");
+        assert_eq!(
+            locations(&references),
+            vec![
+                ("schema.graphql", 7, 2),
+                ("query.graphql", 3, 4),
+                ("query.graphql", 14, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_field_references_from_selection_in_cached_documents() {
+        let schema = SchemaIndex::from_schema(SCHEMA_DOC);
+        let provider = FindReferencesProvider::new();
+
+        // Documents that aren't open are still searched through their cached ASTs
+        let mut doc_index = DocumentIndex::new();
+        doc_index.cache_ast(
+            "other.graphql".to_string(),
+            std::sync::Arc::new(Parser::new("query Other { user { name } }").parse()),
+        );
+        let all_documents = vec![("query.graphql".to_string(), QUERY_DOC.to_string())];
+
+        // On `name` selected on `Post`
+        let position = Position {
+            line: 6,
+            character: 4,
+        };
+        let references = provider
+            .find_references(
+                QUERY_DOC,
+                position,
+                &doc_index,
+                &schema,
+                &all_documents,
+                false,
+            )
+            .expect("Should find references");
+        assert_eq!(locations(&references), vec![("query.graphql", 6, 4)]);
+
+        // On `name` selected on `User`
+        let position = Position {
+            line: 3,
+            character: 4,
+        };
+        let references = provider
+            .find_references(
+                QUERY_DOC,
+                position,
+                &doc_index,
+                &schema,
+                &all_documents,
+                false,
+            )
+            .expect("Should find references");
+        assert_eq!(
+            locations(&references),
+            vec![
+                ("query.graphql", 3, 4),
+                ("query.graphql", 14, 2),
+                ("other.graphql", 0, 21),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_open_document_that_is_also_cached_is_searched_once() {
+        let schema = SchemaIndex::from_schema(SCHEMA_DOC);
+        let provider = FindReferencesProvider::new();
+
+        // The open document is keyed by URI while its cached AST is keyed by path
+        let mut doc_index = DocumentIndex::new();
+        doc_index.cache_ast(
+            "/query.graphql".to_string(),
+            std::sync::Arc::new(Parser::new(QUERY_DOC).parse()),
+        );
+        let all_documents = vec![("file:///query.graphql".to_string(), QUERY_DOC.to_string())];
+
+        // On `name` selected on `Post`
+        let position = Position {
+            line: 6,
+            character: 4,
+        };
+        let references = provider
+            .find_references(
+                QUERY_DOC,
+                position,
+                &doc_index,
+                &schema,
+                &all_documents,
+                false,
+            )
+            .expect("Should find references");
+        assert_eq!(
+            locations(&references),
+            vec![("file:///query.graphql", 6, 4)]
+        );
+    }
+
+    #[test]
+    fn test_streamed_references_match_batch_references() {
+        let schema = SchemaIndex::from_schema(SCHEMA_DOC);
+        let provider = FindReferencesProvider::new();
+        let all_documents = schema_and_query_documents();
+
+        // On the `User` type definition name, with the declaration
+        let position = Position {
+            line: 6,
+            character: 5,
+        };
+        let batch = provider
+            .find_references(
+                SCHEMA_DOC,
+                position,
+                &DocumentIndex::new(),
+                &schema,
+                &all_documents,
+                true,
+            )
+            .expect("Should find references");
+
+        let mut streamed = Vec::new();
+        provider.find_references_streaming(
+            SCHEMA_DOC,
+            position,
+            &DocumentIndex::new(),
+            &schema,
+            &all_documents,
+            true,
+            None,
+            None,
+            None,
+            |reference| streamed.push(reference),
+        );
+
+        assert_eq!(streamed, batch);
+    }
 }
@@ -0,0 +1,167 @@
+use crate::{LineIndex, PositionEncoding};
+use apollo_parser::{cst::CstNode, Parser, SyntaxKind, SyntaxTree};
+
+/// Kind of region a folding range covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FoldingRangeKind {
+    /// Selection sets and argument lists
+    Region,
+    /// Block string descriptions
+    Comment,
+}
+
+/// Range of lines that can be folded away
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FoldingRange {
+    /// Line of the opening delimiter
+    pub start_line: usize,
+    /// Line of the closing delimiter
+    pub end_line: usize,
+    pub kind: FoldingRangeKind,
+}
+
+impl FoldingRange {
+    /// Shift this range from block-relative to file lines
+    ///
+    /// Used for GraphQL extracted from TypeScript/JavaScript, where the block starts at
+    /// `start_line` in the original file.
+    #[must_use]
+    pub const fn offset_by(mut self, start_line: usize) -> Self {
+        self.start_line += start_line;
+        self.end_line += start_line;
+        self
+    }
+}
+
+/// Folding range provider
+///
+/// Folds selection sets, argument lists, and block string descriptions that span more than
+/// one line.
+pub struct FoldingRangeProvider {
+    /// Unit `Position::character` columns are counted in
+    position_encoding: PositionEncoding,
+}
+
+impl FoldingRangeProvider {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            position_encoding: PositionEncoding::Utf16,
+        }
+    }
+
+    /// Count `Position::character` columns in `encoding` instead of UTF-16 code units
+    #[must_use]
+    pub const fn with_position_encoding(mut self, encoding: PositionEncoding) -> Self {
+        self.position_encoding = encoding;
+        self
+    }
+
+    /// Get the folding ranges for a GraphQL document, in document order
+    #[must_use]
+    pub fn folding_ranges(&self, source: &str) -> Vec<FoldingRange> {
+        let tree = Parser::new(source).parse();
+        self.folding_ranges_with_ast(source, &tree)
+    }
+
+    /// Get the folding ranges for a GraphQL document using an already parsed AST
+    #[must_use]
+    pub fn folding_ranges_with_ast(&self, source: &str, tree: &SyntaxTree) -> Vec<FoldingRange> {
+        let line_index = LineIndex::with_encoding(source, self.position_encoding);
+
+        tree.document()
+            .syntax()
+            .descendants()
+            .filter_map(|node| {
+                let kind = match node.kind() {
+                    SyntaxKind::SELECTION_SET
+                    | SyntaxKind::ARGUMENTS
+                    | SyntaxKind::ARGUMENTS_DEFINITION => FoldingRangeKind::Region,
+                    SyntaxKind::DESCRIPTION if node.text().to_string().starts_with("\"\"\"") => {
+                        FoldingRangeKind::Comment
+                    }
+                    _ => return None,
+                };
+
+                let range = node.text_range();
+                let start: usize = range.start().into();
+                let end: usize = range.end().into();
+                // Nodes carry the whitespace that follows them
+                let end = start + source.get(start..end)?.trim_end().len();
+
+                let start_line = line_index.offset_to_position(start).line;
+                let end_line = line_index.offset_to_position(end).line;
+                (end_line > start_line).then_some(FoldingRange {
+                    start_line,
+                    end_line,
+                    kind,
+                })
+            })
+            .collect()
+    }
+}
+
+impl Default for FoldingRangeProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(ranges: &[FoldingRange]) -> Vec<(usize, usize)> {
+        ranges
+            .iter()
+            .map(|range| (range.start_line, range.end_line))
+            .collect()
+    }
+
+    #[test]
+    fn test_nested_selection_sets() {
+        let source = r#"query GetUser {
+  user(
+    id: "1"
+  ) {
+    id
+    friends {
+      name
+    }
+    posts { title }
+  }
+}
+"#;
+
+        let ranges = FoldingRangeProvider::new().folding_ranges(source);
+
+        assert_eq!(lines(&ranges), vec![(0, 10), (1, 3), (3, 9), (5, 7)]);
+        assert!(ranges
+            .iter()
+            .all(|range| range.kind == FoldingRangeKind::Region));
+    }
+
+    #[test]
+    fn test_block_string_descriptions() {
+        let source =
+            "\"\"\"\nA user\nof the app\n\"\"\"\ntype User {\n  \"Inline\"\n  id: ID!\n}\n";
+
+        let ranges = FoldingRangeProvider::new().folding_ranges(source);
+
+        assert_eq!(
+            ranges,
+            vec![FoldingRange {
+                start_line: 0,
+                end_line: 3,
+                kind: FoldingRangeKind::Comment,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_offset_by_shifts_lines() {
+        let ranges = FoldingRangeProvider::new().folding_ranges("{\n  a\n}");
+
+        assert_eq!(lines(&[ranges[0].offset_by(12)]), vec![(12, 14)]);
+    }
+}
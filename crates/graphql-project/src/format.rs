@@ -0,0 +1,395 @@
+use apollo_parser::{cst::CstNode, Parser, SyntaxKind, SyntaxNode, SyntaxToken};
+
+/// Format a GraphQL document
+///
+/// Definitions are separated by a blank line, selection sets and type bodies are indented
+/// with two spaces one item per line, and arguments, variable definitions, lists, and
+/// object values are written inline separated by `, `. Arguments and variable definitions
+/// containing a description or comment are written one per line instead. Selection order,
+/// comments, descriptions, and single blank lines between items are preserved.
+///
+/// Works on executable documents and schema files alike. Returns `None` if the document
+/// has syntax errors, so a half-typed document is never rewritten.
+#[must_use]
+pub fn format_document(source: &str) -> Option<String> {
+    let tree = Parser::new(source).parse();
+    if tree.errors().len() > 0 {
+        return None;
+    }
+
+    let mut printer = Printer::default();
+    printer.node(tree.document().syntax());
+
+    let mut output = printer.output;
+    if !output.is_empty() {
+        output.push('\n');
+    }
+    Some(output)
+}
+
+/// Whitespace requested before the next token
+///
+/// Requests only ever grow until a token is written, so a line break asked for by a
+/// comment is never downgraded to a space by a separator.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+enum Pending {
+    #[default]
+    None,
+    Space,
+    Newline,
+    BlankLine,
+}
+
+#[derive(Default)]
+struct Printer {
+    output: String,
+    indent: usize,
+    pending: Pending,
+    /// Newlines in the source whitespace since the last printed token
+    newlines: usize,
+    /// Kind of the last printed token and of its parent node
+    previous: Option<(SyntaxKind, SyntaxKind)>,
+    /// Whether the last thing printed was a comment
+    after_comment: bool,
+}
+
+impl Printer {
+    fn node(&mut self, node: &SyntaxNode) {
+        let parent_kind = node.parent().map(|parent| parent.kind());
+        let is_first = node.prev_sibling().is_none();
+
+        match parent_kind {
+            // Comments directly above a definition stay attached to it
+            Some(SyntaxKind::DOCUMENT) if !is_first => self.request(if self.after_comment {
+                Pending::Newline
+            } else {
+                Pending::BlankLine
+            }),
+            Some(parent)
+                if is_block_item(node.kind(), parent)
+                    || node.parent().is_some_and(|list| is_multiline_list(&list)) =>
+            {
+                self.request(if self.newlines >= 2 && !is_first {
+                    Pending::BlankLine
+                } else {
+                    Pending::Newline
+                });
+            }
+            Some(parent) if is_list(parent) && !is_first => self.write(",", None),
+            _ => {}
+        }
+
+        for child in node.children_with_tokens() {
+            if let Some(child) = child.as_node() {
+                self.node(child);
+            } else if let Some(token) = child.as_token() {
+                self.token(token);
+            }
+        }
+
+        // A description goes on its own line, unless it is inside a list written inline
+        let in_inline_list = node
+            .parent()
+            .and_then(|described| described.parent())
+            .is_some_and(|list| is_list(list.kind()) && !is_multiline_list(&list));
+        if node.kind() == SyntaxKind::DESCRIPTION && !in_inline_list {
+            self.request(Pending::Newline);
+        }
+    }
+
+    fn token(&mut self, token: &SyntaxToken) {
+        let kind = token.kind();
+        let parent_kind = token.parent().map_or(SyntaxKind::DOCUMENT, |p| p.kind());
+
+        match kind {
+            SyntaxKind::WHITESPACE => {
+                self.newlines += token.text().matches('\n').count();
+                return;
+            }
+            // Separators are written by `node` so they are consistent
+            SyntaxKind::COMMA | SyntaxKind::EOF => return,
+            SyntaxKind::COMMENT => {
+                let is_trailing = self.newlines == 0
+                    && !self.output.is_empty()
+                    && self.pending < Pending::Newline;
+                self.request(if is_trailing {
+                    Pending::Space
+                } else if self.newlines >= 2 && !self.output.is_empty() {
+                    Pending::BlankLine
+                } else {
+                    Pending::Newline
+                });
+                self.write(token.text().trim_end(), None);
+                self.request(Pending::Newline);
+                self.previous = None;
+                self.after_comment = true;
+                return;
+            }
+            _ => {}
+        }
+
+        match kind {
+            SyntaxKind::L_CURLY if is_block(parent_kind) => {
+                self.request(Pending::Space);
+                self.write("{", Some((kind, parent_kind)));
+                self.indent += 1;
+            }
+            SyntaxKind::R_CURLY if is_block(parent_kind) => {
+                self.indent = self.indent.saturating_sub(1);
+                self.request(Pending::Newline);
+                self.write("}", Some((kind, parent_kind)));
+            }
+            SyntaxKind::L_PAREN if token.parent().is_some_and(|p| is_multiline_list(&p)) => {
+                self.write("(", Some((kind, parent_kind)));
+                self.indent += 1;
+            }
+            SyntaxKind::R_PAREN if token.parent().is_some_and(|p| is_multiline_list(&p)) => {
+                self.indent = self.indent.saturating_sub(1);
+                self.request(Pending::Newline);
+                self.write(")", Some((kind, parent_kind)));
+            }
+            SyntaxKind::L_CURLY => {
+                self.request(Pending::Space);
+                self.write("{", Some((kind, parent_kind)));
+                self.request(Pending::Space);
+            }
+            SyntaxKind::R_CURLY => {
+                self.request(Pending::Space);
+                self.write("}", Some((kind, parent_kind)));
+            }
+            _ => {
+                if self.needs_space_before(kind) {
+                    self.request(Pending::Space);
+                }
+                self.write(token.text(), Some((kind, parent_kind)));
+            }
+        }
+    }
+
+    fn needs_space_before(&self, kind: SyntaxKind) -> bool {
+        let Some((previous, previous_parent)) = self.previous else {
+            return true;
+        };
+
+        match (previous, kind) {
+            (
+                _,
+                SyntaxKind::COLON | SyntaxKind::BANG | SyntaxKind::R_PAREN | SyntaxKind::R_BRACK,
+            )
+            | (
+                SyntaxKind::DOLLAR | SyntaxKind::AT | SyntaxKind::L_PAREN | SyntaxKind::L_BRACK,
+                _,
+            )
+            | (SyntaxKind::IDENT, SyntaxKind::L_PAREN) => false,
+            // `...Name` is a spread, `... on Type` an inline fragment
+            (SyntaxKind::SPREAD, _) => previous_parent != SyntaxKind::FRAGMENT_SPREAD,
+            _ => true,
+        }
+    }
+
+    fn request(&mut self, pending: Pending) {
+        self.pending = self.pending.max(pending);
+    }
+
+    fn write(&mut self, text: &str, token: Option<(SyntaxKind, SyntaxKind)>) {
+        match self.pending {
+            Pending::None => {}
+            Pending::Space => {
+                if !self.output.is_empty() && !self.output.ends_with('\n') {
+                    self.output.push(' ');
+                }
+            }
+            Pending::Newline | Pending::BlankLine => {
+                if !self.output.is_empty() {
+                    let trimmed = self.output.trim_end_matches(' ').len();
+                    self.output.truncate(trimmed);
+                    self.output.push('\n');
+                    if self.pending == Pending::BlankLine {
+                        self.output.push('\n');
+                    }
+                }
+                self.output.push_str(&"  ".repeat(self.indent));
+            }
+        }
+
+        self.output.push_str(text);
+        self.pending = Pending::None;
+        self.newlines = 0;
+        self.after_comment = false;
+        if token.is_some() {
+            self.previous = token;
+        }
+    }
+}
+
+/// Nodes whose items are written one per line inside braces
+const fn is_block(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::SELECTION_SET
+            | SyntaxKind::FIELDS_DEFINITION
+            | SyntaxKind::ENUM_VALUES_DEFINITION
+            | SyntaxKind::INPUT_FIELDS_DEFINITION
+            | SyntaxKind::SCHEMA_DEFINITION
+            | SyntaxKind::SCHEMA_EXTENSION
+    )
+}
+
+const fn is_block_item(kind: SyntaxKind, parent: SyntaxKind) -> bool {
+    is_block(parent)
+        && matches!(
+            kind,
+            SyntaxKind::FIELD
+                | SyntaxKind::FRAGMENT_SPREAD
+                | SyntaxKind::INLINE_FRAGMENT
+                | SyntaxKind::FIELD_DEFINITION
+                | SyntaxKind::ENUM_VALUE_DEFINITION
+                | SyntaxKind::INPUT_VALUE_DEFINITION
+                | SyntaxKind::ROOT_OPERATION_TYPE_DEFINITION
+        )
+}
+
+/// Nodes whose items are written inline separated by `, `
+const fn is_list(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::ARGUMENTS
+            | SyntaxKind::ARGUMENTS_DEFINITION
+            | SyntaxKind::VARIABLE_DEFINITIONS
+            | SyntaxKind::LIST_VALUE
+            | SyntaxKind::OBJECT_VALUE
+    )
+}
+
+/// Parenthesized lists written one item per line, because an item has a description or
+/// a comment that would otherwise end the line in the middle of the list
+fn is_multiline_list(node: &SyntaxNode) -> bool {
+    matches!(
+        node.kind(),
+        SyntaxKind::ARGUMENTS | SyntaxKind::ARGUMENTS_DEFINITION | SyntaxKind::VARIABLE_DEFINITIONS
+    ) && node.descendants_with_tokens().any(|element| {
+        matches!(
+            element.kind(),
+            SyntaxKind::DESCRIPTION | SyntaxKind::COMMENT
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_operation() {
+        let source = r#"query   GetUser($id:ID!,$withPosts :Boolean=false){
+user(id:$id){id,name
+    posts(first:10,orderBy:{field:CREATED_AT,direction:DESC}) @include(if:$withPosts){ title tags(names:["a","b"]) }
+
+
+  ...UserFields
+  ... on Admin { role }
+}}
+fragment UserFields on User{email}"#;
+
+        let expected = r#"query GetUser($id: ID!, $withPosts: Boolean = false) {
+  user(id: $id) {
+    id
+    name
+    posts(first: 10, orderBy: { field: CREATED_AT, direction: DESC }) @include(if: $withPosts) {
+      title
+      tags(names: ["a", "b"])
+    }
+
+    ...UserFields
+    ... on Admin {
+      role
+    }
+  }
+}
+
+fragment UserFields on User {
+  email
+}
+"#;
+
+        assert_eq!(format_document(source).as_deref(), Some(expected));
+    }
+
+    #[test]
+    fn test_format_preserves_comments() {
+        let source = "# Fetch the viewer\nquery Viewer {\n  # The current user\n  viewer { id # primary key\n  }\n}\n\n# Shared fields\nfragment F on User { id }\n";
+
+        let expected = "# Fetch the viewer\nquery Viewer {\n  # The current user\n  viewer {\n    id # primary key\n  }\n}\n\n# Shared fields\nfragment F on User {\n  id\n}\n";
+
+        assert_eq!(format_document(source).as_deref(), Some(expected));
+    }
+
+    #[test]
+    fn test_format_schema() {
+        let source = r#""A user"
+type User implements Node&Entity @key(fields:"id"){
+"The ID"
+id:ID!
+friends(first:Int=10):[User!]!
+}
+enum Role{ADMIN USER}
+union Result=User|Post"#;
+
+        let expected = r#""A user"
+type User implements Node & Entity @key(fields: "id") {
+  "The ID"
+  id: ID!
+  friends(first: Int = 10): [User!]!
+}
+
+enum Role {
+  ADMIN
+  USER
+}
+
+union Result = User | Post
+"#;
+
+        assert_eq!(format_document(source).as_deref(), Some(expected));
+    }
+
+    #[test]
+    fn test_format_described_arguments() {
+        let source = r#"type Query {
+  user("The id" id: ID!, "Include drafts" drafts: Boolean = false): User
+  posts(first: Int # page size
+  ): [Post]
+  node(id: ID!): Node
+}"#;
+
+        let expected = r#"type Query {
+  user(
+    "The id"
+    id: ID!
+    "Include drafts"
+    drafts: Boolean = false
+  ): User
+  posts(
+    first: Int # page size
+  ): [Post]
+  node(id: ID!): Node
+}
+"#;
+
+        let formatted = format_document(source).unwrap();
+        assert_eq!(formatted, expected);
+        assert_eq!(format_document(&formatted).as_deref(), Some(expected));
+    }
+
+    #[test]
+    fn test_format_is_idempotent() {
+        let source = "query A($a: [Int!]) { f(a: $a) { ...B } }\nfragment B on T { x }";
+        let formatted = format_document(source).unwrap();
+        assert_eq!(format_document(&formatted).as_deref(), Some(&*formatted));
+    }
+
+    #[test]
+    fn test_syntax_errors_are_not_formatted() {
+        assert_eq!(format_document("query { user { id }"), None);
+    }
+}
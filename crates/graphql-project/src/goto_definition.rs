@@ -1,6 +1,6 @@
 #![allow(clippy::too_many_lines)]
 
-use crate::{DocumentIndex, Position, Range, SchemaIndex};
+use crate::{DocumentIndex, LineIndex, OperationType, Position, Range, SchemaIndex};
 use apollo_parser::{
     cst::{self, CstNode},
     Parser,
@@ -92,12 +92,8 @@ impl GotoDefinitionProvider {
 
         let doc = tree.document();
 
-        // Try to use cached LineIndex for O(1) position-to-offset conversion
-        // Fall back to O(N) character iteration if not available
-        let byte_offset = document_index
-            .get_line_index(file_path)
-            .and_then(|line_index| Self::position_to_offset_with_index(&line_index, position))
-            .or_else(|| Self::position_to_offset(source, position));
+        let line_index = document_index.line_index_for(Some(file_path), source);
+        let byte_offset = line_index.position_to_offset(position);
 
         if byte_offset.is_none() {
             tracing::debug!("Could not convert position to offset");
@@ -119,6 +115,7 @@ impl GotoDefinitionProvider {
             schema_index,
             source,
             file_path,
+            &line_index,
         );
 
         if let Some(locations) = &result {
@@ -130,51 +127,75 @@ impl GotoDefinitionProvider {
         result
     }
 
-    /// Convert a line/column position to a byte offset
+    /// Get the implementations of the abstract type referenced at a position
     ///
-    /// # Performance Note
+    /// When the cursor is on a reference to an interface or union, such as a fragment's
+    /// type condition, returns the definitions of every implementing or member type.
+    /// Returns `None` for concrete types and anything that isn't a type reference.
     ///
-    /// This is a fallback implementation that uses O(N) character iteration.
-    /// Callers should prefer using a cached `LineIndex` when available for O(1) lookups.
-    fn position_to_offset(source: &str, position: Position) -> Option<usize> {
-        let mut current_line = 0;
-        let mut current_col = 0;
-        let mut offset = 0;
-
-        for ch in source.chars() {
-            if current_line == position.line && current_col == position.character {
-                return Some(offset);
-            }
-
-            if ch == '\n' {
-                current_line += 1;
-                current_col = 0;
-            } else {
-                current_col += 1;
-            }
+    /// `line_index` is the cached index of `source`, if there is one; otherwise one counting
+    /// UTF-16 columns is built.
+    #[must_use]
+    pub fn goto_implementation(
+        &self,
+        source: &str,
+        position: Position,
+        schema_index: &SchemaIndex,
+        cached_ast: Option<&apollo_parser::SyntaxTree>,
+        line_index: Option<&LineIndex>,
+    ) -> Option<Vec<DefinitionLocation>> {
+        let tree_holder;
+        let tree = if let Some(ast) = cached_ast {
+            ast
+        } else {
+            tree_holder = Parser::new(source).parse();
+            &tree_holder
+        };
 
-            offset += ch.len_utf8();
+        if tree.errors().count() > 0 {
+            return None;
         }
 
-        if current_line == position.line && current_col == position.character {
-            Some(offset)
-        } else {
+        let byte_offset = match line_index {
+            Some(line_index) => line_index.position_to_offset(position)?,
+            None => LineIndex::new(source).position_to_offset(position)?,
+        };
+        let element_type =
+            Self::find_element_at_position(&tree.document(), byte_offset, source, schema_index)?;
+
+        let ElementType::TypeReference { type_name } = element_type else {
+            return None;
+        };
+
+        let locations: Vec<DefinitionLocation> = schema_index
+            .find_implementations(&type_name)
+            .into_iter()
+            .map(|type_def| {
+                let range = Range {
+                    start: Position {
+                        line: type_def.line,
+                        character: type_def.column,
+                    },
+                    end: Position {
+                        line: type_def.line,
+                        character: type_def.column + type_def.type_name.len(),
+                    },
+                };
+                DefinitionLocation::new(type_def.file_path, range)
+            })
+            .collect();
+
+        tracing::debug!(type_name = %type_name, count = locations.len(), "Found implementations");
+
+        if locations.is_empty() {
             None
+        } else {
+            Some(locations)
         }
     }
 
-    /// Convert a line/column position to a byte offset using a cached `LineIndex`
-    ///
-    /// This is the fast path with O(1) complexity instead of O(N) character iteration.
-    fn position_to_offset_with_index(
-        line_index: &crate::LineIndex,
-        position: Position,
-    ) -> Option<usize> {
-        line_index.position_to_offset(position)
-    }
-
     /// Find the GraphQL element at the given byte offset
-    fn find_element_at_position(
+    pub(crate) fn find_element_at_position(
         doc: &cst::Document,
         byte_offset: usize,
         source: &str,
@@ -345,26 +366,10 @@ impl GotoDefinitionProvider {
         op: &cst::OperationDefinition,
         schema_index: &SchemaIndex,
     ) -> String {
-        let root_type_name = op.operation_type().map_or_else(
-            || schema_index.schema().schema_definition.query.as_ref(),
-            |op_type| {
-                if op_type.query_token().is_some() {
-                    schema_index.schema().schema_definition.query.as_ref()
-                } else if op_type.mutation_token().is_some() {
-                    schema_index.schema().schema_definition.mutation.as_ref()
-                } else if op_type.subscription_token().is_some() {
-                    schema_index
-                        .schema()
-                        .schema_definition
-                        .subscription
-                        .as_ref()
-                } else {
-                    schema_index.schema().schema_definition.query.as_ref()
-                }
-            },
-        );
-
-        root_type_name.map_or_else(|| "Query".to_string(), std::string::ToString::to_string)
+        schema_index
+            .root_type(OperationType::of(op))
+            .unwrap_or("Query")
+            .to_string()
     }
 
     /// Check if the byte offset is within a selection set
@@ -903,6 +908,7 @@ impl GotoDefinitionProvider {
         var_name: &str,
         operation_offset: usize,
         file_path: &str,
+        line_index: &LineIndex,
     ) -> Option<Vec<DefinitionLocation>> {
         // Parse from the operation start to find variable definitions
         let parser = Parser::new(&source[operation_offset..]);
@@ -930,14 +936,11 @@ impl GotoDefinitionProvider {
                                     let abs_end = operation_offset + end;
 
                                     // Convert to line/column positions
-                                    let start_pos = Self::offset_to_position(source, abs_start)?;
-                                    let end_pos = Self::offset_to_position(source, abs_end)?;
-
                                     return Some(vec![DefinitionLocation::new(
                                         file_path.to_string(),
                                         Range {
-                                            start: start_pos,
-                                            end: end_pos,
+                                            start: line_index.offset_to_position(abs_start),
+                                            end: line_index.offset_to_position(abs_end),
                                         },
                                     )]);
                                 }
@@ -953,40 +956,6 @@ impl GotoDefinitionProvider {
         None
     }
 
-    /// Convert a byte offset to a line/column position
-    fn offset_to_position(source: &str, offset: usize) -> Option<Position> {
-        let mut current_line = 0;
-        let mut current_col = 0;
-        let mut current_offset = 0;
-
-        for ch in source.chars() {
-            if current_offset == offset {
-                return Some(Position {
-                    line: current_line,
-                    character: current_col,
-                });
-            }
-
-            if ch == '\n' {
-                current_line += 1;
-                current_col = 0;
-            } else {
-                current_col += 1;
-            }
-
-            current_offset += ch.len_utf8();
-        }
-
-        if current_offset == offset {
-            Some(Position {
-                line: current_line,
-                character: current_col,
-            })
-        } else {
-            None
-        }
-    }
-
     /// Resolve the definition location based on the element type
     fn resolve_definition(
         element_type: ElementType,
@@ -994,6 +963,7 @@ impl GotoDefinitionProvider {
         schema_index: &SchemaIndex,
         source: &str,
         file_path: &str,
+        line_index: &LineIndex,
     ) -> Option<Vec<DefinitionLocation>> {
         match element_type {
             ElementType::FragmentSpread { fragment_name } => document_index
@@ -1040,8 +1010,9 @@ impl GotoDefinitionProvider {
                     })
             }
             ElementType::OperationDefinition { operation_name } => {
-                // When on an operation definition name, show all other definitions with the same name
-                document_index
+                // When on an operation definition name, show all other definitions with the same
+                // name, sorted so duplicates are listed in a stable order
+                let mut locations: Vec<DefinitionLocation> = document_index
                     .get_operations(&operation_name)
                     .map(|operations| {
                         operations
@@ -1063,6 +1034,21 @@ impl GotoDefinitionProvider {
                             })
                             .collect()
                     })
+                    .unwrap_or_default();
+
+                locations.sort_by(|a, b| {
+                    (&a.file_path, a.range.start.line, a.range.start.character).cmp(&(
+                        &b.file_path,
+                        b.range.start.line,
+                        b.range.start.character,
+                    ))
+                });
+
+                if locations.is_empty() {
+                    None
+                } else {
+                    Some(locations)
+                }
             }
             ElementType::TypeReference { type_name } => {
                 // Find the type definition in the schema
@@ -1086,7 +1072,13 @@ impl GotoDefinitionProvider {
                 operation_offset,
             } => {
                 // Find the variable definition in the operation
-                Self::find_variable_definition(source, &var_name, operation_offset, file_path)
+                Self::find_variable_definition(
+                    source,
+                    &var_name,
+                    operation_offset,
+                    file_path,
+                    line_index,
+                )
             }
             ElementType::ArgumentReference {
                 argument_name,
@@ -1207,7 +1199,7 @@ impl Default for GotoDefinitionProvider {
 
 /// Type of GraphQL element at a position
 #[derive(Debug, Clone, PartialEq)]
-enum ElementType {
+pub(crate) enum ElementType {
     FragmentSpread {
         fragment_name: String,
     },
@@ -1574,6 +1566,185 @@ query GetUser {
         assert_eq!(locations[1].file_path, "/path/to/queries2.graphql");
     }
 
+    #[test]
+    fn test_goto_from_operation_name_with_single_definition() {
+        use crate::{OperationInfo, OperationType};
+
+        let mut doc_index = DocumentIndex::new();
+        doc_index.add_operation(
+            Some("GetUser".to_string()),
+            OperationInfo {
+                name: Some("GetUser".to_string()),
+                operation_type: OperationType::Query,
+                file_path: "/path/to/queries.graphql".to_string(),
+                line: 1,
+                column: 6,
+            },
+        );
+
+        let schema = SchemaIndex::new();
+        let provider = GotoDefinitionProvider::new();
+
+        let document = r"
+query GetUser {
+    user {
+        id
+    }
+}
+";
+
+        let position = Position {
+            line: 1,
+            character: 8,
+        };
+
+        let locations = provider
+            .goto_definition(
+                document,
+                position,
+                &doc_index,
+                &schema,
+                "/path/to/queries.graphql",
+            )
+            .expect("Should find the operation definition");
+
+        assert_eq!(
+            locations,
+            vec![DefinitionLocation::new(
+                "/path/to/queries.graphql".to_string(),
+                Range {
+                    start: Position {
+                        line: 1,
+                        character: 6,
+                    },
+                    end: Position {
+                        line: 1,
+                        character: 13,
+                    },
+                },
+            )]
+        );
+    }
+
+    #[test]
+    fn test_no_goto_from_anonymous_operation() {
+        use crate::{OperationInfo, OperationType};
+
+        let mut doc_index = DocumentIndex::new();
+        doc_index.add_operation(
+            None,
+            OperationInfo {
+                name: None,
+                operation_type: OperationType::Query,
+                file_path: "/path/to/queries.graphql".to_string(),
+                line: 1,
+                column: 0,
+            },
+        );
+
+        let schema = SchemaIndex::new();
+        let provider = GotoDefinitionProvider::new();
+
+        let document = r"
+query {
+    user {
+        id
+    }
+}
+";
+
+        // Cursor on the `query` keyword of an anonymous operation
+        let position = Position {
+            line: 1,
+            character: 2,
+        };
+
+        let locations = provider.goto_definition(
+            document,
+            position,
+            &doc_index,
+            &schema,
+            "/path/to/queries.graphql",
+        );
+
+        assert!(locations.is_none());
+    }
+
+    #[test]
+    fn test_goto_implementation_from_fragment_type_condition() {
+        let schema = SchemaIndex::from_schema(
+            r"
+type Query {
+    node(id: ID!): Node
+}
+
+interface Node {
+    id: ID!
+}
+
+type User implements Node {
+    id: ID!
+    name: String!
+}
+
+type Post implements Node {
+    id: ID!
+    title: String!
+}
+
+type Comment {
+    id: ID!
+}
+",
+        );
+        let provider = GotoDefinitionProvider::new();
+
+        let document = "fragment NodeFields on Node { id }";
+
+        let locations = provider
+            .goto_implementation(
+                document,
+                Position {
+                    line: 0,
+                    character: 24,
+                },
+                &schema,
+                None,
+                None,
+            )
+            .expect("Should find implementations of Node");
+
+        let mut lines: Vec<usize> = locations.iter().map(|loc| loc.range.start.line).collect();
+        lines.sort_unstable();
+        // User and Post, but not Comment
+        assert_eq!(lines, vec![9, 14]);
+        assert!(locations.iter().all(|loc| loc.range.start.character == 5));
+    }
+
+    #[test]
+    fn test_no_goto_implementation_for_concrete_type() {
+        let schema = SchemaIndex::from_schema(
+            "type Query { user: User }
+type User { id: ID! }",
+        );
+        let provider = GotoDefinitionProvider::new();
+
+        let document = "fragment UserFields on User { id }";
+
+        let locations = provider.goto_implementation(
+            document,
+            Position {
+                line: 0,
+                character: 24,
+            },
+            &schema,
+            None,
+            None,
+        );
+
+        assert!(locations.is_none());
+    }
+
     #[test]
     fn test_goto_field_definition() {
         let doc_index = DocumentIndex::new();
@@ -1633,6 +1804,142 @@ query GetUser {
         assert_eq!(locations[0].range.start.character, 2);
     }
 
+    #[test]
+    fn test_goto_field_definition_in_spread_fragments() {
+        let doc_index = DocumentIndex::new();
+
+        let schema_str = r"
+type Query {
+  user: User
+}
+
+interface Node {
+  id: ID!
+}
+
+type User implements Node {
+  id: ID!
+  posts: [Post!]!
+}
+
+type Post implements Node {
+  id: ID!
+  title: String!
+}
+";
+
+        let schema = SchemaIndex::from_schema(schema_str);
+        let provider = GotoDefinitionProvider::new();
+
+        // Fields in fragments resolve against each fragment's type condition, however
+        // the fragment is reached from the operation
+        let document = r"
+query GetUser {
+    user {
+        ...UserFields
+    }
+}
+
+fragment UserFields on User {
+    posts {
+        ...PostFields
+    }
+}
+
+fragment PostFields on Post {
+    title
+    ...NodeFields
+}
+
+fragment NodeFields on Node {
+    id
+}
+";
+
+        let goto = |line, character| {
+            provider
+                .goto_definition(
+                    document,
+                    Position { line, character },
+                    &doc_index,
+                    &schema,
+                    "file:///test.graphql",
+                )
+                .expect("Should find field definition")
+        };
+
+        // "title" in PostFields is Post.title
+        let locations = goto(14, 4);
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].file_path, "schema.graphql");
+        assert_eq!(locations[0].range.start.line, 16);
+        assert_eq!(locations[0].range.start.character, 2);
+
+        // "id" in NodeFields is the interface's field
+        let locations = goto(19, 4);
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].range.start.line, 6);
+        assert_eq!(locations[0].range.start.character, 2);
+
+        // "posts" in UserFields is User.posts
+        let locations = goto(8, 4);
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].range.start.line, 11);
+    }
+
+    #[test]
+    fn test_goto_mutation_field_with_root_from_schema_extension() {
+        let doc_index = DocumentIndex::new();
+
+        let schema = SchemaIndex::from_schema_files(vec![
+            (
+                "schema.graphql".to_string(),
+                "type Query {\n  user: User\n}\n\ntype User {\n  id: ID!\n}\n".to_string(),
+            ),
+            (
+                "mutations.graphql".to_string(),
+                "extend schema {\n  mutation: AppMutation\n}\n\ntype AppMutation {\n  createUser(name: String!): User\n}\n"
+                    .to_string(),
+            ),
+        ]);
+        assert_eq!(
+            schema
+                .schema()
+                .schema_definition
+                .mutation
+                .as_ref()
+                .map(|name| name.as_str()),
+            Some("AppMutation")
+        );
+
+        let provider = GotoDefinitionProvider::new();
+        let document = r#"
+mutation CreateUser {
+    createUser(name: "Ada") {
+        id
+    }
+}
+"#;
+
+        let locations = provider
+            .goto_definition(
+                document,
+                Position {
+                    line: 2,
+                    character: 6,
+                },
+                &doc_index,
+                &schema,
+                "file:///test.graphql",
+            )
+            .expect("Should find mutation field definition");
+
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].file_path, "mutations.graphql");
+        assert_eq!(locations[0].range.start.line, 5);
+        assert_eq!(locations[0].range.start.character, 2);
+    }
+
     #[test]
     fn test_field_position_calculation() {
         let schema_str = r"type Query {
@@ -2722,4 +3029,54 @@ query GetUsers {
         // "USER" enum value
         assert_eq!(locations[0].range.start.line, 3);
     }
+
+    #[test]
+    fn test_goto_field_definition_with_surrogate_pairs() {
+        let doc_index = DocumentIndex::new();
+
+        let schema_str = r"
+type Query {
+  user(id: ID!): User
+  posts: [Post!]!
+}
+
+type User {
+  id: ID!
+  name: String!
+  email: String!
+}
+
+type Post {
+  id: ID!
+  title: String!
+}
+";
+
+        let schema = SchemaIndex::from_schema(schema_str);
+        let provider = GotoDefinitionProvider::new();
+
+        // Each 😀 is two UTF-16 code units but a single char
+        let document = r#"query GetUser($s: String = "😀😀") { user(id: "1") { name id } }"#;
+
+        // UTF-16 column of the "e" in "name"; counting chars would land on "id"
+        let position = Position {
+            line: 0,
+            character: 56,
+        };
+
+        let locations = provider
+            .goto_definition(
+                document,
+                position,
+                &doc_index,
+                &schema,
+                "file:///test.graphql",
+            )
+            .expect("Should find field definition");
+
+        assert_eq!(locations.len(), 1);
+        // "name" field of User
+        assert_eq!(locations[0].range.start.line, 8);
+        assert_eq!(locations[0].range.start.character, 2);
+    }
 }
@@ -5,7 +5,7 @@
 #![allow(clippy::single_match_else)]
 #![allow(clippy::only_used_in_recursion)]
 
-use crate::{DocumentIndex, Position, Range, SchemaIndex};
+use crate::{DocumentIndex, OperationType, Position, Range, SchemaIndex};
 use apollo_parser::{
     cst::{self, CstNode},
     Parser,
@@ -45,6 +45,9 @@ enum ElementType {
     },
     Variable {
         var_name: String,
+        /// Declared type, when the enclosing operation defines the variable
+        type_name: Option<String>,
+        default_value: Option<String>,
     },
     FragmentSpread {
         fragment_name: String,
@@ -56,6 +59,10 @@ enum ElementType {
     Directive {
         directive_name: String,
     },
+    DirectiveArgument {
+        argument_name: String,
+        directive_name: String,
+    },
     EnumValue {
         value_name: String,
         enum_type: Option<String>,
@@ -91,7 +98,7 @@ impl HoverProvider {
     /// This method accepts optional cached data to avoid expensive operations:
     /// - `cached_ast`: Pre-parsed AST of the source document
     /// - `document_index`: Document index for accessing cached `LineIndex`
-    /// - `file_path`: Source file path for O(1) position conversion
+    /// - `file_path`: Source file path, for looking up its cached `LineIndex`
     #[must_use]
     #[allow(clippy::option_if_let_else)]
     pub fn hover_with_ast(
@@ -124,60 +131,19 @@ impl HoverProvider {
 
         let doc = tree.document();
 
-        // Try to use cached LineIndex for O(1) position-to-offset conversion
-        // Fall back to O(N) character iteration if not available
-        let byte_offset = document_index
-            .and_then(|idx| file_path.and_then(|path| idx.get_line_index(path)))
-            .and_then(|line_index| Self::position_to_offset_with_index(&line_index, position))
-            .or_else(|| Self::position_to_offset(source, position))?;
+        // Use the cached LineIndex when there is one for this source
+        let byte_offset = match document_index {
+            Some(index) => index
+                .line_index_for(file_path, source)
+                .position_to_offset(position)?,
+            None => crate::LineIndex::new(source).position_to_offset(position)?,
+        };
 
         // Find the element at this position
         let element_type = Self::find_element_at_position(&doc, byte_offset, source, schema_index)?;
 
         // Generate hover content based on element type
-        Self::generate_hover_content(element_type, schema_index)
-    }
-
-    /// Convert a line/column position to a byte offset using a cached `LineIndex`
-    ///
-    /// This is the fast path with O(1) complexity instead of O(N) character iteration.
-    fn position_to_offset_with_index(
-        line_index: &crate::LineIndex,
-        position: Position,
-    ) -> Option<usize> {
-        line_index.position_to_offset(position)
-    }
-
-    /// Convert a line/column position to a byte offset (fallback O(N) implementation)
-    ///
-    /// This is the slow path used when no cached `LineIndex` is available.
-    /// Prefers `position_to_offset_with_index` when possible.
-    fn position_to_offset(source: &str, position: Position) -> Option<usize> {
-        let mut current_line = 0;
-        let mut current_col = 0;
-        let mut offset = 0;
-
-        for ch in source.chars() {
-            if current_line == position.line && current_col == position.character {
-                return Some(offset);
-            }
-
-            if ch == '\n' {
-                current_line += 1;
-                current_col = 0;
-            } else {
-                current_col += 1;
-            }
-
-            offset += ch.len_utf8();
-        }
-
-        // Handle position at end of file
-        if current_line == position.line && current_col == position.character {
-            Some(offset)
-        } else {
-            None
-        }
+        Self::generate_hover_content(element_type, schema_index, document_index)
     }
 
     /// Find the GraphQL element at the given byte offset
@@ -190,22 +156,10 @@ impl HoverProvider {
         for definition in doc.definitions() {
             match definition {
                 cst::Definition::OperationDefinition(op) => {
-                    // Check if we're on the operation keyword or name
-                    if let Some(element) = Self::check_operation_definition(&op, byte_offset) {
-                        return Some(element);
-                    }
-
-                    // Check the selection set
-                    if let Some(selection_set) = op.selection_set() {
-                        if let Some(element) = Self::check_selection_set(
-                            &selection_set,
-                            byte_offset,
-                            Self::get_operation_root_type(&op, source, schema_index),
-                            source,
-                            schema_index,
-                        ) {
-                            return Some(element);
-                        }
+                    if let Some(element) =
+                        Self::find_element_in_operation(&op, byte_offset, source, schema_index)
+                    {
+                        return Some(Self::resolve_variable_definition(element, &op));
                     }
                 }
                 cst::Definition::FragmentDefinition(frag) => {
@@ -316,6 +270,70 @@ impl HoverProvider {
         None
     }
 
+    /// Find the element at the byte offset within an operation definition
+    fn find_element_in_operation(
+        op: &cst::OperationDefinition,
+        byte_offset: usize,
+        source: &str,
+        schema_index: &SchemaIndex,
+    ) -> Option<ElementType> {
+        // Check if we're on the operation keyword or name
+        if let Some(element) = Self::check_operation_definition(op, byte_offset) {
+            return Some(element);
+        }
+
+        if let Some(element) = Self::check_directives(op.directives(), byte_offset) {
+            return Some(element);
+        }
+
+        // Check the selection set
+        Self::check_selection_set(
+            &op.selection_set()?,
+            byte_offset,
+            Self::get_operation_root_type(op, source, schema_index),
+            source,
+            schema_index,
+        )
+    }
+
+    /// Fill in a variable's declared type and default value from the operation's variable
+    /// definitions
+    ///
+    /// Applies to both the definition and its usages. Other elements, and variables the
+    /// operation doesn't define, are returned unchanged.
+    fn resolve_variable_definition(
+        element: ElementType,
+        op: &cst::OperationDefinition,
+    ) -> ElementType {
+        let ElementType::Variable { var_name, .. } = &element else {
+            return element;
+        };
+
+        let Some(definition) = op
+            .variable_definitions()
+            .into_iter()
+            .flat_map(|defs| defs.variable_definitions())
+            .find(|def| {
+                def.variable()
+                    .and_then(|variable| variable.name())
+                    .is_some_and(|name| *var_name == name.text())
+            })
+        else {
+            return element;
+        };
+
+        ElementType::Variable {
+            var_name: var_name.clone(),
+            type_name: definition
+                .ty()
+                .map(|ty| ty.syntax().text().to_string().trim().to_string()),
+            default_value: definition
+                .default_value()
+                .and_then(|default| default.value())
+                .map(|value| value.syntax().text().to_string().trim().to_string()),
+        }
+    }
+
     /// Check if the byte offset is within an operation definition
     fn check_operation_definition(
         op: &cst::OperationDefinition,
@@ -361,6 +379,8 @@ impl HoverProvider {
                         if byte_offset >= start && byte_offset < end {
                             return Some(ElementType::Variable {
                                 var_name: name.text().to_string(),
+                                type_name: None,
+                                default_value: None,
                             });
                         }
                     }
@@ -377,25 +397,10 @@ impl HoverProvider {
         _source: &str,
         schema_index: &SchemaIndex,
     ) -> String {
-        let root_type_name = if let Some(op_type) = op.operation_type() {
-            if op_type.query_token().is_some() {
-                schema_index.schema().schema_definition.query.as_ref()
-            } else if op_type.mutation_token().is_some() {
-                schema_index.schema().schema_definition.mutation.as_ref()
-            } else if op_type.subscription_token().is_some() {
-                schema_index
-                    .schema()
-                    .schema_definition
-                    .subscription
-                    .as_ref()
-            } else {
-                schema_index.schema().schema_definition.query.as_ref()
-            }
-        } else {
-            schema_index.schema().schema_definition.query.as_ref()
-        };
-
-        root_type_name.map_or_else(|| "Query".to_string(), std::string::ToString::to_string)
+        schema_index
+            .root_type(OperationType::of(op))
+            .unwrap_or("Query")
+            .to_string()
     }
 
     /// Check if the byte offset is within a selection set
@@ -423,6 +428,10 @@ impl HoverProvider {
                         }
                     }
 
+                    if let Some(element) = Self::check_directives(field.directives(), byte_offset) {
+                        return Some(element);
+                    }
+
                     // Check if we're on an argument
                     if let Some(arguments) = field.arguments() {
                         for arg in arguments.arguments() {
@@ -452,6 +461,43 @@ impl HoverProvider {
                                 {
                                     return Some(element);
                                 }
+
+                                if let cst::Value::EnumValue(enum_value) = &value {
+                                    let range = enum_value.syntax().text_range();
+                                    let start: usize = range.start().into();
+                                    let end: usize = range.end().into();
+
+                                    if byte_offset >= start && byte_offset < end {
+                                        let field_name = field
+                                            .name()
+                                            .map(|n| n.text().to_string())
+                                            .unwrap_or_default();
+                                        let arg_name = arg
+                                            .name()
+                                            .map(|n| n.text().to_string())
+                                            .unwrap_or_default();
+                                        let enum_type = schema_index
+                                            .get_fields(&parent_type)
+                                            .and_then(|fields| {
+                                                fields.into_iter().find(|f| f.name == field_name)
+                                            })
+                                            .and_then(|f| {
+                                                f.arguments.into_iter().find(|a| a.name == arg_name)
+                                            })
+                                            .map(|a| {
+                                                a.type_name
+                                                    .trim_matches(|c| {
+                                                        c == '[' || c == ']' || c == '!'
+                                                    })
+                                                    .to_string()
+                                            });
+
+                                        return Some(ElementType::EnumValue {
+                                            value_name: enum_value.text().to_string(),
+                                            enum_type,
+                                        });
+                                    }
+                                }
                             }
                         }
                     }
@@ -504,6 +550,11 @@ impl HoverProvider {
                             });
                         }
                     }
+
+                    if let Some(element) = Self::check_directives(spread.directives(), byte_offset)
+                    {
+                        return Some(element);
+                    }
                 }
                 cst::Selection::InlineFragment(inline_frag) => {
                     // Check if we're on the type condition
@@ -523,6 +574,12 @@ impl HoverProvider {
                         }
                     }
 
+                    if let Some(element) =
+                        Self::check_directives(inline_frag.directives(), byte_offset)
+                    {
+                        return Some(element);
+                    }
+
                     // Check nested selection set
                     if let Some(nested_selection_set) = inline_frag.selection_set() {
                         let type_name = inline_frag
@@ -548,6 +605,55 @@ impl HoverProvider {
         None
     }
 
+    /// Check if the byte offset is on a directive name or one of its argument names
+    fn check_directives(
+        directives: Option<cst::Directives>,
+        byte_offset: usize,
+    ) -> Option<ElementType> {
+        for directive in directives?.directives() {
+            let directive_name = directive
+                .name()
+                .map(|n| n.text().to_string())
+                .unwrap_or_default();
+
+            if let Some(name) = directive.name() {
+                let range = name.syntax().text_range();
+                let start: usize = range.start().into();
+                let end: usize = range.end().into();
+
+                if byte_offset >= start && byte_offset < end {
+                    return Some(ElementType::Directive { directive_name });
+                }
+            }
+
+            let Some(arguments) = directive.arguments() else {
+                continue;
+            };
+            for arg in arguments.arguments() {
+                if let Some(name) = arg.name() {
+                    let range = name.syntax().text_range();
+                    let start: usize = range.start().into();
+                    let end: usize = range.end().into();
+
+                    if byte_offset >= start && byte_offset < end {
+                        return Some(ElementType::DirectiveArgument {
+                            argument_name: name.text().to_string(),
+                            directive_name,
+                        });
+                    }
+                }
+
+                if let Some(value) = arg.value() {
+                    if let Some(element) = Self::check_value_for_variable(&value, byte_offset) {
+                        return Some(element);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
     /// Check if a value contains a variable at the byte offset
     fn check_value_for_variable(value: &cst::Value, byte_offset: usize) -> Option<ElementType> {
         if let cst::Value::Variable(var) = value {
@@ -559,6 +665,8 @@ impl HoverProvider {
                 if byte_offset >= start && byte_offset < end {
                     return Some(ElementType::Variable {
                         var_name: name.text().to_string(),
+                        type_name: None,
+                        default_value: None,
                     });
                 }
             }
@@ -930,6 +1038,7 @@ impl HoverProvider {
     fn generate_hover_content(
         element_type: ElementType,
         schema_index: &SchemaIndex,
+        document_index: Option<&DocumentIndex>,
     ) -> Option<HoverInfo> {
         match element_type {
             ElementType::Field {
@@ -947,10 +1056,18 @@ impl HoverProvider {
                 parent_type,
             } => Self::generate_argument_hover(&arg_name, &field_name, &parent_type, schema_index),
 
-            ElementType::Variable { var_name } => Self::generate_variable_hover(&var_name),
+            ElementType::Variable {
+                var_name,
+                type_name,
+                default_value,
+            } => Self::generate_variable_hover(
+                &var_name,
+                type_name.as_deref(),
+                default_value.as_deref(),
+            ),
 
             ElementType::FragmentSpread { fragment_name } => {
-                Self::generate_fragment_spread_hover(&fragment_name)
+                Self::generate_fragment_spread_hover(&fragment_name, document_index)
             }
 
             ElementType::FragmentDefinition {
@@ -962,6 +1079,15 @@ impl HoverProvider {
                 Self::generate_directive_hover(&directive_name, schema_index)
             }
 
+            ElementType::DirectiveArgument {
+                argument_name,
+                directive_name,
+            } => Self::generate_directive_argument_hover(
+                &argument_name,
+                &directive_name,
+                schema_index,
+            ),
+
             ElementType::EnumValue {
                 value_name,
                 enum_type,
@@ -983,7 +1109,8 @@ impl HoverProvider {
         let fields = schema_index.get_fields(parent_type)?;
         let field_info = fields.iter().find(|f| f.name == field_name)?;
 
-        let mut content = format!("### Field: `{field_name}`\n");
+        let mut content = deprecation_notice(field_info.deprecated.as_deref());
+        content.push_str(&format!("### Field: `{field_name}`\n"));
         content.push_str(&format!("**Type:** `{}`\n\n", field_info.type_name));
 
         if let Some(ref description) = field_info.description {
@@ -991,10 +1118,6 @@ impl HoverProvider {
             content.push_str("\n\n");
         }
 
-        if let Some(ref deprecated) = field_info.deprecated {
-            content.push_str(&format!("⚠️ **Deprecated:** {deprecated}\n\n"));
-        }
-
         if !field_info.arguments.is_empty() {
             content.push_str("**Arguments:**\n");
             for arg in &field_info.arguments {
@@ -1095,15 +1218,75 @@ impl HoverProvider {
     }
 
     /// Generate hover content for a variable
-    fn generate_variable_hover(var_name: &str) -> Option<HoverInfo> {
-        let content = format!("### Variable: `${var_name}`\n\nVariable usage in this operation");
+    ///
+    /// Shows the variable's definition, with its default value if it has one, when the
+    /// enclosing operation defines it.
+    fn generate_variable_hover(
+        var_name: &str,
+        type_name: Option<&str>,
+        default_value: Option<&str>,
+    ) -> Option<HoverInfo> {
+        let Some(type_name) = type_name else {
+            let content =
+                format!("### Variable: `${var_name}`\n\nVariable usage in this operation");
+            return Some(HoverInfo::new(content, None));
+        };
+
+        let mut content =
+            format!("### Variable: `${var_name}`\n\n```graphql\n${var_name}: {type_name}");
+        if let Some(default_value) = default_value {
+            content.push_str(&format!(" = {default_value}"));
+        }
+        content.push_str("\n```");
+
         Some(HoverInfo::new(content, None))
     }
 
     /// Generate hover content for a fragment spread
-    fn generate_fragment_spread_hover(fragment_name: &str) -> Option<HoverInfo> {
-        let content =
-            format!("### Fragment Spread: `{fragment_name}`\n\nReferences the fragment definition");
+    ///
+    /// When the fragment is in the document index, shows its type condition and the file
+    /// and line where it is defined, listing every definition if there are several.
+    fn generate_fragment_spread_hover(
+        fragment_name: &str,
+        document_index: Option<&DocumentIndex>,
+    ) -> Option<HoverInfo> {
+        let mut content = format!("### Fragment Spread: `{fragment_name}`\n\n");
+
+        let definitions: &[crate::FragmentInfo] = document_index
+            .and_then(|index| index.get_fragments_by_name(fragment_name))
+            .map_or(&[], Vec::as_slice);
+
+        match definitions {
+            [] => content.push_str("References the fragment definition"),
+            [fragment] => {
+                content.push_str(&format!(
+                    "**Type condition:** `{}`\n\n",
+                    fragment.type_condition
+                ));
+                if let Some(source) =
+                    document_index.and_then(|index| index.get_fragment_source(fragment))
+                {
+                    content.push_str(&format!("```graphql\n{source}\n```\n\n"));
+                }
+                content.push_str(&format!(
+                    "**Defined in:** `{}:{}`",
+                    fragment.file_path,
+                    fragment.line + 1
+                ));
+            }
+            fragments => {
+                content.push_str("**Defined in:**\n");
+                for fragment in fragments {
+                    content.push_str(&format!(
+                        "- `{}:{}` on `{}`\n",
+                        fragment.file_path,
+                        fragment.line + 1,
+                        fragment.type_condition
+                    ));
+                }
+            }
+        }
+
         Some(HoverInfo::new(content, None))
     }
 
@@ -1113,7 +1296,7 @@ impl HoverProvider {
         type_condition: &str,
     ) -> Option<HoverInfo> {
         let content = format!(
-            "### Fragment: `{fragment_name}`\n**Type condition:** `{type_condition}`\n\nFragment definition"
+            "### Fragment: `{fragment_name}`\n**Type condition:** `{type_condition}`\n\n```graphql\nfragment {fragment_name} on {type_condition}\n```"
         );
         Some(HoverInfo::new(content, None))
     }
@@ -1142,14 +1325,60 @@ impl HoverProvider {
         Some(HoverInfo::new(content, None))
     }
 
+    /// Generate hover content for a directive argument
+    fn generate_directive_argument_hover(
+        argument_name: &str,
+        directive_name: &str,
+        schema_index: &SchemaIndex,
+    ) -> Option<HoverInfo> {
+        let directive_info = schema_index.get_directive(directive_name)?;
+        let arg_info = directive_info
+            .arguments
+            .iter()
+            .find(|a| a.name == argument_name)?;
+
+        let mut content = format!(
+            "### Directive Argument: `{argument_name}: {}`\n\n",
+            arg_info.type_name
+        );
+
+        if let Some(ref description) = arg_info.description {
+            content.push_str(description);
+            content.push_str("\n\n");
+        }
+
+        if let Some(ref default) = arg_info.default_value {
+            content.push_str(&format!("**Default value:** `{default}`\n\n"));
+        }
+
+        content.push_str(&format!("**Defined in:** `@{directive_name}` directive"));
+
+        Some(HoverInfo::new(content, None))
+    }
+
     /// Generate hover content for an enum value
-    const fn generate_enum_value_hover(
-        _value_name: &str,
-        _enum_type: Option<&str>,
-        _schema_index: &SchemaIndex,
+    fn generate_enum_value_hover(
+        value_name: &str,
+        enum_type: Option<&str>,
+        schema_index: &SchemaIndex,
     ) -> Option<HoverInfo> {
-        // TODO: Implement enum value hover
-        None
+        let enum_type = enum_type?;
+        let value_info = schema_index
+            .get_enum_values(enum_type)
+            .into_iter()
+            .find(|value| value.name == value_name)?;
+
+        let mut content = deprecation_notice(value_info.deprecated.as_deref());
+        content.push_str(&format!("### Enum Value: `{value_name}`\n\n"));
+
+        if let Some(ref description) = value_info.description {
+            content.push_str(description);
+            content.push_str("\n\n");
+        }
+
+        content.push_str(&format!("**Defined in:** `{enum_type}` enum"));
+
+        Some(HoverInfo::new(content, None))
     }
 
     /// Generate hover content for an operation
@@ -1184,6 +1413,14 @@ impl HoverProvider {
     }
 }
 
+/// Markdown opening a hover with a warning that the element is deprecated, or nothing if it
+/// isn't
+fn deprecation_notice(reason: Option<&str>) -> String {
+    reason.map_or_else(String::new, |reason| {
+        format!("⚠️ **Deprecated:** {reason}\n\n---\n\n")
+    })
+}
+
 impl Default for HoverProvider {
     fn default() -> Self {
         Self::new()
@@ -1255,7 +1492,7 @@ mod tests {
                 """
                 Get all users
                 """
-                users: [User!]!
+                users(status: Status): [User!]!
             }
 
             type Mutation {
@@ -1265,10 +1502,20 @@ mod tests {
             enum Status {
                 ACTIVE
                 INACTIVE
-                PENDING
+                PENDING @deprecated(reason: "Treated as INACTIVE")
             }
 
-            directive @auth(requires: String!) on FIELD_DEFINITION
+            enum Role {
+                ADMIN
+                USER
+            }
+
+            directive @auth(
+                """
+                The role required to access the field
+                """
+                requires: Role!
+            ) on FIELD_DEFINITION | FIELD
 
             "#,
         )
@@ -1327,8 +1574,67 @@ query GetUser($id: ID!) {
 
         let info = hover_info.unwrap();
         assert!(info.contents.contains("Field: `email`"));
-        assert!(info.contents.contains("Deprecated"));
-        assert!(info.contents.contains("Use emailAddress instead"));
+        assert!(info
+            .contents
+            .starts_with("⚠️ **Deprecated:** Use emailAddress instead\n\n"));
+
+        // Fields that aren't deprecated have no notice
+        let info = provider
+            .hover(
+                document,
+                Position {
+                    line: 2,
+                    character: 4,
+                },
+                &schema,
+            )
+            .unwrap();
+        assert!(info.contents.starts_with("### Field: `user`"));
+        assert!(!info.contents.contains("Deprecated"));
+    }
+
+    #[test]
+    fn test_hover_on_deprecated_enum_value() {
+        let schema = create_test_schema();
+        let provider = HoverProvider::new();
+
+        let document = r"
+query Users {
+    users(status: PENDING) {
+        id
+    }
+}
+";
+
+        // Hover on "PENDING"
+        let info = provider
+            .hover(
+                document,
+                Position {
+                    line: 2,
+                    character: 20,
+                },
+                &schema,
+            )
+            .unwrap();
+        assert!(info
+            .contents
+            .starts_with("⚠️ **Deprecated:** Treated as INACTIVE\n\n"));
+        assert!(info.contents.contains("Enum Value: `PENDING`"));
+        assert!(info.contents.contains("**Defined in:** `Status` enum"));
+
+        let document = "query Users {\n    users(status: ACTIVE) {\n        id\n    }\n}\n";
+        let info = provider
+            .hover(
+                document,
+                Position {
+                    line: 1,
+                    character: 20,
+                },
+                &schema,
+            )
+            .unwrap();
+        assert!(info.contents.starts_with("### Enum Value: `ACTIVE`"));
     }
 
     #[test]
@@ -1392,6 +1698,36 @@ query GetUser($id: ID!) {
         assert!(info.contents.contains("Required"));
     }
 
+    #[test]
+    fn test_hover_on_directive_argument() {
+        let schema = create_test_schema();
+        let provider = HoverProvider::new();
+
+        let document = r"
+query GetUser {
+    user(id: 1) {
+        email @auth(requires: ADMIN)
+    }
+}
+";
+
+        // Hover on "requires" argument
+        let position = Position {
+            line: 3,
+            character: 22,
+        };
+
+        let hover_info = provider.hover(document, position, &schema);
+        assert!(hover_info.is_some());
+
+        let info = hover_info.unwrap();
+        assert!(info.contents.contains("`requires: Role!`"));
+        assert!(info
+            .contents
+            .contains("The role required to access the field"));
+        assert!(info.contents.contains("`@auth` directive"));
+    }
+
     #[test]
     fn test_hover_on_type() {
         let schema = create_test_schema();
@@ -1444,6 +1780,47 @@ query GetUser($userId: ID!) {
 
         let info = hover_info.unwrap();
         assert!(info.contents.contains("Variable: `$userId`"));
+        assert!(info.contents.contains("$userId: ID!"));
+    }
+
+    #[test]
+    fn test_hover_on_variable_usage_shows_type_and_default() {
+        let schema = create_test_schema();
+        let provider = HoverProvider::new();
+
+        let document = r#"
+query GetUser($userId: ID! = "1") {
+    user(id: $userId) @include(if: $userId) {
+        id
+    }
+}
+"#;
+
+        // Hover on "$userId" in the field argument
+        let info = provider
+            .hover(
+                document,
+                Position {
+                    line: 2,
+                    character: 15,
+                },
+                &schema,
+            )
+            .unwrap();
+        assert!(info.contents.contains("$userId: ID! = \"1\""));
+
+        // Hover on "$userId" in the directive argument
+        let info = provider
+            .hover(
+                document,
+                Position {
+                    line: 2,
+                    character: 37,
+                },
+                &schema,
+            )
+            .unwrap();
+        assert!(info.contents.contains("$userId: ID! = \"1\""));
     }
 
     #[test]
@@ -1472,6 +1849,97 @@ fragment UserFields on User {
         assert!(info.contents.contains("User"));
     }
 
+    #[test]
+    fn test_hover_on_fragment_spread_shows_defining_file() {
+        let schema = create_test_schema();
+        let provider = HoverProvider::new();
+
+        let mut document_index = DocumentIndex::new();
+        document_index.add_fragment(
+            "UserFields".to_string(),
+            crate::FragmentInfo {
+                name: "UserFields".to_string(),
+                type_condition: "User".to_string(),
+                file_path: "/path/to/fragments.graphql".to_string(),
+                line: 4,
+                column: 9,
+            },
+        );
+
+        let document = r#"
+query GetUser {
+    user(id: "1") {
+        ...UserFields
+    }
+}
+"#;
+
+        // Hover on "UserFields" in the spread
+        let position = Position {
+            line: 3,
+            character: 13,
+        };
+
+        let info = provider
+            .hover_with_ast(
+                document,
+                position,
+                &schema,
+                None,
+                Some(&document_index),
+                None,
+            )
+            .expect("Should hover on the fragment spread");
+
+        assert!(info.contents.contains("Fragment Spread: `UserFields`"));
+        assert!(info.contents.contains("**Type condition:** `User`"));
+        assert!(info.contents.contains("`/path/to/fragments.graphql:5`"));
+    }
+
+    #[test]
+    fn test_hover_on_fragment_spread_shows_selection_set() {
+        let schema = create_test_schema();
+        let provider = HoverProvider::new();
+
+        let fragments = "query Other {\n  users {\n    id\n  }\n}\n\nfragment UserFields on User {\n  id\n  name\n}\n";
+        let mut document_index = DocumentIndex::new();
+        document_index.cache_ast(
+            "fragments.graphql".to_string(),
+            std::sync::Arc::new(Parser::new(fragments).parse()),
+        );
+        document_index.add_fragment(
+            "UserFields".to_string(),
+            crate::FragmentInfo {
+                name: "UserFields".to_string(),
+                type_condition: "User".to_string(),
+                file_path: "fragments.graphql".to_string(),
+                line: 6,
+                column: 9,
+            },
+        );
+
+        let document = "query GetUser {\n    user(id: \"1\") {\n        ...UserFields\n    }\n}\n";
+
+        let info = provider
+            .hover_with_ast(
+                document,
+                Position {
+                    line: 2,
+                    character: 13,
+                },
+                &schema,
+                None,
+                Some(&document_index),
+                None,
+            )
+            .expect("Should hover on the fragment spread");
+
+        assert!(info
+            .contents
+            .contains("```graphql\nfragment UserFields on User {\n  id\n  name\n}\n```"));
+        assert!(info.contents.contains("`fragments.graphql:7`"));
+    }
+
     #[test]
     fn test_hover_on_operation() {
         let schema = create_test_schema();
@@ -1651,4 +2119,24 @@ type User {
         assert!(info.contents.contains("Type: `Post`"));
         assert!(info.contents.contains("Object"));
     }
+
+    #[test]
+    fn test_hover_with_surrogate_pairs_uses_utf16_columns() {
+        let schema = create_test_schema();
+        let provider = HoverProvider::new();
+
+        // Each 😀 is two UTF-16 code units but a single char
+        let document = r#"query GetUser($s: String = "😀😀") { user(id: "1") { name id } }"#;
+
+        // UTF-16 column of the "e" in "name"; counting chars would land on "id"
+        let position = Position {
+            line: 0,
+            character: 56,
+        };
+
+        let info = provider
+            .hover(document, position, &schema)
+            .expect("Should find hover info");
+        assert!(info.contents.contains("Field: `name`"));
+    }
 }
@@ -1,5 +1,6 @@
+use crate::completion::FieldCompletionCache;
 use apollo_compiler::{
-    schema::{ExtendedType, FieldDefinition},
+    schema::{DirectiveDefinition, ExtendedType, FieldDefinition, InputValueDefinition},
     Schema,
 };
 use std::sync::Arc;
@@ -11,6 +12,60 @@ use std::sync::Arc;
 #[derive(Debug, Clone)]
 pub struct SchemaIndex {
     schema: Arc<Schema>,
+    /// Field completions by parent type, built lazily; a new index starts empty, so a
+    /// schema change invalidates it
+    field_completions: Arc<FieldCompletionCache>,
+    /// Errors found while building the schema, which is then only a partial schema
+    build_errors: Arc<Vec<SchemaBuildError>>,
+}
+
+/// An error found while building the schema, with the schema file it applies to
+#[derive(Debug, Clone)]
+pub struct SchemaBuildError {
+    /// Path of the schema file, or `None` if the error has no location
+    pub file_path: Option<String>,
+    pub diagnostic: crate::Diagnostic,
+}
+
+/// Convert apollo-compiler schema errors, keeping the file each was found in
+pub(crate) fn schema_build_errors(
+    errors: &apollo_compiler::validation::DiagnosticList,
+) -> Vec<SchemaBuildError> {
+    use apollo_compiler::diagnostic::ToCliReport;
+
+    errors
+        .iter()
+        .map(|diag| {
+            let file_path = diag
+                .error
+                .location()
+                .and_then(|location| diag.sources.get(&location.file_id()))
+                .map(|file| file.path().display().to_string());
+            let start = diag.line_column_range().map(|range| range.start);
+            let end = diag.line_column_range().map(|range| range.end);
+            // apollo-compiler uses 1-based positions, we use 0-based
+            let range = crate::Range {
+                start: crate::Position {
+                    line: start.map_or(0, |start| start.line.saturating_sub(1)),
+                    character: start.map_or(0, |start| start.column.saturating_sub(1)),
+                },
+                end: crate::Position {
+                    line: end.map_or(0, |end| end.line.saturating_sub(1)),
+                    character: end.map_or(0, |end| end.column.saturating_sub(1)),
+                },
+            };
+            SchemaBuildError {
+                file_path,
+                diagnostic: crate::Diagnostic::error(range, diag.error.to_string())
+                    .with_source("graphql"),
+            }
+        })
+        .collect()
+}
+
+/// Whether a schema source was loaded from a URL rather than a file
+fn is_remote_source(path: &str) -> bool {
+    path.starts_with("http://") || path.starts_with("https://")
 }
 
 impl Default for SchemaIndex {
@@ -29,24 +84,69 @@ impl SchemaIndex {
         });
         Self {
             schema: Arc::new(schema),
+            field_completions: Arc::default(),
+            build_errors: Arc::default(),
         }
     }
 
     /// Build index from multiple schema files using apollo-compiler
     ///
     /// Uses `SchemaBuilder` to parse each file separately, preserving source locations.
+    /// All files are parsed before the schema is built, so `extend type` in one file can
+    /// refer to a type defined in another.
     ///
     /// # Returns
     ///
-    /// A `SchemaIndex` with the parsed schema, or an empty schema if parsing fails.
+    /// A `SchemaIndex` with the built schema. If building fails, the index holds the
+    /// partial schema apollo-compiler recovered, and [`Self::build_errors`] reports why.
     #[must_use]
     pub fn from_schema_files(schema_files: Vec<(String, String)>) -> Self {
-        use apollo_compiler::schema::SchemaBuilder;
-
         if schema_files.is_empty() {
             return Self::new();
         }
 
+        match Self::build_schema(schema_files) {
+            Ok(schema) => Self {
+                schema: Arc::new(schema),
+                field_completions: Arc::default(),
+                build_errors: Arc::default(),
+            },
+            Err(with_errors) => {
+                tracing::warn!("Schema built with errors: {}", with_errors.errors);
+                Self {
+                    build_errors: Arc::new(schema_build_errors(&with_errors.errors)),
+                    schema: Arc::new(with_errors.partial),
+                    field_completions: Arc::default(),
+                }
+            }
+        }
+    }
+
+    /// Build index from multiple schema files, returning `None` if the schema fails to build
+    ///
+    /// Unlike [`Self::from_schema_files`], this does not fall back to a partial schema, so
+    /// callers can tell whether the result fully reflects the given files.
+    #[must_use]
+    pub fn try_from_schema_files(schema_files: Vec<(String, String)>) -> Option<Self> {
+        match Self::build_schema(schema_files) {
+            Ok(schema) => Some(Self {
+                schema: Arc::new(schema),
+                field_completions: Arc::default(),
+                build_errors: Arc::default(),
+            }),
+            Err(with_errors) => {
+                tracing::warn!("Failed to build schema: {}", with_errors.errors);
+                None
+            }
+        }
+    }
+
+    /// Parse every schema file into one builder, then build the schema
+    fn build_schema(
+        schema_files: Vec<(String, String)>,
+    ) -> Result<Schema, apollo_compiler::validation::WithErrors<Schema>> {
+        use apollo_compiler::schema::SchemaBuilder;
+
         let mut builder = SchemaBuilder::new();
 
         // Parse each file separately so apollo-compiler tracks sources correctly
@@ -54,16 +154,29 @@ impl SchemaIndex {
             builder = builder.parse(content, path);
         }
 
-        // Build the schema
-        match builder.build() {
-            Ok(schema) => Self {
-                schema: Arc::new(schema),
-            },
-            Err(diagnostics) => {
-                tracing::warn!("Failed to build schema: {:?}", diagnostics);
-                Self::new()
-            }
-        }
+        builder.build()
+    }
+
+    /// Errors found while building the schema
+    ///
+    /// Empty when the schema built cleanly. Otherwise the index holds a partial schema,
+    /// and each error names the schema file it was found in.
+    #[must_use]
+    pub fn build_errors(&self) -> &[SchemaBuildError] {
+        &self.build_errors
+    }
+
+    /// Build index from schema files previously stored by [`crate::SchemaCache`]
+    ///
+    /// The files were stored because they built cleanly, so each keeps its original path
+    /// and the result is not checked again.
+    #[must_use]
+    pub(crate) fn from_cached_files(schema_files: Vec<(String, String)>) -> Option<Self> {
+        Self::build_schema(schema_files).ok().map(|schema| Self {
+            schema: Arc::new(schema),
+            field_completions: Arc::default(),
+            build_errors: Arc::default(),
+        })
     }
 
     /// Build index from schema string using apollo-compiler
@@ -73,18 +186,40 @@ impl SchemaIndex {
     ///
     /// # Returns
     ///
-    /// A `SchemaIndex` with the parsed schema, or an empty schema if parsing fails.
+    /// A `SchemaIndex` with the parsed schema, or the partial schema apollo-compiler
+    /// recovered if building fails.
     #[must_use]
     pub fn from_schema(schema_str: &str) -> Self {
         Self::from_schema_files(vec![("schema.graphql".to_string(), schema_str.to_string())])
     }
 
+    /// Cache of field completions by parent type for this schema
+    #[must_use]
+    pub(crate) fn field_completions(&self) -> &FieldCompletionCache {
+        &self.field_completions
+    }
+
     /// Get the underlying apollo-compiler Schema
     #[must_use]
     pub fn schema(&self) -> &Schema {
         &self.schema
     }
 
+    /// Name of the root type for operations of `operation_type`
+    ///
+    /// Comes from the merged schema definition, so roots declared by `extend schema` and
+    /// implicit `Query`/`Mutation`/`Subscription` roots are both included.
+    #[must_use]
+    pub fn root_type(&self, operation_type: OperationType) -> Option<&str> {
+        let schema_definition = &self.schema.schema_definition;
+        let root = match operation_type {
+            OperationType::Query => schema_definition.query.as_ref(),
+            OperationType::Mutation => schema_definition.mutation.as_ref(),
+            OperationType::Subscription => schema_definition.subscription.as_ref(),
+        };
+        root.map(|name| name.as_str())
+    }
+
     /// Get a type by name
     #[must_use]
     pub fn get_type(&self, name: &str) -> Option<TypeInfo> {
@@ -135,18 +270,7 @@ impl SchemaIndex {
     #[must_use]
     pub fn get_directive(&self, name: &str) -> Option<DirectiveInfo> {
         let directive = self.schema.directive_definitions.get(name)?;
-        Some(DirectiveInfo {
-            name: directive.name.to_string(),
-            description: directive
-                .description
-                .as_ref()
-                .map(std::string::ToString::to_string),
-            locations: directive
-                .locations
-                .iter()
-                .map(|loc| format!("{loc:?}"))
-                .collect(),
-        })
+        Some(DirectiveInfo::from_directive_definition(directive))
     }
 
     /// Find the location of a field definition in the schema source
@@ -184,15 +308,7 @@ impl SchemaIndex {
         let line_col_range = name.line_column_range(&self.schema.sources)?;
 
         // Get the file path from the name's location
-        let location = name.location()?;
-        let file_id = location.file_id();
-        let file_path = self
-            .schema
-            .sources
-            .get(&file_id)?
-            .path()
-            .to_string_lossy()
-            .to_string();
+        let file_path = self.local_source_path(name.location()?)?;
 
         // Convert to 0-indexed (apollo-compiler uses 1-indexed)
         let result_line = line_col_range.start.line.saturating_sub(1);
@@ -222,6 +338,7 @@ impl SchemaIndex {
     /// Supports all GraphQL type kinds: Object, Interface, Union, Enum, Scalar, and `InputObject`.
     ///
     /// The location points to the start of the type name, not the description (if any).
+    /// Returns `None` for types from a schema loaded from a URL, which has no local file.
     #[must_use]
     pub fn find_type_definition(&self, type_name: &str) -> Option<TypeDefinitionLocation> {
         use apollo_compiler::schema::ExtendedType;
@@ -244,15 +361,7 @@ impl SchemaIndex {
         let line_col_range = name.line_column_range(&self.schema.sources)?;
 
         // Get the file path from the name's location
-        let location = name.location()?;
-        let file_id = location.file_id();
-        let file_path = self
-            .schema
-            .sources
-            .get(&file_id)?
-            .path()
-            .to_string_lossy()
-            .to_string();
+        let file_path = self.local_source_path(name.location()?)?;
 
         // Convert to 0-indexed (apollo-compiler uses 1-indexed)
         let result_line = line_col_range.start.line.saturating_sub(1);
@@ -273,6 +382,142 @@ impl SchemaIndex {
         })
     }
 
+    /// Find where a name in the schema is defined
+    ///
+    /// Returns the file path and the 0-indexed line and column of the name, or `None` for
+    /// names without a local schema file.
+    #[must_use]
+    pub(crate) fn name_location(
+        &self,
+        name: &apollo_compiler::Name,
+    ) -> Option<(String, usize, usize)> {
+        let line_col_range = name.line_column_range(&self.schema.sources)?;
+        let file_path = self.local_source_path(name.location()?)?;
+
+        Some((
+            file_path,
+            line_col_range.start.line.saturating_sub(1),
+            line_col_range.start.column.saturating_sub(1),
+        ))
+    }
+
+    /// Path of the local schema file a definition was parsed from
+    ///
+    /// Returns `None` for schemas loaded from a URL, which have no file an editor could
+    /// open.
+    fn local_source_path(&self, location: apollo_compiler::parser::SourceSpan) -> Option<String> {
+        let path = self
+            .schema
+            .sources
+            .get(&location.file_id())?
+            .path()
+            .to_string_lossy()
+            .to_string();
+
+        if is_remote_source(&path) {
+            tracing::debug!("Definition is in remote schema {path}, which has no local file");
+            return None;
+        }
+
+        Some(path)
+    }
+
+    /// Names of the types that implement an interface or belong to a union
+    ///
+    /// For an interface this includes both object types and interfaces that implement it.
+    /// Returns an empty list for concrete or unknown types.
+    #[must_use]
+    pub(crate) fn implementation_names(&self, type_name: &str) -> Vec<String> {
+        match self.schema.types.get(type_name) {
+            Some(ExtendedType::Union(union)) => {
+                union.members.iter().map(ToString::to_string).collect()
+            }
+            Some(ExtendedType::Interface(_)) => self
+                .schema
+                .types
+                .iter()
+                .filter(|(_, ty)| match ty {
+                    ExtendedType::Object(obj) => obj
+                        .implements_interfaces
+                        .iter()
+                        .any(|iface| iface.as_str() == type_name),
+                    ExtendedType::Interface(iface) => iface
+                        .implements_interfaces
+                        .iter()
+                        .any(|iface| iface.as_str() == type_name),
+                    _ => false,
+                })
+                .map(|(name, _)| name.to_string())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Names of the object types that implement an interface or belong to a union
+    ///
+    /// Unlike [`Self::implementation_names`], interfaces implementing an interface are left
+    /// out, since only object types can be the concrete type of a value. Returns an empty
+    /// list for object, unknown, and other non-abstract types.
+    #[must_use]
+    pub fn possible_types(&self, abstract_type: &str) -> Vec<String> {
+        self.implementation_names(abstract_type)
+            .into_iter()
+            .filter(|name| {
+                matches!(
+                    self.schema.types.get(name.as_str()),
+                    Some(ExtendedType::Object(_))
+                )
+            })
+            .collect()
+    }
+
+    /// Names of the interfaces an object or interface type declares it implements
+    ///
+    /// Returns an empty list for unknown types and types that can't implement interfaces.
+    #[must_use]
+    pub fn implemented_interfaces(&self, type_name: &str) -> Vec<String> {
+        match self.schema.types.get(type_name) {
+            Some(ExtendedType::Object(obj)) => obj
+                .implements_interfaces
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            Some(ExtendedType::Interface(iface)) => iface
+                .implements_interfaces
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Names of the object types a value of `type_name` can be at runtime
+    ///
+    /// An object type can only be itself; a union or interface can be any of its
+    /// [possible types](Self::possible_types).
+    #[must_use]
+    pub(crate) fn possible_object_types(
+        &self,
+        type_name: &str,
+    ) -> std::collections::HashSet<String> {
+        match self.schema.types.get(type_name) {
+            Some(ExtendedType::Object(_)) => std::iter::once(type_name.to_string()).collect(),
+            _ => self.possible_types(type_name).into_iter().collect(),
+        }
+    }
+
+    /// Find the definitions of the types that implement an interface or belong to a union
+    ///
+    /// Returns the location of each implementing or member type's name. Returns an empty
+    /// list for concrete or unknown types.
+    #[must_use]
+    pub fn find_implementations(&self, type_name: &str) -> Vec<TypeDefinitionLocation> {
+        self.implementation_names(type_name)
+            .iter()
+            .filter_map(|name| self.find_type_definition(name))
+            .collect()
+    }
+
     /// Find the location of an argument definition in a field
     #[must_use]
     pub fn find_argument_definition(
@@ -300,15 +545,7 @@ impl SchemaIndex {
             .find(|a| a.name.as_str() == argument_name)?;
 
         let line_col_range = arg.name.line_column_range(&self.schema.sources)?;
-        let location = arg.name.location()?;
-        let file_id = location.file_id();
-        let file_path = self
-            .schema
-            .sources
-            .get(&file_id)?
-            .path()
-            .to_string_lossy()
-            .to_string();
+        let file_path = self.local_source_path(arg.name.location()?)?;
 
         let result_line = line_col_range.start.line.saturating_sub(1);
         let result_col = line_col_range.start.column.saturating_sub(1);
@@ -339,15 +576,7 @@ impl SchemaIndex {
         let value = enum_def.values.get(enum_value)?;
 
         let line_col_range = value.value.line_column_range(&self.schema.sources)?;
-        let location = value.value.location()?;
-        let file_id = location.file_id();
-        let file_path = self
-            .schema
-            .sources
-            .get(&file_id)?
-            .path()
-            .to_string_lossy()
-            .to_string();
+        let file_path = self.local_source_path(value.value.location()?)?;
 
         let result_line = line_col_range.start.line.saturating_sub(1);
         let result_col = line_col_range.start.column.saturating_sub(1);
@@ -369,15 +598,7 @@ impl SchemaIndex {
         let directive = self.schema.directive_definitions.get(directive_name)?;
 
         let line_col_range = directive.name.line_column_range(&self.schema.sources)?;
-        let location = directive.name.location()?;
-        let file_id = location.file_id();
-        let file_path = self
-            .schema
-            .sources
-            .get(&file_id)?
-            .path()
-            .to_string_lossy()
-            .to_string();
+        let file_path = self.local_source_path(directive.name.location()?)?;
 
         let result_line = line_col_range.start.line.saturating_sub(1);
         let result_col = line_col_range.start.column.saturating_sub(1);
@@ -405,15 +626,7 @@ impl SchemaIndex {
             .find(|a| a.name.as_str() == argument_name)?;
 
         let line_col_range = arg.name.line_column_range(&self.schema.sources)?;
-        let location = arg.name.location()?;
-        let file_id = location.file_id();
-        let file_path = self
-            .schema
-            .sources
-            .get(&file_id)?
-            .path()
-            .to_string_lossy()
-            .to_string();
+        let file_path = self.local_source_path(arg.name.location()?)?;
 
         let result_line = line_col_range.start.line.saturating_sub(1);
         let result_col = line_col_range.start.column.saturating_sub(1);
@@ -442,18 +655,7 @@ impl SchemaIndex {
         self.schema
             .directive_definitions
             .iter()
-            .map(|(_, directive)| DirectiveInfo {
-                name: directive.name.to_string(),
-                description: directive
-                    .description
-                    .as_ref()
-                    .map(std::string::ToString::to_string),
-                locations: directive
-                    .locations
-                    .iter()
-                    .map(|loc| format!("{loc:?}"))
-                    .collect(),
-            })
+            .map(|(_, directive)| DirectiveInfo::from_directive_definition(directive))
             .collect()
     }
 
@@ -665,15 +867,7 @@ impl FieldInfo {
         let arguments = field
             .arguments
             .iter()
-            .map(|arg| ArgumentInfo {
-                name: arg.name.to_string(),
-                type_name: arg.ty.to_string(),
-                description: arg
-                    .description
-                    .as_ref()
-                    .map(std::string::ToString::to_string),
-                default_value: arg.default_value.as_ref().map(ToString::to_string),
-            })
+            .map(|arg| ArgumentInfo::from_input_value_definition(arg))
             .collect();
 
         Self {
@@ -698,12 +892,49 @@ pub struct ArgumentInfo {
     pub default_value: Option<String>,
 }
 
+impl ArgumentInfo {
+    fn from_input_value_definition(arg: &InputValueDefinition) -> Self {
+        Self {
+            name: arg.name.to_string(),
+            type_name: arg.ty.to_string(),
+            description: arg
+                .description
+                .as_ref()
+                .map(std::string::ToString::to_string),
+            default_value: arg.default_value.as_ref().map(ToString::to_string),
+        }
+    }
+}
+
 /// Directive information extracted from schema
 #[derive(Debug, Clone)]
 pub struct DirectiveInfo {
     pub name: String,
     pub description: Option<String>,
     pub locations: Vec<String>,
+    pub arguments: Vec<ArgumentInfo>,
+}
+
+impl DirectiveInfo {
+    fn from_directive_definition(directive: &DirectiveDefinition) -> Self {
+        Self {
+            name: directive.name.to_string(),
+            description: directive
+                .description
+                .as_ref()
+                .map(std::string::ToString::to_string),
+            locations: directive
+                .locations
+                .iter()
+                .map(|loc| format!("{loc:?}"))
+                .collect(),
+            arguments: directive
+                .arguments
+                .iter()
+                .map(|arg| ArgumentInfo::from_input_value_definition(arg))
+                .collect(),
+        }
+    }
 }
 
 /// Enum value information extracted from schema
@@ -732,6 +963,14 @@ pub struct ExtractedBlock {
     pub parsed: std::sync::Arc<apollo_parser::SyntaxTree>,
 }
 
+/// Path of a document key, which is either a file path or a `file://` URI
+///
+/// Open documents are keyed by URI while indexed files are keyed by path, so keys must be
+/// normalized before comparing them.
+pub(crate) fn uri_to_file_path(file_path: &str) -> &str {
+    file_path.strip_prefix("file://").unwrap_or(file_path)
+}
+
 /// Index of GraphQL documents (operations and fragments)
 #[derive(Debug, Default)]
 pub struct DocumentIndex {
@@ -754,6 +993,9 @@ pub struct DocumentIndex {
     /// Cached line break indices for fast position<->offset conversion
     /// Provides O(1) `position_to_offset` instead of O(N) character iteration
     pub line_indices: std::collections::HashMap<String, std::sync::Arc<crate::LineIndex>>,
+
+    /// Unit the line indices count `Position::character` columns in
+    pub position_encoding: crate::PositionEncoding,
 }
 
 #[derive(Debug, Clone)]
@@ -767,13 +1009,25 @@ pub struct OperationInfo {
     pub column: usize,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OperationType {
     Query,
     Mutation,
     Subscription,
 }
 
+impl OperationType {
+    /// Type of an operation definition, where the `{ ... }` shorthand is a query
+    #[must_use]
+    pub fn of(op: &apollo_parser::cst::OperationDefinition) -> Self {
+        match op.operation_type() {
+            Some(op_type) if op_type.mutation_token().is_some() => Self::Mutation,
+            Some(op_type) if op_type.subscription_token().is_some() => Self::Subscription,
+            _ => Self::Query,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct FragmentInfo {
     pub name: String,
@@ -827,6 +1081,34 @@ impl DocumentIndex {
         self.fragments.get(name).and_then(|frags| frags.first())
     }
 
+    /// Get the source text of a fragment's definition, from its document's cached AST
+    ///
+    /// Returns `None` if the document hasn't been parsed and cached.
+    #[must_use]
+    pub fn get_fragment_source(&self, fragment: &FragmentInfo) -> Option<String> {
+        use apollo_parser::cst::{self, CstNode};
+
+        // The block containing the definition, or the whole document for pure GraphQL files
+        let ast = self
+            .get_extracted_blocks(&fragment.file_path)
+            .and_then(|blocks| {
+                blocks.iter().find(|block| {
+                    block.start_line <= fragment.line && fragment.line <= block.end_line
+                })
+            })
+            .map(|block| std::sync::Arc::clone(&block.parsed))
+            .or_else(|| self.get_ast(&fragment.file_path))?;
+
+        ast.document().definitions().find_map(|definition| {
+            let cst::Definition::FragmentDefinition(definition) = definition else {
+                return None;
+            };
+            let name = definition.fragment_name()?.name()?;
+            (name.text() == fragment.name.as_str())
+                .then(|| definition.syntax().text().to_string().trim().to_string())
+        })
+    }
+
     /// Cache a parsed AST for a document
     pub fn cache_ast(&mut self, file_path: String, ast: std::sync::Arc<apollo_parser::SyntaxTree>) {
         self.parsed_asts.insert(file_path, ast);
@@ -870,6 +1152,28 @@ impl DocumentIndex {
         self.line_indices.get(file_path).cloned()
     }
 
+    /// Get the cached `LineIndex` of a file if it indexes `source`, or build one for `source`
+    ///
+    /// The cached index is only used if it was built from exactly `source`. An embedded
+    /// GraphQL block, or content that changed since the file was indexed, gets an index of
+    /// its own, counting columns in `position_encoding`.
+    #[must_use]
+    pub fn line_index_for(
+        &self,
+        file_path: Option<&str>,
+        source: &str,
+    ) -> std::sync::Arc<crate::LineIndex> {
+        file_path
+            .and_then(|path| self.get_line_index(path))
+            .filter(|line_index| line_index.indexes(source))
+            .unwrap_or_else(|| {
+                std::sync::Arc::new(crate::LineIndex::with_encoding(
+                    source,
+                    self.position_encoding,
+                ))
+            })
+    }
+
     /// Remove cached line index for a document
     pub fn remove_line_index(&mut self, file_path: &str) {
         self.line_indices.remove(file_path);
@@ -1024,6 +1328,41 @@ impl DocumentIndex {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_schema_files_with_build_error_keep_partial_schema() {
+        let index = SchemaIndex::from_schema_files(vec![
+            (
+                "base.graphql".to_string(),
+                "type Query { user: User }\ntype User { id: ID! }".to_string(),
+            ),
+            (
+                "extensions.graphql".to_string(),
+                "extend type User { name: String }".to_string(),
+            ),
+            (
+                "broken.graphql".to_string(),
+                "# Extends a type no file defines\nextend type Missing { id: ID! }".to_string(),
+            ),
+        ]);
+
+        // Definitions and cross-file extensions survive the error in another file
+        let fields = index.get_fields("User").expect("User fields should exist");
+        let field_names: Vec<_> = fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(field_names, vec!["id", "name"]);
+        assert!(index.get_type("Query").is_some());
+
+        let errors = index.build_errors();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].file_path.as_deref(), Some("broken.graphql"));
+        assert_eq!(errors[0].diagnostic.range.start.line, 1);
+
+        let clean = SchemaIndex::from_schema_files(vec![(
+            "base.graphql".to_string(),
+            "type Query { user: ID }".to_string(),
+        )]);
+        assert!(clean.build_errors().is_empty());
+    }
+
     #[test]
     fn test_parse_simple_object_type() {
         let schema = r"
@@ -1509,8 +1848,12 @@ mod tests {
 
         let index = SchemaIndex::from_schema(schema);
 
-        // Should return empty schema on syntax errors
-        assert!(index.get_type("User").is_none());
+        // Syntax errors are reported rather than silently emptying the schema
+        assert!(!index.build_errors().is_empty());
+        assert_eq!(
+            index.build_errors()[0].file_path.as_deref(),
+            Some("schema.graphql")
+        );
     }
 
     #[test]
@@ -1610,6 +1953,156 @@ type Query {
         );
     }
 
+    #[test]
+    fn test_remote_schema_definitions_have_no_location() {
+        let index = SchemaIndex::from_schema_files(vec![
+            (
+                "https://api.example.com/graphql".to_string(),
+                "type Query { user(id: ID!): User }\ntype User { id: ID! }".to_string(),
+            ),
+            (
+                "extensions.graphql".to_string(),
+                "extend type User { nickname: String }".to_string(),
+            ),
+        ]);
+
+        // The types are still known, just not navigable
+        assert!(index.get_type("User").is_some());
+        assert!(index.find_type_definition("User").is_none());
+        assert!(index.find_field_definition("User", "id").is_none());
+        assert!(index
+            .find_argument_definition("Query", "user", "id")
+            .is_none());
+
+        // Definitions from local files next to the remote schema still resolve
+        let nickname = index
+            .find_field_definition("User", "nickname")
+            .expect("Should find local extension field");
+        assert_eq!(nickname.file_path, "extensions.graphql");
+    }
+
+    #[test]
+    fn test_possible_types_of_interface() {
+        let schema = SchemaIndex::from_schema(
+            r"
+            type Query { node: Node }
+
+            interface Node { id: ID! }
+            interface Resource implements Node { id: ID! url: String! }
+
+            type User implements Node { id: ID! }
+            type Repository implements Resource & Node { id: ID! url: String! }
+            type Comment { body: String! }
+        ",
+        );
+
+        // Object types only, not the `Resource` interface that implements `Node`
+        assert_eq!(schema.possible_types("Node"), vec!["User", "Repository"]);
+        assert_eq!(schema.possible_types("Resource"), vec!["Repository"]);
+        assert!(schema.possible_types("User").is_empty());
+        assert!(schema.possible_types("Missing").is_empty());
+
+        assert_eq!(
+            schema.implemented_interfaces("Repository"),
+            vec!["Resource", "Node"]
+        );
+        assert_eq!(schema.implemented_interfaces("Resource"), vec!["Node"]);
+        assert!(schema.implemented_interfaces("Comment").is_empty());
+    }
+
+    #[test]
+    fn test_possible_types_of_union() {
+        let schema = SchemaIndex::from_schema(
+            r"
+            type Query { search: [SearchResult!]! }
+
+            union SearchResult = User | Post
+
+            type User { id: ID! }
+            type Post { id: ID! }
+        ",
+        );
+
+        assert_eq!(schema.possible_types("SearchResult"), vec!["User", "Post"]);
+        assert!(schema.implemented_interfaces("SearchResult").is_empty());
+    }
+
+    #[test]
+    fn test_line_index_for_rebuilds_for_changed_content_of_same_length() {
+        let mut document_index = DocumentIndex::new();
+        document_index.cache_line_index(
+            "query.graphql".to_string(),
+            std::sync::Arc::new(crate::LineIndex::new("query A {\n  a\n}")),
+        );
+
+        // Same length, but the line break moved
+        let source = "query A {  a\n\n}";
+        let line_index = document_index.line_index_for(Some("query.graphql"), source);
+        assert!(line_index.indexes(source));
+        assert_eq!(
+            line_index.offset_to_position(13),
+            crate::Position {
+                line: 1,
+                character: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_line_index_for_counts_columns_in_position_encoding() {
+        let document_index = DocumentIndex {
+            position_encoding: crate::PositionEncoding::Utf8,
+            ..DocumentIndex::new()
+        };
+
+        let source = "# é\nquery A { a }";
+        let line_index = document_index.line_index_for(None, source);
+        assert_eq!(
+            line_index.offset_to_position(4),
+            crate::Position {
+                line: 0,
+                character: 4,
+            }
+        );
+    }
+
+    #[test]
+    fn test_root_types_include_schema_extensions() {
+        let schema = SchemaIndex::from_schema_files(vec![
+            (
+                "schema.graphql".to_string(),
+                "schema { query: RootQuery }\ntype RootQuery { a: ID }".to_string(),
+            ),
+            (
+                "mutations.graphql".to_string(),
+                "extend schema { mutation: RootMutation }\ntype RootMutation { b: ID }".to_string(),
+            ),
+        ]);
+
+        assert_eq!(schema.root_type(OperationType::Query), Some("RootQuery"));
+        assert_eq!(
+            schema.root_type(OperationType::Mutation),
+            Some("RootMutation")
+        );
+        assert_eq!(schema.root_type(OperationType::Subscription), None);
+
+        let tree = apollo_parser::Parser::new("subscription S { a } { a }").parse();
+        let operation_types: Vec<_> = tree
+            .document()
+            .definitions()
+            .filter_map(|definition| match definition {
+                apollo_parser::cst::Definition::OperationDefinition(op) => {
+                    Some(OperationType::of(&op))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            operation_types,
+            vec![OperationType::Subscription, OperationType::Query]
+        );
+    }
+
     #[test]
     fn test_document_index_tracks_duplicate_operations() {
         let mut index = DocumentIndex::new();
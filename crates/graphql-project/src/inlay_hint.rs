@@ -0,0 +1,454 @@
+use crate::index::FieldInfo;
+use crate::{LineIndex, OperationType, Position, PositionEncoding, SchemaIndex, TypeKind};
+use apollo_parser::{
+    cst::{self, CstNode},
+    Parser, SyntaxTree,
+};
+use serde::{Deserialize, Serialize};
+
+/// Which fields get a type hint
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldTypeHints {
+    /// Every field whose type can be resolved
+    #[default]
+    All,
+    /// Only fields that return a scalar, such as `String` or a custom scalar
+    Scalars,
+    /// No field type hints
+    Off,
+}
+
+/// Inlay hint configuration, read from `extensions.project.inlayHints`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InlayHintConfig {
+    pub field_types: FieldTypeHints,
+    /// Show the schema default of each argument a field selection leaves out
+    pub argument_defaults: bool,
+}
+
+/// What an inlay hint annotates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InlayHintKind {
+    /// The type of a selected field
+    Type,
+    /// The default value of omitted arguments
+    Parameter,
+}
+
+/// Annotation shown inline in a document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InlayHint {
+    /// Position the hint is rendered at
+    pub position: Position,
+    pub label: String,
+    pub kind: InlayHintKind,
+}
+
+impl InlayHint {
+    /// Shift this hint from a block-relative to a file position
+    ///
+    /// Used for GraphQL extracted from TypeScript/JavaScript, where the block starts at
+    /// `start_line`/`start_column` in the original file.
+    #[must_use]
+    pub const fn offset_by(mut self, start_line: usize, start_column: usize) -> Self {
+        if self.position.line == 0 {
+            self.position.character += start_column;
+        }
+        self.position.line += start_line;
+        self
+    }
+}
+
+/// Inlay hint provider
+///
+/// Shows the schema type of each selected field, like `: User!`, after the field name and
+/// its arguments. When enabled, also shows the schema defaults of the arguments a field
+/// selection omits, like `limit: 10`.
+pub struct InlayHintProvider {
+    /// Unit `Position::character` columns are counted in
+    position_encoding: PositionEncoding,
+}
+
+impl InlayHintProvider {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            position_encoding: PositionEncoding::Utf16,
+        }
+    }
+
+    /// Count `Position::character` columns in `encoding` instead of UTF-16 code units
+    #[must_use]
+    pub const fn with_position_encoding(mut self, encoding: PositionEncoding) -> Self {
+        self.position_encoding = encoding;
+        self
+    }
+
+    /// Get the inlay hints for a GraphQL document
+    #[must_use]
+    pub fn inlay_hints(
+        &self,
+        source: &str,
+        schema_index: &SchemaIndex,
+        config: &InlayHintConfig,
+    ) -> Vec<InlayHint> {
+        let tree = Parser::new(source).parse();
+        self.inlay_hints_with_ast(source, &tree, schema_index, config)
+    }
+
+    /// Get the inlay hints for a GraphQL document using an already parsed AST
+    ///
+    /// Fields whose type can't be resolved against the schema get no hint, and neither do
+    /// the fields selected below them.
+    #[must_use]
+    pub fn inlay_hints_with_ast(
+        &self,
+        source: &str,
+        tree: &SyntaxTree,
+        schema_index: &SchemaIndex,
+        config: &InlayHintConfig,
+    ) -> Vec<InlayHint> {
+        let mut hints = Vec::new();
+        if config.field_types == FieldTypeHints::Off && !config.argument_defaults {
+            return hints;
+        }
+
+        let line_index = LineIndex::with_encoding(source, self.position_encoding);
+
+        for definition in tree.document().definitions() {
+            let (type_name, selection_set) = match definition {
+                cst::Definition::OperationDefinition(operation) => (
+                    schema_index
+                        .root_type(OperationType::of(&operation))
+                        .map(ToString::to_string),
+                    operation.selection_set(),
+                ),
+                cst::Definition::FragmentDefinition(fragment) => (
+                    fragment
+                        .type_condition()
+                        .and_then(|tc| tc.named_type())
+                        .and_then(|nt| nt.name())
+                        .map(|name| name.text().to_string()),
+                    fragment.selection_set(),
+                ),
+                _ => continue,
+            };
+
+            if let (Some(type_name), Some(selection_set)) = (type_name, selection_set) {
+                selection_set_hints(
+                    source,
+                    &line_index,
+                    &selection_set,
+                    &type_name,
+                    schema_index,
+                    config,
+                    &mut hints,
+                );
+            }
+        }
+
+        hints
+    }
+}
+
+impl Default for InlayHintProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn selection_set_hints(
+    source: &str,
+    line_index: &LineIndex,
+    selection_set: &cst::SelectionSet,
+    parent_type: &str,
+    schema_index: &SchemaIndex,
+    config: &InlayHintConfig,
+    hints: &mut Vec<InlayHint>,
+) {
+    let fields = schema_index.get_fields(parent_type).unwrap_or_default();
+
+    for selection in selection_set.selections() {
+        match selection {
+            cst::Selection::Field(field) => {
+                let Some(name) = field.name() else {
+                    continue;
+                };
+                let Some(field_info) = fields.iter().find(|f| f.name == name.text()) else {
+                    continue;
+                };
+
+                let base_type = field_info
+                    .type_name
+                    .trim_matches(|c| c == '[' || c == ']' || c == '!');
+
+                let is_shown = match config.field_types {
+                    FieldTypeHints::All => true,
+                    FieldTypeHints::Scalars => schema_index
+                        .get_type(base_type)
+                        .is_some_and(|type_info| type_info.kind == TypeKind::Scalar),
+                    FieldTypeHints::Off => false,
+                };
+
+                if config.argument_defaults {
+                    if let Some(hint) =
+                        argument_defaults_hint(source, line_index, &field, &name, field_info)
+                    {
+                        hints.push(hint);
+                    }
+                }
+
+                if is_shown {
+                    // After the arguments if there are any, so the hint reads like a
+                    // field definition
+                    let anchor = field
+                        .arguments()
+                        .map_or_else(|| name.syntax().clone(), |args| args.syntax().clone());
+                    hints.push(InlayHint {
+                        position: line_index.offset_to_position(trimmed_end(source, &anchor)),
+                        label: format!(": {}", field_info.type_name),
+                        kind: InlayHintKind::Type,
+                    });
+                }
+
+                if let Some(nested) = field.selection_set() {
+                    selection_set_hints(
+                        source,
+                        line_index,
+                        &nested,
+                        base_type,
+                        schema_index,
+                        config,
+                        hints,
+                    );
+                }
+            }
+            cst::Selection::InlineFragment(inline_fragment) => {
+                let type_name = inline_fragment
+                    .type_condition()
+                    .and_then(|tc| tc.named_type())
+                    .and_then(|nt| nt.name())
+                    .map_or_else(|| parent_type.to_string(), |name| name.text().to_string());
+
+                if let Some(nested) = inline_fragment.selection_set() {
+                    selection_set_hints(
+                        source,
+                        line_index,
+                        &nested,
+                        &type_name,
+                        schema_index,
+                        config,
+                        hints,
+                    );
+                }
+            }
+            cst::Selection::FragmentSpread(_) => {}
+        }
+    }
+}
+
+/// Hint listing the defaults of the arguments `field` leaves out, if it leaves any out
+///
+/// With an argument list the hint goes before its closing parenthesis, like
+/// `(first: 5, after: 10)`; without one it goes after the field name as its own list.
+fn argument_defaults_hint(
+    source: &str,
+    line_index: &LineIndex,
+    field: &cst::Field,
+    name: &cst::Name,
+    field_info: &FieldInfo,
+) -> Option<InlayHint> {
+    let arguments = field.arguments();
+    let given: Vec<String> = arguments
+        .iter()
+        .flat_map(cst::Arguments::arguments)
+        .filter_map(|argument| argument.name())
+        .map(|name| name.text().to_string())
+        .collect();
+
+    let defaults: Vec<String> = field_info
+        .arguments
+        .iter()
+        .filter(|argument| !given.contains(&argument.name))
+        .filter_map(|argument| {
+            let default_value = argument.default_value.as_ref()?;
+            Some(format!("{}: {default_value}", argument.name))
+        })
+        .collect();
+    if defaults.is_empty() {
+        return None;
+    }
+    let defaults = defaults.join(", ");
+
+    let (offset, label) = match arguments {
+        Some(arguments) => {
+            // Before the closing parenthesis
+            let end = trimmed_end(source, arguments.syntax());
+            let label = if given.is_empty() {
+                defaults
+            } else {
+                format!(", {defaults}")
+            };
+            (end.saturating_sub(1), label)
+        }
+        None => (trimmed_end(source, name.syntax()), format!("({defaults})")),
+    };
+
+    Some(InlayHint {
+        position: line_index.offset_to_position(offset),
+        label,
+        kind: InlayHintKind::Parameter,
+    })
+}
+
+/// End offset of a node, excluding the whitespace and commas the parser attaches after it
+fn trimmed_end(source: &str, node: &apollo_parser::SyntaxNode) -> usize {
+    let range = node.text_range();
+    let start: usize = range.start().into();
+    let end: usize = range.end().into();
+    start
+        + source[start..end]
+            .trim_end_matches(|c: char| c.is_whitespace() || c == ',')
+            .len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_schema() -> SchemaIndex {
+        SchemaIndex::from_schema(
+            r"
+            scalar DateTime
+
+            type Query {
+                user(id: ID!): User
+                posts(limit: Int = 10, offset: Int = 0): [Post!]!
+            }
+
+            type User {
+                id: ID!
+                name: String
+            }
+
+            type Post {
+                title: String!
+                createdAt: DateTime!
+                author: User!
+            }
+            ",
+        )
+    }
+
+    fn labels_by_line(hints: &[InlayHint]) -> Vec<(usize, usize, &str)> {
+        hints
+            .iter()
+            .map(|hint| {
+                (
+                    hint.position.line,
+                    hint.position.character,
+                    hint.label.as_str(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_field_type_hints() {
+        let source = r#"query {
+  user(id: "1") {
+    name
+  }
+  posts {
+    ... on Post { title }
+    ...PostFields
+  }
+}
+
+fragment PostFields on Post {
+  author { id }
+}
+"#;
+
+        let hints = InlayHintProvider::new().inlay_hints(
+            source,
+            &create_test_schema(),
+            &InlayHintConfig::default(),
+        );
+
+        assert_eq!(
+            labels_by_line(&hints),
+            vec![
+                (1, 15, ": User"),
+                (2, 8, ": String"),
+                (4, 7, ": [Post!]!"),
+                (5, 23, ": String!"),
+                (11, 8, ": User!"),
+                (11, 13, ": ID!"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scalars_only() {
+        let source = "query { posts { title createdAt author { id } } }";
+        let config = InlayHintConfig {
+            field_types: FieldTypeHints::Scalars,
+            ..InlayHintConfig::default()
+        };
+
+        let hints = InlayHintProvider::new().inlay_hints(source, &create_test_schema(), &config);
+        let labels: Vec<_> = hints.iter().map(|hint| hint.label.as_str()).collect();
+
+        assert_eq!(labels, vec![": String!", ": DateTime!", ": ID!"]);
+    }
+
+    #[test]
+    fn test_unresolved_types_get_no_hints() {
+        let source = "query { missing { id } }\nfragment F on Missing { id }";
+
+        let hints = InlayHintProvider::new().inlay_hints(
+            source,
+            &create_test_schema(),
+            &InlayHintConfig::default(),
+        );
+
+        assert!(hints.is_empty());
+    }
+
+    #[test]
+    fn test_config_from_json() {
+        let config: InlayHintConfig =
+            serde_json::from_value(serde_json::json!({ "fieldTypes": "scalars" })).unwrap();
+        assert_eq!(config.field_types, FieldTypeHints::Scalars);
+
+        let config: InlayHintConfig = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert_eq!(config.field_types, FieldTypeHints::All);
+        assert!(!config.argument_defaults);
+
+        let config: InlayHintConfig =
+            serde_json::from_value(serde_json::json!({ "argumentDefaults": true })).unwrap();
+        assert!(config.argument_defaults);
+    }
+
+    #[test]
+    fn test_argument_default_hints() {
+        let source = "query {\n  posts(offset: 5) { title }\n  more: posts { title }\n  user(id: \"1\") { name }\n}";
+        let config = InlayHintConfig {
+            field_types: FieldTypeHints::Off,
+            argument_defaults: true,
+        };
+
+        let hints = InlayHintProvider::new().inlay_hints(source, &create_test_schema(), &config);
+
+        assert_eq!(
+            labels_by_line(&hints),
+            vec![(1, 17, ", limit: 10"), (2, 13, "(limit: 10, offset: 0)"),]
+        );
+        assert!(hints
+            .iter()
+            .all(|hint| hint.kind == InlayHintKind::Parameter));
+    }
+}
@@ -0,0 +1,524 @@
+use crate::{ProjectError, Result};
+use serde_json::Value;
+
+/// Standard introspection query sent to remote schema endpoints
+pub const INTROSPECTION_QUERY: &str = r"
+query IntrospectionQuery {
+  __schema {
+    queryType { name }
+    mutationType { name }
+    subscriptionType { name }
+    types { ...FullType }
+    directives {
+      name
+      description
+      isRepeatable
+      locations
+      args { ...InputValue }
+    }
+  }
+}
+
+fragment FullType on __Type {
+  kind
+  name
+  description
+  specifiedByURL
+  fields(includeDeprecated: true) {
+    name
+    description
+    args { ...InputValue }
+    type { ...TypeRef }
+    isDeprecated
+    deprecationReason
+  }
+  inputFields { ...InputValue }
+  interfaces { ...TypeRef }
+  enumValues(includeDeprecated: true) {
+    name
+    description
+    isDeprecated
+    deprecationReason
+  }
+  possibleTypes { ...TypeRef }
+}
+
+fragment InputValue on __InputValue {
+  name
+  description
+  type { ...TypeRef }
+  defaultValue
+}
+
+fragment TypeRef on __Type {
+  kind
+  name
+  ofType {
+    kind
+    name
+    ofType {
+      kind
+      name
+      ofType {
+        kind
+        name
+        ofType {
+          kind
+          name
+          ofType {
+            kind
+            name
+            ofType {
+              kind
+              name
+              ofType { kind name }
+            }
+          }
+        }
+      }
+    }
+  }
+}
+";
+
+/// [`INTROSPECTION_QUERY`] without `__Directive.isRepeatable` and `__Type.specifiedByURL`
+///
+/// Servers that predate those fields reject the standard query, so introspection is
+/// retried with this one.
+pub(crate) fn legacy_introspection_query() -> String {
+    INTROSPECTION_QUERY
+        .replace("      isRepeatable\n", "")
+        .replace("  specifiedByURL\n", "")
+}
+
+/// Scalars every GraphQL schema has, which are never printed
+const BUILT_IN_SCALARS: &[&str] = &["String", "Int", "Float", "Boolean", "ID"];
+
+/// Directives defined by the GraphQL spec, which are never printed
+const BUILT_IN_DIRECTIVES: &[&str] = &["skip", "include", "deprecated", "specifiedBy", "oneOf"];
+
+/// Convert an introspection result to SDL
+///
+/// Accepts either a full response (`{ "data": { "__schema": ... } }`) or just the
+/// `data` object (`{ "__schema": ... }`). Built-in scalars, introspection types, and
+/// spec directives are left out so the SDL can be combined with other schema files.
+pub fn introspection_to_sdl(introspection: &Value) -> Result<String> {
+    let schema = introspection
+        .get("data")
+        .unwrap_or(introspection)
+        .get("__schema")
+        .ok_or_else(|| {
+            ProjectError::SchemaParse("Introspection result has no __schema field".to_string())
+        })?;
+
+    let mut definitions = Vec::new();
+
+    if let Some(schema_definition) = print_schema_definition(schema) {
+        definitions.push(schema_definition);
+    }
+
+    for directive in array(schema, "directives") {
+        let Some(name) = str_field(directive, "name") else {
+            continue;
+        };
+        if !BUILT_IN_DIRECTIVES.contains(&name) {
+            definitions.push(print_directive(name, directive));
+        }
+    }
+
+    for ty in array(schema, "types") {
+        let Some(name) = str_field(ty, "name") else {
+            continue;
+        };
+        if name.starts_with("__") || BUILT_IN_SCALARS.contains(&name) {
+            continue;
+        }
+        definitions.push(print_type(name, ty)?);
+    }
+
+    Ok(definitions.join("\n\n") + "\n")
+}
+
+/// The `schema { ... }` definition, if the root types don't use the default names
+fn print_schema_definition(schema: &Value) -> Option<String> {
+    let roots = [
+        ("query", "queryType", "Query"),
+        ("mutation", "mutationType", "Mutation"),
+        ("subscription", "subscriptionType", "Subscription"),
+    ]
+    .into_iter()
+    .filter_map(|(operation, field, default_name)| {
+        let name = schema.get(field).and_then(|ty| str_field(ty, "name"))?;
+        Some((operation, name, default_name))
+    })
+    .collect::<Vec<_>>();
+
+    if roots
+        .iter()
+        .all(|(_, name, default_name)| name == default_name)
+    {
+        return None;
+    }
+
+    let mut sdl = "schema {\n".to_string();
+    for (operation, name, _) in roots {
+        sdl.push_str(&format!("  {operation}: {name}\n"));
+    }
+    sdl.push('}');
+    Some(sdl)
+}
+
+fn print_directive(name: &str, directive: &Value) -> String {
+    let mut sdl = print_description(directive, "");
+    sdl.push_str(&format!(
+        "directive @{name}{}",
+        print_arguments(directive, "")
+    ));
+    if directive.get("isRepeatable").and_then(Value::as_bool) == Some(true) {
+        sdl.push_str(" repeatable");
+    }
+    let locations = array(directive, "locations")
+        .filter_map(Value::as_str)
+        .collect::<Vec<_>>();
+    sdl.push_str(&format!(" on {}", locations.join(" | ")));
+    sdl
+}
+
+fn print_type(name: &str, ty: &Value) -> Result<String> {
+    let mut sdl = print_description(ty, "");
+
+    match str_field(ty, "kind") {
+        Some("SCALAR") => {
+            sdl.push_str(&format!("scalar {name}"));
+            if let Some(url) = str_field(ty, "specifiedByURL") {
+                sdl.push_str(&format!(" @specifiedBy(url: {})", quote(url)));
+            }
+        }
+        Some("OBJECT") => {
+            sdl.push_str(&format!("type {name}{}", print_implements(ty)));
+            sdl.push_str(&print_fields(ty)?);
+        }
+        Some("INTERFACE") => {
+            sdl.push_str(&format!("interface {name}{}", print_implements(ty)));
+            sdl.push_str(&print_fields(ty)?);
+        }
+        Some("UNION") => {
+            let members = array(ty, "possibleTypes")
+                .filter_map(|member| str_field(member, "name"))
+                .collect::<Vec<_>>();
+            sdl.push_str(&format!("union {name}"));
+            if !members.is_empty() {
+                sdl.push_str(&format!(" = {}", members.join(" | ")));
+            }
+        }
+        Some("ENUM") => {
+            sdl.push_str(&format!("enum {name} {{"));
+            for value in array(ty, "enumValues") {
+                let Some(value_name) = str_field(value, "name") else {
+                    continue;
+                };
+                sdl.push_str(&format!(
+                    "\n{}  {value_name}{}",
+                    print_description(value, "  "),
+                    print_deprecated(value)
+                ));
+            }
+            sdl.push_str("\n}");
+        }
+        Some("INPUT_OBJECT") => {
+            sdl.push_str(&format!("input {name} {{"));
+            for field in array(ty, "inputFields") {
+                sdl.push('\n');
+                sdl.push_str(&print_input_value(field, "  ")?);
+            }
+            sdl.push_str("\n}");
+        }
+        kind => {
+            return Err(ProjectError::SchemaParse(format!(
+                "Unknown kind {} for type {name} in introspection result",
+                kind.unwrap_or("(missing)")
+            )));
+        }
+    }
+
+    Ok(sdl)
+}
+
+fn print_implements(ty: &Value) -> String {
+    let interfaces = array(ty, "interfaces")
+        .filter_map(|interface| str_field(interface, "name"))
+        .collect::<Vec<_>>();
+    if interfaces.is_empty() {
+        String::new()
+    } else {
+        format!(" implements {}", interfaces.join(" & "))
+    }
+}
+
+fn print_fields(ty: &Value) -> Result<String> {
+    let mut sdl = " {".to_string();
+    for field in array(ty, "fields") {
+        let Some(name) = str_field(field, "name") else {
+            continue;
+        };
+        sdl.push_str(&format!(
+            "\n{}  {name}{}: {}{}",
+            print_description(field, "  "),
+            print_arguments(field, "  "),
+            print_type_ref(field.get("type"))?,
+            print_deprecated(field)
+        ));
+    }
+    sdl.push_str("\n}");
+    Ok(sdl)
+}
+
+/// Arguments of a field or directive, one per line when any has a description
+fn print_arguments(value: &Value, indent: &str) -> String {
+    let args = array(value, "args").collect::<Vec<_>>();
+    if args.is_empty() {
+        return String::new();
+    }
+
+    let multiline = args
+        .iter()
+        .any(|arg| str_field(arg, "description").is_some());
+    if multiline {
+        let arg_indent = format!("{indent}  ");
+        let args = args
+            .iter()
+            .filter_map(|arg| print_input_value(arg, &arg_indent).ok())
+            .collect::<Vec<_>>();
+        format!("(\n{}\n{indent})", args.join("\n"))
+    } else {
+        let args = args
+            .iter()
+            .filter_map(|arg| print_input_value(arg, "").ok())
+            .collect::<Vec<_>>();
+        format!("({})", args.join(", "))
+    }
+}
+
+fn print_input_value(value: &Value, indent: &str) -> Result<String> {
+    let name = str_field(value, "name").unwrap_or_default();
+    let mut sdl = print_description(value, indent);
+    sdl.push_str(&format!(
+        "{indent}{name}: {}",
+        print_type_ref(value.get("type"))?
+    ));
+    if let Some(default_value) = str_field(value, "defaultValue") {
+        sdl.push_str(&format!(" = {default_value}"));
+    }
+    sdl.push_str(&print_deprecated(value));
+    Ok(sdl)
+}
+
+/// Print a type reference like `[User!]!`
+fn print_type_ref(type_ref: Option<&Value>) -> Result<String> {
+    let type_ref = type_ref
+        .ok_or_else(|| ProjectError::SchemaParse("Missing type in introspection result".into()))?;
+    match str_field(type_ref, "kind") {
+        Some("NON_NULL") => Ok(format!("{}!", print_type_ref(type_ref.get("ofType"))?)),
+        Some("LIST") => Ok(format!("[{}]", print_type_ref(type_ref.get("ofType"))?)),
+        _ => str_field(type_ref, "name")
+            .map(ToString::to_string)
+            .ok_or_else(|| {
+                ProjectError::SchemaParse("Unnamed type in introspection result".to_string())
+            }),
+    }
+}
+
+/// A description as a block string on its own line, or nothing if there is none
+fn print_description(value: &Value, indent: &str) -> String {
+    match str_field(value, "description") {
+        Some(description) if !description.is_empty() => {
+            let description = description.replace("\"\"\"", "\\\"\"\"");
+            let lines = description
+                .lines()
+                .map(|line| format!("{indent}{line}"))
+                .collect::<Vec<_>>();
+            format!("{indent}\"\"\"\n{}\n{indent}\"\"\"\n", lines.join("\n"))
+        }
+        _ => String::new(),
+    }
+}
+
+fn print_deprecated(value: &Value) -> String {
+    if value.get("isDeprecated").and_then(Value::as_bool) != Some(true) {
+        return String::new();
+    }
+    match str_field(value, "deprecationReason") {
+        Some(reason) if reason != "No longer supported" => {
+            format!(" @deprecated(reason: {})", quote(reason))
+        }
+        _ => " @deprecated".to_string(),
+    }
+}
+
+/// A string literal, with quotes, backslashes, and control characters escaped
+fn quote(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| format!("\"{value}\""))
+}
+
+fn str_field<'a>(value: &'a Value, field: &str) -> Option<&'a str> {
+    value.get(field).and_then(Value::as_str)
+}
+
+fn array<'a>(value: &'a Value, field: &str) -> impl Iterator<Item = &'a Value> {
+    value
+        .get(field)
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_legacy_query_leaves_out_newer_fields() {
+        let legacy = legacy_introspection_query();
+
+        assert!(INTROSPECTION_QUERY.contains("isRepeatable"));
+        assert!(INTROSPECTION_QUERY.contains("specifiedByURL"));
+        assert!(!legacy.contains("isRepeatable"));
+        assert!(!legacy.contains("specifiedByURL"));
+        assert!(apollo_parser::Parser::new(&legacy).parse().errors().len() == 0);
+    }
+
+    fn named(kind: &str, name: &str) -> Value {
+        json!({ "kind": kind, "name": name, "ofType": null })
+    }
+
+    fn non_null(of_type: Value) -> Value {
+        json!({ "kind": "NON_NULL", "name": null, "ofType": of_type })
+    }
+
+    #[test]
+    fn test_introspection_to_sdl() {
+        let response = json!({
+            "data": {
+                "__schema": {
+                    "queryType": { "name": "Query" },
+                    "mutationType": null,
+                    "subscriptionType": null,
+                    "directives": [
+                        {
+                            "name": "include",
+                            "locations": ["FIELD"],
+                            "args": [{ "name": "if", "type": non_null(named("SCALAR", "Boolean")) }]
+                        },
+                        {
+                            "name": "auth",
+                            "isRepeatable": false,
+                            "locations": ["FIELD_DEFINITION", "OBJECT"],
+                            "args": [{ "name": "role", "type": named("SCALAR", "String"), "defaultValue": "\"user\"" }]
+                        }
+                    ],
+                    "types": [
+                        { "kind": "SCALAR", "name": "String" },
+                        { "kind": "OBJECT", "name": "__Type", "fields": [] },
+                        {
+                            "kind": "OBJECT",
+                            "name": "Query",
+                            "interfaces": [],
+                            "fields": [{
+                                "name": "users",
+                                "args": [{ "name": "first", "type": named("SCALAR", "Int"), "defaultValue": "10" }],
+                                "type": non_null(json!({ "kind": "LIST", "name": null, "ofType": non_null(named("OBJECT", "User")) })),
+                                "isDeprecated": false
+                            }]
+                        },
+                        {
+                            "kind": "OBJECT",
+                            "name": "User",
+                            "description": "A registered user",
+                            "interfaces": [named("INTERFACE", "Node")],
+                            "fields": [
+                                { "name": "id", "args": [], "type": non_null(named("SCALAR", "ID")) },
+                                {
+                                    "name": "login",
+                                    "args": [],
+                                    "type": named("SCALAR", "String"),
+                                    "isDeprecated": true,
+                                    "deprecationReason": "Use `email`"
+                                },
+                                { "name": "role", "args": [], "type": named("ENUM", "Role") }
+                            ]
+                        },
+                        {
+                            "kind": "INTERFACE",
+                            "name": "Node",
+                            "interfaces": [],
+                            "fields": [{ "name": "id", "args": [], "type": non_null(named("SCALAR", "ID")) }]
+                        },
+                        {
+                            "kind": "ENUM",
+                            "name": "Role",
+                            "enumValues": [
+                                { "name": "ADMIN", "isDeprecated": false },
+                                { "name": "GUEST", "isDeprecated": true, "deprecationReason": "No longer supported" }
+                            ]
+                        },
+                        { "kind": "UNION", "name": "SearchResult", "possibleTypes": [named("OBJECT", "User")] },
+                        {
+                            "kind": "INPUT_OBJECT",
+                            "name": "UserFilter",
+                            "inputFields": [{ "name": "role", "type": named("ENUM", "Role"), "defaultValue": "ADMIN" }]
+                        },
+                        { "kind": "SCALAR", "name": "DateTime", "specifiedByURL": "https://example.com/datetime" }
+                    ]
+                }
+            }
+        });
+
+        let sdl = introspection_to_sdl(&response).unwrap();
+
+        assert_eq!(
+            sdl,
+            r#"directive @auth(role: String = "user") on FIELD_DEFINITION | OBJECT
+
+type Query {
+  users(first: Int = 10): [User!]!
+}
+
+"""
+A registered user
+"""
+type User implements Node {
+  id: ID!
+  login: String @deprecated(reason: "Use `email`")
+  role: Role
+}
+
+interface Node {
+  id: ID!
+}
+
+enum Role {
+  ADMIN
+  GUEST @deprecated
+}
+
+union SearchResult = User
+
+input UserFilter {
+  role: Role = ADMIN
+}
+
+scalar DateTime @specifiedBy(url: "https://example.com/datetime")
+"#
+        );
+
+        // The converted SDL builds into a usable schema
+        let index = crate::SchemaIndex::from_schema(&sdl);
+        assert!(index.get_type("User").is_some());
+    }
+
+    #[test]
+    fn test_introspection_to_sdl_requires_schema() {
+        assert!(introspection_to_sdl(&json!({ "data": null })).is_err());
+    }
+}
@@ -1,34 +1,62 @@
+mod code_action;
 mod completion;
 mod diagnostics;
 mod document;
+mod document_highlight;
+mod document_symbol;
 mod error;
 mod find_references;
+mod folding_range;
+mod format;
 mod goto_definition;
 mod hover;
 mod index;
+mod inlay_hint;
+mod introspection;
 mod line_index;
 mod lint;
 mod project;
+mod rename;
 mod schema;
+mod schema_cache;
+mod schema_diff;
+mod semantic_tokens;
+mod signature_help;
 mod validation;
+mod workspace_symbol;
 
 // Export diagnostics types for LSP package to use when converting DiagnosticList
-pub use completion::{CompletionItem, CompletionItemKind, CompletionProvider};
-pub use diagnostics::{Diagnostic, Position, Range, RelatedInfo, Severity};
+pub use code_action::{CodeAction, CodeActionProvider, MovedOperation, SplitOperations, TextEdit};
+pub use completion::{CompletionConfig, CompletionItem, CompletionItemKind, CompletionProvider};
+pub use diagnostics::{Diagnostic, DiagnosticData, Position, Range, RelatedInfo, Severity};
 pub use document::DocumentLoader;
+pub use document_highlight::{DocumentHighlight, DocumentHighlightKind, DocumentHighlightProvider};
+pub use document_symbol::{DocumentSymbol, DocumentSymbolKind, DocumentSymbolProvider};
 pub use error::{ProjectError, Result};
 pub use find_references::{FindReferencesProvider, ReferenceLocation};
+pub use folding_range::{FoldingRange, FoldingRangeKind, FoldingRangeProvider};
+pub use format::format_document;
 pub use goto_definition::{DefinitionLocation, GotoDefinitionProvider};
 pub use hover::{HoverInfo, HoverProvider};
 pub use index::{
     DocumentIndex, ExtractedBlock, FieldDefinitionLocation, FragmentInfo, OperationInfo,
-    OperationType, SchemaIndex, TypeInfo,
+    OperationType, SchemaBuildError, SchemaIndex, TypeInfo, TypeKind,
 };
-pub use line_index::LineIndex;
+pub use inlay_hint::{
+    FieldTypeHints, InlayHint, InlayHintConfig, InlayHintKind, InlayHintProvider,
+};
+pub use introspection::{introspection_to_sdl, INTROSPECTION_QUERY};
+pub use line_index::{LineIndex, PositionEncoding};
 pub use lint::{LintConfig, LintRuleConfig, LintSeverity, Linter};
 pub use project::GraphQLProject;
-pub use schema::SchemaLoader;
+pub use rename::RenameProvider;
+pub use schema::{RemoteSchemaConfig, SchemaLoader};
+pub use schema_cache::SchemaCache;
+pub use schema_diff::{diff_schemas, ChangeCriticality, SchemaChange, SchemaChangeKind};
+pub use semantic_tokens::{SemanticToken, SemanticTokenKind, SemanticTokensProvider};
+pub use signature_help::{SignatureHelp, SignatureHelpProvider, SignatureParameter};
 pub use validation::Validator;
+pub use workspace_symbol::{WorkspaceSymbol, WorkspaceSymbolKind, WorkspaceSymbolProvider};
 
 // Re-export common types from dependencies
 pub use apollo_compiler::validation::DiagnosticList;
@@ -1,15 +1,91 @@
 use crate::Position;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+/// Unit used to count `Position::character` columns
+///
+/// LSP clients and servers negotiate this during `initialize`. UTF-16 is the default
+/// mandated by the LSP specification and is used unless another encoding was negotiated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PositionEncoding {
+    /// Columns count UTF-8 bytes
+    Utf8,
+    /// Columns count UTF-16 code units
+    #[default]
+    Utf16,
+    /// Columns count Unicode scalar values
+    Utf32,
+}
+
+impl PositionEncoding {
+    /// Number of column units `ch` occupies in this encoding
+    #[must_use]
+    pub const fn char_len(self, ch: char) -> usize {
+        match self {
+            Self::Utf8 => ch.len_utf8(),
+            Self::Utf16 => ch.len_utf16(),
+            Self::Utf32 => 1,
+        }
+    }
+
+    /// Convert a byte offset to a line/column position by scanning `source`
+    ///
+    /// This is the O(N) fallback for callers without a cached `LineIndex`. Returns `None`
+    /// if `offset` is past the end of `source` or not on a character boundary.
+    #[must_use]
+    pub fn offset_to_position(self, source: &str, offset: usize) -> Option<Position> {
+        if offset > source.len() || !source.is_char_boundary(offset) {
+            return None;
+        }
+
+        let before = &source[..offset];
+        let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+        let line = before[..line_start].matches('\n').count();
+        let character = before[line_start..]
+            .chars()
+            .map(|ch| self.char_len(ch))
+            .sum();
+
+        Some(Position { line, character })
+    }
+
+    /// Convert a line/column position to a byte offset by scanning `source`
+    ///
+    /// This is the O(N) fallback for callers without a cached `LineIndex`. Returns `None`
+    /// if the position is past the end of its line or falls inside a character.
+    #[must_use]
+    pub fn position_to_offset(self, source: &str, position: Position) -> Option<usize> {
+        let mut line_start = 0;
+        for _ in 0..position.line {
+            line_start += source[line_start..].find('\n')? + 1;
+        }
+
+        let line = source[line_start..].split('\n').next().unwrap_or_default();
+        let mut column = 0;
+        for (idx, ch) in line.char_indices() {
+            if column == position.character {
+                return Some(line_start + idx);
+            }
+            if column > position.character {
+                return None;
+            }
+            column += self.char_len(ch);
+        }
+
+        (column == position.character).then_some(line_start + line.len())
+    }
+}
 
 /// Fast line-to-offset and offset-to-line conversion using a pre-built index.
 ///
 /// This index eliminates the O(N) character iteration required for position-to-offset
-/// conversions by maintaining a cached list of line start offsets.
+/// conversions by maintaining a cached list of line start offsets, plus the location of
+/// every non-ASCII character so columns can be converted for any `PositionEncoding`.
 ///
 /// # Performance
 ///
 /// - Build time: O(N) where N is the length of the source text
-/// - Memory: O(L) where L is the number of lines (~8 bytes per line)
-/// - Lookup time: O(1) for `position_to_offset`
+/// - Memory: O(L + W) where L is the number of lines and W the number of non-ASCII characters
+/// - Lookup time: O(1) for `position_to_offset` on ASCII-only lines
 /// - Lookup time: O(log L) for `offset_to_position` (binary search)
 ///
 /// # Example
@@ -35,43 +111,106 @@ pub struct LineIndex {
     /// Index 0 is always 0 (start of file)
     /// Index N is the byte offset immediately after the Nth '\n' character
     line_starts: Vec<usize>,
+    /// Byte offset and value of every non-ASCII character, in source order
+    wide_chars: Vec<(usize, char)>,
+    /// Length in bytes of the indexed text
+    text_len: usize,
+    /// Hash of the indexed text, to tell whether the index describes a given text
+    text_hash: u64,
+    /// Unit `Position::character` columns are counted in
+    encoding: PositionEncoding,
 }
 
 impl LineIndex {
-    /// Build a line index from source text
+    /// Build a line index from source text, counting columns in UTF-16 code units
     ///
     /// This scans the entire source once to find all '\n' characters
     /// and records their byte offsets.
     #[must_use]
     pub fn new(text: &str) -> Self {
+        Self::with_encoding(text, PositionEncoding::Utf16)
+    }
+
+    /// Build a line index from source text, counting columns in `encoding`
+    #[must_use]
+    pub fn with_encoding(text: &str, encoding: PositionEncoding) -> Self {
         let mut line_starts = vec![0];
-        let mut offset = 0;
+        let mut wide_chars = Vec::new();
 
-        for ch in text.chars() {
-            offset += ch.len_utf8();
+        for (offset, ch) in text.char_indices() {
             if ch == '\n' {
-                line_starts.push(offset);
+                line_starts.push(offset + 1);
+            } else if !ch.is_ascii() {
+                wide_chars.push((offset, ch));
             }
         }
 
-        Self { line_starts }
+        Self {
+            line_starts,
+            wide_chars,
+            text_len: text.len(),
+            text_hash: Self::hash_text(text),
+            encoding,
+        }
+    }
+
+    /// Unit `Position::character` columns are counted in
+    #[must_use]
+    pub const fn encoding(&self) -> PositionEncoding {
+        self.encoding
+    }
+
+    /// Whether this index was built from `text`
+    ///
+    /// Compares the length first, so only text of the same length is hashed.
+    #[must_use]
+    pub fn indexes(&self, text: &str) -> bool {
+        self.text_len == text.len() && self.text_hash == Self::hash_text(text)
+    }
+
+    fn hash_text(text: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        text.hash(&mut hasher);
+        hasher.finish()
     }
 
     /// Convert a line/column position to a byte offset
     ///
+    /// Columns are interpreted in the index's [`PositionEncoding`].
     /// Returns `None` if the position is out of bounds.
     ///
     /// # Complexity
     ///
-    /// O(1) - Direct array lookup and addition
+    /// O(1) for ASCII-only lines - Direct array lookup and addition
     #[must_use]
     pub fn position_to_offset(&self, position: Position) -> Option<usize> {
         let line_start = *self.line_starts.get(position.line)?;
-        Some(line_start + position.character)
+
+        let mut offset = line_start;
+        let mut remaining = position.character;
+        for &(char_offset, ch) in self.wide_chars_in_line(position.line) {
+            let ascii_run = char_offset - offset;
+            if remaining <= ascii_run {
+                return Some(offset + remaining);
+            }
+            remaining -= ascii_run;
+
+            let units = self.encoding.char_len(ch);
+            if remaining < units {
+                // Position falls inside a multi-unit character; snap to its start
+                return Some(char_offset);
+            }
+            remaining -= units;
+            offset = char_offset + ch.len_utf8();
+        }
+
+        Some(offset + remaining)
     }
 
     /// Convert a byte offset to a line/column position
     ///
+    /// Columns are counted in the index's [`PositionEncoding`].
+    ///
     /// # Complexity
     ///
     /// O(log L) where L is the number of lines - uses binary search
@@ -86,10 +225,37 @@ impl LineIndex {
         };
 
         let line_start = self.line_starts[line];
-        Position {
-            line,
-            character: offset.saturating_sub(line_start),
+        let mut character = offset.saturating_sub(line_start);
+        for &(char_offset, ch) in self.wide_chars_in_line(line) {
+            if char_offset >= offset {
+                break;
+            }
+            character = character.saturating_sub(ch.len_utf8()) + self.encoding.char_len(ch);
         }
+
+        Position { line, character }
+    }
+
+    /// Non-ASCII characters on the given line
+    fn wide_chars_in_line(&self, line: usize) -> &[(usize, char)] {
+        let start = self.line_starts[line];
+        let end = self
+            .line_starts
+            .get(line + 1)
+            .copied()
+            .unwrap_or(usize::MAX);
+
+        let first = self
+            .wide_chars
+            .partition_point(|&(offset, _)| offset < start);
+        let last = self.wide_chars.partition_point(|&(offset, _)| offset < end);
+        &self.wide_chars[first..last]
+    }
+
+    /// Get the length in bytes of the indexed text
+    #[must_use]
+    pub const fn text_len(&self) -> usize {
+        self.text_len
     }
 
     /// Get the number of lines in the indexed text
@@ -324,4 +490,102 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_position_encodings() {
+        // 😀 is 4 UTF-8 bytes, 2 UTF-16 code units, and 1 scalar value
+        let source = "query 😀 { a }";
+        let a_offset = source.find('a').unwrap();
+
+        let cases = [
+            (PositionEncoding::Utf8, 13),
+            (PositionEncoding::Utf16, 11),
+            (PositionEncoding::Utf32, 10),
+        ];
+
+        for (encoding, character) in cases {
+            let index = LineIndex::with_encoding(source, encoding);
+            let position = Position { line: 0, character };
+            assert_eq!(
+                index.position_to_offset(position),
+                Some(a_offset),
+                "{encoding:?}"
+            );
+            assert_eq!(index.offset_to_position(a_offset), position, "{encoding:?}");
+            assert_eq!(
+                encoding.position_to_offset(source, position),
+                Some(a_offset)
+            );
+            assert_eq!(
+                encoding.offset_to_position(source, a_offset),
+                Some(position)
+            );
+        }
+    }
+
+    #[test]
+    fn test_position_encodings_only_affect_own_line() {
+        let source = "# 😀😀\nquery { a }";
+        let index = LineIndex::new(source);
+        let a_offset = source.find('a').unwrap();
+
+        let position = Position {
+            line: 1,
+            character: 8,
+        };
+        assert_eq!(index.position_to_offset(position), Some(a_offset));
+        assert_eq!(index.offset_to_position(a_offset), position);
+    }
+
+    #[test]
+    fn test_position_inside_surrogate_pair_snaps_to_char_start() {
+        let source = "a😀b";
+        let index = LineIndex::new(source);
+
+        let offset = index.position_to_offset(Position {
+            line: 0,
+            character: 2,
+        });
+        assert_eq!(offset, Some(1));
+    }
+
+    #[test]
+    fn test_matches_scanning_at_random_positions() {
+        let source = "query Q($id: ID = \"é\") {\r\n  user(id: $id) { name } # 世界 😀\n\n\tfriends { ñame }\n😀😀x\n";
+        let boundaries: Vec<usize> = source
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .chain(std::iter::once(source.len()))
+            .collect();
+
+        // xorshift, so failures reproduce without a random number crate
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        for encoding in [
+            PositionEncoding::Utf8,
+            PositionEncoding::Utf16,
+            PositionEncoding::Utf32,
+        ] {
+            let index = LineIndex::with_encoding(source, encoding);
+            for _ in 0..500 {
+                let offset = boundaries[usize::try_from(next()).unwrap() % boundaries.len()];
+
+                let scanned = encoding.offset_to_position(source, offset).unwrap();
+                let indexed = index.offset_to_position(offset);
+                assert_eq!(indexed, scanned, "offset {offset} ({encoding:?})");
+
+                assert_eq!(
+                    index.position_to_offset(scanned),
+                    encoding.position_to_offset(source, scanned),
+                    "position {scanned:?} ({encoding:?})"
+                );
+            }
+        }
+    }
 }
@@ -10,6 +10,21 @@ pub enum LintSeverity {
     Error,
 }
 
+impl std::str::FromStr for LintSeverity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(Self::Off),
+            "warn" => Ok(Self::Warn),
+            "error" => Ok(Self::Error),
+            _ => Err(format!(
+                "invalid severity '{s}', expected one of: error, warn, off"
+            )),
+        }
+    }
+}
+
 /// Configuration for a single lint rule
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -82,6 +97,45 @@ impl LintConfig {
         }
     }
 
+    /// Get the options configured for a rule, if it uses the detailed form
+    #[must_use]
+    pub fn get_options(&self, rule_name: &str) -> Option<&serde_json::Value> {
+        match self {
+            Self::Recommended(_) => None,
+            Self::Rules { rules } => match rules.get(rule_name)? {
+                LintRuleConfig::Severity(_) => None,
+                LintRuleConfig::Detailed { options, .. } => options.as_ref(),
+            },
+        }
+    }
+
+    /// Override the severity of a rule, keeping any options it's configured with
+    ///
+    /// A recommended preset keeps applying to the other rules.
+    pub fn set_severity(&mut self, rule_name: &str, severity: LintSeverity) {
+        if matches!(self, Self::Recommended(_)) {
+            *self = Self::Rules {
+                rules: HashMap::from([(
+                    "recommended".to_string(),
+                    LintRuleConfig::Severity(LintSeverity::Warn),
+                )]),
+            };
+        }
+        let Self::Rules { rules } = self else {
+            return;
+        };
+
+        match rules.get_mut(rule_name) {
+            Some(LintRuleConfig::Detailed {
+                severity: configured,
+                ..
+            }) => *configured = severity,
+            _ => {
+                rules.insert(rule_name.to_string(), LintRuleConfig::Severity(severity));
+            }
+        }
+    }
+
     /// Check if a rule is enabled (not Off and not None)
     #[must_use]
     pub fn is_enabled(&self, rule_name: &str) -> bool {
@@ -152,6 +206,56 @@ mod tests {
         assert!(!config.is_enabled("deprecated_field"));
     }
 
+    #[test]
+    fn test_parse_rule_options() {
+        let yaml = "\nmax_selections:\n  severity: error\n  options:\n    max: 50\n";
+        let config: LintConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.is_enabled("max_selections"));
+        assert_eq!(
+            config.get_options("max_selections"),
+            Some(&serde_json::json!({ "max": 50 }))
+        );
+        assert_eq!(config.get_options("unique_names"), None);
+    }
+
+    #[test]
+    fn test_set_severity_overrides() {
+        let yaml = "\nmax_selections:\n  severity: warn\n  options:\n    max: 50\n";
+        let mut config: LintConfig = serde_yaml::from_str(yaml).unwrap();
+
+        config.set_severity("max_selections", LintSeverity::Error);
+        config.set_severity("unique_names", LintSeverity::Warn);
+
+        assert_eq!(
+            config.get_severity("max_selections"),
+            Some(LintSeverity::Error)
+        );
+        assert_eq!(
+            config.get_options("max_selections"),
+            Some(&serde_json::json!({ "max": 50 }))
+        );
+        assert_eq!(
+            config.get_severity("unique_names"),
+            Some(LintSeverity::Warn)
+        );
+
+        let mut config = LintConfig::recommended();
+        config.set_severity("deprecated_field", LintSeverity::Off);
+        assert!(!config.is_enabled("deprecated_field"));
+        assert_eq!(
+            config.get_severity("unique_names"),
+            Some(LintSeverity::Error)
+        );
+    }
+
+    #[test]
+    fn test_severity_from_str() {
+        assert_eq!("error".parse(), Ok(LintSeverity::Error));
+        assert_eq!("warn".parse(), Ok(LintSeverity::Warn));
+        assert_eq!("off".parse(), Ok(LintSeverity::Off));
+        assert!("warning".parse::<LintSeverity>().is_err());
+    }
+
     #[test]
     fn test_recommended_constructor() {
         let config = LintConfig::recommended();
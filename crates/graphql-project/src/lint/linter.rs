@@ -1,4 +1,4 @@
-use crate::{Diagnostic, DocumentIndex, SchemaIndex, Severity};
+use crate::{Diagnostic, DocumentIndex, PositionEncoding, SchemaIndex, Severity};
 
 use super::config::{LintConfig, LintSeverity};
 use super::rules;
@@ -6,13 +6,27 @@ use super::rules;
 /// Linter that runs configured lint rules
 pub struct Linter {
     config: LintConfig,
+    /// Unit `lint_document` counts diagnostic columns in
+    position_encoding: PositionEncoding,
 }
 
 impl Linter {
     /// Create a new linter with the given configuration
     #[must_use]
     pub const fn new(config: LintConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            position_encoding: PositionEncoding::Utf16,
+        }
+    }
+
+    /// Count `lint_document` diagnostic columns in `encoding` instead of UTF-16 code units
+    ///
+    /// Project-wide lints count columns in the `DocumentIndex`'s encoding.
+    #[must_use]
+    pub const fn with_position_encoding(mut self, encoding: PositionEncoding) -> Self {
+        self.position_encoding = encoding;
+        self
     }
 
     /// Run all enabled lints on a document
@@ -37,7 +51,8 @@ impl Linter {
             }
 
             // Run the rule
-            let mut rule_diagnostics = rule.check(document, schema_index, file_name);
+            let mut rule_diagnostics =
+                rule.check(document, schema_index, file_name, self.position_encoding);
 
             // Apply configured severity
             if let Some(severity) = self.config.get_severity(rule_name) {
@@ -77,7 +92,44 @@ impl Linter {
             }
 
             // Run the rule
-            let mut rule_diagnostics = rule.check_project(document_index, schema_index);
+            let mut rule_diagnostics = rule.check_project_with_options(
+                document_index,
+                schema_index,
+                self.config.get_options(rule_name),
+            );
+
+            // Apply configured severity
+            if let Some(severity) = self.config.get_severity(rule_name) {
+                for diag in &mut rule_diagnostics {
+                    diag.severity = match severity {
+                        LintSeverity::Error => Severity::Error,
+                        LintSeverity::Warn => Severity::Warning,
+                        LintSeverity::Off => unreachable!("Off rules are skipped"),
+                    };
+                }
+            }
+
+            diagnostics.extend(rule_diagnostics);
+        }
+
+        diagnostics
+    }
+
+    /// Run all enabled schema lints
+    #[must_use]
+    pub fn lint_schema(&self, schema_index: &SchemaIndex) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for rule in rules::all_schema_rules() {
+            let rule_name = rule.name();
+
+            // Skip if rule is not enabled (opt-in behavior)
+            if !self.config.is_enabled(rule_name) {
+                continue;
+            }
+
+            let mut rule_diagnostics =
+                rule.check_schema_with_options(schema_index, self.config.get_options(rule_name));
 
             // Apply configured severity
             if let Some(severity) = self.config.get_severity(rule_name) {
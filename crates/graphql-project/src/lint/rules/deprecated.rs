@@ -1,4 +1,4 @@
-use crate::{Diagnostic, Position, Range, SchemaIndex};
+use crate::{Diagnostic, LineIndex, OperationType, PositionEncoding, Range, SchemaIndex};
 use apollo_parser::cst::{self, CstNode};
 use apollo_parser::Parser;
 
@@ -21,6 +21,7 @@ impl LintRule for DeprecatedFieldRule {
         document: &str,
         schema_index: &SchemaIndex,
         _file_name: &str,
+        encoding: PositionEncoding,
     ) -> Vec<Diagnostic> {
         let mut warnings = Vec::new();
         let parser = Parser::new(document);
@@ -32,35 +33,21 @@ impl LintRule for DeprecatedFieldRule {
         }
 
         let doc_cst = tree.document();
+        let line_index = LineIndex::with_encoding(document, encoding);
 
         // Walk through all definitions in the document
         for definition in doc_cst.definitions() {
             if let cst::Definition::OperationDefinition(operation) = definition {
                 // Get the root type name for this operation
-                let root_type_name = match operation.operation_type() {
-                    Some(op_type) if op_type.query_token().is_some() => {
-                        schema_index.schema().schema_definition.query.as_ref()
-                    }
-                    Some(op_type) if op_type.mutation_token().is_some() => {
-                        schema_index.schema().schema_definition.mutation.as_ref()
-                    }
-                    Some(op_type) if op_type.subscription_token().is_some() => schema_index
-                        .schema()
-                        .schema_definition
-                        .subscription
-                        .as_ref(),
-                    None => schema_index.schema().schema_definition.query.as_ref(),
-                    _ => None,
-                };
-
-                if let Some(root_type_name) = root_type_name {
+                if let Some(root_type_name) = schema_index.root_type(OperationType::of(&operation))
+                {
                     if let Some(selection_set) = operation.selection_set() {
                         check_selection_set_cst(
                             &selection_set,
-                            root_type_name.as_str(),
+                            root_type_name,
                             schema_index,
                             &mut warnings,
-                            document,
+                            &line_index,
                         );
                     }
                 }
@@ -77,7 +64,7 @@ fn check_selection_set_cst(
     parent_type_name: &str,
     schema_index: &SchemaIndex,
     warnings: &mut Vec<Diagnostic>,
-    document: &str,
+    line_index: &LineIndex,
 ) {
     for selection in selection_set.selections() {
         match selection {
@@ -92,17 +79,12 @@ fn check_selection_set_cst(
                                 // Get the source location of the field
                                 let syntax_node = field_name.syntax();
                                 let offset: usize = syntax_node.text_range().start().into();
-                                let line_col = offset_to_line_col(document, offset);
+                                let start = line_index.offset_to_position(offset);
 
                                 let range = Range {
-                                    start: Position {
-                                        line: line_col.0,
-                                        character: line_col.1,
-                                    },
-                                    end: Position {
-                                        line: line_col.0,
-                                        character: line_col.1 + field_name_str.len(),
-                                    },
+                                    start,
+                                    end: line_index
+                                        .offset_to_position(offset + field_name_str.len()),
                                 };
 
                                 let message =
@@ -127,7 +109,7 @@ fn check_selection_set_cst(
                                     nested_type,
                                     schema_index,
                                     warnings,
-                                    document,
+                                    line_index,
                                 );
                             }
                         }
@@ -154,7 +136,7 @@ fn check_selection_set_cst(
                         type_name_ref,
                         schema_index,
                         warnings,
-                        document,
+                        line_index,
                     );
                 }
             }
@@ -162,30 +144,6 @@ fn check_selection_set_cst(
     }
 }
 
-/// Convert a byte offset to a line and column (0-indexed)
-fn offset_to_line_col(document: &str, offset: usize) -> (usize, usize) {
-    let mut line = 0;
-    let mut col = 0;
-    let mut current_offset = 0;
-
-    for ch in document.chars() {
-        if current_offset >= offset {
-            break;
-        }
-
-        if ch == '\n' {
-            line += 1;
-            col = 0;
-        } else {
-            col += 1;
-        }
-
-        current_offset += ch.len_utf8();
-    }
-
-    (line, col)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -219,7 +177,7 @@ mod tests {
             }
         ";
 
-        let warnings = rule.check(document, &schema, "test.graphql");
+        let warnings = rule.check(document, &schema, "test.graphql", PositionEncoding::Utf16);
 
         assert_eq!(warnings.len(), 1, "Should have exactly one warning");
         assert!(warnings[0].message.contains("email"));
@@ -257,7 +215,7 @@ mod tests {
             }
         ";
 
-        let warnings = rule.check(document, &schema, "test.graphql");
+        let warnings = rule.check(document, &schema, "test.graphql", PositionEncoding::Utf16);
 
         assert_eq!(warnings.len(), 2, "Should have two warnings");
         assert!(warnings.iter().any(|w| w.message.contains("oldName")));
@@ -301,7 +259,7 @@ mod tests {
             }
         ";
 
-        let warnings = rule.check(document, &schema, "test.graphql");
+        let warnings = rule.check(document, &schema, "test.graphql", PositionEncoding::Utf16);
 
         assert_eq!(warnings.len(), 1, "Should have one warning");
         assert!(warnings[0].message.contains("oldAvatar"));
@@ -336,7 +294,7 @@ mod tests {
             }
         ";
 
-        let warnings = rule.check(document, &schema, "test.graphql");
+        let warnings = rule.check(document, &schema, "test.graphql", PositionEncoding::Utf16);
 
         assert_eq!(warnings.len(), 0, "Should have no warnings");
     }
@@ -0,0 +1,250 @@
+use crate::{Diagnostic, DocumentIndex, LineIndex, SchemaIndex};
+use apollo_parser::cst;
+use std::collections::{HashMap, HashSet};
+
+use super::{fragment_selection_sets, operation_name_range, project_documents, ProjectLintRule};
+
+/// Depth allowed when the rule is enabled without a `maxDepth` option
+const DEFAULT_MAX_DEPTH: usize = 7;
+
+/// Lint rule that checks operations don't nest selections deeper than a configured limit
+///
+/// Each field with a selection set adds a level; inline fragments don't. Spread fragments
+/// add the depth of their own selections. Configure the limit with:
+///
+/// ```yaml
+/// max-depth:
+///   severity: error
+///   options:
+///     maxDepth: 5
+/// ```
+pub struct MaxDepthRule;
+
+impl ProjectLintRule for MaxDepthRule {
+    fn name(&self) -> &'static str {
+        "max-depth"
+    }
+
+    fn description(&self) -> &'static str {
+        "Reports operations whose selections nest deeper than the configured limit, counting fragments"
+    }
+
+    fn check_project(
+        &self,
+        document_index: &DocumentIndex,
+        schema_index: &SchemaIndex,
+    ) -> Vec<Diagnostic> {
+        self.check_project_with_options(document_index, schema_index, None)
+    }
+
+    fn check_project_with_options(
+        &self,
+        document_index: &DocumentIndex,
+        _schema_index: &SchemaIndex,
+        options: Option<&serde_json::Value>,
+    ) -> Vec<Diagnostic> {
+        let max_depth = options
+            .and_then(|options| options.get("maxDepth"))
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|max_depth| usize::try_from(max_depth).ok())
+            .unwrap_or(DEFAULT_MAX_DEPTH);
+
+        let documents = project_documents(document_index);
+        let fragments = fragment_selection_sets(&documents);
+
+        let mut measurer = DepthMeasurer {
+            fragments: &fragments,
+            fragment_depths: HashMap::new(),
+            visiting: HashSet::new(),
+        };
+
+        let mut diagnostics = Vec::new();
+        for document in &documents {
+            let line_index =
+                LineIndex::with_encoding(&document.source, document_index.position_encoding);
+
+            for definition in document.tree.document().definitions() {
+                let cst::Definition::OperationDefinition(operation) = definition else {
+                    continue;
+                };
+                let Some(selection_set) = operation.selection_set() else {
+                    continue;
+                };
+
+                let depth = measurer.depth(&selection_set);
+                if depth <= max_depth {
+                    continue;
+                }
+
+                let subject = operation.name().map_or_else(
+                    || "Anonymous operation".to_string(),
+                    |name| format!("Operation '{}'", name.text()),
+                );
+                let message = format!(
+                    "{subject} has a selection depth of {depth}, more than the maximum of {max_depth}"
+                );
+
+                let Some(range) = operation_name_range(&operation, document, &line_index) else {
+                    continue;
+                };
+
+                diagnostics.push(
+                    Diagnostic::error(range, message)
+                        .with_code("max-depth")
+                        .with_source(format!("graphql-linter:{}", document.file_path)),
+                );
+            }
+        }
+
+        diagnostics
+    }
+}
+
+/// Measures the nesting depth of selection sets, expanding fragment spreads
+struct DepthMeasurer<'a> {
+    fragments: &'a HashMap<String, cst::SelectionSet>,
+    /// Fragment depths, so each fragment is only walked once
+    fragment_depths: HashMap<String, usize>,
+    /// Fragments being expanded, to stop at cycles
+    visiting: HashSet<String>,
+}
+
+impl DepthMeasurer<'_> {
+    fn depth(&mut self, selection_set: &cst::SelectionSet) -> usize {
+        let mut depth = 0;
+
+        for selection in selection_set.selections() {
+            let selected = match selection {
+                cst::Selection::Field(field) => {
+                    field
+                        .selection_set()
+                        .map_or(0, |nested| self.depth(&nested))
+                        + 1
+                }
+                cst::Selection::InlineFragment(inline_fragment) => inline_fragment
+                    .selection_set()
+                    .map_or(0, |nested| self.depth(&nested)),
+                cst::Selection::FragmentSpread(spread) => spread
+                    .fragment_name()
+                    .and_then(|name| name.name())
+                    .map_or(0, |name| self.fragment_depth(&name.text())),
+            };
+            depth = depth.max(selected);
+        }
+
+        depth
+    }
+
+    fn fragment_depth(&mut self, name: &str) -> usize {
+        if let Some(&depth) = self.fragment_depths.get(name) {
+            return depth;
+        }
+        // A cycle is reported by validation; stop at the fragment's second visit
+        if !self.visiting.insert(name.to_string()) {
+            return 0;
+        }
+        let Some(selection_set) = self.fragments.get(name) else {
+            self.visiting.remove(name);
+            return 0;
+        };
+
+        let depth = self.depth(selection_set);
+        self.visiting.remove(name);
+        self.fragment_depths.insert(name.to_string(), depth);
+        depth
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Position, Range};
+    use apollo_parser::Parser;
+    use std::sync::Arc;
+
+    fn create_document_index(files: &[(&str, &str)]) -> DocumentIndex {
+        let mut index = DocumentIndex::new();
+        for (file_path, source) in files {
+            index.cache_ast(
+                (*file_path).to_string(),
+                Arc::new(Parser::new(source).parse()),
+            );
+        }
+        index
+    }
+
+    fn check(document_index: &DocumentIndex, max_depth: usize) -> Vec<Diagnostic> {
+        MaxDepthRule.check_project_with_options(
+            document_index,
+            &SchemaIndex::new(),
+            Some(&serde_json::json!({ "maxDepth": max_depth })),
+        )
+    }
+
+    #[test]
+    fn test_deep_operation_is_flagged() {
+        let document_index = create_document_index(&[(
+            "/queries.graphql",
+            "query Deep { a { b { c { d { e { f { g { h { i { j } } } } } } } } } }\n\nquery Shallow { a { b } }\n",
+        )]);
+
+        let diagnostics = check(&document_index, 5);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "Operation 'Deep' has a selection depth of 10, more than the maximum of 5"
+        );
+        assert_eq!(diagnostics[0].code.as_deref(), Some("max-depth"));
+        assert_eq!(diagnostics[0].source, "graphql-linter:/queries.graphql");
+        assert_eq!(
+            diagnostics[0].range,
+            Range {
+                start: Position {
+                    line: 0,
+                    character: 6
+                },
+                end: Position {
+                    line: 0,
+                    character: 10
+                },
+            }
+        );
+
+        assert!(check(&document_index, 10).is_empty());
+    }
+
+    #[test]
+    fn test_fragment_depth_is_counted() {
+        let document_index = create_document_index(&[
+            (
+                "/queries.graphql",
+                "query GetUser { user { ...UserFields } }\n",
+            ),
+            (
+                "/fragments.graphql",
+                "fragment UserFields on User {\n  id\n  ... on User { friends { ...FriendFields } }\n}\n\nfragment FriendFields on User { posts { title } }\n",
+            ),
+        ]);
+
+        // user, friends, posts, title
+        assert!(check(&document_index, 4).is_empty());
+
+        let diagnostics = check(&document_index, 3);
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].message.contains("selection depth of 4"));
+    }
+
+    #[test]
+    fn test_fragment_cycles_terminate() {
+        let document_index = create_document_index(&[(
+            "/cycle.graphql",
+            "query Q { node { ...A } }\nfragment A on Node { parent { ...B } }\nfragment B on Node { parent { ...A } }\n",
+        )]);
+
+        let diagnostics = check(&document_index, 2);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].message.contains("selection depth of 3"));
+    }
+}
@@ -0,0 +1,232 @@
+use crate::{Diagnostic, DocumentIndex, LineIndex, SchemaIndex};
+use apollo_parser::cst;
+use std::collections::{HashMap, HashSet};
+
+use super::{fragment_selection_sets, operation_name_range, project_documents, ProjectLintRule};
+
+/// Budget used when the rule is enabled without a `max` option
+const DEFAULT_MAX_SELECTIONS: usize = 200;
+
+/// Lint rule that checks operations don't select more fields than a configured budget
+///
+/// Every field selection counts, including aliases of the same field and the fields of
+/// spread fragments, expanded each time they are spread. Configure the budget with:
+///
+/// ```yaml
+/// max_selections:
+///   severity: error
+///   options:
+///     max: 100
+/// ```
+pub struct MaxSelectionsRule;
+
+impl ProjectLintRule for MaxSelectionsRule {
+    fn name(&self) -> &'static str {
+        "max_selections"
+    }
+
+    fn description(&self) -> &'static str {
+        "Reports operations that select more fields than the configured budget, counting fragments"
+    }
+
+    fn check_project(
+        &self,
+        document_index: &DocumentIndex,
+        schema_index: &SchemaIndex,
+    ) -> Vec<Diagnostic> {
+        self.check_project_with_options(document_index, schema_index, None)
+    }
+
+    fn check_project_with_options(
+        &self,
+        document_index: &DocumentIndex,
+        _schema_index: &SchemaIndex,
+        options: Option<&serde_json::Value>,
+    ) -> Vec<Diagnostic> {
+        let max = options
+            .and_then(|options| options.get("max"))
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|max| usize::try_from(max).ok())
+            .unwrap_or(DEFAULT_MAX_SELECTIONS);
+
+        let documents = project_documents(document_index);
+        let fragments = fragment_selection_sets(&documents);
+
+        let mut counter = SelectionCounter {
+            fragments: &fragments,
+            fragment_counts: HashMap::new(),
+            visiting: HashSet::new(),
+        };
+
+        let mut diagnostics = Vec::new();
+        for document in &documents {
+            let line_index =
+                LineIndex::with_encoding(&document.source, document_index.position_encoding);
+
+            for definition in document.tree.document().definitions() {
+                let cst::Definition::OperationDefinition(operation) = definition else {
+                    continue;
+                };
+                let Some(selection_set) = operation.selection_set() else {
+                    continue;
+                };
+
+                let count = counter.count(&selection_set);
+                if count <= max {
+                    continue;
+                }
+
+                let subject = operation.name().map_or_else(
+                    || "Anonymous operation".to_string(),
+                    |name| format!("Operation '{}'", name.text()),
+                );
+                let message =
+                    format!("{subject} selects {count} fields, more than the budget of {max}");
+
+                let Some(range) = operation_name_range(&operation, document, &line_index) else {
+                    continue;
+                };
+
+                diagnostics.push(
+                    Diagnostic::error(range, message)
+                        .with_code("max_selections")
+                        .with_source(format!("graphql-linter:{}", document.file_path)),
+                );
+            }
+        }
+
+        diagnostics
+    }
+}
+
+/// Counts field selections, expanding fragment spreads
+struct SelectionCounter<'a> {
+    fragments: &'a HashMap<String, cst::SelectionSet>,
+    /// Fragment selection counts, so each fragment is only walked once
+    fragment_counts: HashMap<String, usize>,
+    /// Fragments being expanded, to stop at cycles
+    visiting: HashSet<String>,
+}
+
+impl SelectionCounter<'_> {
+    fn count(&mut self, selection_set: &cst::SelectionSet) -> usize {
+        let mut count: usize = 0;
+
+        for selection in selection_set.selections() {
+            let selected = match selection {
+                cst::Selection::Field(field) => field
+                    .selection_set()
+                    .map_or(0, |nested| self.count(&nested))
+                    .saturating_add(1),
+                cst::Selection::InlineFragment(inline_fragment) => inline_fragment
+                    .selection_set()
+                    .map_or(0, |nested| self.count(&nested)),
+                cst::Selection::FragmentSpread(spread) => spread
+                    .fragment_name()
+                    .and_then(|name| name.name())
+                    .map_or(0, |name| self.count_fragment(&name.text())),
+            };
+            count = count.saturating_add(selected);
+        }
+
+        count
+    }
+
+    fn count_fragment(&mut self, name: &str) -> usize {
+        if let Some(&count) = self.fragment_counts.get(name) {
+            return count;
+        }
+        // A cycle is reported by validation; count the fragment once and stop
+        if !self.visiting.insert(name.to_string()) {
+            return 0;
+        }
+        let Some(selection_set) = self.fragments.get(name) else {
+            self.visiting.remove(name);
+            return 0;
+        };
+
+        let count = self.count(selection_set);
+        self.visiting.remove(name);
+        self.fragment_counts.insert(name.to_string(), count);
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Position, Range};
+    use apollo_parser::Parser;
+    use std::sync::Arc;
+
+    fn create_document_index(files: &[(&str, &str)]) -> DocumentIndex {
+        let mut index = DocumentIndex::new();
+        for (file_path, source) in files {
+            index.cache_ast(
+                (*file_path).to_string(),
+                Arc::new(Parser::new(source).parse()),
+            );
+        }
+        index
+    }
+
+    fn check(document_index: &DocumentIndex, max: usize) -> Vec<Diagnostic> {
+        MaxSelectionsRule.check_project_with_options(
+            document_index,
+            &SchemaIndex::new(),
+            Some(&serde_json::json!({ "max": max })),
+        )
+    }
+
+    #[test]
+    fn test_operation_over_budget() {
+        let document_index = create_document_index(&[
+            (
+                "/queries.graphql",
+                "query GetUser {\n  user {\n    id\n    ...UserFields\n    friends { ...UserFields }\n  }\n}\n\nquery Small { viewer { id } }\n",
+            ),
+            (
+                "/fragments.graphql",
+                "fragment UserFields on User {\n  name\n  email\n}\n",
+            ),
+        ]);
+
+        // user, id, 2 from UserFields, friends, 2 more from UserFields
+        let diagnostics = check(&document_index, 6);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "Operation 'GetUser' selects 7 fields, more than the budget of 6"
+        );
+        assert_eq!(diagnostics[0].source, "graphql-linter:/queries.graphql");
+        assert_eq!(
+            diagnostics[0].range,
+            Range {
+                start: Position {
+                    line: 0,
+                    character: 6
+                },
+                end: Position {
+                    line: 0,
+                    character: 13
+                },
+            }
+        );
+
+        assert!(check(&document_index, 7).is_empty());
+    }
+
+    #[test]
+    fn test_fragment_cycles_terminate() {
+        let document_index = create_document_index(&[(
+            "/cycle.graphql",
+            "query Q { node { ...A } }\nfragment A on Node { id ...B }\nfragment B on Node { name ...A }\n",
+        )]);
+
+        let diagnostics = check(&document_index, 2);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].message.contains("selects 3 fields"));
+    }
+}
@@ -1,12 +1,32 @@
 mod deprecated;
+mod max_depth;
+mod max_selections;
+mod naming_convention;
+mod no_anonymous_operations;
+mod no_duplicate_fields;
+mod no_unused_variables;
+mod require_description;
+mod require_id;
 mod unique_names;
 mod unused_fields;
 
 pub use deprecated::DeprecatedFieldRule;
+pub use max_depth::MaxDepthRule;
+pub use max_selections::MaxSelectionsRule;
+pub use naming_convention::NamingConventionRule;
+pub use no_anonymous_operations::NoAnonymousOperationsRule;
+pub use no_duplicate_fields::NoDuplicateFieldsRule;
+pub use no_unused_variables::NoUnusedVariablesRule;
+pub use require_description::RequireDescriptionRule;
+pub use require_id::RequireIdRule;
 pub use unique_names::UniqueNamesRule;
 pub use unused_fields::UnusedFieldsRule;
 
-use crate::{Diagnostic, DocumentIndex, SchemaIndex};
+use crate::{Diagnostic, DocumentIndex, LineIndex, Position, PositionEncoding, Range, SchemaIndex};
+use apollo_parser::cst::{self, CstNode};
+use apollo_parser::SyntaxTree;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
 
 /// Trait for implementing per-document lint rules
 pub trait LintRule {
@@ -17,9 +37,14 @@ pub trait LintRule {
     #[allow(dead_code)]
     fn description(&self) -> &'static str;
 
-    /// Run the lint check on a document
-    fn check(&self, document: &str, schema_index: &SchemaIndex, file_name: &str)
-        -> Vec<Diagnostic>;
+    /// Run the lint check on a document, counting diagnostic columns in `encoding`
+    fn check(
+        &self,
+        document: &str,
+        schema_index: &SchemaIndex,
+        file_name: &str,
+        encoding: PositionEncoding,
+    ) -> Vec<Diagnostic>;
 }
 
 /// Trait for implementing project-wide lint rules that need access to all documents
@@ -37,14 +62,192 @@ pub trait ProjectLintRule {
         document_index: &DocumentIndex,
         schema_index: &SchemaIndex,
     ) -> Vec<Diagnostic>;
+
+    /// Run the lint check with the `options` configured for the rule
+    ///
+    /// Rules without options don't need to override this.
+    fn check_project_with_options(
+        &self,
+        document_index: &DocumentIndex,
+        schema_index: &SchemaIndex,
+        _options: Option<&serde_json::Value>,
+    ) -> Vec<Diagnostic> {
+        self.check_project(document_index, schema_index)
+    }
+}
+
+/// Trait for implementing lint rules that check the schema itself
+pub trait SchemaLintRule {
+    /// Unique identifier for this rule (e.g., "require-description")
+    fn name(&self) -> &'static str;
+
+    /// Human-readable description
+    #[allow(dead_code)]
+    fn description(&self) -> &'static str;
+
+    /// Run the lint check on the schema
+    fn check_schema(&self, schema_index: &SchemaIndex) -> Vec<Diagnostic>;
+
+    /// Run the lint check with the `options` configured for the rule
+    ///
+    /// Rules without options don't need to override this.
+    fn check_schema_with_options(
+        &self,
+        schema_index: &SchemaIndex,
+        _options: Option<&serde_json::Value>,
+    ) -> Vec<Diagnostic> {
+        self.check_schema(schema_index)
+    }
 }
 
 /// Get all available per-document lint rules
 pub fn all_rules() -> Vec<Box<dyn LintRule>> {
-    vec![Box::new(DeprecatedFieldRule)]
+    vec![
+        Box::new(DeprecatedFieldRule),
+        Box::new(NoAnonymousOperationsRule),
+        Box::new(NoUnusedVariablesRule),
+        Box::new(NoDuplicateFieldsRule),
+    ]
 }
 
 /// Get all available project-wide lint rules
 pub fn all_project_rules() -> Vec<Box<dyn ProjectLintRule>> {
-    vec![Box::new(UniqueNamesRule), Box::new(UnusedFieldsRule)]
+    vec![
+        Box::new(UniqueNamesRule),
+        Box::new(UnusedFieldsRule),
+        Box::new(MaxSelectionsRule),
+        Box::new(MaxDepthRule),
+        Box::new(RequireIdRule),
+        Box::new(NamingConventionRule),
+    ]
+}
+
+/// Get all available schema lint rules
+pub fn all_schema_rules() -> Vec<Box<dyn SchemaLintRule>> {
+    vec![Box::new(RequireDescriptionRule)]
+}
+
+/// Parse the options configured for a rule
+///
+/// Options that don't match the rule's format are reported with a warning and the
+/// defaults are used, rather than silently ignoring what was configured.
+fn parse_options<T: DeserializeOwned + Default>(
+    rule_name: &str,
+    options: Option<&serde_json::Value>,
+) -> T {
+    let Some(options) = options else {
+        return T::default();
+    };
+
+    serde_json::from_value(options.clone()).unwrap_or_else(|e| {
+        tracing::warn!("Ignoring invalid options for lint rule '{rule_name}': {e}");
+        T::default()
+    })
+}
+
+/// A GraphQL document of the project, with where it starts in its file
+struct ProjectDocument<'a> {
+    file_path: &'a str,
+    tree: &'a SyntaxTree,
+    source: String,
+    start_line: usize,
+    start_column: usize,
+}
+
+/// Every document in the project: pure GraphQL files and blocks extracted from other files
+fn project_documents(document_index: &DocumentIndex) -> Vec<ProjectDocument<'_>> {
+    let mut documents = Vec::new();
+    for (file_path, tree) in &document_index.parsed_asts {
+        documents.push(ProjectDocument {
+            file_path,
+            tree,
+            source: tree.document().syntax().to_string(),
+            start_line: 0,
+            start_column: 0,
+        });
+    }
+    for (file_path, blocks) in &document_index.extracted_blocks {
+        for block in blocks {
+            documents.push(ProjectDocument {
+                file_path,
+                tree: &block.parsed,
+                source: block.content.clone(),
+                start_line: block.start_line,
+                start_column: block.start_column,
+            });
+        }
+    }
+    documents
+}
+
+/// The selection set of each fragment defined in the documents, by fragment name
+///
+/// A fragment defined more than once keeps its first definition.
+fn fragment_selection_sets(documents: &[ProjectDocument]) -> HashMap<String, cst::SelectionSet> {
+    let mut fragments = HashMap::new();
+    for document in documents {
+        for definition in document.tree.document().definitions() {
+            if let cst::Definition::FragmentDefinition(fragment) = definition {
+                let name = fragment
+                    .fragment_name()
+                    .and_then(|name| name.name())
+                    .map(|name| name.text().to_string());
+                if let (Some(name), Some(selection_set)) = (name, fragment.selection_set()) {
+                    fragments.entry(name).or_insert(selection_set);
+                }
+            }
+        }
+    }
+    fragments
+}
+
+/// File range of an operation's name, or of the keyword (or opening brace) of an anonymous
+/// operation
+fn operation_name_range(
+    operation: &cst::OperationDefinition,
+    document: &ProjectDocument,
+    line_index: &LineIndex,
+) -> Option<Range> {
+    let text_range = operation.name().map_or_else(
+        || {
+            operation.operation_type().map_or_else(
+                || {
+                    operation
+                        .selection_set()
+                        .and_then(|selection_set| selection_set.syntax().first_token())
+                        .map(|token| token.text_range())
+                },
+                |op_type| Some(op_type.syntax().text_range()),
+            )
+        },
+        |name| Some(name.syntax().text_range()),
+    )?;
+    let start: usize = text_range.start().into();
+    let end: usize = text_range.end().into();
+    let end = start + document.source[start..end].trim_end().len();
+
+    Some(Range {
+        start: offset_position(
+            line_index.offset_to_position(start),
+            document.start_line,
+            document.start_column,
+        ),
+        end: offset_position(
+            line_index.offset_to_position(end),
+            document.start_line,
+            document.start_column,
+        ),
+    })
+}
+
+/// Shift a block-relative position to a file position
+const fn offset_position(position: Position, start_line: usize, start_column: usize) -> Position {
+    Position {
+        line: position.line + start_line,
+        character: if position.line == 0 {
+            position.character + start_column
+        } else {
+            position.character
+        },
+    }
 }
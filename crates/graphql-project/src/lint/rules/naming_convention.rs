@@ -0,0 +1,546 @@
+use crate::{Diagnostic, DocumentIndex, LineIndex, OperationType, Position, Range, SchemaIndex};
+use apollo_compiler::schema::ExtendedType;
+use apollo_compiler::Name;
+use apollo_parser::cst;
+use serde::Deserialize;
+
+use super::{operation_name_range, parse_options, project_documents, ProjectLintRule};
+
+/// Lint rule that checks schema names follow the configured casing conventions
+///
+/// By default type names are `PascalCase`, field and argument names `camelCase`, and enum
+/// values `SCREAMING_SNAKE_CASE`. Leading underscores are ignored, so names like `_service`
+/// pass. Configure the conventions and exempt names (or coordinates like `User.ID`) with:
+///
+/// ```yaml
+/// naming-convention:
+///   severity: warn
+///   options:
+///     fields: snake_case
+///     allow: [SSN, User.legacy_id]
+/// ```
+///
+/// Operation names can also be required to end with a suffix for their kind. An empty
+/// suffix requires the name to not end with another kind's suffix, so with the options
+/// below a query named `GetUserMutation` is reported:
+///
+/// ```yaml
+/// naming-convention:
+///   options:
+///     operationSuffixes:
+///       query: ""
+///       mutation: Mutation
+///       subscription: Subscription
+/// ```
+pub struct NamingConventionRule;
+
+/// A casing convention a name can be checked against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+enum Convention {
+    #[serde(rename = "PascalCase")]
+    PascalCase,
+    #[serde(rename = "camelCase")]
+    CamelCase,
+    #[serde(rename = "SCREAMING_SNAKE_CASE")]
+    ScreamingSnakeCase,
+    #[serde(rename = "snake_case")]
+    SnakeCase,
+}
+
+impl Convention {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::PascalCase => "PascalCase",
+            Self::CamelCase => "camelCase",
+            Self::ScreamingSnakeCase => "SCREAMING_SNAKE_CASE",
+            Self::SnakeCase => "snake_case",
+        }
+    }
+
+    fn matches(self, name: &str) -> bool {
+        let name = name.trim_start_matches('_');
+        let Some(first) = name.chars().next() else {
+            return true;
+        };
+
+        match self {
+            Self::PascalCase => {
+                first.is_ascii_uppercase() && name.chars().all(|c| c.is_ascii_alphanumeric())
+            }
+            Self::CamelCase => {
+                first.is_ascii_lowercase() && name.chars().all(|c| c.is_ascii_alphanumeric())
+            }
+            Self::ScreamingSnakeCase => name
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_'),
+            Self::SnakeCase => name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'),
+        }
+    }
+}
+
+/// Options for the naming convention rule
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct NamingConventionOptions {
+    types: Convention,
+    fields: Convention,
+    arguments: Convention,
+    enum_values: Convention,
+    /// Names or schema coordinates that are exempt from the conventions
+    allow: Vec<String>,
+    operation_suffixes: OperationSuffixes,
+}
+
+/// Suffix required of operation names, for each kind of operation
+///
+/// Kinds without a suffix aren't checked.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct OperationSuffixes {
+    query: Option<String>,
+    mutation: Option<String>,
+    subscription: Option<String>,
+}
+
+impl OperationSuffixes {
+    const fn get(&self, operation_type: OperationType) -> Option<&String> {
+        match operation_type {
+            OperationType::Query => self.query.as_ref(),
+            OperationType::Mutation => self.mutation.as_ref(),
+            OperationType::Subscription => self.subscription.as_ref(),
+        }
+    }
+
+    /// Why `name` doesn't fit the suffix for its kind, or `None` if it does
+    fn check(&self, name: &str, operation_type: OperationType) -> Option<String> {
+        let suffix = self.get(operation_type)?;
+        if !suffix.is_empty() {
+            return (!name.ends_with(suffix.as_str()))
+                .then(|| format!("should end with '{suffix}'"));
+        }
+
+        [
+            OperationType::Query,
+            OperationType::Mutation,
+            OperationType::Subscription,
+        ]
+        .into_iter()
+        .filter(|other| *other != operation_type)
+        .filter_map(|other| Some((other, self.get(other)?)))
+        .find(|(_, other_suffix)| !other_suffix.is_empty() && name.ends_with(other_suffix.as_str()))
+        .map(|(other, other_suffix)| {
+            format!(
+                "shouldn't end with '{other_suffix}', the suffix for {}s",
+                operation_kind(other).to_lowercase()
+            )
+        })
+    }
+}
+
+const fn operation_kind(operation_type: OperationType) -> &'static str {
+    match operation_type {
+        OperationType::Query => "Query",
+        OperationType::Mutation => "Mutation",
+        OperationType::Subscription => "Subscription",
+    }
+}
+
+impl Default for NamingConventionOptions {
+    fn default() -> Self {
+        Self {
+            types: Convention::PascalCase,
+            fields: Convention::CamelCase,
+            arguments: Convention::CamelCase,
+            enum_values: Convention::ScreamingSnakeCase,
+            allow: Vec::new(),
+            operation_suffixes: OperationSuffixes::default(),
+        }
+    }
+}
+
+impl NamingConventionRule {
+    /// Check type, field, argument, and enum value names against their conventions
+    fn check_schema_names(
+        schema_index: &SchemaIndex,
+        options: &NamingConventionOptions,
+    ) -> Vec<Diagnostic> {
+        let mut checker = NameChecker {
+            schema_index,
+            allow: &options.allow,
+            diagnostics: Vec::new(),
+        };
+
+        for (type_name, ext_type) in &schema_index.schema().types {
+            if ext_type.is_built_in() || type_name.starts_with("__") {
+                continue;
+            }
+
+            checker.check("Type", type_name, type_name, options.types);
+
+            match ext_type {
+                ExtendedType::Object(obj) => {
+                    for (field_name, field) in &obj.fields {
+                        let coordinate = format!("{type_name}.{field_name}");
+                        checker.check("Field", &coordinate, field_name, options.fields);
+                        for argument in &field.arguments {
+                            checker.check(
+                                "Argument",
+                                &format!("{coordinate}({}:)", argument.name),
+                                &argument.name,
+                                options.arguments,
+                            );
+                        }
+                    }
+                }
+                ExtendedType::Interface(iface) => {
+                    for (field_name, field) in &iface.fields {
+                        let coordinate = format!("{type_name}.{field_name}");
+                        checker.check("Field", &coordinate, field_name, options.fields);
+                        for argument in &field.arguments {
+                            checker.check(
+                                "Argument",
+                                &format!("{coordinate}({}:)", argument.name),
+                                &argument.name,
+                                options.arguments,
+                            );
+                        }
+                    }
+                }
+                ExtendedType::InputObject(input) => {
+                    for field_name in input.fields.keys() {
+                        checker.check(
+                            "Field",
+                            &format!("{type_name}.{field_name}"),
+                            field_name,
+                            options.fields,
+                        );
+                    }
+                }
+                ExtendedType::Enum(enum_def) => {
+                    for value_name in enum_def.values.keys() {
+                        checker.check(
+                            "Enum value",
+                            &format!("{type_name}.{value_name}"),
+                            value_name,
+                            options.enum_values,
+                        );
+                    }
+                }
+                ExtendedType::Union(_) | ExtendedType::Scalar(_) => {}
+            }
+        }
+
+        checker.diagnostics
+    }
+
+    /// Check that operation names end with the suffix configured for their kind
+    fn check_operation_names(
+        document_index: &DocumentIndex,
+        options: &NamingConventionOptions,
+    ) -> Vec<Diagnostic> {
+        let suffixes = &options.operation_suffixes;
+
+        let mut diagnostics = Vec::new();
+        for document in &project_documents(document_index) {
+            let line_index =
+                LineIndex::with_encoding(&document.source, document_index.position_encoding);
+
+            for definition in document.tree.document().definitions() {
+                let cst::Definition::OperationDefinition(operation) = definition else {
+                    continue;
+                };
+                let Some(name) = operation.name() else {
+                    continue;
+                };
+                let name = name.text().to_string();
+                if options.allow.contains(&name) {
+                    continue;
+                }
+
+                let operation_type = OperationType::of(&operation);
+                let Some(problem) = suffixes.check(&name, operation_type) else {
+                    continue;
+                };
+                let Some(range) = operation_name_range(&operation, document, &line_index) else {
+                    continue;
+                };
+
+                diagnostics.push(
+                    Diagnostic::warning(
+                        range,
+                        format!("{} '{name}' {problem}", operation_kind(operation_type)),
+                    )
+                    .with_code("naming-convention")
+                    .with_source(format!("graphql-linter:{}", document.file_path)),
+                );
+            }
+        }
+
+        diagnostics
+    }
+}
+
+impl ProjectLintRule for NamingConventionRule {
+    fn name(&self) -> &'static str {
+        "naming-convention"
+    }
+
+    fn description(&self) -> &'static str {
+        "Requires casing conventions for schema names and kind suffixes for operation names"
+    }
+
+    fn check_project(
+        &self,
+        document_index: &DocumentIndex,
+        schema_index: &SchemaIndex,
+    ) -> Vec<Diagnostic> {
+        self.check_project_with_options(document_index, schema_index, None)
+    }
+
+    fn check_project_with_options(
+        &self,
+        document_index: &DocumentIndex,
+        schema_index: &SchemaIndex,
+        options: Option<&serde_json::Value>,
+    ) -> Vec<Diagnostic> {
+        let options: NamingConventionOptions = parse_options(self.name(), options);
+
+        let mut diagnostics = Self::check_schema_names(schema_index, &options);
+        diagnostics.extend(Self::check_operation_names(document_index, &options));
+        diagnostics
+    }
+}
+
+/// Checks names against a convention, collecting a diagnostic for each violation
+struct NameChecker<'a> {
+    schema_index: &'a SchemaIndex,
+    allow: &'a [String],
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl NameChecker<'_> {
+    /// Check the definition `name`, known by its schema `coordinate`
+    ///
+    /// Names without a location in a local schema file are skipped, since there is nowhere
+    /// to report them.
+    fn check(&mut self, kind: &str, coordinate: &str, name: &Name, convention: Convention) {
+        if convention.matches(name) {
+            return;
+        }
+        if self
+            .allow
+            .iter()
+            .any(|allowed| allowed == name.as_str() || allowed == coordinate)
+        {
+            return;
+        }
+        let Some((file_path, line, column)) = self.schema_index.name_location(name) else {
+            return;
+        };
+
+        let range = Range {
+            start: Position {
+                line,
+                character: column,
+            },
+            end: Position {
+                line,
+                character: column + name.len(),
+            },
+        };
+        let message = format!("{kind} '{coordinate}' should be {}", convention.as_str());
+
+        self.diagnostics.push(
+            Diagnostic::warning(range, message)
+                .with_code("naming-convention")
+                .with_source(format!("graphql-linter:{file_path}")),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = r"type Query {
+  user(user_id: ID!): user_profile
+  search(term: String): [SearchResult]
+}
+
+type user_profile {
+  id: ID!
+  first_name: String
+  role: Role
+}
+
+union SearchResult = user_profile
+
+enum Role {
+  ADMIN
+  editor
+}
+
+input UserFilter {
+  NameContains: String
+}
+";
+
+    #[test]
+    fn test_default_conventions() {
+        let schema = SchemaIndex::from_schema(SCHEMA);
+
+        let diagnostics = NamingConventionRule.check_project(&DocumentIndex::new(), &schema);
+        let messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+
+        assert_eq!(
+            messages,
+            vec![
+                "Argument 'Query.user(user_id:)' should be camelCase",
+                "Type 'user_profile' should be PascalCase",
+                "Field 'user_profile.first_name' should be camelCase",
+                "Enum value 'Role.editor' should be SCREAMING_SNAKE_CASE",
+                "Field 'UserFilter.NameContains' should be camelCase",
+            ]
+        );
+        assert_eq!(diagnostics[1].source, "graphql-linter:schema.graphql");
+        assert_eq!(
+            diagnostics[1].range,
+            Range {
+                start: Position {
+                    line: 5,
+                    character: 5
+                },
+                end: Position {
+                    line: 5,
+                    character: 17
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn test_configured_conventions_and_allow_list() {
+        let schema = SchemaIndex::from_schema(SCHEMA);
+
+        let diagnostics = NamingConventionRule.check_project_with_options(
+            &DocumentIndex::new(),
+            &schema,
+            Some(&serde_json::json!({
+                "fields": "snake_case",
+                "allow": ["user_profile", "Role.editor", "user_id"],
+            })),
+        );
+        let messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+
+        assert_eq!(
+            messages,
+            vec!["Field 'UserFilter.NameContains' should be snake_case"]
+        );
+    }
+
+    #[test]
+    fn test_invalid_options_fall_back_to_defaults() {
+        let schema = SchemaIndex::from_schema(SCHEMA);
+
+        let diagnostics = NamingConventionRule.check_project_with_options(
+            &DocumentIndex::new(),
+            &schema,
+            Some(&serde_json::json!({ "fields": "kebab-case" })),
+        );
+
+        assert_eq!(
+            diagnostics.len(),
+            NamingConventionRule
+                .check_project(&DocumentIndex::new(), &schema)
+                .len()
+        );
+    }
+
+    fn check_operations(source: &str) -> Vec<Diagnostic> {
+        let mut document_index = DocumentIndex::new();
+        document_index.cache_ast(
+            "/operations.graphql".to_string(),
+            std::sync::Arc::new(apollo_parser::Parser::new(source).parse()),
+        );
+
+        NamingConventionRule.check_project_with_options(
+            &document_index,
+            &SchemaIndex::new(),
+            Some(&serde_json::json!({
+                "operationSuffixes": {
+                    "query": "",
+                    "mutation": "Mutation",
+                    "subscription": "Subscription",
+                },
+            })),
+        )
+    }
+
+    #[test]
+    fn test_query_suffix() {
+        let diagnostics = check_operations(
+            "query GetUser { user { id } }\nquery GetUserMutation { user { id } }\n",
+        );
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "Query 'GetUserMutation' shouldn't end with 'Mutation', the suffix for mutations"
+        );
+        assert_eq!(diagnostics[0].source, "graphql-linter:/operations.graphql");
+        assert_eq!(
+            diagnostics[0].range,
+            Range {
+                start: Position {
+                    line: 1,
+                    character: 6
+                },
+                end: Position {
+                    line: 1,
+                    character: 21
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn test_mutation_suffix() {
+        let diagnostics = check_operations(
+            "mutation UpdateUserMutation { updateUser { id } }\nmutation UpdateUser { updateUser { id } }\n",
+        );
+
+        let messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec!["Mutation 'UpdateUser' should end with 'Mutation'"]
+        );
+    }
+
+    #[test]
+    fn test_subscription_suffix() {
+        let diagnostics = check_operations(
+            "subscription OnMessageSubscription { message }\nsubscription OnMessage { message }\n",
+        );
+
+        let messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec!["Subscription 'OnMessage' should end with 'Subscription'"]
+        );
+    }
+
+    #[test]
+    fn test_operation_suffixes_unchecked_by_default() {
+        let mut document_index = DocumentIndex::new();
+        document_index.cache_ast(
+            "/operations.graphql".to_string(),
+            std::sync::Arc::new(apollo_parser::Parser::new("mutation UpdateUser { id }").parse()),
+        );
+
+        assert!(NamingConventionRule
+            .check_project(&document_index, &SchemaIndex::new())
+            .is_empty());
+    }
+}
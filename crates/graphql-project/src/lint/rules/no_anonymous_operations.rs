@@ -0,0 +1,144 @@
+use crate::{Diagnostic, LineIndex, PositionEncoding, Range, SchemaIndex};
+use apollo_parser::cst::{self, CstNode};
+use apollo_parser::Parser;
+
+use super::LintRule;
+
+/// Lint rule that checks every operation has a name
+///
+/// Anonymous operations can't be referenced by name, registered with persisted query
+/// tooling, or listed in the document outline.
+pub struct NoAnonymousOperationsRule;
+
+impl LintRule for NoAnonymousOperationsRule {
+    fn name(&self) -> &'static str {
+        "no-anonymous-operations"
+    }
+
+    fn description(&self) -> &'static str {
+        "Requires every operation to have a name"
+    }
+
+    fn check(
+        &self,
+        document: &str,
+        _schema_index: &SchemaIndex,
+        _file_name: &str,
+        encoding: PositionEncoding,
+    ) -> Vec<Diagnostic> {
+        let mut warnings = Vec::new();
+        let parser = Parser::new(document);
+        let tree = parser.parse();
+
+        if tree.errors().len() > 0 {
+            return warnings;
+        }
+
+        let line_index = LineIndex::with_encoding(document, encoding);
+
+        for definition in tree.document().definitions() {
+            let cst::Definition::OperationDefinition(operation) = definition else {
+                continue;
+            };
+            if operation.name().is_some() {
+                continue;
+            }
+
+            // Point at the keyword, or the opening brace of the `{ ... }` shorthand
+            let node = operation.operation_type().map_or_else(
+                || {
+                    operation
+                        .selection_set()
+                        .and_then(|selection_set| selection_set.syntax().first_token())
+                        .map(|token| token.text_range())
+                },
+                |op_type| Some(op_type.syntax().text_range()),
+            );
+            let Some(text_range) = node else {
+                continue;
+            };
+            let start: usize = text_range.start().into();
+            let end: usize = text_range.end().into();
+            let end = start + document[start..end].trim_end().len();
+
+            let range = Range {
+                start: line_index.offset_to_position(start),
+                end: line_index.offset_to_position(end),
+            };
+
+            warnings.push(
+                Diagnostic::warning(
+                    range,
+                    "Anonymous operation. Give the operation a name so it can be referenced and identified.",
+                )
+                .with_code("no-anonymous-operations")
+                .with_source("graphql-linter"),
+            );
+        }
+
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Position;
+
+    #[test]
+    fn test_named_operation_passes() {
+        let schema = SchemaIndex::new();
+        let document = "query GetUser { user { id } }\nmutation UpdateUser { updateUser { id } }\n";
+
+        let warnings = NoAnonymousOperationsRule.check(
+            document,
+            &schema,
+            "test.graphql",
+            PositionEncoding::Utf16,
+        );
+
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_anonymous_operations_are_flagged() {
+        let schema = SchemaIndex::new();
+        let document = "{ user { id } }\n\nquery { viewer { id } }\n";
+
+        let warnings = NoAnonymousOperationsRule.check(
+            document,
+            &schema,
+            "test.graphql",
+            PositionEncoding::Utf16,
+        );
+
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0].code.as_deref(), Some("no-anonymous-operations"));
+        assert_eq!(
+            warnings[0].range,
+            Range {
+                start: Position {
+                    line: 0,
+                    character: 0
+                },
+                end: Position {
+                    line: 0,
+                    character: 1
+                },
+            }
+        );
+        assert_eq!(
+            warnings[1].range,
+            Range {
+                start: Position {
+                    line: 2,
+                    character: 0
+                },
+                end: Position {
+                    line: 2,
+                    character: 5
+                },
+            }
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{Diagnostic, LineIndex, PositionEncoding, Range, SchemaIndex};
+use apollo_parser::cst::{self, CstNode};
+use apollo_parser::Parser;
+use std::collections::HashSet;
+
+use super::LintRule;
+
+/// Lint rule that checks a selection set doesn't select the same response key twice
+///
+/// Duplicates like `{ name name }` are merged into one field of the response, so the second
+/// selection is usually a copy-paste mistake. Selections under different aliases, like
+/// `{ first: name second: name }`, are distinct and not reported.
+pub struct NoDuplicateFieldsRule;
+
+impl LintRule for NoDuplicateFieldsRule {
+    fn name(&self) -> &'static str {
+        "no-duplicate-fields"
+    }
+
+    fn description(&self) -> &'static str {
+        "Warns when a selection set selects the same field more than once"
+    }
+
+    fn check(
+        &self,
+        document: &str,
+        _schema_index: &SchemaIndex,
+        _file_name: &str,
+        encoding: PositionEncoding,
+    ) -> Vec<Diagnostic> {
+        let mut warnings = Vec::new();
+        let parser = Parser::new(document);
+        let tree = parser.parse();
+
+        if tree.errors().len() > 0 {
+            return warnings;
+        }
+
+        let line_index = LineIndex::with_encoding(document, encoding);
+
+        for selection_set in tree
+            .document()
+            .syntax()
+            .descendants()
+            .filter_map(cst::SelectionSet::cast)
+        {
+            let mut seen = HashSet::new();
+
+            for selection in selection_set.selections() {
+                let cst::Selection::Field(field) = selection else {
+                    continue;
+                };
+                // The alias if there is one, since that's the field's key in the response
+                let Some(key) = field
+                    .alias()
+                    .and_then(|alias| alias.name())
+                    .or_else(|| field.name())
+                else {
+                    continue;
+                };
+
+                let key_text = key.text().to_string();
+                if seen.insert(key_text.clone()) {
+                    continue;
+                }
+
+                let start: usize = key.syntax().text_range().start().into();
+                let range = Range {
+                    start: line_index.offset_to_position(start),
+                    end: line_index.offset_to_position(start + key_text.len()),
+                };
+
+                warnings.push(
+                    Diagnostic::warning(
+                        range,
+                        format!("Field '{key_text}' is already selected in this selection set"),
+                    )
+                    .with_code("no-duplicate-fields")
+                    .with_source("graphql-linter"),
+                );
+            }
+        }
+
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Position;
+
+    #[test]
+    fn test_duplicate_fields_are_flagged() {
+        let schema = SchemaIndex::new();
+        let document = "query GetUser {\n  user {\n    id\n    name\n    name\n    friends { id id }\n  }\n}\n";
+
+        let warnings =
+            NoDuplicateFieldsRule.check(document, &schema, "test.graphql", PositionEncoding::Utf16);
+
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(
+            warnings[0].message,
+            "Field 'name' is already selected in this selection set"
+        );
+        assert_eq!(warnings[0].code.as_deref(), Some("no-duplicate-fields"));
+        assert_eq!(
+            warnings[0].range,
+            Range {
+                start: Position {
+                    line: 4,
+                    character: 4
+                },
+                end: Position {
+                    line: 4,
+                    character: 8
+                },
+            }
+        );
+        assert_eq!(warnings[1].range.start.line, 5);
+        assert_eq!(warnings[1].range.start.character, 17);
+    }
+
+    #[test]
+    fn test_aliased_fields_pass() {
+        let schema = SchemaIndex::new();
+        let document = "query GetUsers {\n  first: user(id: 1) { name }\n  second: user(id: 2) { name }\n  user { name }\n}\n\nfragment UserName on User { name ... on User { name } }\n";
+
+        let warnings =
+            NoDuplicateFieldsRule.check(document, &schema, "test.graphql", PositionEncoding::Utf16);
+
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+}
@@ -0,0 +1,201 @@
+use crate::{Diagnostic, LineIndex, PositionEncoding, Range, SchemaIndex};
+use apollo_parser::cst::{self, CstNode};
+use apollo_parser::Parser;
+use std::collections::{HashMap, HashSet};
+
+use super::LintRule;
+
+/// Lint rule that checks every variable an operation declares is used
+///
+/// A variable counts as used when it's referenced anywhere in the operation's selections or
+/// directives, including in inline fragments and in fragments defined in the same document.
+/// Operations that spread a fragment from another file aren't checked, since the fragment
+/// could use the variable.
+pub struct NoUnusedVariablesRule;
+
+impl LintRule for NoUnusedVariablesRule {
+    fn name(&self) -> &'static str {
+        "no-unused-variables"
+    }
+
+    fn description(&self) -> &'static str {
+        "Warns on operation variables that are declared but never used"
+    }
+
+    fn check(
+        &self,
+        document: &str,
+        _schema_index: &SchemaIndex,
+        _file_name: &str,
+        encoding: PositionEncoding,
+    ) -> Vec<Diagnostic> {
+        let mut warnings = Vec::new();
+        let parser = Parser::new(document);
+        let tree = parser.parse();
+
+        if tree.errors().len() > 0 {
+            return warnings;
+        }
+
+        let doc_cst = tree.document();
+        let fragments: HashMap<String, cst::FragmentDefinition> = doc_cst
+            .definitions()
+            .filter_map(|definition| match definition {
+                cst::Definition::FragmentDefinition(fragment) => {
+                    let name = fragment.fragment_name()?.name()?.text().to_string();
+                    Some((name, fragment))
+                }
+                _ => None,
+            })
+            .collect();
+
+        let line_index = LineIndex::with_encoding(document, encoding);
+
+        for definition in doc_cst.definitions() {
+            let cst::Definition::OperationDefinition(operation) = definition else {
+                continue;
+            };
+            let Some(variable_definitions) = operation.variable_definitions() else {
+                continue;
+            };
+            let Some(used) = used_variables(&operation, &fragments) else {
+                continue;
+            };
+
+            for variable_definition in variable_definitions.variable_definitions() {
+                let Some(variable) = variable_definition.variable() else {
+                    continue;
+                };
+                let Some(name) = variable.name() else {
+                    continue;
+                };
+                let name = name.text().to_string();
+                if used.contains(&name) {
+                    continue;
+                }
+
+                let text_range = variable.syntax().text_range();
+                let range = Range {
+                    start: line_index.offset_to_position(text_range.start().into()),
+                    end: line_index.offset_to_position(text_range.end().into()),
+                };
+                let message = operation.name().map_or_else(
+                    || format!("Variable '${name}' is never used"),
+                    |operation_name| {
+                        format!(
+                            "Variable '${name}' is never used in operation '{}'",
+                            operation_name.text()
+                        )
+                    },
+                );
+
+                warnings.push(
+                    Diagnostic::warning(range, message)
+                        .with_code("no-unused-variables")
+                        .with_source("graphql-linter"),
+                );
+            }
+        }
+
+        warnings
+    }
+}
+
+/// Names of the variables referenced by an operation, following the fragments it spreads
+///
+/// Returns `None` if the operation spreads a fragment that isn't in `fragments`.
+fn used_variables(
+    operation: &cst::OperationDefinition,
+    fragments: &HashMap<String, cst::FragmentDefinition>,
+) -> Option<HashSet<String>> {
+    let mut used = HashSet::new();
+    let mut visited = HashSet::new();
+
+    let mut pending: Vec<apollo_parser::SyntaxNode> = Vec::new();
+    if let Some(directives) = operation.directives() {
+        pending.push(directives.syntax().clone());
+    }
+    if let Some(selection_set) = operation.selection_set() {
+        pending.push(selection_set.syntax().clone());
+    }
+
+    while let Some(node) = pending.pop() {
+        for descendant in node.descendants() {
+            if let Some(variable) = cst::Variable::cast(descendant.clone()) {
+                if let Some(name) = variable.name() {
+                    used.insert(name.text().to_string());
+                }
+            } else if let Some(spread) = cst::FragmentSpread::cast(descendant) {
+                let Some(name) = spread.fragment_name().and_then(|name| name.name()) else {
+                    continue;
+                };
+                let name = name.text().to_string();
+                let fragment = fragments.get(&name)?;
+                if visited.insert(name) {
+                    pending.push(fragment.syntax().clone());
+                }
+            }
+        }
+    }
+
+    Some(used)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Position;
+
+    #[test]
+    fn test_variable_used_in_nested_argument_passes() {
+        let schema = SchemaIndex::new();
+        let document = "query GetUser($id: ID!, $size: Int, $withName: Boolean!) {\n  viewer {\n    user(id: $id) {\n      ... on User { avatar(size: $size) }\n      ...UserName\n    }\n  }\n}\n\nfragment UserName on User {\n  name @include(if: $withName)\n}\n";
+
+        let warnings =
+            NoUnusedVariablesRule.check(document, &schema, "test.graphql", PositionEncoding::Utf16);
+
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+
+    #[test]
+    fn test_unused_variable_is_flagged() {
+        let schema = SchemaIndex::new();
+        let document =
+            "query GetUser($id: ID!, $unused: String) {\n  viewer {\n    user(id: $id) { name }\n  }\n}\n";
+
+        let warnings =
+            NoUnusedVariablesRule.check(document, &schema, "test.graphql", PositionEncoding::Utf16);
+
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warnings[0].message,
+            "Variable '$unused' is never used in operation 'GetUser'"
+        );
+        assert_eq!(warnings[0].code.as_deref(), Some("no-unused-variables"));
+        assert_eq!(
+            warnings[0].range,
+            Range {
+                start: Position {
+                    line: 0,
+                    character: 24
+                },
+                end: Position {
+                    line: 0,
+                    character: 31
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn test_operations_spreading_other_files_fragments_are_skipped() {
+        let schema = SchemaIndex::new();
+        let document =
+            "query GetUser($id: ID!, $size: Int) {\n  user(id: $id) { ...UserAvatar }\n}\n";
+
+        let warnings =
+            NoUnusedVariablesRule.check(document, &schema, "test.graphql", PositionEncoding::Utf16);
+
+        assert!(warnings.is_empty());
+    }
+}
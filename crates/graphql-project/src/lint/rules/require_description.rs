@@ -0,0 +1,221 @@
+use crate::{Diagnostic, Position, Range, SchemaIndex, TypeKind};
+use serde::Deserialize;
+
+use super::{parse_options, SchemaLintRule};
+
+/// Lint rule that checks object and interface types, their fields, and the fields'
+/// arguments have descriptions
+///
+/// Each kind of definition can be exempted, for example to only require type descriptions:
+///
+/// ```yaml
+/// require-description:
+///   severity: warn
+///   options:
+///     fields: false
+///     arguments: false
+/// ```
+pub struct RequireDescriptionRule;
+
+/// Options for the require description rule
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default)]
+struct RequireDescriptionOptions {
+    types: bool,
+    fields: bool,
+    arguments: bool,
+}
+
+impl Default for RequireDescriptionOptions {
+    fn default() -> Self {
+        Self {
+            types: true,
+            fields: true,
+            arguments: true,
+        }
+    }
+}
+
+impl SchemaLintRule for RequireDescriptionRule {
+    fn name(&self) -> &'static str {
+        "require-description"
+    }
+
+    fn description(&self) -> &'static str {
+        "Requires descriptions on object and interface types, fields, and arguments"
+    }
+
+    fn check_schema(&self, schema_index: &SchemaIndex) -> Vec<Diagnostic> {
+        self.check_schema_with_options(schema_index, None)
+    }
+
+    fn check_schema_with_options(
+        &self,
+        schema_index: &SchemaIndex,
+        options: Option<&serde_json::Value>,
+    ) -> Vec<Diagnostic> {
+        let options: RequireDescriptionOptions = parse_options(self.name(), options);
+
+        let mut diagnostics = Vec::new();
+
+        for type_info in schema_index.all_types() {
+            if !matches!(type_info.kind, TypeKind::Object | TypeKind::Interface)
+                || type_info.name.starts_with("__")
+            {
+                continue;
+            }
+            // Types without a local definition, like those of a remote schema, can't be
+            // given a description here
+            let Some(location) = schema_index.find_type_definition(&type_info.name) else {
+                continue;
+            };
+
+            if options.types && type_info.description.is_none() {
+                diagnostics.push(missing_description(
+                    &format!("Type '{}'", type_info.name),
+                    &location.file_path,
+                    location.line,
+                    location.column,
+                    type_info.name.len(),
+                ));
+            }
+
+            if !options.fields && !options.arguments {
+                continue;
+            }
+
+            for field in schema_index.get_fields(&type_info.name).unwrap_or_default() {
+                let coordinate = format!("{}.{}", type_info.name, field.name);
+
+                if options.fields && field.description.is_none() {
+                    if let Some(location) =
+                        schema_index.find_field_definition(&type_info.name, &field.name)
+                    {
+                        diagnostics.push(missing_description(
+                            &format!("Field '{coordinate}'"),
+                            &location.file_path,
+                            location.line,
+                            location.column,
+                            field.name.len(),
+                        ));
+                    }
+                }
+
+                if !options.arguments {
+                    continue;
+                }
+                for argument in &field.arguments {
+                    if argument.description.is_some() {
+                        continue;
+                    }
+                    let Some(location) = schema_index.find_argument_definition(
+                        &type_info.name,
+                        &field.name,
+                        &argument.name,
+                    ) else {
+                        continue;
+                    };
+                    diagnostics.push(missing_description(
+                        &format!("Argument '{coordinate}({}:)'", argument.name),
+                        &location.file_path,
+                        location.line,
+                        location.column,
+                        argument.name.len(),
+                    ));
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
+
+fn missing_description(
+    subject: &str,
+    file_path: &str,
+    line: usize,
+    column: usize,
+    name_len: usize,
+) -> Diagnostic {
+    let range = Range {
+        start: Position {
+            line,
+            character: column,
+        },
+        end: Position {
+            line,
+            character: column + name_len,
+        },
+    };
+
+    Diagnostic::warning(range, format!("{subject} has no description"))
+        .with_code("require-description")
+        .with_source(format!("graphql-linter:{file_path}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = r#"type Query {
+  "Look up a user"
+  user(id: ID!): User
+}
+
+"A person with an account"
+type User {
+  id: ID!
+  "The user's display name"
+  name: String
+}
+
+enum Role {
+  ADMIN
+}
+"#;
+
+    #[test]
+    fn test_missing_descriptions_are_reported() {
+        let schema = SchemaIndex::from_schema(SCHEMA);
+
+        let diagnostics = RequireDescriptionRule.check_schema(&schema);
+        let messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+
+        assert_eq!(
+            messages,
+            vec![
+                "Type 'Query' has no description",
+                "Argument 'Query.user(id:)' has no description",
+                "Field 'User.id' has no description",
+            ]
+        );
+        assert_eq!(diagnostics[0].code.as_deref(), Some("require-description"));
+        assert_eq!(diagnostics[2].source, "graphql-linter:schema.graphql");
+        assert_eq!(
+            diagnostics[2].range,
+            Range {
+                start: Position {
+                    line: 7,
+                    character: 2
+                },
+                end: Position {
+                    line: 7,
+                    character: 4
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn test_kinds_can_be_exempted() {
+        let schema = SchemaIndex::from_schema(SCHEMA);
+
+        let diagnostics = RequireDescriptionRule.check_schema_with_options(
+            &schema,
+            Some(&serde_json::json!({ "types": false, "arguments": false })),
+        );
+        let messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+
+        assert_eq!(messages, vec!["Field 'User.id' has no description"]);
+    }
+}
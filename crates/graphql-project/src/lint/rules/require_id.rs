@@ -0,0 +1,309 @@
+use crate::{Diagnostic, DocumentIndex, LineIndex, OperationType, Range, SchemaIndex};
+use apollo_parser::cst::{self, CstNode};
+use std::collections::{HashMap, HashSet};
+
+use super::{
+    fragment_selection_sets, offset_position, project_documents, ProjectDocument, ProjectLintRule,
+};
+
+/// Lint rule that checks `id` is selected on every type that declares one
+///
+/// Normalized caches like Relay's and Apollo Client's identify objects by their `id`, so a
+/// selection on a type with an `id: ID` field should include it. An `id` selected through a
+/// fragment spread, or an inline fragment on the same type, counts. The root selection of a
+/// fragment definition isn't checked, since the selection it's spread into can select `id`.
+pub struct RequireIdRule;
+
+impl ProjectLintRule for RequireIdRule {
+    fn name(&self) -> &'static str {
+        "require-id"
+    }
+
+    fn description(&self) -> &'static str {
+        "Requires selecting the id field of types that declare one"
+    }
+
+    fn check_project(
+        &self,
+        document_index: &DocumentIndex,
+        schema_index: &SchemaIndex,
+    ) -> Vec<Diagnostic> {
+        let documents = project_documents(document_index);
+        let fragments = fragment_selection_sets(&documents);
+
+        let mut diagnostics = Vec::new();
+        for document in &documents {
+            let mut checker = IdChecker {
+                schema_index,
+                fragments: &fragments,
+                document,
+                line_index: LineIndex::with_encoding(
+                    &document.source,
+                    document_index.position_encoding,
+                ),
+                diagnostics: &mut diagnostics,
+            };
+
+            for definition in document.tree.document().definitions() {
+                match definition {
+                    cst::Definition::OperationDefinition(operation) => {
+                        if let (Some(root_type_name), Some(selection_set)) = (
+                            schema_index.root_type(OperationType::of(&operation)),
+                            operation.selection_set(),
+                        ) {
+                            checker.check_nested(&selection_set, root_type_name);
+                        }
+                    }
+                    cst::Definition::FragmentDefinition(fragment) => {
+                        let type_name = fragment
+                            .type_condition()
+                            .and_then(|tc| tc.named_type())
+                            .and_then(|nt| nt.name())
+                            .map(|name| name.text().to_string());
+                        if let (Some(type_name), Some(selection_set)) =
+                            (type_name, fragment.selection_set())
+                        {
+                            checker.check_nested(&selection_set, &type_name);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
+
+/// Walks the selections of one document, reporting selections missing an `id`
+struct IdChecker<'a> {
+    schema_index: &'a SchemaIndex,
+    fragments: &'a HashMap<String, cst::SelectionSet>,
+    document: &'a ProjectDocument<'a>,
+    line_index: LineIndex,
+    diagnostics: &'a mut Vec<Diagnostic>,
+}
+
+impl IdChecker<'_> {
+    /// Check the selection sets nested in `selection_set`, whose type is `parent_type_name`
+    fn check_nested(&mut self, selection_set: &cst::SelectionSet, parent_type_name: &str) {
+        for selection in selection_set.selections() {
+            match selection {
+                cst::Selection::Field(field) => {
+                    let (Some(field_name), Some(nested)) = (field.name(), field.selection_set())
+                    else {
+                        continue;
+                    };
+                    let field_name_str = field_name.text();
+                    let Some(field_type) = self
+                        .schema_index
+                        .get_fields(parent_type_name)
+                        .and_then(|fields| fields.into_iter().find(|f| f.name == field_name_str))
+                        .map(|f| base_type_name(&f.type_name).to_string())
+                    else {
+                        continue;
+                    };
+
+                    if self.declares_id(&field_type)
+                        && !selects_id(&nested, &field_type, self.fragments, &mut HashSet::new())
+                    {
+                        self.report(&field_name, &field_type);
+                    }
+                    self.check_nested(&nested, &field_type);
+                }
+                cst::Selection::InlineFragment(inline_fragment) => {
+                    let Some(nested) = inline_fragment.selection_set() else {
+                        continue;
+                    };
+                    let type_name = inline_fragment
+                        .type_condition()
+                        .and_then(|tc| tc.named_type())
+                        .and_then(|nt| nt.name())
+                        .map_or_else(
+                            || parent_type_name.to_string(),
+                            |name| name.text().to_string(),
+                        );
+                    self.check_nested(&nested, &type_name);
+                }
+                // Fragment definitions are checked on their own
+                cst::Selection::FragmentSpread(_) => {}
+            }
+        }
+    }
+
+    /// Whether a type has an `id` field of type `ID`
+    fn declares_id(&self, type_name: &str) -> bool {
+        self.schema_index
+            .get_fields(type_name)
+            .is_some_and(|fields| {
+                fields
+                    .iter()
+                    .any(|f| f.name == "id" && base_type_name(&f.type_name) == "ID")
+            })
+    }
+
+    fn report(&mut self, field_name: &cst::Name, type_name: &str) {
+        let start: usize = field_name.syntax().text_range().start().into();
+        let end = start + field_name.text().len();
+        let range = Range {
+            start: offset_position(
+                self.line_index.offset_to_position(start),
+                self.document.start_line,
+                self.document.start_column,
+            ),
+            end: offset_position(
+                self.line_index.offset_to_position(end),
+                self.document.start_line,
+                self.document.start_column,
+            ),
+        };
+
+        self.diagnostics.push(
+            Diagnostic::warning(
+                range,
+                format!(
+                    "Selection of '{}' doesn't include 'id', which type '{type_name}' declares",
+                    field_name.text()
+                ),
+            )
+            .with_code("require-id")
+            .with_source(format!("graphql-linter:{}", self.document.file_path)),
+        );
+    }
+}
+
+/// Whether `selection_set`, on type `type_name`, selects `id`
+///
+/// Follows fragment spreads, and inline fragments without a type condition or on the same
+/// type. `visiting` holds the fragments being followed, to stop at cycles.
+fn selects_id(
+    selection_set: &cst::SelectionSet,
+    type_name: &str,
+    fragments: &HashMap<String, cst::SelectionSet>,
+    visiting: &mut HashSet<String>,
+) -> bool {
+    selection_set.selections().any(|selection| match selection {
+        cst::Selection::Field(field) => field.name().is_some_and(|name| name.text() == "id"),
+        cst::Selection::InlineFragment(inline_fragment) => {
+            let same_type = inline_fragment
+                .type_condition()
+                .and_then(|tc| tc.named_type())
+                .and_then(|nt| nt.name())
+                .map_or(true, |name| name.text() == type_name);
+            same_type
+                && inline_fragment
+                    .selection_set()
+                    .is_some_and(|nested| selects_id(&nested, type_name, fragments, visiting))
+        }
+        cst::Selection::FragmentSpread(spread) => {
+            let Some(name) = spread.fragment_name().and_then(|name| name.name()) else {
+                return false;
+            };
+            let name = name.text().to_string();
+            let Some(fragment) = fragments.get(&name) else {
+                return false;
+            };
+            if !visiting.insert(name.clone()) {
+                return false;
+            }
+            let selects = selects_id(fragment, type_name, fragments, visiting);
+            visiting.remove(&name);
+            selects
+        }
+    })
+}
+
+/// The named type of a field type, without list and non-null wrappers
+fn base_type_name(type_name: &str) -> &str {
+    type_name.trim_matches(|c| c == '[' || c == ']' || c == '!')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use apollo_parser::Parser;
+    use std::sync::Arc;
+
+    fn create_test_schema() -> SchemaIndex {
+        SchemaIndex::from_schema(
+            r"
+            type Query {
+                user(id: ID!): User
+                posts: [Post!]!
+            }
+
+            type User {
+                id: ID!
+                name: String!
+                friends: [User!]!
+            }
+
+            type Post {
+                title: String!
+                author: User
+            }
+            ",
+        )
+    }
+
+    fn create_document_index(files: &[(&str, &str)]) -> DocumentIndex {
+        let mut index = DocumentIndex::new();
+        for (file_path, source) in files {
+            index.cache_ast(
+                (*file_path).to_string(),
+                Arc::new(Parser::new(source).parse()),
+            );
+        }
+        index
+    }
+
+    #[test]
+    fn test_missing_id_is_reported() {
+        let document_index = create_document_index(&[(
+            "/queries.graphql",
+            "query GetUser {\n  user(id: \"1\") {\n    name\n    friends { id name }\n  }\n}\n",
+        )]);
+
+        let diagnostics = RequireIdRule.check_project(&document_index, &create_test_schema());
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "Selection of 'user' doesn't include 'id', which type 'User' declares"
+        );
+        assert_eq!(diagnostics[0].code.as_deref(), Some("require-id"));
+        assert_eq!(diagnostics[0].range.start.line, 1);
+        assert_eq!(diagnostics[0].range.start.character, 2);
+        assert_eq!(diagnostics[0].range.end.character, 6);
+    }
+
+    #[test]
+    fn test_id_selected_through_fragments() {
+        let document_index = create_document_index(&[
+            (
+                "/queries.graphql",
+                "query GetUser {\n  user(id: \"1\") { ...UserFields }\n  posts { author { ... on User { id } name } }\n}\n",
+            ),
+            (
+                "/fragments.graphql",
+                "fragment UserFields on User {\n  name\n  ...UserId\n}\n\nfragment UserId on User {\n  id\n}\n",
+            ),
+        ]);
+
+        let diagnostics = RequireIdRule.check_project(&document_index, &create_test_schema());
+
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    }
+
+    #[test]
+    fn test_types_without_id_are_not_reported() {
+        let document_index = create_document_index(&[(
+            "/queries.graphql",
+            "query GetPosts {\n  posts {\n    title\n  }\n}\n",
+        )]);
+
+        let diagnostics = RequireIdRule.check_project(&document_index, &create_test_schema());
+
+        assert!(diagnostics.is_empty());
+    }
+}
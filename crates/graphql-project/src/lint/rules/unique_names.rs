@@ -168,19 +168,7 @@ mod tests {
                     cst::Definition::OperationDefinition(op_def) => {
                         if let Some(name_node) = op_def.name() {
                             let name = name_node.text().to_string();
-                            let op_type = if op_def
-                                .operation_type()
-                                .is_some_and(|t| t.mutation_token().is_some())
-                            {
-                                crate::OperationType::Mutation
-                            } else if op_def
-                                .operation_type()
-                                .is_some_and(|t| t.subscription_token().is_some())
-                            {
-                                crate::OperationType::Subscription
-                            } else {
-                                crate::OperationType::Query
-                            };
+                            let op_type = crate::OperationType::of(&op_def);
 
                             let operation_info = crate::OperationInfo {
                                 name: Some(name.clone()),
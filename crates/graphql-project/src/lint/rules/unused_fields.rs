@@ -1,4 +1,4 @@
-use crate::{Diagnostic, DocumentIndex, Position, Range, SchemaIndex};
+use crate::{Diagnostic, DocumentIndex, OperationType, Position, Range, SchemaIndex};
 use apollo_compiler::schema::ExtendedType;
 use apollo_parser::cst;
 use std::collections::{HashMap, HashSet};
@@ -131,27 +131,13 @@ fn collect_all_used_fields(
                 match definition {
                     cst::Definition::OperationDefinition(op_def) => {
                         // Get the root type name for this operation
-                        let root_type_name = match op_def.operation_type() {
-                            Some(op_type) if op_type.query_token().is_some() => {
-                                schema_index.schema().schema_definition.query.as_ref()
-                            }
-                            Some(op_type) if op_type.mutation_token().is_some() => {
-                                schema_index.schema().schema_definition.mutation.as_ref()
-                            }
-                            Some(op_type) if op_type.subscription_token().is_some() => schema_index
-                                .schema()
-                                .schema_definition
-                                .subscription
-                                .as_ref(),
-                            None => schema_index.schema().schema_definition.query.as_ref(),
-                            _ => None,
-                        };
-
-                        if let Some(root_type_name) = root_type_name {
+                        if let Some(root_type_name) =
+                            schema_index.root_type(OperationType::of(&op_def))
+                        {
                             if let Some(selection_set) = op_def.selection_set() {
                                 collect_fields_from_selection_set(
                                     &selection_set,
-                                    root_type_name.as_str(),
+                                    root_type_name,
                                     schema_index,
                                     &mut used_fields,
                                 );
@@ -192,29 +178,13 @@ fn collect_all_used_fields(
                     match definition {
                         cst::Definition::OperationDefinition(op_def) => {
                             // Get the root type name for this operation
-                            let root_type_name = match op_def.operation_type() {
-                                Some(op_type) if op_type.query_token().is_some() => {
-                                    schema_index.schema().schema_definition.query.as_ref()
-                                }
-                                Some(op_type) if op_type.mutation_token().is_some() => {
-                                    schema_index.schema().schema_definition.mutation.as_ref()
-                                }
-                                Some(op_type) if op_type.subscription_token().is_some() => {
-                                    schema_index
-                                        .schema()
-                                        .schema_definition
-                                        .subscription
-                                        .as_ref()
-                                }
-                                None => schema_index.schema().schema_definition.query.as_ref(),
-                                _ => None,
-                            };
-
-                            if let Some(root_type_name) = root_type_name {
+                            if let Some(root_type_name) =
+                                schema_index.root_type(OperationType::of(&op_def))
+                            {
                                 if let Some(selection_set) = op_def.selection_set() {
                                     collect_fields_from_selection_set(
                                         &selection_set,
-                                        root_type_name.as_str(),
+                                        root_type_name,
                                         schema_index,
                                         &mut used_fields,
                                     );
@@ -436,19 +406,7 @@ mod tests {
                     None
                 }
             }) {
-                let op_type = if op_def
-                    .operation_type()
-                    .is_some_and(|t| t.mutation_token().is_some())
-                {
-                    crate::OperationType::Mutation
-                } else if op_def
-                    .operation_type()
-                    .is_some_and(|t| t.subscription_token().is_some())
-                {
-                    crate::OperationType::Subscription
-                } else {
-                    crate::OperationType::Query
-                };
+                let op_type = crate::OperationType::of(&op_def);
 
                 let operation_name = op_def.name().map(|n| n.text().to_string());
 
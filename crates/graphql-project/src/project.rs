@@ -1,7 +1,12 @@
 use crate::{
-    CompletionItem, CompletionProvider, DefinitionLocation, Diagnostic, DocumentIndex,
-    DocumentLoader, FindReferencesProvider, GotoDefinitionProvider, HoverInfo, HoverProvider,
-    Position, ReferenceLocation, Result, SchemaIndex, SchemaLoader, Validator,
+    CodeAction, CodeActionProvider, CompletionItem, CompletionProvider, DefinitionLocation,
+    Diagnostic, DocumentHighlight, DocumentHighlightProvider, DocumentIndex, DocumentLoader,
+    DocumentSymbol, DocumentSymbolProvider, FindReferencesProvider, FoldingRange,
+    FoldingRangeProvider, GotoDefinitionProvider, HoverInfo, HoverProvider, InlayHint,
+    InlayHintConfig, InlayHintProvider, Position, ReferenceLocation, RemoteSchemaConfig,
+    RenameProvider, Result, SchemaCache, SchemaIndex, SchemaLoader, SemanticToken,
+    SemanticTokensProvider, SignatureHelp, SignatureHelpProvider, SplitOperations, Validator,
+    WorkspaceSymbol, WorkspaceSymbolProvider,
 };
 use apollo_compiler::validation::DiagnosticList;
 use graphql_config::{GraphQLConfig, ProjectConfig};
@@ -13,17 +18,45 @@ pub struct GraphQLProject {
     config: ProjectConfig,
     base_dir: Option<std::path::PathBuf>,
     schema_index: Arc<RwLock<SchemaIndex>>,
+    /// The `(path, content)` of each schema file the index was last built from, so an edit
+    /// to one file can rebuild the schema without reading the others from disk again
+    schema_files: Arc<RwLock<Vec<(String, String)>>>,
     document_index: Arc<RwLock<DocumentIndex>>,
+    schema_cache: Option<SchemaCache>,
+    /// `extensions.extractConfig` from the root of a multi-project config
+    root_extract_config: Option<serde_json::Value>,
 }
 
 /// Extract `ExtractConfig` from `ProjectConfig` extensions
-fn get_extract_config(config: &ProjectConfig) -> ExtractConfig {
-    config
+///
+/// `root` is the `extractConfig` shared by all projects of a multi-project config. The
+/// project's own `extractConfig` is merged over it field by field, so a project can
+/// override `magicComment` and still inherit the root's `tagIdentifiers`.
+fn get_extract_config(config: &ProjectConfig, root: Option<&serde_json::Value>) -> ExtractConfig {
+    let project = config
         .extensions
         .as_ref()
+        .and_then(|ext| ext.get("extractConfig"));
+
+    let merged = match (root, project) {
+        (Some(serde_json::Value::Object(root)), Some(serde_json::Value::Object(project))) => {
+            let mut merged = root.clone();
+            merged.extend(project.clone());
+            serde_json::Value::Object(merged)
+        }
+        (_, Some(value)) | (Some(value), None) => value.clone(),
+        (None, None) => return ExtractConfig::default(),
+    };
+
+    serde_json::from_value(merged).unwrap_or_default()
+}
+
+/// The `extensions.extractConfig` shared by every project of a multi-project config
+fn root_extract_config(config: &GraphQLConfig) -> Option<serde_json::Value> {
+    config
+        .root_extensions()
         .and_then(|ext| ext.get("extractConfig"))
-        .and_then(|value| serde_json::from_value(value.clone()).ok())
-        .unwrap_or_default()
+        .cloned()
 }
 
 /// Extract `LintConfig` from `ProjectConfig` extensions
@@ -37,18 +70,236 @@ fn get_lint_config(config: &ProjectConfig) -> crate::LintConfig {
         .unwrap_or_default()
 }
 
+/// Extract custom scalar mappings from `extensions.scalars`
+///
+/// Maps custom scalar names to the built-in scalar whose literals they accept.
+fn get_scalar_mappings(config: &ProjectConfig) -> std::collections::HashMap<String, String> {
+    config
+        .extensions
+        .as_ref()
+        .and_then(|ext| ext.get("scalars"))
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Extract `InlayHintConfig` from `extensions.project.inlayHints`
+fn get_inlay_hint_config(config: &ProjectConfig) -> InlayHintConfig {
+    config
+        .extensions
+        .as_ref()
+        .and_then(|ext| ext.get("project"))
+        .and_then(|value| value.get("inlayHints"))
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Extract `CompletionConfig` from `extensions.completion`
+fn get_completion_config(config: &ProjectConfig) -> crate::CompletionConfig {
+    config
+        .extensions
+        .as_ref()
+        .and_then(|ext| ext.get("completion"))
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Files imported by `#import "<path>"` comments in a document, relative to `file_path`
+fn import_pragmas(source: &str, file_path: &std::path::Path) -> Vec<std::path::PathBuf> {
+    let dir = file_path
+        .parent()
+        .unwrap_or_else(|| std::path::Path::new(""));
+
+    source
+        .lines()
+        .filter_map(|line| {
+            let rest = line.trim_start().strip_prefix("#import")?.trim();
+            let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+            let end = rest[1..].find(quote)?;
+            Some(dir.join(&rest[1..=end]))
+        })
+        .collect()
+}
+
+/// Extract the read-only path globs from `extensions.readOnlyPaths`
+fn get_read_only_paths(config: &ProjectConfig) -> Vec<String> {
+    config
+        .extensions
+        .as_ref()
+        .and_then(|ext| ext.get("readOnlyPaths"))
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Length of a glob pattern up to its first wildcard, or the whole pattern if it has none
+fn literal_prefix_len(pattern: &str) -> usize {
+    pattern
+        .find(|c| matches!(c, '*' | '?' | '[' | '{'))
+        .unwrap_or(pattern.len())
+}
+
+/// Replace apollo-compiler's unknown field errors for fields selected directly on unions
+///
+/// apollo-compiler reports these as fields the union doesn't have; the union check reports
+/// the same field with a suggestion to use an inline fragment, so only that is kept.
+fn replace_union_field_errors(diagnostics: &mut Vec<Diagnostic>, union_errors: Vec<Diagnostic>) {
+    diagnostics.retain(|diagnostic| {
+        diagnostic.code.as_deref() != Some("unknown-field")
+            || !union_errors.iter().any(|union_error| {
+                let start = union_error.range.start;
+                let (range_start, range_end) = (diagnostic.range.start, diagnostic.range.end);
+                (range_start.line, range_start.character) <= (start.line, start.character)
+                    && (start.line, start.character) <= (range_end.line, range_end.character)
+            })
+    });
+    diagnostics.extend(union_errors);
+}
+
+/// Replace apollo-compiler's errors for undeclared variables used by spread fragments
+///
+/// apollo-compiler reports such a variable at its usage inside the fragment, while
+/// `check_fragment_variables` reports it at the spread. Errors for undeclared variables
+/// used directly by an operation, within `operation_lines`, are kept.
+fn replace_fragment_variable_errors(
+    diagnostics: &mut Vec<Diagnostic>,
+    variable_errors: Vec<Diagnostic>,
+    operation_lines: &[std::ops::RangeInclusive<usize>],
+) {
+    let undefined: Vec<String> = variable_errors
+        .iter()
+        .filter_map(|error| error.message.split('\'').nth(1))
+        .map(|variable| format!("`{variable}` is not defined"))
+        .collect();
+    diagnostics.retain(|diagnostic| {
+        operation_lines
+            .iter()
+            .any(|lines| lines.contains(&diagnostic.range.start.line))
+            || !undefined
+                .iter()
+                .any(|message| diagnostic.message.contains(message.as_str()))
+    });
+    diagnostics.extend(variable_errors);
+}
+
+/// Replace apollo-compiler's errors for conditional subscription root fields with ours
+///
+/// apollo-compiler reports `@skip` and `@include` on a subscription's root fields once for
+/// the operation, while `check_subscription_root_directives` reports each directive, so
+/// apollo-compiler's error is dropped for operations where we report one.
+fn replace_subscription_directive_errors(
+    diagnostics: &mut Vec<Diagnostic>,
+    directive_errors: Vec<Diagnostic>,
+    operation_lines: &[std::ops::RangeInclusive<usize>],
+) {
+    diagnostics.retain(|diagnostic| {
+        !diagnostic
+            .message
+            .to_lowercase()
+            .contains("@skip or @include")
+            || !operation_lines.iter().any(|lines| {
+                lines.contains(&diagnostic.range.start.line)
+                    && directive_errors
+                        .iter()
+                        .any(|error| lines.contains(&error.range.start.line))
+            })
+    });
+    diagnostics.extend(directive_errors);
+}
+
+/// The lines spanned by each operation defined in a document
+fn operation_lines(source: &str) -> Vec<std::ops::RangeInclusive<usize>> {
+    use apollo_parser::{
+        cst::{self, CstNode},
+        Parser,
+    };
+
+    let line_of = |offset: usize| source[..offset].matches('\n').count();
+    Parser::new(source)
+        .parse()
+        .document()
+        .definitions()
+        .filter_map(|definition| {
+            let cst::Definition::OperationDefinition(operation) = definition else {
+                return None;
+            };
+            let range = operation.syntax().text_range();
+            Some(line_of(range.start().into())..=line_of(range.end().into()))
+        })
+        .collect()
+}
+
+/// The diagnostics of the schema errors located in a file
+fn errors_in_file(errors: &[crate::SchemaBuildError], file_path: &str) -> Vec<Diagnostic> {
+    let canonical_file_path = std::path::Path::new(file_path)
+        .canonicalize()
+        .unwrap_or_else(|_| std::path::PathBuf::from(file_path));
+
+    errors
+        .iter()
+        .filter(|error| {
+            error.file_path.as_deref().is_some_and(|path| {
+                std::path::Path::new(path)
+                    .canonicalize()
+                    .unwrap_or_else(|_| std::path::PathBuf::from(path))
+                    == canonical_file_path
+            })
+        })
+        .map(|error| error.diagnostic.clone())
+        .collect()
+}
+
+/// Extract `RemoteSchemaConfig` from `extensions.remoteSchema`
+fn get_remote_schema_config(config: &ProjectConfig) -> RemoteSchemaConfig {
+    config
+        .extensions
+        .as_ref()
+        .and_then(|ext| ext.get("remoteSchema"))
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Build the on-disk `SchemaCache` if enabled via `extensions.project.schemaCache`
+fn get_schema_cache(config: &ProjectConfig) -> Option<SchemaCache> {
+    let enabled = config
+        .extensions
+        .as_ref()
+        .and_then(|ext| ext.get("project"))
+        .and_then(|value| value.get("schemaCache"))
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false);
+
+    if enabled {
+        SchemaCache::default_dir().map(SchemaCache::new)
+    } else {
+        None
+    }
+}
+
 impl GraphQLProject {
     /// Create a new project from configuration
     #[must_use]
     pub fn new(config: ProjectConfig) -> Self {
+        let schema_cache = get_schema_cache(&config);
         Self {
             config,
             base_dir: None,
             schema_index: Arc::new(RwLock::new(SchemaIndex::new())),
+            schema_files: Arc::default(),
             document_index: Arc::new(RwLock::new(DocumentIndex::new())),
+            schema_cache,
+            root_extract_config: None,
         }
     }
 
+    /// Use an on-disk cache for the built schema
+    ///
+    /// When set, `load_schema` reuses a cached schema if the schema files are unchanged,
+    /// and stores newly built schemas for later runs.
+    #[must_use]
+    pub fn with_schema_cache(mut self, cache: SchemaCache) -> Self {
+        self.schema_cache = Some(cache);
+        self
+    }
+
     /// Create a new project with a base directory for resolving relative paths
     #[must_use]
     pub fn with_base_dir(mut self, base_dir: std::path::PathBuf) -> Self {
@@ -56,12 +307,28 @@ impl GraphQLProject {
         self
     }
 
+    /// Count `Position::character` columns in `encoding` instead of UTF-16 code units
+    ///
+    /// The language server sets this to the encoding negotiated with the client.
+    #[must_use]
+    pub fn with_position_encoding(self, encoding: crate::PositionEncoding) -> Self {
+        self.document_index.write().unwrap().position_encoding = encoding;
+        self
+    }
+
+    /// Unit `Position::character` columns are counted in
+    #[must_use]
+    pub fn position_encoding(&self) -> crate::PositionEncoding {
+        self.document_index.read().unwrap().position_encoding
+    }
+
     /// Create projects from GraphQL config (single or multi-project)
     pub fn from_config(config: &GraphQLConfig) -> Result<Vec<(String, Self)>> {
         let mut projects = Vec::new();
 
         for (name, project_config) in config.projects() {
-            let project = Self::new(project_config.clone());
+            let mut project = Self::new(project_config.clone());
+            project.root_extract_config = root_extract_config(config);
             projects.push((name.to_string(), project));
         }
 
@@ -76,7 +343,9 @@ impl GraphQLProject {
         let mut projects = Vec::new();
 
         for (name, project_config) in config.projects() {
-            let project = Self::new(project_config.clone()).with_base_dir(base_dir.to_path_buf());
+            let mut project =
+                Self::new(project_config.clone()).with_base_dir(base_dir.to_path_buf());
+            project.root_extract_config = root_extract_config(config);
             projects.push((name.to_string(), project));
         }
 
@@ -85,56 +354,74 @@ impl GraphQLProject {
 
     /// Load the schema from configured sources
     pub async fn load_schema(&self) -> Result<()> {
-        let loader = SchemaLoader::new(self.config.schema.clone());
+        let loader = SchemaLoader::new(self.config.schema.clone())
+            .with_remote_config(get_remote_schema_config(&self.config));
         let schema_files = loader.load_with_paths().await?;
 
-        // Build index from schema files (preserves source locations per file)
-        let index = SchemaIndex::from_schema_files(schema_files);
+        let index = if let Some(ref cache) = self.schema_cache {
+            if let Some(index) = cache.load(&schema_files) {
+                tracing::debug!("Loaded schema from cache in {}", cache.dir().display());
+                index
+            } else {
+                let index = SchemaIndex::from_schema_files(schema_files.clone());
+                // Only schemas that built cleanly are cached
+                if index.build_errors().is_empty() {
+                    if let Err(e) = cache.store(&schema_files) {
+                        tracing::warn!("Failed to write schema cache: {}", e);
+                    }
+                }
+                index
+            }
+        } else {
+            // Build index from schema files (preserves source locations per file)
+            SchemaIndex::from_schema_files(schema_files.clone())
+        };
 
-        // Update state
-        {
-            let mut schema_index = self.schema_index.write().unwrap();
-            *schema_index = index;
-        }
+        self.swap_schema(schema_files, index);
 
         Ok(())
     }
 
     /// Update schema index with in-memory content for a specific schema file
     ///
-    /// This reloads the entire schema from disk, replacing the specified file's content
-    /// with the provided in-memory content. This is used by the LSP to keep the schema
-    /// up-to-date with editor changes without needing to save to disk.
-    ///
-    /// Note: Unlike document updates, we reload the entire schema because:
-    /// 1. Schema files change less frequently than operation files
-    /// 2. Schema relationships (extends, implements) require full rebuild
-    /// 3. The performance impact is acceptable for typical schema sizes
+    /// Only the edited file's content is replaced: the other schema files are taken from
+    /// what was last loaded, so an edit doesn't read every schema file from disk again. The
+    /// whole schema is still rebuilt, since relationships between files (extensions,
+    /// implementations) need a full build. If no schema has been loaded yet, the files are
+    /// read from disk first.
     pub async fn update_schema_index(&self, file_path: &str, content: &str) -> Result<()> {
-        let loader = SchemaLoader::new(self.config.schema.clone());
+        let mut schema_files = self.schema_files.read().unwrap().clone();
 
-        // Set base path if we have one
-        let loader = if let Some(ref base_dir) = self.base_dir {
-            loader.with_base_path(base_dir)
-        } else {
-            loader
-        };
+        if schema_files.is_empty() {
+            let loader = SchemaLoader::new(self.config.schema.clone())
+                .with_remote_config(get_remote_schema_config(&self.config));
+
+            // Set base path if we have one
+            let loader = if let Some(ref base_dir) = self.base_dir {
+                loader.with_base_path(base_dir)
+            } else {
+                loader
+            };
 
-        let mut schema_files = loader.load_with_paths().await?;
+            schema_files = loader.load_with_paths().await?;
+        }
+
+        // Introspection JSON is converted to SDL like it is when loaded from disk
+        let content = crate::schema::schema_file_sdl(file_path, content.to_string());
 
         // Replace the content of the specified file with in-memory content
+        let normalized_file_path = std::path::Path::new(file_path)
+            .canonicalize()
+            .unwrap_or_else(|_| std::path::PathBuf::from(file_path));
         let mut found = false;
         for (path, file_content) in &mut schema_files {
             // Normalize paths for comparison (handle both absolute and canonical paths)
             let normalized_path = std::path::Path::new(path.as_str())
                 .canonicalize()
                 .unwrap_or_else(|_| std::path::PathBuf::from(path.as_str()));
-            let normalized_file_path = std::path::Path::new(file_path)
-                .canonicalize()
-                .unwrap_or_else(|_| std::path::PathBuf::from(file_path));
 
             if normalized_path == normalized_file_path {
-                *file_content = content.to_string();
+                file_content.clone_from(&content);
                 found = true;
                 break;
             }
@@ -143,21 +430,23 @@ impl GraphQLProject {
         // If the file wasn't found in the schema files, add it
         // (This can happen if the file matches the schema pattern but wasn't loaded yet)
         if !found {
-            schema_files.push((file_path.to_string(), content.to_string()));
+            schema_files.push((file_path.to_string(), content));
         }
 
         // Rebuild the schema index with updated content
-        let index = SchemaIndex::from_schema_files(schema_files);
+        let index = SchemaIndex::from_schema_files(schema_files.clone());
 
-        // Update state
-        {
-            let mut schema_index = self.schema_index.write().unwrap();
-            *schema_index = index;
-        }
+        self.swap_schema(schema_files, index);
 
         Ok(())
     }
 
+    /// Replace the schema index, along with the schema files it was built from
+    fn swap_schema(&self, schema_files: Vec<(String, String)>, index: SchemaIndex) {
+        *self.schema_files.write().unwrap() = schema_files;
+        *self.schema_index.write().unwrap() = index;
+    }
+
     /// Load documents from configured sources
     pub fn load_documents(&self) -> Result<()> {
         // Return early if no documents configured
@@ -173,7 +462,10 @@ impl GraphQLProject {
         }
 
         // Set extract config from project extensions
-        loader = loader.with_extract_config(get_extract_config(&self.config));
+        loader = loader
+            .with_extract_config(self.get_extract_config())
+            .with_excluded_patterns(get_read_only_paths(&self.config))
+            .with_position_encoding(self.position_encoding());
 
         let index = loader.load()?;
 
@@ -186,6 +478,33 @@ impl GraphQLProject {
         Ok(())
     }
 
+    /// Find the files matching this project's document patterns
+    pub fn document_files(&self) -> Result<Vec<std::path::PathBuf>> {
+        let Some(ref documents_config) = self.config.documents else {
+            return Ok(Vec::new());
+        };
+
+        let mut loader = DocumentLoader::new(documents_config.clone())
+            .with_excluded_patterns(get_read_only_paths(&self.config));
+        if let Some(ref base_dir) = self.base_dir {
+            loader = loader.with_base_path(base_dir);
+        }
+
+        loader.find_document_files()
+    }
+
+    /// Find this project's local schema files
+    ///
+    /// Remote schemas are skipped.
+    pub fn schema_files(&self) -> Result<Vec<std::path::PathBuf>> {
+        let mut loader = SchemaLoader::new(self.config.schema.clone());
+        if let Some(ref base_dir) = self.base_dir {
+            loader = loader.with_base_path(base_dir);
+        }
+
+        loader.find_local_files()
+    }
+
     /// Validate a single document string against the loaded schema
     ///
     /// Returns Ok(()) if valid, or Err with a `DiagnosticList` containing errors and warnings.
@@ -231,6 +550,7 @@ impl GraphQLProject {
             parsed_asts: index.parsed_asts.clone(),
             extracted_blocks: index.extracted_blocks.clone(),
             line_indices: index.line_indices.clone(),
+            position_encoding: index.position_encoding,
         }
     }
 
@@ -244,7 +564,7 @@ impl GraphQLProject {
     /// Get the extract configuration for this project
     #[must_use]
     pub fn get_extract_config(&self) -> ExtractConfig {
-        get_extract_config(&self.config)
+        get_extract_config(&self.config, self.root_extract_config.as_ref())
     }
 
     /// Get the lint configuration for this project
@@ -253,17 +573,26 @@ impl GraphQLProject {
         get_lint_config(&self.config)
     }
 
-    /// Run project-wide lint rules on all documents
+    /// Run project-wide lint rules on all documents, and schema lint rules on the schema
     ///
     /// This runs lint rules that require analyzing the entire project, such as
     /// detecting unused schema fields across all operations and fragments.
     #[must_use]
     pub fn lint_project(&self) -> Vec<Diagnostic> {
-        let linter = crate::Linter::new(self.get_lint_config());
+        self.lint_project_with_config(self.get_lint_config())
+    }
+
+    /// Run project-wide and schema lint rules with a lint configuration other than the
+    /// project's, like one with rule severities overridden on the command line
+    #[must_use]
+    pub fn lint_project_with_config(&self, lint_config: crate::LintConfig) -> Vec<Diagnostic> {
+        let linter = crate::Linter::new(lint_config);
         let document_index = self.document_index.read().unwrap();
         let schema_index = self.schema_index.read().unwrap();
 
-        linter.lint_project(&document_index, &schema_index)
+        let mut diagnostics = linter.lint_project(&document_index, &schema_index);
+        diagnostics.extend(linter.lint_schema(&schema_index));
+        diagnostics
     }
 
     /// Update document index for a single file with in-memory content
@@ -285,14 +614,14 @@ impl GraphQLProject {
             |ext| match ext.to_lowercase().as_str() {
                 "ts" | "tsx" => Language::TypeScript,
                 "js" | "jsx" => Language::JavaScript,
+                "svelte" => Language::Svelte,
                 _ => Language::GraphQL,
             },
         );
 
         // Extract GraphQL from the content
-        let extracted =
-            extract_from_source(content, language, &get_extract_config(&self.config))
-                .map_err(|e| crate::ProjectError::DocumentLoad(format!("Extract error: {e}")))?;
+        let extracted = extract_from_source(content, language, &self.get_extract_config())
+            .map_err(|e| crate::ProjectError::DocumentLoad(format!("Extract error: {e}")))?;
 
         // Acquire write lock and update index
         {
@@ -329,7 +658,8 @@ impl GraphQLProject {
             }
 
             // Build and cache line index for fast position<->offset conversion
-            let line_index = crate::LineIndex::new(content);
+            let line_index =
+                crate::LineIndex::with_encoding(content, document_index.position_encoding);
             document_index.cache_line_index(file_path.to_string(), std::sync::Arc::new(line_index));
 
             // Cache extracted blocks with their parsed ASTs
@@ -402,26 +732,28 @@ impl GraphQLProject {
         // Add the current document
         Parser::new().parse_into_executable_builder(source, file_name, &mut builder);
 
+        // Sources of the referenced fragments, for checking the variables they need
+        let mut fragment_sources = std::collections::HashMap::new();
+
         // Only add referenced fragments (and their dependencies) if this document uses fragment spreads
         if !is_fragment_only && source.contains("...") {
             // Find all fragment names referenced in this document (recursively)
-            let referenced_fragments = Self::collect_referenced_fragments(source, self);
+            let referenced_fragments = self.collect_referenced_fragments(source, file_name);
 
             // Add each referenced fragment individually
-            for fragment_name in referenced_fragments {
-                if let Some(frag_info) = self.get_fragment(&fragment_name) {
-                    // Extract just this specific fragment from the file
-                    if let Some(fragment_source) = self.extract_fragment_from_file(
-                        std::path::Path::new(&frag_info.file_path),
-                        &fragment_name,
-                    ) {
-                        // Add this specific fragment to the builder
-                        Parser::new().parse_into_executable_builder(
-                            &fragment_source,
-                            &frag_info.file_path,
-                            &mut builder,
-                        );
-                    }
+            for (fragment_name, fragment_file) in referenced_fragments {
+                // Extract just this specific fragment from the file
+                if let Some(fragment_source) = self.extract_fragment_from_file(
+                    std::path::Path::new(&fragment_file),
+                    &fragment_name,
+                ) {
+                    // Add this specific fragment to the builder
+                    Parser::new().parse_into_executable_builder(
+                        &fragment_source,
+                        &fragment_file,
+                        &mut builder,
+                    );
+                    fragment_sources.insert(fragment_name, fragment_source);
                 }
             }
         }
@@ -451,15 +783,80 @@ impl GraphQLProject {
             )
         };
 
+        let validator = Validator::new().with_position_encoding(self.position_encoding());
+
+        // Report fragments on types the schema doesn't define once, at their type condition,
+        // rather than with an error for every field they select
+        if is_fragment_only {
+            let unknown_type_conditions =
+                validator.check_fragment_type_conditions(source, &schema_index, file_name);
+            diagnostics.retain(|diagnostic| {
+                !unknown_type_conditions.iter().any(|(definition, _)| {
+                    (definition.start.line..=definition.end.line)
+                        .contains(&diagnostic.range.start.line)
+                })
+            });
+            diagnostics.extend(unknown_type_conditions.into_iter().map(|(_, error)| error));
+        }
+
         // Add deprecation warnings
-        let validator = Validator::new();
         let deprecation_warnings =
             validator.check_deprecated_fields_custom(source, &schema_index, file_name);
         diagnostics.extend(deprecation_warnings);
+        diagnostics.extend(validator.check_deprecated_fields_in_fragments(
+            source,
+            &schema_index,
+            &self.document_index.read().unwrap(),
+            file_name,
+        ));
+
+        // Lines of this document's operations, for matching apollo-compiler's errors
+        let operation_lines = operation_lines(source);
+
+        // Add errors for fields selected directly on union types
+        replace_union_field_errors(
+            &mut diagnostics,
+            validator.check_union_field_selections(source, &schema_index, file_name),
+        );
+
+        // Add errors for operation and fragment names reserved for introspection
+        diagnostics.extend(validator.check_reserved_names(source, file_name));
+
+        // Add errors for conditional subscription root fields
+        replace_subscription_directive_errors(
+            &mut diagnostics,
+            validator.check_subscription_root_directives(source),
+            &operation_lines,
+        );
+
+        // Add errors for literals passed to custom scalars mapped to a built-in scalar
+        diagnostics.extend(validator.check_custom_scalar_arguments(
+            source,
+            &schema_index,
+            &get_scalar_mappings(&self.config),
+            file_name,
+        ));
+
+        // Add errors for @stream on fields that don't return lists
+        diagnostics.extend(validator.check_stream_on_lists(source, &schema_index, file_name));
+
+        // Add errors for variables used at conflicting types
+        diagnostics.extend(validator.check_variable_usage_types(source, &schema_index, file_name));
+
+        // Add errors for spread fragments that need variables the operation doesn't declare
+        replace_fragment_variable_errors(
+            &mut diagnostics,
+            validator.check_fragment_variables(source, &fragment_sources),
+            &operation_lines,
+        );
 
         // Add unused fragment warnings for fragments defined in this file
-        let unused_fragment_warnings =
-            Self::check_unused_fragments_in_file(source, file_name, &used_fragments);
+        let unused_fragment_warnings = Self::check_unused_fragments_in_file(
+            source,
+            file_name,
+            &used_fragments,
+            self.position_encoding(),
+        );
         diagnostics.extend(unused_fragment_warnings);
 
         // Note: Within-document unique name validation is handled by apollo-compiler
@@ -497,6 +894,7 @@ impl GraphQLProject {
         let schema_index = self.schema_index.read().unwrap();
         let schema = schema_index.schema();
         let valid_schema = Valid::assume_valid_ref(schema);
+        let scalar_mappings = get_scalar_mappings(&self.config);
 
         let mut all_diagnostics = Vec::new();
 
@@ -522,33 +920,34 @@ impl GraphQLProject {
                 .source_offset(offset)
                 .parse_into_executable_builder(source, file_path, &mut builder);
 
+            // Sources of the referenced fragments, for checking the variables they need
+            let mut fragment_sources = std::collections::HashMap::new();
+
             // Only add referenced fragments (and their dependencies) if this document uses fragment spreads
             if !is_fragment_only && source.contains("...") {
                 // Find all fragment names referenced in this document (recursively)
-                let referenced_fragments = Self::collect_referenced_fragments(source, self);
+                let referenced_fragments = self.collect_referenced_fragments(source, file_path);
                 let current_path = std::path::Path::new(file_path);
 
                 // Add only the referenced fragments from OTHER files
                 // Fragments in the current file are already in the builder
-                for fragment_name in referenced_fragments {
-                    if let Some(frag_info) = self.get_fragment(&fragment_name) {
-                        // Skip fragments from the current file
-                        if std::path::Path::new(&frag_info.file_path) == current_path {
-                            continue;
-                        }
+                for (fragment_name, fragment_file) in referenced_fragments {
+                    let is_current_file = std::path::Path::new(&fragment_file) == current_path;
 
-                        // Extract just this specific fragment from the file
-                        if let Some(fragment_source) = self.extract_fragment_from_file(
-                            std::path::Path::new(&frag_info.file_path),
-                            &fragment_name,
-                        ) {
-                            // Add this specific fragment to the builder
+                    // Extract just this specific fragment from the file
+                    if let Some(fragment_source) = self.extract_fragment_from_file(
+                        std::path::Path::new(&fragment_file),
+                        &fragment_name,
+                    ) {
+                        // Add this specific fragment to the builder
+                        if !is_current_file {
                             Parser::new().parse_into_executable_builder(
                                 &fragment_source,
-                                &frag_info.file_path,
+                                &fragment_file,
                                 &mut builder,
                             );
                         }
+                        fragment_sources.insert(fragment_name, fragment_source);
                     }
                 }
             }
@@ -604,12 +1003,37 @@ impl GraphQLProject {
                 diags
             };
 
+            let validator = Validator::new().with_position_encoding(self.position_encoding());
+
+            // Report fragments on types the schema doesn't define once, at their type condition,
+            // rather than with an error for every field they select
+            if is_fragment_only {
+                let unknown_type_conditions =
+                    validator.check_fragment_type_conditions(source, &schema_index, file_path);
+                diagnostics.retain(|diagnostic| {
+                    !unknown_type_conditions.iter().any(|(definition, _)| {
+                        (definition.start.line + line_offset..=definition.end.line + line_offset)
+                            .contains(&diagnostic.range.start.line)
+                    })
+                });
+                for (_, mut error) in unknown_type_conditions {
+                    error.range.start.line += line_offset;
+                    error.range.end.line += line_offset;
+                    diagnostics.push(error);
+                }
+            }
+
             // Add deprecation warnings
             // Note: We still need to manually adjust line offsets for deprecation warnings
             // since check_deprecated_fields_custom uses apollo-parser directly without offset support
-            let validator = Validator::new();
-            let deprecation_warnings =
+            let mut deprecation_warnings =
                 validator.check_deprecated_fields_custom(source, &schema_index, file_path);
+            deprecation_warnings.extend(validator.check_deprecated_fields_in_fragments(
+                source,
+                &schema_index,
+                &self.document_index.read().unwrap(),
+                file_path,
+            ));
 
             for mut warning in deprecation_warnings {
                 warning.range.start.line += line_offset;
@@ -617,9 +1041,92 @@ impl GraphQLProject {
                 diagnostics.push(warning);
             }
 
+            // Lines of this document's operations, for matching apollo-compiler's errors
+            let operation_lines: Vec<_> = operation_lines(source)
+                .into_iter()
+                .map(|lines| lines.start() + line_offset..=lines.end() + line_offset)
+                .collect();
+
+            // Add errors for fields selected directly on union types
+            let mut union_errors =
+                validator.check_union_field_selections(source, &schema_index, file_path);
+            for error in &mut union_errors {
+                error.range.start.line += line_offset;
+                error.range.end.line += line_offset;
+            }
+            replace_union_field_errors(&mut diagnostics, union_errors);
+
+            // Add errors for operation and fragment names reserved for introspection
+            for mut error in validator.check_reserved_names(source, file_path) {
+                error.range.start.line += line_offset;
+                error.range.end.line += line_offset;
+                diagnostics.push(error);
+            }
+
+            // Add errors for conditional subscription root fields
+            let mut directive_errors = validator.check_subscription_root_directives(source);
+            for error in &mut directive_errors {
+                error.range.start.line += line_offset;
+                error.range.end.line += line_offset;
+            }
+            replace_subscription_directive_errors(
+                &mut diagnostics,
+                directive_errors,
+                &operation_lines,
+            );
+
+            // Add errors for literals passed to custom scalars mapped to a built-in scalar
+            let scalar_errors = validator.check_custom_scalar_arguments(
+                source,
+                &schema_index,
+                &scalar_mappings,
+                file_path,
+            );
+            for mut error in scalar_errors {
+                error.range.start.line += line_offset;
+                error.range.end.line += line_offset;
+                diagnostics.push(error);
+            }
+
+            // Add errors for @stream on fields that don't return lists
+            let stream_errors = validator.check_stream_on_lists(source, &schema_index, file_path);
+            for mut error in stream_errors {
+                error.range.start.line += line_offset;
+                error.range.end.line += line_offset;
+                diagnostics.push(error);
+            }
+
+            // Add errors for variables used at conflicting types
+            // and for spread fragments that need variables the operation doesn't declare
+            let mut variable_errors =
+                validator.check_variable_usage_types(source, &schema_index, file_path);
+            let mut fragment_variable_errors =
+                validator.check_fragment_variables(source, &fragment_sources);
+            for error in variable_errors
+                .iter_mut()
+                .chain(&mut fragment_variable_errors)
+            {
+                error.range.start.line += line_offset;
+                error.range.end.line += line_offset;
+                for related in &mut error.related_info {
+                    related.location.range.start.line += line_offset;
+                    related.location.range.end.line += line_offset;
+                }
+            }
+            diagnostics.extend(variable_errors);
+            replace_fragment_variable_errors(
+                &mut diagnostics,
+                fragment_variable_errors,
+                &operation_lines,
+            );
+
             // Add unused fragment warnings for fragments defined in this extracted block
-            let unused_warnings =
-                Self::check_unused_fragments_in_file(source, file_path, &used_fragments);
+            let unused_warnings = Self::check_unused_fragments_in_file(
+                source,
+                file_path,
+                &used_fragments,
+                self.position_encoding(),
+            );
             for mut warning in unused_warnings {
                 warning.range.start.line += line_offset;
                 warning.range.end.line += line_offset;
@@ -737,19 +1244,31 @@ impl GraphQLProject {
         }
     }
 
-    /// Collect all fragment names referenced in a document (recursively)
+    /// Collect all fragments referenced in a document (recursively), with the file
+    /// defining each
     ///
     /// This finds all fragment spreads in the document, then recursively finds
     /// fragments that those fragments depend on, building a complete set of all
-    /// fragments needed to validate this document.
+    /// fragments needed to validate this document. Fragments from files pulled in with
+    /// `#import` comments are used first, then the document index. Fragments that can't
+    /// be found are left out.
     fn collect_referenced_fragments(
+        &self,
         source: &str,
-        project: &Self,
-    ) -> std::collections::HashSet<String> {
+        file_name: &str,
+    ) -> std::collections::HashMap<String, String> {
         use apollo_parser::{cst, Parser};
-        use std::collections::{HashSet, VecDeque};
+        use std::collections::{HashMap, HashSet, VecDeque};
+
+        let imported = self.collect_imported_fragments(source, file_name);
+        let fragment_file = |name: &str| {
+            imported
+                .get(name)
+                .cloned()
+                .or_else(|| self.get_fragment(name).map(|info| info.file_path))
+        };
 
-        let mut referenced = HashSet::new();
+        let mut referenced = HashMap::new();
         let mut to_process = VecDeque::new();
 
         // First, find all fragment spreads directly in this document
@@ -765,9 +1284,12 @@ impl GraphQLProject {
                         &mut direct_fragments,
                     );
                     for frag_name in direct_fragments {
-                        if !referenced.contains(&frag_name) {
-                            referenced.insert(frag_name.clone());
-                            to_process.push_back(frag_name);
+                        if referenced.contains_key(&frag_name) {
+                            continue;
+                        }
+                        if let Some(file) = fragment_file(&frag_name) {
+                            referenced.insert(frag_name.clone(), file.clone());
+                            to_process.push_back(file);
                         }
                     }
                 }
@@ -775,12 +1297,13 @@ impl GraphQLProject {
         }
 
         // Now recursively process fragment dependencies
-        while let Some(fragment_name) = to_process.pop_front() {
-            // Get the fragment definition and scan it for more fragment spreads
-            if let Some(frag_info) = project.get_fragment(&fragment_name) {
+        let mut processed_files = HashSet::new();
+        while let Some(file) = to_process.pop_front() {
+            // Scan the fragment definitions in the file for more fragment spreads
+            if processed_files.insert(file.clone()) {
                 if let Ok(frag_extracted) = graphql_extract::extract_from_file(
-                    std::path::Path::new(&frag_info.file_path),
-                    &get_extract_config(&project.config),
+                    std::path::Path::new(&file),
+                    &self.get_extract_config(),
                 ) {
                     for frag_item in frag_extracted {
                         let frag_parser = Parser::new(&frag_item.source);
@@ -795,9 +1318,13 @@ impl GraphQLProject {
                                         &mut nested_fragments,
                                     );
                                     for nested_frag_name in nested_fragments {
-                                        if !referenced.contains(&nested_frag_name) {
-                                            referenced.insert(nested_frag_name.clone());
-                                            to_process.push_back(nested_frag_name);
+                                        if referenced.contains_key(&nested_frag_name) {
+                                            continue;
+                                        }
+                                        if let Some(file) = fragment_file(&nested_frag_name) {
+                                            referenced
+                                                .insert(nested_frag_name.clone(), file.clone());
+                                            to_process.push_back(file);
                                         }
                                     }
                                 }
@@ -811,6 +1338,71 @@ impl GraphQLProject {
         referenced
     }
 
+    /// Collect the fragments defined in files imported by `#import "<path>"` comments,
+    /// with the file defining each
+    ///
+    /// Imports are followed transitively, and each file is read once, so cyclic imports
+    /// terminate. Import paths are relative to the importing file; a relative `file_name`
+    /// is relative to the project's base directory.
+    fn collect_imported_fragments(
+        &self,
+        source: &str,
+        file_name: &str,
+    ) -> std::collections::HashMap<String, String> {
+        use apollo_parser::{cst, Parser};
+        use std::collections::{HashMap, HashSet, VecDeque};
+        use std::path::{Path, PathBuf};
+
+        let mut fragments = HashMap::new();
+
+        let file_path = Path::new(file_name.strip_prefix("file://").unwrap_or(file_name));
+        let file_path = match &self.base_dir {
+            Some(base_dir) => base_dir.join(file_path),
+            None => file_path.to_path_buf(),
+        };
+
+        let mut visited: HashSet<PathBuf> = HashSet::new();
+        visited.insert(
+            file_path
+                .canonicalize()
+                .unwrap_or_else(|_| file_path.clone()),
+        );
+        let mut to_process: VecDeque<PathBuf> = import_pragmas(source, &file_path).into();
+
+        while let Some(imported_path) = to_process.pop_front() {
+            let key = imported_path
+                .canonicalize()
+                .unwrap_or_else(|_| imported_path.clone());
+            if !visited.insert(key) {
+                continue;
+            }
+
+            let Ok(extracted) =
+                graphql_extract::extract_from_file(&imported_path, &self.get_extract_config())
+            else {
+                tracing::warn!("Failed to read imported file: {}", imported_path.display());
+                continue;
+            };
+
+            let imported_file = imported_path.display().to_string();
+            for item in extracted {
+                let tree = Parser::new(&item.source).parse();
+                for definition in tree.document().definitions() {
+                    if let cst::Definition::FragmentDefinition(fragment) = definition {
+                        if let Some(name) = fragment.fragment_name().and_then(|n| n.name()) {
+                            fragments
+                                .entry(name.text().to_string())
+                                .or_insert_with(|| imported_file.clone());
+                        }
+                    }
+                }
+                to_process.extend(import_pragmas(&item.source, &imported_path));
+            }
+        }
+
+        fragments
+    }
+
     /// Get a fragment by name from the document index
     fn get_fragment(&self, name: &str) -> Option<crate::FragmentInfo> {
         let document_index = self.document_index.read().unwrap();
@@ -833,8 +1425,7 @@ impl GraphQLProject {
 
         // Extract GraphQL from the file
         let extracted =
-            graphql_extract::extract_from_file(file_path, &get_extract_config(&self.config))
-                .ok()?;
+            graphql_extract::extract_from_file(file_path, &self.get_extract_config()).ok()?;
 
         // Parse each extracted block looking for the fragment
         for item in extracted {
@@ -901,6 +1492,24 @@ impl GraphQLProject {
                 continue;
             }
 
+            // Tag the errors that have quick fixes so code actions can find them
+            let code = if message_lower.contains("selection of subfields")
+                || message_lower.contains("subselection")
+            {
+                Some("missing-subselection".to_string())
+            } else if message_lower.contains("required argument") {
+                Some("missing-required-argument".to_string())
+            } else {
+                None
+            };
+            let data = Self::diagnostic_data_from_message(&message);
+            let code = code.or_else(|| {
+                data.as_ref().map(|data| match data {
+                    crate::DiagnosticData::UnknownField { .. } => "unknown-field".to_string(),
+                    crate::DiagnosticData::UnknownType { .. } => "unknown-type".to_string(),
+                })
+            });
+
             if let Some(loc_range) = diag.line_column_range() {
                 diagnostics.push(Diagnostic {
                     range: crate::Range {
@@ -915,10 +1524,11 @@ impl GraphQLProject {
                         },
                     },
                     severity: crate::Severity::Error,
-                    code: None,
+                    code,
                     source: "graphql".to_string(),
                     message,
                     related_info: Vec::new(),
+                    data,
                 });
             }
         }
@@ -926,6 +1536,43 @@ impl GraphQLProject {
         diagnostics
     }
 
+    /// Recover what an unknown field or type error refers to from its message
+    ///
+    /// apollo-compiler quotes names with backticks (``type `User` does not have a field
+    /// `fulName` ``), while graphql-js style messages use double quotes
+    /// (`Cannot query field "fulName" on type "User"`); both are handled.
+    fn diagnostic_data_from_message(message: &str) -> Option<crate::DiagnosticData> {
+        let message_lower = message.to_lowercase();
+        let quoted: Vec<&str> = message.split(['`', '"']).skip(1).step_by(2).collect();
+
+        if message_lower.contains("does not have a field") {
+            let [parent_type, field_name, ..] = quoted.as_slice() else {
+                return None;
+            };
+            Some(crate::DiagnosticData::UnknownField {
+                parent_type: (*parent_type).to_string(),
+                field_name: (*field_name).to_string(),
+            })
+        } else if message_lower.contains("cannot query field") {
+            let [field_name, parent_type, ..] = quoted.as_slice() else {
+                return None;
+            };
+            Some(crate::DiagnosticData::UnknownField {
+                parent_type: (*parent_type).to_string(),
+                field_name: (*field_name).to_string(),
+            })
+        } else if message_lower.contains("cannot find type")
+            || message_lower.contains("unknown type")
+        {
+            let type_name = quoted.first()?;
+            Some(crate::DiagnosticData::UnknownType {
+                type_name: (*type_name).to_string(),
+            })
+        } else {
+            None
+        }
+    }
+
     /// Check for unused fragments defined in this specific file/source
     ///
     /// Returns warnings for any fragment definitions in the source that are not
@@ -934,6 +1581,7 @@ impl GraphQLProject {
         source: &str,
         _file_name: &str,
         used_fragments: &std::collections::HashSet<String>,
+        encoding: crate::PositionEncoding,
     ) -> Vec<Diagnostic> {
         use crate::{Diagnostic, Position, Range};
         use apollo_parser::{cst::CstNode, Parser};
@@ -959,7 +1607,7 @@ impl GraphQLProject {
                             // Fragment is truly unused - create a warning
                             let syntax_node = name_node.syntax();
                             let offset: usize = syntax_node.text_range().start().into();
-                            let (line, col) = Self::offset_to_line_col(source, offset);
+                            let (line, col) = Self::offset_to_line_col(source, offset, encoding);
 
                             let range = Range {
                                 start: Position {
@@ -991,7 +1639,11 @@ impl GraphQLProject {
     }
 
     /// Convert a byte offset to a line and column (0-indexed)
-    fn offset_to_line_col(source: &str, offset: usize) -> (usize, usize) {
+    fn offset_to_line_col(
+        source: &str,
+        offset: usize,
+        encoding: crate::PositionEncoding,
+    ) -> (usize, usize) {
         let mut line = 0;
         let mut col = 0;
         let mut current_offset = 0;
@@ -1005,7 +1657,7 @@ impl GraphQLProject {
                 line += 1;
                 col = 0;
             } else {
-                col += 1;
+                col += encoding.char_len(ch);
             }
 
             current_offset += ch.len_utf8();
@@ -1067,7 +1719,8 @@ impl GraphQLProject {
         let is_ts_file = file_path.ends_with(".ts")
             || file_path.ends_with(".tsx")
             || file_path.ends_with(".js")
-            || file_path.ends_with(".jsx");
+            || file_path.ends_with(".jsx")
+            || file_path.ends_with(".svelte");
 
         if is_ts_file {
             tracing::debug!(
@@ -1138,26 +1791,389 @@ impl GraphQLProject {
         }
     }
 
-    /// Get completion items for a position in a GraphQL document
+    /// Get the outline of operations and fragments in a document
+    ///
+    /// For TypeScript/JavaScript files, symbols come from the cached extracted blocks and
+    /// their positions are mapped back to the original file.
     #[must_use]
-    pub fn complete(
-        &self,
-        source: &str,
-        position: Position,
-        file_path: &str,
-    ) -> Option<Vec<CompletionItem>> {
-        let cached_ast = {
-            let document_index = self.document_index.read().unwrap();
-            document_index.get_ast(file_path)
-        };
+    #[allow(clippy::case_sensitive_file_extension_comparisons)]
+    pub fn document_symbols(&self, file_path: &str, full_content: &str) -> Vec<DocumentSymbol> {
+        let is_ts_file = file_path.ends_with(".ts")
+            || file_path.ends_with(".tsx")
+            || file_path.ends_with(".js")
+            || file_path.ends_with(".jsx")
+            || file_path.ends_with(".svelte");
 
-        let document_index = self.document_index.read().unwrap();
-        let schema_index = self.schema_index.read().unwrap();
-        let completion_provider = CompletionProvider::new();
+        let provider =
+            DocumentSymbolProvider::new().with_position_encoding(self.position_encoding());
 
-        completion_provider.complete_with_ast(
-            source,
-            position,
+        if !is_ts_file {
+            return provider.document_symbols(full_content);
+        }
+
+        self.get_extracted_blocks(file_path)
+            .unwrap_or_default()
+            .iter()
+            .flat_map(|block| {
+                provider
+                    .document_symbols_with_ast(&block.content, &block.parsed)
+                    .into_iter()
+                    .map(|symbol| symbol.offset_by(block.start_line, block.start_column))
+            })
+            .collect()
+    }
+
+    /// Get the semantic tokens for a document, in document order
+    ///
+    /// For TypeScript/JavaScript files, tokens come from the cached extracted blocks and
+    /// their positions are mapped back to the original file.
+    #[must_use]
+    #[allow(clippy::case_sensitive_file_extension_comparisons)]
+    pub fn semantic_tokens(&self, file_path: &str, full_content: &str) -> Vec<SemanticToken> {
+        let is_ts_file = file_path.ends_with(".ts")
+            || file_path.ends_with(".tsx")
+            || file_path.ends_with(".js")
+            || file_path.ends_with(".jsx")
+            || file_path.ends_with(".svelte");
+
+        let provider =
+            SemanticTokensProvider::new().with_position_encoding(self.position_encoding());
+
+        if !is_ts_file {
+            return provider.semantic_tokens(full_content);
+        }
+
+        let mut tokens: Vec<SemanticToken> = self
+            .get_extracted_blocks(file_path)
+            .unwrap_or_default()
+            .iter()
+            .flat_map(|block| {
+                provider
+                    .semantic_tokens_with_ast(&block.content, &block.parsed)
+                    .into_iter()
+                    .map(|token| token.offset_by(block.start_line, block.start_column))
+            })
+            .collect();
+        tokens.sort_by_key(|token| (token.start.line, token.start.character));
+        tokens
+    }
+
+    /// Get the folding ranges for a document
+    ///
+    /// For TypeScript/JavaScript files, only the extracted GraphQL blocks are folded, with
+    /// their lines mapped back to the original file.
+    #[must_use]
+    #[allow(clippy::case_sensitive_file_extension_comparisons)]
+    pub fn folding_ranges(&self, file_path: &str, full_content: &str) -> Vec<FoldingRange> {
+        let is_ts_file = file_path.ends_with(".ts")
+            || file_path.ends_with(".tsx")
+            || file_path.ends_with(".js")
+            || file_path.ends_with(".jsx")
+            || file_path.ends_with(".svelte");
+
+        let provider = FoldingRangeProvider::new().with_position_encoding(self.position_encoding());
+
+        if !is_ts_file {
+            return provider.folding_ranges(full_content);
+        }
+
+        self.get_extracted_blocks(file_path)
+            .unwrap_or_default()
+            .iter()
+            .flat_map(|block| {
+                provider
+                    .folding_ranges_with_ast(&block.content, &block.parsed)
+                    .into_iter()
+                    .map(|range| range.offset_by(block.start_line))
+            })
+            .collect()
+    }
+
+    /// Get the highlights for the variable, fragment, or field at a position
+    ///
+    /// Highlights are local to the document, so this uses the cached AST rather than
+    /// searching the project. For TypeScript/JavaScript files, the position is mapped into
+    /// the cached extracted block that contains it, and the highlights back to the file.
+    #[must_use]
+    #[allow(clippy::case_sensitive_file_extension_comparisons)]
+    pub fn document_highlights(
+        &self,
+        file_path: &str,
+        position: Position,
+        full_content: &str,
+    ) -> Vec<DocumentHighlight> {
+        let is_ts_file = file_path.ends_with(".ts")
+            || file_path.ends_with(".tsx")
+            || file_path.ends_with(".js")
+            || file_path.ends_with(".jsx")
+            || file_path.ends_with(".svelte");
+
+        let provider =
+            DocumentHighlightProvider::new().with_position_encoding(self.position_encoding());
+        let schema_index = self.schema_index.read().unwrap();
+
+        if !is_ts_file {
+            let cached_ast = self.document_index.read().unwrap().get_ast(file_path);
+            return match cached_ast {
+                Some(tree) => provider.document_highlights_with_ast(
+                    full_content,
+                    &tree,
+                    position,
+                    &schema_index,
+                ),
+                None => provider.document_highlights(full_content, position, &schema_index),
+            };
+        }
+
+        let Some(blocks) = self.get_extracted_blocks(file_path) else {
+            return Vec::new();
+        };
+        let Some(block) = blocks
+            .iter()
+            .find(|block| position.line >= block.start_line && position.line <= block.end_line)
+        else {
+            return Vec::new();
+        };
+
+        let relative_position = Position {
+            line: position.line - block.start_line,
+            character: if position.line == block.start_line {
+                position.character.saturating_sub(block.start_column)
+            } else {
+                position.character
+            },
+        };
+
+        provider
+            .document_highlights_with_ast(
+                &block.content,
+                &block.parsed,
+                relative_position,
+                &schema_index,
+            )
+            .into_iter()
+            .map(|highlight| highlight.offset_by(block.start_line, block.start_column))
+            .collect()
+    }
+
+    /// Get the field type hints for a document
+    ///
+    /// For TypeScript/JavaScript files, hints come from the cached extracted blocks and
+    /// their positions are mapped back to the original file.
+    #[must_use]
+    #[allow(clippy::case_sensitive_file_extension_comparisons)]
+    pub fn inlay_hints(&self, file_path: &str, full_content: &str) -> Vec<InlayHint> {
+        let is_ts_file = file_path.ends_with(".ts")
+            || file_path.ends_with(".tsx")
+            || file_path.ends_with(".js")
+            || file_path.ends_with(".jsx")
+            || file_path.ends_with(".svelte");
+
+        let provider = InlayHintProvider::new().with_position_encoding(self.position_encoding());
+        let config = get_inlay_hint_config(&self.config);
+        let schema_index = self.schema_index.read().unwrap();
+
+        if !is_ts_file {
+            return provider.inlay_hints(full_content, &schema_index, &config);
+        }
+
+        self.get_extracted_blocks(file_path)
+            .unwrap_or_default()
+            .iter()
+            .flat_map(|block| {
+                provider
+                    .inlay_hints_with_ast(&block.content, &block.parsed, &schema_index, &config)
+                    .into_iter()
+                    .map(|hint| hint.offset_by(block.start_line, block.start_column))
+            })
+            .collect()
+    }
+
+    /// Get the signature help for the argument list at a position
+    ///
+    /// Signature help is requested as `(` and `,` are typed, before the cached ASTs and
+    /// extracted blocks catch up with the edit, so the content is parsed here. For
+    /// TypeScript/JavaScript files, the position is mapped into the GraphQL block that
+    /// contains it.
+    #[must_use]
+    #[allow(clippy::case_sensitive_file_extension_comparisons)]
+    pub fn signature_help(
+        &self,
+        file_path: &str,
+        position: Position,
+        full_content: &str,
+    ) -> Option<SignatureHelp> {
+        use graphql_extract::{extract_from_source, Language};
+
+        let language = if file_path.ends_with(".ts") || file_path.ends_with(".tsx") {
+            Language::TypeScript
+        } else if file_path.ends_with(".js") || file_path.ends_with(".jsx") {
+            Language::JavaScript
+        } else if file_path.ends_with(".svelte") {
+            Language::Svelte
+        } else {
+            Language::GraphQL
+        };
+
+        let provider =
+            SignatureHelpProvider::new().with_position_encoding(self.position_encoding());
+        let schema_index = self.schema_index.read().unwrap();
+
+        if matches!(language, Language::GraphQL) {
+            return provider.signature_help(full_content, position, &schema_index);
+        }
+
+        let extracted =
+            extract_from_source(full_content, language, &self.get_extract_config()).ok()?;
+        let item = extracted.iter().find(|item| {
+            position.line >= item.location.range.start.line
+                && position.line <= item.location.range.end.line
+        })?;
+
+        let start = &item.location.range.start;
+        let relative_position = Position {
+            line: position.line - start.line,
+            character: if position.line == start.line {
+                position.character.saturating_sub(start.column)
+            } else {
+                position.character
+            },
+        };
+
+        provider.signature_help(&item.source, relative_position, &schema_index)
+    }
+
+    /// Search this project's operations, fragments, and schema types by name
+    #[must_use]
+    pub fn workspace_symbols(&self, query: &str) -> Vec<WorkspaceSymbol> {
+        let document_index = self.document_index.read().unwrap();
+        let schema_index = self.schema_index.read().unwrap();
+
+        WorkspaceSymbolProvider::new().workspace_symbols(query, &document_index, &schema_index)
+    }
+
+    /// Get the quick fixes for a diagnostic reported in a document
+    ///
+    /// `code` and `range` identify the diagnostic, and `data` is its structured context, if
+    /// any. For TypeScript/JavaScript files, the
+    /// diagnostic is mapped into the cached extracted block that contains it, and the
+    /// resulting edits are mapped back to the original file.
+    #[must_use]
+    #[allow(clippy::case_sensitive_file_extension_comparisons)]
+    pub fn code_actions(
+        &self,
+        file_path: &str,
+        full_content: &str,
+        code: &str,
+        range: crate::Range,
+        data: Option<&crate::DiagnosticData>,
+    ) -> Vec<CodeAction> {
+        let is_ts_file = file_path.ends_with(".ts")
+            || file_path.ends_with(".tsx")
+            || file_path.ends_with(".js")
+            || file_path.ends_with(".jsx")
+            || file_path.ends_with(".svelte");
+
+        if self.is_read_only_uri(file_path) {
+            return Vec::new();
+        }
+
+        let provider = CodeActionProvider::new().with_position_encoding(self.position_encoding());
+        let schema_index = self.schema_index.read().unwrap();
+
+        if !is_ts_file {
+            return provider.code_actions(full_content, code, range, data, &schema_index);
+        }
+
+        let Some(blocks) = self.get_extracted_blocks(file_path) else {
+            return Vec::new();
+        };
+        let Some(block) = blocks.iter().find(|block| {
+            range.start.line >= block.start_line && range.start.line <= block.end_line
+        }) else {
+            return Vec::new();
+        };
+
+        let to_block = |position: Position| Position {
+            line: position.line - block.start_line,
+            character: if position.line == block.start_line {
+                position.character.saturating_sub(block.start_column)
+            } else {
+                position.character
+            },
+        };
+        let to_file = |position: Position| Position {
+            line: position.line + block.start_line,
+            character: if position.line == 0 {
+                position.character + block.start_column
+            } else {
+                position.character
+            },
+        };
+
+        let block_range = crate::Range {
+            start: to_block(range.start),
+            end: to_block(range.end),
+        };
+
+        provider
+            .code_actions(&block.content, code, block_range, data, &schema_index)
+            .into_iter()
+            .map(|mut action| {
+                for edit in &mut action.edits {
+                    edit.range = crate::Range {
+                        start: to_file(edit.range.start),
+                        end: to_file(edit.range.end),
+                    };
+                }
+                action
+            })
+            .collect()
+    }
+
+    /// Split a GraphQL document with several operations into one operation per file
+    ///
+    /// Returns `None` for read-only files and for TypeScript/JavaScript files, whose
+    /// operations are embedded in code that can't be split up the same way.
+    #[must_use]
+    #[allow(clippy::case_sensitive_file_extension_comparisons)]
+    pub fn split_operations(&self, file_path: &str, content: &str) -> Option<SplitOperations> {
+        let is_ts_file = file_path.ends_with(".ts")
+            || file_path.ends_with(".tsx")
+            || file_path.ends_with(".js")
+            || file_path.ends_with(".jsx")
+            || file_path.ends_with(".svelte");
+
+        if is_ts_file || self.is_read_only_uri(file_path) {
+            return None;
+        }
+
+        CodeActionProvider::new()
+            .with_position_encoding(self.position_encoding())
+            .split_operations(content)
+    }
+
+    /// Get completion items for a position in a GraphQL document
+    #[must_use]
+    pub fn complete(
+        &self,
+        source: &str,
+        position: Position,
+        file_path: &str,
+    ) -> Option<Vec<CompletionItem>> {
+        let cached_ast = {
+            let document_index = self.document_index.read().unwrap();
+            document_index.get_ast(file_path)
+        };
+
+        let document_index = self.document_index.read().unwrap();
+        let schema_index = self.schema_index.read().unwrap();
+        let completion_provider =
+            CompletionProvider::with_config(get_completion_config(&self.config));
+
+        completion_provider.complete_with_ast(
+            source,
+            position,
             &document_index,
             &schema_index,
             cached_ast.as_deref(),
@@ -1192,6 +2208,73 @@ impl GraphQLProject {
         )
     }
 
+    /// Get the implementations of the abstract type referenced at a position
+    ///
+    /// For TypeScript/JavaScript files, the position is mapped into the cached extracted
+    /// block that contains it. Results point at schema type definitions, so they need no
+    /// mapping back.
+    #[must_use]
+    #[allow(clippy::case_sensitive_file_extension_comparisons)]
+    pub fn goto_implementation(
+        &self,
+        file_path: &str,
+        position: Position,
+        full_content: &str,
+    ) -> Option<Vec<DefinitionLocation>> {
+        let is_ts_file = file_path.ends_with(".ts")
+            || file_path.ends_with(".tsx")
+            || file_path.ends_with(".js")
+            || file_path.ends_with(".jsx")
+            || file_path.ends_with(".svelte");
+
+        let schema_index = self.schema_index.read().unwrap();
+        let provider = GotoDefinitionProvider::new();
+
+        if !is_ts_file {
+            let (cached_ast, line_index) = {
+                let document_index = self.document_index.read().unwrap();
+                (
+                    document_index.get_ast(file_path),
+                    document_index.line_index_for(Some(file_path), full_content),
+                )
+            };
+            return provider.goto_implementation(
+                full_content,
+                position,
+                &schema_index,
+                cached_ast.as_deref(),
+                Some(&line_index),
+            );
+        }
+
+        let blocks = self.get_extracted_blocks(file_path)?;
+        let block = blocks
+            .iter()
+            .find(|block| position.line >= block.start_line && position.line <= block.end_line)?;
+
+        let relative_position = Position {
+            line: position.line - block.start_line,
+            character: if position.line == block.start_line {
+                position.character.saturating_sub(block.start_column)
+            } else {
+                position.character
+            },
+        };
+
+        let line_index = self
+            .document_index
+            .read()
+            .unwrap()
+            .line_index_for(None, &block.content);
+        provider.goto_implementation(
+            &block.content,
+            relative_position,
+            &schema_index,
+            Some(&block.parsed),
+            Some(&line_index),
+        )
+    }
+
     /// Find all references to the element at a position in a GraphQL document
     ///
     /// Returns all locations where the element at the given position is referenced.
@@ -1261,6 +2344,71 @@ impl GraphQLProject {
         )
     }
 
+    /// Compute the locations to rewrite when renaming the element at a position
+    ///
+    /// Supports fragments (definitions and spreads), named operations, and variables within
+    /// their operation. Returns `Ok(None)` if the position is not on a renameable element, or
+    /// an error if `new_name` is not a valid GraphQL name. Locations in read-only files are
+    /// left out.
+    ///
+    /// # Arguments
+    /// * `source` - The GraphQL source code of the current document
+    /// * `position` - The position of the element to rename
+    /// * `new_name` - The new name for the element
+    /// * `all_documents` - All GraphQL documents in the project (for finding usages)
+    /// * `source_file_path` - File path of the source document (for `LineIndex` lookup)
+    pub fn rename(
+        &self,
+        source: &str,
+        position: Position,
+        new_name: &str,
+        all_documents: &[(String, String)],
+        source_file_path: Option<&str>,
+    ) -> Result<Option<Vec<ReferenceLocation>>> {
+        let document_index = self.document_index.read().unwrap();
+        let schema_index = self.schema_index.read().unwrap();
+
+        let locations = RenameProvider::new().rename(
+            source,
+            position,
+            new_name,
+            &document_index,
+            &schema_index,
+            all_documents,
+            source_file_path,
+        )?;
+
+        Ok(locations.map(|locations| {
+            locations
+                .into_iter()
+                .filter(|location| !self.is_read_only_uri(&location.file_path))
+                .collect()
+        }))
+    }
+
+    /// Check whether the element at a position can be renamed
+    ///
+    /// Returns the range of the name under the cursor for fragments, operations, and
+    /// variables used in an operation, `Ok(None)` for other elements, and an error for schema
+    /// types.
+    pub fn prepare_rename(
+        &self,
+        source: &str,
+        position: Position,
+        source_file_path: Option<&str>,
+    ) -> Result<Option<crate::Range>> {
+        let document_index = self.document_index.read().unwrap();
+        let schema_index = self.schema_index.read().unwrap();
+
+        RenameProvider::new().prepare_rename(
+            source,
+            position,
+            &document_index,
+            &schema_index,
+            source_file_path,
+        )
+    }
+
     /// Check if a file path matches the schema configuration
     ///
     /// This is used by the LSP to determine if a file should be validated
@@ -1269,6 +2417,10 @@ impl GraphQLProject {
     pub fn is_schema_file(&self, file_path: &std::path::Path) -> bool {
         use glob::Pattern;
 
+        if self.is_read_only_file(file_path) {
+            return true;
+        }
+
         let schema_patterns = self.config.schema.paths();
 
         // Get the file path as a string for matching
@@ -1324,30 +2476,221 @@ impl GraphQLProject {
         false
     }
 
+    /// Check if a file matches this project's document patterns
+    ///
+    /// Used to find the project a document belongs to in multi-project workspaces, so that
+    /// its fragments are resolved against that project's documents only. Files in
+    /// `node_modules` never match, as when loading documents.
+    #[must_use]
+    pub fn is_document_file(&self, file_path: &std::path::Path) -> bool {
+        let Some(ref documents_config) = self.config.documents else {
+            return false;
+        };
+
+        if file_path
+            .components()
+            .any(|component| component.as_os_str() == "node_modules")
+            || self.is_read_only_file(file_path)
+        {
+            return false;
+        }
+
+        documents_config
+            .patterns()
+            .into_iter()
+            .flat_map(DocumentLoader::expand_braces)
+            .any(|pattern| {
+                let normalized_pattern = pattern.strip_prefix("./").unwrap_or(&pattern);
+                let full_pattern = self.base_dir.as_ref().map_or_else(
+                    || normalized_pattern.to_string(),
+                    |base| base.join(normalized_pattern).display().to_string(),
+                );
+                glob::Pattern::new(&full_pattern)
+                    .is_ok_and(|full_pattern| full_pattern.matches_path(file_path))
+            })
+    }
+
+    /// How specifically this project claims a file, or `None` if it doesn't
+    ///
+    /// A file is claimed by the project's `documents`, `schema`, and `include` patterns,
+    /// unless one of its `exclude` patterns matches. The score is the length of the literal
+    /// prefix, before any wildcard, of the longest pattern that claims the file: a file
+    /// matching both `src/web/**/*.graphql` and `src/**/*.graphql` belongs more specifically
+    /// to the project with the first. Used by the LSP to pick a file's project when several
+    /// projects claim it.
+    #[must_use]
+    pub fn match_specificity(&self, file_path: &std::path::Path) -> Option<usize> {
+        let exclude = self.config.exclude.iter().flatten().map(String::as_str);
+        if self.best_pattern_match(exclude, file_path).is_some() {
+            return None;
+        }
+
+        let documents = self
+            .config
+            .documents
+            .as_ref()
+            .filter(|_| self.is_document_file(file_path))
+            .and_then(|documents| self.best_pattern_match(documents.patterns(), file_path));
+
+        // Schema files matched by path rather than by glob, like symlinks, still count
+        let read_only_paths = get_read_only_paths(&self.config);
+        let schema = self.is_schema_file(file_path).then(|| {
+            let patterns = self
+                .config
+                .schema
+                .paths()
+                .into_iter()
+                .chain(read_only_paths.iter().map(String::as_str));
+            self.best_pattern_match(patterns, file_path).unwrap_or(0)
+        });
+
+        let include = self.config.include.iter().flatten().map(String::as_str);
+        let include = self.best_pattern_match(include, file_path);
+
+        [documents, schema, include].into_iter().flatten().max()
+    }
+
+    /// Literal prefix length of the most specific of `patterns` matching a file
+    ///
+    /// Patterns are resolved against the project's base directory, as in
+    /// [`Self::is_document_file`].
+    fn best_pattern_match<'a>(
+        &self,
+        patterns: impl IntoIterator<Item = &'a str>,
+        file_path: &std::path::Path,
+    ) -> Option<usize> {
+        patterns
+            .into_iter()
+            .flat_map(DocumentLoader::expand_braces)
+            .filter_map(|pattern| {
+                let normalized_pattern = pattern.strip_prefix("./").unwrap_or(&pattern);
+                let full_pattern = self.base_dir.as_ref().map_or_else(
+                    || normalized_pattern.to_string(),
+                    |base| base.join(normalized_pattern).display().to_string(),
+                );
+                glob::Pattern::new(&full_pattern)
+                    .is_ok_and(|glob| glob.matches_path(file_path))
+                    .then(|| literal_prefix_len(&full_pattern))
+            })
+            .max()
+    }
+
+    /// Check if a file matches the `extensions.readOnlyPaths` globs
+    ///
+    /// Read-only files, like vendored schemas, are treated as schema files that are never
+    /// edited: they aren't loaded or validated as documents, and rename and code actions
+    /// leave them alone. Go-to-definition can still navigate into them.
+    #[must_use]
+    pub fn is_read_only_file(&self, file_path: &std::path::Path) -> bool {
+        get_read_only_paths(&self.config)
+            .iter()
+            .flat_map(|pattern| DocumentLoader::expand_braces(pattern))
+            .any(|pattern| {
+                let normalized_pattern = pattern.strip_prefix("./").unwrap_or(&pattern);
+                let full_pattern = self.base_dir.as_ref().map_or_else(
+                    || normalized_pattern.to_string(),
+                    |base| base.join(normalized_pattern).display().to_string(),
+                );
+                glob::Pattern::new(&full_pattern)
+                    .is_ok_and(|full_pattern| full_pattern.matches_path(file_path))
+            })
+    }
+
+    /// [`Self::is_read_only_file`] for a path that may be a `file://` URI
+    fn is_read_only_uri(&self, file_path: &str) -> bool {
+        self.is_read_only_file(std::path::Path::new(crate::index::uri_to_file_path(
+            file_path,
+        )))
+    }
+
+    /// Whether any of this project's schema is loaded from a URL
+    ///
+    /// Definitions from a remote schema have no local file, so go-to-definition can't
+    /// navigate to them.
+    #[must_use]
+    pub fn has_remote_schema(&self) -> bool {
+        self.config
+            .schema
+            .paths()
+            .iter()
+            .any(|path| path.starts_with("http://") || path.starts_with("https://"))
+    }
+
+    /// Errors from building the schema that were found in a schema file
+    ///
+    /// When the schema fails to build, the project keeps the partial schema so other
+    /// features still work; these diagnostics explain what's missing from it.
+    #[must_use]
+    pub fn schema_build_diagnostics(&self, file_path: &str) -> Vec<Diagnostic> {
+        let schema_index = self.schema_index.read().unwrap();
+        errors_in_file(schema_index.build_errors(), file_path)
+    }
+
+    /// Diagnostics for a schema file, from building and validating the project's schema
+    ///
+    /// Validates the schema as currently loaded, so after [`Self::update_schema_index`] this
+    /// covers unsaved edits. Reports build errors like duplicate type definitions along with
+    /// validation errors like references to undefined types, misused directives, or repeated
+    /// argument names, keeping only those located in `file_path`.
+    #[must_use]
+    pub fn validate_schema_source(&self, file_path: &str) -> Vec<Diagnostic> {
+        let schema_index = self.schema_index.read().unwrap().clone();
+        let schema = schema_index.schema().clone();
+
+        let mut diagnostics = self.schema_build_diagnostics(file_path);
+        if let Err(with_errors) = schema.validate() {
+            let errors = crate::index::schema_build_errors(&with_errors.errors);
+            diagnostics.extend(errors_in_file(&errors, file_path));
+        }
+
+        // Repeated arguments, reported at the repeated name. apollo-compiler may report the
+        // same argument, so its error at that position is replaced.
+        let duplicate_arguments = errors_in_file(
+            &Validator::new().check_duplicate_argument_definitions(&schema_index),
+            file_path,
+        );
+        diagnostics.retain(|diagnostic| {
+            !duplicate_arguments
+                .iter()
+                .any(|duplicate| duplicate.range.start == diagnostic.range.start)
+        });
+        diagnostics.extend(duplicate_arguments);
+
+        diagnostics
+    }
+
+    /// Glob patterns for this project's local schema files, resolved against the base dir
+    ///
+    /// Remote schemas (http/https URLs) are skipped.
+    #[must_use]
+    pub fn schema_globs(&self) -> Vec<String> {
+        self.config
+            .schema
+            .paths()
+            .into_iter()
+            .filter(|pattern| !pattern.starts_with("http://") && !pattern.starts_with("https://"))
+            .map(|pattern_str| {
+                self.base_dir.as_ref().map_or_else(
+                    || pattern_str.to_string(),
+                    |base| {
+                        let normalized_pattern =
+                            pattern_str.strip_prefix("./").unwrap_or(pattern_str);
+                        base.join(normalized_pattern).display().to_string()
+                    },
+                )
+            })
+            .collect()
+    }
+
     /// Get all schema file paths for this project
     ///
     /// Returns a list of all schema files that match the project's schema patterns.
     /// This is used by the LSP to find schema files that need to be revalidated.
     #[must_use]
     pub fn get_schema_file_paths(&self) -> Vec<String> {
-        let schema_patterns = self.config.schema.paths();
         let mut schema_files = Vec::new();
 
-        for pattern_str in schema_patterns {
-            // Skip remote schemas (http/https URLs)
-            if pattern_str.starts_with("http://") || pattern_str.starts_with("https://") {
-                continue;
-            }
-
-            // Resolve pattern to absolute path if we have a base_dir
-            let pattern_to_glob = self.base_dir.as_ref().map_or_else(
-                || pattern_str.to_string(),
-                |base| {
-                    let normalized_pattern = pattern_str.strip_prefix("./").unwrap_or(pattern_str);
-                    base.join(normalized_pattern).display().to_string()
-                },
-            );
-
+        for pattern_to_glob in self.schema_globs() {
             // Use glob to find matching files
             if let Ok(paths) = glob::glob(&pattern_to_glob) {
                 for entry in paths.flatten() {
@@ -1403,7 +2746,7 @@ mod tests {
             exclude: None,
             extensions: None,
         };
-        let extract_config = get_extract_config(&config);
+        let extract_config = get_extract_config(&config, None);
         assert_eq!(extract_config.magic_comment, "GraphQL");
         assert_eq!(extract_config.tag_identifiers, vec!["gql", "graphql"]);
         assert!(!extract_config.allow_global_identifiers);
@@ -1428,7 +2771,7 @@ mod tests {
             exclude: None,
             extensions: Some(extensions),
         };
-        let extract_config = get_extract_config(&config);
+        let extract_config = get_extract_config(&config, None);
         assert_eq!(extract_config.magic_comment, "CustomGraphQL");
         assert_eq!(extract_config.tag_identifiers, vec!["gql", "customTag"]);
         assert_eq!(extract_config.modules, vec!["custom-module"]);
@@ -1451,9 +2794,120 @@ mod tests {
             exclude: None,
             extensions: Some(extensions),
         };
-        let extract_config = get_extract_config(&config);
+        let extract_config = get_extract_config(&config, None);
         assert_eq!(extract_config.magic_comment, "GraphQL");
         assert_eq!(extract_config.tag_identifiers, vec!["gql", "graphql"]);
         assert!(extract_config.allow_global_identifiers);
     }
+
+    #[test]
+    fn test_get_extract_config_inherits_root() {
+        let yaml = r#"
+projects:
+  app:
+    schema: schema.graphql
+    extensions:
+      extractConfig:
+        magicComment: AppGraphQL
+  admin:
+    schema: schema.graphql
+extensions:
+  extractConfig:
+    magicComment: SharedGraphQL
+    tagIdentifiers: ["gql", "sharedTag"]
+"#;
+        let config: GraphQLConfig = serde_yaml::from_str(yaml).unwrap();
+        let projects = GraphQLProject::from_config(&config).unwrap();
+        let extract_config = |name: &str| {
+            projects
+                .iter()
+                .find(|(project_name, _)| project_name == name)
+                .map(|(_, project)| project.get_extract_config())
+                .unwrap()
+        };
+
+        let app = extract_config("app");
+        assert_eq!(app.magic_comment, "AppGraphQL");
+        assert_eq!(app.tag_identifiers, vec!["gql", "sharedTag"]);
+
+        let admin = extract_config("admin");
+        assert_eq!(admin.magic_comment, "SharedGraphQL");
+        assert_eq!(admin.tag_identifiers, vec!["gql", "sharedTag"]);
+    }
+
+    #[test]
+    fn test_match_specificity_partitions_file_tree() {
+        let yaml = r#"
+projects:
+  web:
+    schema: schema.graphql
+    documents: "src/**/*.graphql"
+    exclude: ["src/mobile/**"]
+  mobile:
+    schema: schema.graphql
+    documents: "src/mobile/**/*.graphql"
+  legacy:
+    schema: schema.graphql
+    include: ["src/legacy/**/*.graphql"]
+"#;
+        let config: GraphQLConfig = serde_yaml::from_str(yaml).unwrap();
+        let base = std::path::Path::new("/repo");
+        let projects = GraphQLProject::from_config_with_base(&config, base).unwrap();
+        let specificity = |name: &str, file: &str| {
+            projects
+                .iter()
+                .find(|(project_name, _)| project_name == name)
+                .and_then(|(_, project)| project.match_specificity(&base.join(file)))
+        };
+
+        let web_query = "src/web/queries/user.graphql";
+        assert!(specificity("web", web_query).is_some());
+        assert_eq!(specificity("mobile", web_query), None);
+
+        // Excluded from web, so only mobile claims it
+        let mobile_query = "src/mobile/screens/feed.graphql";
+        assert_eq!(specificity("web", mobile_query), None);
+        assert!(specificity("mobile", mobile_query).is_some());
+
+        // Both web's documents and legacy's include match, but legacy's glob is more specific
+        let legacy_query = "src/legacy/old.graphql";
+        assert!(specificity("legacy", legacy_query) > specificity("web", legacy_query));
+        assert!(specificity("web", legacy_query).is_some());
+
+        // Files no pattern matches belong to no project
+        assert_eq!(specificity("web", "scripts/seed.graphql"), None);
+        assert_eq!(specificity("mobile", "scripts/seed.graphql"), None);
+    }
+
+    #[test]
+    fn test_diagnostic_data_from_message() {
+        assert_eq!(
+            GraphQLProject::diagnostic_data_from_message(
+                "type `User` does not have a field `fulName`"
+            ),
+            Some(crate::DiagnosticData::UnknownField {
+                parent_type: "User".to_string(),
+                field_name: "fulName".to_string(),
+            })
+        );
+        assert_eq!(
+            GraphQLProject::diagnostic_data_from_message(
+                r#"Cannot query field "fulName" on type "User""#
+            ),
+            Some(crate::DiagnosticData::UnknownField {
+                parent_type: "User".to_string(),
+                field_name: "fulName".to_string(),
+            })
+        );
+        assert_eq!(
+            GraphQLProject::diagnostic_data_from_message("cannot find type `Usr` in this document"),
+            Some(crate::DiagnosticData::UnknownType {
+                type_name: "Usr".to_string(),
+            })
+        );
+        assert_eq!(
+            GraphQLProject::diagnostic_data_from_message("the operation name is not unique"),
+            None
+        );
+    }
 }
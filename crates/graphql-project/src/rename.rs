@@ -0,0 +1,903 @@
+use crate::{
+    goto_definition::ElementType, index::uri_to_file_path, DocumentIndex, FindReferencesProvider,
+    GotoDefinitionProvider, LineIndex, Position, ProjectError, Range, ReferenceLocation, Result,
+    SchemaIndex,
+};
+use apollo_parser::{
+    cst::{self, CstNode},
+    Parser,
+};
+use std::collections::HashSet;
+
+/// Element that can be renamed
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum RenameTarget {
+    Fragment {
+        name: String,
+    },
+    Operation {
+        name: String,
+    },
+    /// A variable, which is scoped to the operation that defines it
+    Variable {
+        name: String,
+    },
+}
+
+/// Rename provider for fragments, named operations, and operation variables
+pub struct RenameProvider;
+
+impl RenameProvider {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Compute every location that must be rewritten to rename the element at a position
+    ///
+    /// Fragments are renamed at every definition and spread across `all_documents` and the
+    /// files in `document_index`, using `FindReferencesProvider` to locate the spreads. Named
+    /// operations are renamed at each of their definitions, and variables at their definition
+    /// and every usage within the enclosing operation. Only names of the target's kind are
+    /// touched, so fields, types, or other elements that happen to share the name are left
+    /// alone.
+    ///
+    /// Returns `Ok(None)` when the cursor is not on a renameable element, and an error if
+    /// `new_name` is not a legal GraphQL name.
+    #[allow(clippy::too_many_arguments)]
+    pub fn rename(
+        &self,
+        source: &str,
+        position: Position,
+        new_name: &str,
+        document_index: &DocumentIndex,
+        schema_index: &SchemaIndex,
+        all_documents: &[(String, String)],
+        file_path: Option<&str>,
+    ) -> Result<Option<Vec<ReferenceLocation>>> {
+        let tree = Parser::new(source).parse();
+        if tree.errors().count() > 0 {
+            return Ok(None);
+        }
+
+        let line_index = document_index.line_index_for(file_path, source);
+        let Some(byte_offset) = line_index.position_to_offset(position) else {
+            return Ok(None);
+        };
+
+        let Some((target, name_offset)) =
+            Self::find_target_at_offset(&tree.document(), byte_offset)
+        else {
+            return Ok(None);
+        };
+
+        Self::validate_new_name(&target, new_name)?;
+
+        let mut locations = Vec::new();
+        match &target {
+            RenameTarget::Fragment { name } => {
+                // Spreads come from find references; definitions are collected directly since
+                // find references only reports declarations when started from a definition.
+                // Start from the beginning of the name so a cursor at its end still resolves.
+                let name_position = line_index.offset_to_position(name_offset);
+                let spreads = FindReferencesProvider::new().find_references(
+                    source,
+                    name_position,
+                    document_index,
+                    schema_index,
+                    all_documents,
+                    false,
+                );
+                locations.extend(spreads.unwrap_or_default());
+                Self::collect_definitions(
+                    all_documents,
+                    document_index,
+                    &target,
+                    name,
+                    &mut locations,
+                );
+            }
+            RenameTarget::Operation { name } => {
+                Self::collect_definitions(
+                    all_documents,
+                    document_index,
+                    &target,
+                    name,
+                    &mut locations,
+                );
+            }
+            RenameTarget::Variable { name } => {
+                Self::collect_variables(
+                    &tree.document(),
+                    byte_offset,
+                    name,
+                    file_path.unwrap_or_default(),
+                    &line_index,
+                    &mut locations,
+                );
+            }
+        }
+
+        // A location can be reported twice if the same document is reachable more than once,
+        // such as an open document keyed by URI that is also indexed by path
+        let mut seen = HashSet::new();
+        locations.retain(|location| {
+            seen.insert((
+                uri_to_file_path(&location.file_path).to_string(),
+                location.range.start.line,
+                location.range.start.character,
+            ))
+        });
+
+        Ok(Some(locations))
+    }
+
+    /// Check whether the element at a position can be renamed, before prompting for a name
+    ///
+    /// Returns the range of the name token under the cursor for fragment names, operation
+    /// names, and variables used in an operation. Returns `Ok(None)` for anything else,
+    /// including variables in fragments whose defining operation isn't known, and an error for
+    /// schema types, which are defined in read-only schema files.
+    pub fn prepare_rename(
+        &self,
+        source: &str,
+        position: Position,
+        document_index: &DocumentIndex,
+        schema_index: &SchemaIndex,
+        file_path: Option<&str>,
+    ) -> Result<Option<Range>> {
+        let tree = Parser::new(source).parse();
+        if tree.errors().count() > 0 {
+            return Ok(None);
+        }
+
+        let line_index = document_index.line_index_for(file_path, source);
+        let Some(byte_offset) = line_index.position_to_offset(position) else {
+            return Ok(None);
+        };
+
+        let document = tree.document();
+        let element = GotoDefinitionProvider::find_element_at_position(
+            &document,
+            byte_offset,
+            source,
+            schema_index,
+        );
+
+        match element {
+            Some(
+                ElementType::FragmentSpread { .. }
+                | ElementType::FragmentDefinition { .. }
+                | ElementType::OperationDefinition { .. },
+            ) => Ok(Self::name_range_at(source, byte_offset, &line_index)),
+            // Only variables in an operation can be renamed, since that's where they're scoped
+            Some(ElementType::Variable { .. }) => Ok(matches!(
+                Self::find_target_at_offset(&document, byte_offset),
+                Some((RenameTarget::Variable { .. }, _))
+            )
+            .then(|| Self::name_range_at(source, byte_offset, &line_index))
+            .flatten()),
+            Some(ElementType::TypeReference { type_name }) => Err(ProjectError::Validation(
+                format!("Cannot rename schema type '{type_name}'"),
+            )),
+            _ => Ok(None),
+        }
+    }
+
+    /// Range of the name token containing `byte_offset`
+    fn name_range_at(source: &str, byte_offset: usize, line_index: &LineIndex) -> Option<Range> {
+        let byte_offset = byte_offset.min(source.len());
+        if !source.is_char_boundary(byte_offset) {
+            return None;
+        }
+
+        let is_name_byte = |byte: &u8| *byte == b'_' || byte.is_ascii_alphanumeric();
+        let bytes = source.as_bytes();
+
+        let start = bytes[..byte_offset]
+            .iter()
+            .rposition(|byte| !is_name_byte(byte))
+            .map_or(0, |index| index + 1);
+        let end = bytes[byte_offset..]
+            .iter()
+            .position(|byte| !is_name_byte(byte))
+            .map_or(bytes.len(), |index| byte_offset + index);
+
+        if start == end {
+            return None;
+        }
+
+        Some(Range {
+            start: line_index.offset_to_position(start),
+            end: line_index.offset_to_position(end),
+        })
+    }
+
+    /// Check whether `name` is a legal GraphQL name (`/[_A-Za-z][_0-9A-Za-z]*/`)
+    #[must_use]
+    pub fn is_valid_name(name: &str) -> bool {
+        let mut chars = name.chars();
+        chars
+            .next()
+            .is_some_and(|first| first == '_' || first.is_ascii_alphabetic())
+            && chars.all(|ch| ch == '_' || ch.is_ascii_alphanumeric())
+    }
+
+    fn validate_new_name(target: &RenameTarget, new_name: &str) -> Result<()> {
+        if !Self::is_valid_name(new_name) {
+            return Err(ProjectError::Validation(format!(
+                "'{new_name}' is not a valid GraphQL name"
+            )));
+        }
+
+        // `... on` would be parsed as an inline fragment rather than a spread
+        if matches!(target, RenameTarget::Fragment { .. }) && new_name == "on" {
+            return Err(ProjectError::Validation(
+                "A fragment cannot be named 'on'".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Find the fragment or operation name at the given byte offset
+    ///
+    /// Returns the target along with the byte offset where its name starts.
+    fn find_target_at_offset(
+        doc: &cst::Document,
+        byte_offset: usize,
+    ) -> Option<(RenameTarget, usize)> {
+        for definition in doc.definitions() {
+            match definition {
+                cst::Definition::OperationDefinition(op) => {
+                    if let Some(name) = op.name() {
+                        if Self::contains(&name, byte_offset) {
+                            return Some((
+                                RenameTarget::Operation {
+                                    name: name.text().to_string(),
+                                },
+                                name.syntax().text_range().start().into(),
+                            ));
+                        }
+                    }
+                    if let Some(target) = Self::find_variable_at_offset(&op, byte_offset) {
+                        return Some(target);
+                    }
+                    if let Some(selection_set) = op.selection_set() {
+                        if let Some(target) =
+                            Self::find_spread_at_offset(&selection_set, byte_offset)
+                        {
+                            return Some(target);
+                        }
+                    }
+                }
+                cst::Definition::FragmentDefinition(frag) => {
+                    if let Some(name) = frag.fragment_name().and_then(|n| n.name()) {
+                        if Self::contains(&name, byte_offset) {
+                            return Some((
+                                RenameTarget::Fragment {
+                                    name: name.text().to_string(),
+                                },
+                                name.syntax().text_range().start().into(),
+                            ));
+                        }
+                    }
+                    if let Some(selection_set) = frag.selection_set() {
+                        if let Some(target) =
+                            Self::find_spread_at_offset(&selection_set, byte_offset)
+                        {
+                            return Some(target);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        None
+    }
+
+    /// Find the variable at the given byte offset in an operation, including its `$`
+    fn find_variable_at_offset(
+        op: &cst::OperationDefinition,
+        byte_offset: usize,
+    ) -> Option<(RenameTarget, usize)> {
+        op.syntax()
+            .descendants()
+            .filter_map(cst::Variable::cast)
+            .find_map(|variable| {
+                let name = variable.name()?;
+                let start: usize = variable.syntax().text_range().start().into();
+                let end: usize = name.syntax().text_range().end().into();
+                (start <= byte_offset && byte_offset <= end).then(|| {
+                    (
+                        RenameTarget::Variable {
+                            name: name.text().to_string(),
+                        },
+                        name.syntax().text_range().start().into(),
+                    )
+                })
+            })
+    }
+
+    fn find_spread_at_offset(
+        selection_set: &cst::SelectionSet,
+        byte_offset: usize,
+    ) -> Option<(RenameTarget, usize)> {
+        for selection in selection_set.selections() {
+            let nested = match selection {
+                cst::Selection::FragmentSpread(spread) => {
+                    if let Some(name) = spread.fragment_name().and_then(|f| f.name()) {
+                        if Self::contains(&name, byte_offset) {
+                            return Some((
+                                RenameTarget::Fragment {
+                                    name: name.text().to_string(),
+                                },
+                                name.syntax().text_range().start().into(),
+                            ));
+                        }
+                    }
+                    None
+                }
+                cst::Selection::Field(field) => field.selection_set(),
+                cst::Selection::InlineFragment(inline_frag) => inline_frag.selection_set(),
+            };
+
+            if let Some(target) =
+                nested.and_then(|set| Self::find_spread_at_offset(&set, byte_offset))
+            {
+                return Some(target);
+            }
+        }
+
+        None
+    }
+
+    /// Collect the name of every definition of `target` across all documents
+    ///
+    /// Definitions in `all_documents` are found in their current content. Definitions in any
+    /// other file come from `document_index`, so files that aren't open are renamed too.
+    fn collect_definitions(
+        all_documents: &[(String, String)],
+        document_index: &DocumentIndex,
+        target: &RenameTarget,
+        name: &str,
+        locations: &mut Vec<ReferenceLocation>,
+    ) {
+        for (file_path, source) in all_documents {
+            let tree = Parser::new(source).parse();
+            if tree.errors().count() > 0 {
+                continue;
+            }
+            let line_index =
+                document_index.line_index_for(Some(uri_to_file_path(file_path)), source);
+
+            for definition in tree.document().definitions() {
+                let name_node = match (&definition, target) {
+                    (cst::Definition::FragmentDefinition(frag), RenameTarget::Fragment { .. }) => {
+                        frag.fragment_name().and_then(|n| n.name())
+                    }
+                    (cst::Definition::OperationDefinition(op), RenameTarget::Operation { .. }) => {
+                        op.name()
+                    }
+                    _ => None,
+                };
+
+                let Some(name_node) = name_node.filter(|n| n.text() == name) else {
+                    continue;
+                };
+
+                let range = name_node.syntax().text_range();
+                locations.push(ReferenceLocation::new(
+                    file_path.clone(),
+                    Range {
+                        start: line_index.offset_to_position(range.start().into()),
+                        end: line_index.offset_to_position(range.end().into()),
+                    },
+                ));
+            }
+        }
+
+        let indexed: Vec<(&str, usize, usize)> = match target {
+            RenameTarget::Fragment { .. } => document_index
+                .fragments
+                .get(name)
+                .into_iter()
+                .flatten()
+                .map(|info| (info.file_path.as_str(), info.line, info.column))
+                .collect(),
+            RenameTarget::Operation { .. } => document_index
+                .operations
+                .get(name)
+                .into_iter()
+                .flatten()
+                .map(|info| (info.file_path.as_str(), info.line, info.column))
+                .collect(),
+            RenameTarget::Variable { .. } => Vec::new(),
+        };
+        for (file_path, line, column) in indexed {
+            let is_open = all_documents
+                .iter()
+                .any(|(open_path, _)| uri_to_file_path(open_path) == uri_to_file_path(file_path));
+            if is_open {
+                continue;
+            }
+
+            locations.push(ReferenceLocation::new(
+                file_path.to_string(),
+                Range {
+                    start: Position {
+                        line,
+                        character: column,
+                    },
+                    end: Position {
+                        line,
+                        character: column + name.len(),
+                    },
+                },
+            ));
+        }
+    }
+
+    /// Collect the definition and every usage of a variable in the operation at `byte_offset`
+    fn collect_variables(
+        doc: &cst::Document,
+        byte_offset: usize,
+        name: &str,
+        file_path: &str,
+        line_index: &LineIndex,
+        locations: &mut Vec<ReferenceLocation>,
+    ) {
+        let operation = doc.definitions().find_map(|definition| match definition {
+            cst::Definition::OperationDefinition(op) => {
+                let range = op.syntax().text_range();
+                let (start, end): (usize, usize) = (range.start().into(), range.end().into());
+                (start <= byte_offset && byte_offset <= end).then_some(op)
+            }
+            _ => None,
+        });
+        let Some(operation) = operation else {
+            return;
+        };
+
+        for variable in operation
+            .syntax()
+            .descendants()
+            .filter_map(cst::Variable::cast)
+        {
+            let Some(variable_name) = variable.name().filter(|n| n.text() == name) else {
+                continue;
+            };
+            let range = variable_name.syntax().text_range();
+            locations.push(ReferenceLocation::new(
+                file_path.to_string(),
+                Range {
+                    start: line_index.offset_to_position(range.start().into()),
+                    end: line_index.offset_to_position(range.end().into()),
+                },
+            ));
+        }
+    }
+
+    fn contains(name: &cst::Name, byte_offset: usize) -> bool {
+        let range = name.syntax().text_range();
+        let start: usize = range.start().into();
+        let end: usize = range.end().into();
+        byte_offset >= start && byte_offset <= end
+    }
+}
+
+impl Default for RenameProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rename_at(
+        documents: &[(String, String)],
+        file_path: &str,
+        position: Position,
+        new_name: &str,
+    ) -> Result<Option<Vec<ReferenceLocation>>> {
+        let source = &documents
+            .iter()
+            .find(|(path, _)| path == file_path)
+            .unwrap()
+            .1;
+
+        RenameProvider::new().rename(
+            source,
+            position,
+            new_name,
+            &DocumentIndex::new(),
+            &SchemaIndex::new(),
+            documents,
+            Some(file_path),
+        )
+    }
+
+    #[test]
+    fn test_rename_fragment_from_spread() {
+        let documents = vec![
+            (
+                "file:///fragments.graphql".to_string(),
+                "fragment UserFields on User {\n  id\n}\n".to_string(),
+            ),
+            (
+                "file:///query.graphql".to_string(),
+                "query GetUser {\n  user {\n    ...UserFields\n  }\n}\n".to_string(),
+            ),
+        ];
+
+        let locations = rename_at(
+            &documents,
+            "file:///query.graphql",
+            Position {
+                line: 2,
+                character: 8,
+            },
+            "UserDetails",
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(locations.len(), 2);
+        assert!(locations
+            .iter()
+            .any(|l| l.file_path == "file:///fragments.graphql"
+                && l.range.start.line == 0
+                && l.range.start.character == 9
+                && l.range.end.character == 19));
+        assert!(locations
+            .iter()
+            .any(|l| l.file_path == "file:///query.graphql"
+                && l.range.start.line == 2
+                && l.range.start.character == 7
+                && l.range.end.character == 17));
+    }
+
+    #[test]
+    fn test_rename_fragment_duplicated_across_files() {
+        let documents = vec![
+            (
+                "file:///a.graphql".to_string(),
+                "fragment UserFields on User { id }".to_string(),
+            ),
+            (
+                "file:///b.graphql".to_string(),
+                "fragment UserFields on User { name }".to_string(),
+            ),
+            (
+                "file:///query.graphql".to_string(),
+                "query { user { ...UserFields } }".to_string(),
+            ),
+        ];
+
+        let locations = rename_at(
+            &documents,
+            "file:///a.graphql",
+            Position {
+                line: 0,
+                character: 10,
+            },
+            "UserDetails",
+        )
+        .unwrap()
+        .unwrap();
+
+        let mut files: Vec<_> = locations.iter().map(|l| l.file_path.as_str()).collect();
+        files.sort_unstable();
+        assert_eq!(
+            files,
+            vec![
+                "file:///a.graphql",
+                "file:///b.graphql",
+                "file:///query.graphql"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rename_fragment_includes_indexed_files_once() {
+        let mut document_index = DocumentIndex::new();
+        for (file_path, line) in [("/fragments.graphql", 0), ("/other.graphql", 2)] {
+            document_index.add_fragment(
+                "UserFields".to_string(),
+                crate::FragmentInfo {
+                    name: "UserFields".to_string(),
+                    type_condition: "User".to_string(),
+                    file_path: file_path.to_string(),
+                    line,
+                    column: 9,
+                },
+            );
+        }
+        // The open fragments file is also indexed, under its path rather than its URI
+        let documents = vec![
+            (
+                "file:///fragments.graphql".to_string(),
+                "fragment UserFields on User { id }".to_string(),
+            ),
+            (
+                "file:///query.graphql".to_string(),
+                "query { user { ...UserFields } }".to_string(),
+            ),
+        ];
+
+        let locations = RenameProvider::new()
+            .rename(
+                &documents[1].1,
+                Position {
+                    line: 0,
+                    character: 20,
+                },
+                "UserDetails",
+                &document_index,
+                &SchemaIndex::new(),
+                &documents,
+                Some("file:///query.graphql"),
+            )
+            .unwrap()
+            .unwrap();
+
+        let mut files: Vec<_> = locations
+            .iter()
+            .map(|l| uri_to_file_path(&l.file_path))
+            .collect();
+        files.sort_unstable();
+        assert_eq!(
+            files,
+            vec!["/fragments.graphql", "/other.graphql", "/query.graphql"]
+        );
+        assert!(locations.iter().any(|l| l.file_path == "/other.graphql"
+            && l.range.start.line == 2
+            && l.range.start.character == 9
+            && l.range.end.character == 19));
+    }
+
+    #[test]
+    fn test_rename_fragment_ignores_same_named_identifiers() {
+        let documents = vec![(
+            "file:///query.graphql".to_string(),
+            "query User { User { ...User } }\nfragment User on User { id }".to_string(),
+        )];
+
+        let locations = rename_at(
+            &documents,
+            "file:///query.graphql",
+            Position {
+                line: 1,
+                character: 9,
+            },
+            "UserFields",
+        )
+        .unwrap()
+        .unwrap();
+
+        // Only the spread and the fragment definition; not the operation, field, or type
+        assert_eq!(locations.len(), 2);
+        assert!(locations
+            .iter()
+            .any(|l| l.range.start.line == 0 && l.range.start.character == 23));
+        assert!(locations
+            .iter()
+            .any(|l| l.range.start.line == 1 && l.range.start.character == 9));
+    }
+
+    #[test]
+    fn test_rename_operation() {
+        let documents = vec![(
+            "file:///query.graphql".to_string(),
+            "query GetUser { user { id } }".to_string(),
+        )];
+
+        let locations = rename_at(
+            &documents,
+            "file:///query.graphql",
+            Position {
+                line: 0,
+                character: 8,
+            },
+            "FetchUser",
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].range.start.character, 6);
+        assert_eq!(locations[0].range.end.character, 13);
+    }
+
+    #[test]
+    fn test_rename_variable_within_operation() {
+        let documents = vec![(
+            "file:///query.graphql".to_string(),
+            "query GetUser($id: ID!) {\n  user(id: $id) { id }\n}\n\
+             query Other($id: ID!) {\n  user(id: $id) { id }\n}\n"
+                .to_string(),
+        )];
+
+        let locations = rename_at(
+            &documents,
+            "file:///query.graphql",
+            Position {
+                line: 1,
+                character: 12,
+            },
+            "userId",
+        )
+        .unwrap()
+        .unwrap();
+
+        // The definition and the usage, but not the same-named variable in `Other`
+        let ranges: Vec<_> = locations
+            .iter()
+            .map(|l| {
+                (
+                    l.range.start.line,
+                    l.range.start.character,
+                    l.range.end.character,
+                )
+            })
+            .collect();
+        assert_eq!(ranges, vec![(0, 15, 17), (1, 12, 14)]);
+    }
+
+    #[test]
+    fn test_rename_rejects_invalid_name() {
+        let documents = vec![(
+            "file:///query.graphql".to_string(),
+            "query GetUser { user { id } }".to_string(),
+        )];
+
+        let result = rename_at(
+            &documents,
+            "file:///query.graphql",
+            Position {
+                line: 0,
+                character: 8,
+            },
+            "1Invalid",
+        );
+
+        assert!(matches!(result, Err(ProjectError::Validation(_))));
+    }
+
+    #[test]
+    fn test_rename_on_field_returns_none() {
+        let documents = vec![(
+            "file:///query.graphql".to_string(),
+            "query GetUser { user { id } }".to_string(),
+        )];
+
+        let result = rename_at(
+            &documents,
+            "file:///query.graphql",
+            Position {
+                line: 0,
+                character: 17,
+            },
+            "account",
+        )
+        .unwrap();
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_prepare_rename_on_field_returns_none() {
+        let source = "query GetUser { user { id } }";
+
+        let result = RenameProvider::new()
+            .prepare_rename(
+                source,
+                Position {
+                    line: 0,
+                    character: 17,
+                },
+                &DocumentIndex::new(),
+                &SchemaIndex::new(),
+                None,
+            )
+            .unwrap();
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_prepare_rename_on_fragment_spread_returns_name_range() {
+        let source = "query GetUser { user { ...UserFields } }";
+
+        let result = RenameProvider::new()
+            .prepare_rename(
+                source,
+                Position {
+                    line: 0,
+                    character: 30,
+                },
+                &DocumentIndex::new(),
+                &SchemaIndex::new(),
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(
+            result,
+            Some(Range {
+                start: Position {
+                    line: 0,
+                    character: 26,
+                },
+                end: Position {
+                    line: 0,
+                    character: 36,
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_prepare_rename_on_variable_in_fragment_returns_none() {
+        let source = "fragment UserFields on User { friends(first: $count) { id } }";
+
+        let result = RenameProvider::new()
+            .prepare_rename(
+                source,
+                Position {
+                    line: 0,
+                    character: 47,
+                },
+                &DocumentIndex::new(),
+                &SchemaIndex::new(),
+                None,
+            )
+            .unwrap();
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_name_range_at_clamps_offset() {
+        let source = "query Aé";
+        let line_index = LineIndex::new(source);
+
+        // Past the end of the source, the offset is clamped to the last name
+        assert_eq!(
+            RenameProvider::name_range_at("query A", 100, &LineIndex::new("query A")),
+            Some(Range {
+                start: Position {
+                    line: 0,
+                    character: 6,
+                },
+                end: Position {
+                    line: 0,
+                    character: 7,
+                },
+            })
+        );
+        // Inside a multi-byte character
+        assert_eq!(RenameProvider::name_range_at(source, 8, &line_index), None);
+    }
+
+    #[test]
+    fn test_prepare_rename_on_schema_type_is_error() {
+        let source = "fragment UserFields on User { id }";
+
+        let result = RenameProvider::new().prepare_rename(
+            source,
+            Position {
+                line: 0,
+                character: 24,
+            },
+            &DocumentIndex::new(),
+            &SchemaIndex::new(),
+            None,
+        );
+
+        assert!(matches!(result, Err(ProjectError::Validation(_))));
+    }
+}
@@ -1,19 +1,45 @@
-use crate::{ProjectError, Result};
+use crate::{introspection, schema_cache::write_cache_file, ProjectError, Result, SchemaCache};
 use graphql_config::SchemaConfig;
-use std::path::Path;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Options for schemas loaded from a URL, read from `extensions.remoteSchema`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RemoteSchemaConfig {
+    /// Headers sent with the introspection request, like `Authorization`
+    pub headers: HashMap<String, String>,
+    /// Request timeout in seconds
+    pub timeout: u64,
+}
+
+impl Default for RemoteSchemaConfig {
+    fn default() -> Self {
+        Self {
+            headers: HashMap::new(),
+            timeout: 30,
+        }
+    }
+}
 
 /// Schema loader for loading GraphQL schemas from various sources
 pub struct SchemaLoader {
     config: SchemaConfig,
     base_path: Option<std::path::PathBuf>,
+    remote_config: RemoteSchemaConfig,
+    remote_cache_dir: Option<PathBuf>,
 }
 
 impl SchemaLoader {
     #[must_use]
-    pub const fn new(config: SchemaConfig) -> Self {
+    pub fn new(config: SchemaConfig) -> Self {
         Self {
             config,
             base_path: None,
+            remote_config: RemoteSchemaConfig::default(),
+            remote_cache_dir: Self::default_remote_cache_dir(),
         }
     }
 
@@ -23,6 +49,33 @@ impl SchemaLoader {
         self
     }
 
+    /// Set the headers and timeout used to introspect remote schemas
+    #[must_use]
+    pub fn with_remote_config(mut self, config: RemoteSchemaConfig) -> Self {
+        self.remote_config = config;
+        self
+    }
+
+    /// Keep the copy of each remote schema in this directory
+    ///
+    /// After every successful introspection the SDL is written here. When a later request
+    /// fails, the copy is used instead so the schema is still available offline. Defaults
+    /// to [`Self::default_remote_cache_dir`].
+    #[must_use]
+    pub fn with_remote_cache_dir(mut self, dir: impl AsRef<Path>) -> Self {
+        self.remote_cache_dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Default directory for remote schema copies
+    ///
+    /// A `remote` directory next to the [`SchemaCache`] default directory, or `None` if
+    /// there is no cache directory for the current user.
+    #[must_use]
+    pub fn default_remote_cache_dir() -> Option<PathBuf> {
+        SchemaCache::default_dir().and_then(|dir| dir.parent().map(|parent| parent.join("remote")))
+    }
+
     /// Load schema files with their paths for proper source tracking
     pub async fn load_with_paths(&self) -> Result<Vec<(String, String)>> {
         // Include Apollo Client built-in directives
@@ -66,37 +119,68 @@ impl SchemaLoader {
             .join("\n\n"))
     }
 
+    /// Find the local schema files, skipping remote schemas
+    pub fn find_local_files(&self) -> Result<Vec<std::path::PathBuf>> {
+        let mut files = Vec::new();
+        for path in self.config.paths() {
+            if path.starts_with("http://") || path.starts_with("https://") {
+                continue;
+            }
+            for file in self.find_local_paths(path)? {
+                if !files.contains(&file) {
+                    files.push(file);
+                }
+            }
+        }
+        Ok(files)
+    }
+
     /// Load schema from local file(s) with paths, supporting glob patterns
+    ///
+    /// `.json` files are read as introspection results and converted to SDL.
     fn load_local_with_paths(&self, pattern: &str) -> Result<Vec<(String, String)>> {
+        let mut schema_files = Vec::new();
+
+        for path in self.find_local_paths(pattern)? {
+            let content = std::fs::read_to_string(&path)?;
+            let path_str = path.display().to_string();
+            let content = schema_file_sdl(&path_str, content);
+            schema_files.push((path_str, content));
+        }
+
+        Ok(schema_files)
+    }
+
+    /// Find the files matching a local schema path or glob pattern
+    fn find_local_paths(&self, pattern: &str) -> Result<Vec<std::path::PathBuf>> {
         let pattern = self.base_path.as_ref().map_or_else(
             || pattern.to_string(),
             |base| base.join(pattern).display().to_string(),
         );
 
-        let mut schema_files = Vec::new();
+        let mut paths = Vec::new();
 
         // Try as glob pattern first
         match glob::glob(&pattern) {
-            Ok(paths) => {
-                let mut found_any = false;
-                for entry in paths {
+            Ok(entries) => {
+                for entry in entries {
                     match entry {
-                        Ok(path) => {
-                            found_any = true;
-                            let content = std::fs::read_to_string(&path)?;
-                            let path_str = path.display().to_string();
-                            schema_files.push((path_str, content));
-                        }
+                        Ok(path) => paths.push(path),
                         Err(e) => {
                             return Err(ProjectError::SchemaLoad(format!("Glob error: {e}")));
                         }
                     }
                 }
 
-                if !found_any {
-                    return Err(ProjectError::SchemaLoad(format!(
-                        "No files matched pattern: {pattern}"
-                    )));
+                if paths.is_empty() {
+                    // A plain path that matches nothing is a file that doesn't exist,
+                    // usually a typo in the config
+                    let is_glob = pattern.contains(['*', '?', '[']);
+                    return Err(ProjectError::SchemaLoad(if is_glob {
+                        format!("No files matched pattern: {pattern}")
+                    } else {
+                        format!("Schema file not found: {pattern}")
+                    }));
                 }
             }
             Err(e) => {
@@ -106,17 +190,154 @@ impl SchemaLoader {
             }
         }
 
-        Ok(schema_files)
+        Ok(paths)
     }
 
     /// Load schema from remote endpoint via introspection
-    #[allow(clippy::unused_async)] // Will be async when implemented
+    ///
+    /// Falls back to the cached copy from an earlier load if the request fails.
     async fn load_remote(&self, url: &str) -> Result<String> {
-        // TODO: Implement GraphQL introspection query
-        // For now, return a placeholder error
-        Err(ProjectError::SchemaLoad(format!(
-            "Remote schema loading not yet implemented for URL: {url}"
-        )))
+        let cache_path = self.remote_cache_path(url);
+
+        match self.introspect(url).await {
+            Ok(sdl) => {
+                if let Some(ref cache_path) = cache_path {
+                    if let Err(e) = write_cache_file(cache_path, &sdl) {
+                        tracing::warn!("Failed to cache remote schema from {}: {}", url, e);
+                    }
+                }
+                Ok(sdl)
+            }
+            Err(e) => {
+                let Some(sdl) = cache_path.and_then(|path| std::fs::read_to_string(path).ok())
+                else {
+                    return Err(ProjectError::SchemaLoad(format!(
+                        "Failed to introspect remote schema {url}: {e}"
+                    )));
+                };
+                tracing::warn!("Failed to introspect {}, using cached schema: {}", url, e);
+                Ok(sdl)
+            }
+        }
+    }
+
+    /// Send the introspection query to a URL and convert the result to SDL
+    async fn introspect(&self, url: &str) -> Result<String> {
+        let response = self.fetch_introspection(url).await?;
+        introspection::introspection_to_sdl(&response)
+    }
+
+    /// Send the introspection query to a URL, with the configured headers and timeout
+    ///
+    /// Returns the response as is, like `{ "data": { "__schema": ... } }`, which can be
+    /// saved as a `.json` schema file or converted with [`crate::introspection_to_sdl`].
+    /// If the server rejects the query, it is retried without the fields older servers
+    /// don't support. GraphQL errors in the response are returned as
+    /// [`ProjectError::SchemaLoad`]. Unlike loading a remote schema, no cached copy is used
+    /// or written.
+    pub async fn fetch_introspection(&self, url: &str) -> Result<serde_json::Value> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(self.remote_config.timeout))
+            .build()?;
+
+        match self
+            .post_introspection(&client, url, introspection::INTROSPECTION_QUERY)
+            .await
+        {
+            Err(e) if is_rejected_query(&e) => {
+                tracing::debug!(
+                    "Retrying introspection of {} with the legacy query: {}",
+                    url,
+                    e
+                );
+                self.post_introspection(&client, url, &introspection::legacy_introspection_query())
+                    .await
+            }
+            result => result,
+        }
+    }
+
+    /// Send an introspection query to a URL with the configured headers
+    async fn post_introspection(
+        &self,
+        client: &reqwest::Client,
+        url: &str,
+        query: &str,
+    ) -> Result<serde_json::Value> {
+        let mut request = client
+            .post(url)
+            .json(&serde_json::json!({ "query": query }));
+        for (name, value) in &self.remote_config.headers {
+            request = request.header(name, value);
+        }
+
+        let response: serde_json::Value = request.send().await?.error_for_status()?.json().await?;
+
+        if let Some(errors) = response.get("errors").and_then(serde_json::Value::as_array) {
+            let messages = errors
+                .iter()
+                .filter_map(|error| error.get("message").and_then(serde_json::Value::as_str))
+                .collect::<Vec<_>>();
+            if !messages.is_empty() {
+                return Err(ProjectError::SchemaLoad(messages.join("; ")));
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Path of the cached copy of a remote schema, keyed by a hash of its URL and headers
+    ///
+    /// Different headers can expose a different schema, such as with another role's
+    /// credentials, so each set of headers gets its own copy.
+    fn remote_cache_path(&self, url: &str) -> Option<PathBuf> {
+        let mut headers: Vec<(String, String)> = self
+            .remote_config
+            .headers
+            .iter()
+            .map(|(name, value)| (name.to_lowercase(), value.clone()))
+            .collect();
+        headers.sort();
+
+        let mut key_parts = vec![(url.to_string(), String::new())];
+        key_parts.extend(headers);
+        let key = SchemaCache::key(&key_parts);
+        self.remote_cache_dir
+            .as_ref()
+            .map(|dir| dir.join(format!("{key}.graphql")))
+    }
+}
+
+/// Whether an introspection request failed because the server rejected the query
+fn is_rejected_query(error: &ProjectError) -> bool {
+    match error {
+        // GraphQL errors in the response, such as for an unknown field
+        ProjectError::SchemaLoad(_) => true,
+        // Some servers answer an invalid query with a 400 status instead
+        ProjectError::Http(e) => e.status().is_some_and(|status| status.is_client_error()),
+        _ => false,
+    }
+}
+
+/// The SDL for a local schema file's content
+///
+/// Files ending in `.json` hold an introspection result, which is converted to SDL. If
+/// the JSON is invalid or isn't an introspection result, a warning is logged and the file
+/// contributes no definitions. Any other file is already SDL and returned unchanged.
+pub(crate) fn schema_file_sdl(path: &str, content: String) -> String {
+    if !path.to_lowercase().ends_with(".json") {
+        return content;
+    }
+
+    let sdl = serde_json::from_str(&content)
+        .map_err(|e| ProjectError::SchemaParse(e.to_string()))
+        .and_then(|introspection| introspection::introspection_to_sdl(&introspection));
+    match sdl {
+        Ok(sdl) => sdl,
+        Err(e) => {
+            tracing::warn!("Ignoring invalid introspection schema {}: {}", path, e);
+            String::new()
+        }
     }
 }
 
@@ -140,6 +361,36 @@ mod tests {
         assert!(schema.contains("type Query"));
     }
 
+    #[tokio::test]
+    async fn test_missing_schema_file_is_an_error() {
+        let temp_dir = tempdir().unwrap();
+        fs::write(
+            temp_dir.path().join("schema.graphql"),
+            "type Query { hello: String }",
+        )
+        .unwrap();
+
+        let config = SchemaConfig::Paths(vec![
+            "schema.graphql".to_string(),
+            "missing.graphql".to_string(),
+        ]);
+        let loader = SchemaLoader::new(config).with_base_path(temp_dir.path());
+        let error = loader.load_with_paths().await.unwrap_err();
+
+        let missing_path = temp_dir.path().join("missing.graphql");
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "Schema loading error: Schema file not found: {}",
+                missing_path.display()
+            )
+        );
+
+        let config = SchemaConfig::Path(temp_dir.path().join("*.gql").display().to_string());
+        let error = SchemaLoader::new(config).load().await.unwrap_err();
+        assert!(error.to_string().contains("No files matched pattern"));
+    }
+
     #[tokio::test]
     async fn test_load_multiple_files_with_glob() {
         let temp_dir = tempdir().unwrap();
@@ -162,4 +413,151 @@ mod tests {
         assert!(schema.contains("type Query"));
         assert!(schema.contains("type Mutation"));
     }
+
+    #[test]
+    fn test_remote_cache_path_depends_on_headers() {
+        let url = "https://example.com/graphql";
+        let loader = |headers: &[(&str, &str)]| {
+            SchemaLoader::new(SchemaConfig::Path(url.to_string()))
+                .with_remote_config(RemoteSchemaConfig {
+                    headers: headers
+                        .iter()
+                        .map(|(name, value)| ((*name).to_string(), (*value).to_string()))
+                        .collect(),
+                    timeout: 1,
+                })
+                .with_remote_cache_dir("/cache")
+        };
+
+        let admin = loader(&[("Authorization", "Bearer admin")]).remote_cache_path(url);
+        let viewer = loader(&[("Authorization", "Bearer viewer")]).remote_cache_path(url);
+        let anonymous = loader(&[]).remote_cache_path(url);
+
+        assert_ne!(admin, viewer);
+        assert_ne!(admin, anonymous);
+        // Header names are case-insensitive
+        assert_eq!(
+            admin,
+            loader(&[("authorization", "Bearer admin")]).remote_cache_path(url)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_remote_schema_falls_back_to_cached_copy() {
+        let temp_dir = tempdir().unwrap();
+        // Nothing listens on port 1, so introspection fails
+        let url = "http://127.0.0.1:1/graphql";
+
+        let loader = SchemaLoader::new(SchemaConfig::Path(url.to_string()))
+            .with_remote_config(RemoteSchemaConfig {
+                headers: HashMap::new(),
+                timeout: 1,
+            })
+            .with_remote_cache_dir(temp_dir.path());
+        assert!(loader.load().await.is_err());
+
+        write_cache_file(
+            &loader.remote_cache_path(url).unwrap(),
+            "type Query { cached: String }",
+        )
+        .unwrap();
+        let schema = loader.load().await.unwrap();
+
+        assert!(schema.contains("type Query { cached: String }"));
+    }
+
+    #[tokio::test]
+    async fn test_load_introspection_json_with_sdl() {
+        let temp_dir = tempdir().unwrap();
+        fs::write(
+            temp_dir.path().join("introspection.json"),
+            r#"{
+  "data": {
+    "__schema": {
+      "queryType": { "name": "Query" },
+      "mutationType": null,
+      "subscriptionType": null,
+      "directives": [],
+      "types": [
+        {
+          "kind": "OBJECT",
+          "name": "Query",
+          "interfaces": [],
+          "fields": [
+            {
+              "name": "user",
+              "args": [{ "name": "id", "type": { "kind": "NON_NULL", "name": null, "ofType": { "kind": "SCALAR", "name": "ID", "ofType": null } }, "defaultValue": null }],
+              "type": { "kind": "OBJECT", "name": "User", "ofType": null },
+              "isDeprecated": false,
+              "deprecationReason": null
+            }
+          ]
+        },
+        {
+          "kind": "OBJECT",
+          "name": "User",
+          "interfaces": [],
+          "fields": [
+            {
+              "name": "id",
+              "args": [],
+              "type": { "kind": "NON_NULL", "name": null, "ofType": { "kind": "SCALAR", "name": "ID", "ofType": null } },
+              "isDeprecated": false,
+              "deprecationReason": null
+            }
+          ]
+        },
+        { "kind": "SCALAR", "name": "ID" }
+      ]
+    }
+  }
+}"#,
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("extensions.graphql"),
+            "extend type User { name: String }",
+        )
+        .unwrap();
+
+        let config = SchemaConfig::Paths(vec![
+            "introspection.json".to_string(),
+            "extensions.graphql".to_string(),
+        ]);
+        let loader = SchemaLoader::new(config).with_base_path(temp_dir.path());
+        let schema_files = loader.load_with_paths().await.unwrap();
+        let index = crate::SchemaIndex::from_schema_files(schema_files);
+
+        let fields = index.get_fields("User").unwrap();
+        let field_names: Vec<_> = fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(field_names, vec!["id", "name"]);
+        let user = index
+            .get_fields("Query")
+            .unwrap()
+            .into_iter()
+            .find(|f| f.name == "user")
+            .unwrap();
+        assert_eq!(user.type_name, "User");
+        assert_eq!(user.arguments[0].type_name, "ID!");
+    }
+
+    #[tokio::test]
+    async fn test_truncated_introspection_json_is_ignored() {
+        let temp_dir = tempdir().unwrap();
+        fs::write(
+            temp_dir.path().join("introspection.json"),
+            r#"{ "data": { "__schema": { "types": ["#,
+        )
+        .unwrap();
+
+        let config = SchemaConfig::Path("introspection.json".to_string());
+        let loader = SchemaLoader::new(config).with_base_path(temp_dir.path());
+        let schema_files = loader.load_with_paths().await.unwrap();
+
+        let (_, content) = schema_files
+            .iter()
+            .find(|(path, _)| path.ends_with("introspection.json"))
+            .unwrap();
+        assert!(content.is_empty());
+    }
 }
@@ -0,0 +1,195 @@
+use crate::{Result, SchemaIndex};
+use std::path::{Path, PathBuf};
+
+/// Marker written as the first line of every cache entry
+///
+/// Entries are only written for schemas that built without errors, so a matching
+/// marker means the cached SDL can be loaded without re-running schema validation.
+const CACHE_MARKER: &str = "# graphql-lsp schema cache v2: valid";
+
+/// On-disk cache of built schemas, keyed by a hash of the schema file contents
+///
+/// Each entry stores the path and SDL of every schema file, so that a later run with
+/// identical schema files can load them without re-running schema validation. The
+/// schema is rebuilt from the cached files under their original paths, so definitions
+/// loaded from the cache still point at the schema files they came from.
+#[derive(Debug, Clone)]
+pub struct SchemaCache {
+    dir: PathBuf,
+}
+
+impl SchemaCache {
+    #[must_use]
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Default cache directory for the current user
+    ///
+    /// Uses `$XDG_CACHE_HOME/graphql-lsp/schema`, falling back to
+    /// `$HOME/.cache/graphql-lsp/schema`. Returns `None` if neither is set.
+    #[must_use]
+    pub fn default_dir() -> Option<PathBuf> {
+        std::env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+            .map(|dir| dir.join("graphql-lsp").join("schema"))
+    }
+
+    /// Cache directory this cache reads from and writes to
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Compute the cache key for a set of schema files
+    ///
+    /// Uses 64-bit FNV-1a over every path and content so the key is stable across runs
+    /// and compiler versions (unlike `DefaultHasher`).
+    #[must_use]
+    pub fn key(schema_files: &[(String, String)]) -> String {
+        const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+        let mut hash = FNV_OFFSET;
+        for (path, content) in schema_files {
+            // Separate fields with a NUL byte so ("ab", "c") and ("a", "bc") differ
+            for byte in path.bytes().chain([0]).chain(content.bytes()).chain([0]) {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(FNV_PRIME);
+            }
+        }
+
+        format!("{hash:016x}")
+    }
+
+    /// Path of the cache entry for the given key
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.cache"))
+    }
+
+    /// Load a previously cached schema for these schema files
+    ///
+    /// Returns `None` on a cache miss, if the entry is unreadable or not marked valid, or
+    /// if its files differ from `schema_files` (a hash collision).
+    #[must_use]
+    pub fn load(&self, schema_files: &[(String, String)]) -> Option<SchemaIndex> {
+        let path = self.entry_path(&Self::key(schema_files));
+        let contents = std::fs::read_to_string(&path).ok()?;
+
+        let entry = contents.strip_prefix(CACHE_MARKER)?.strip_prefix('\n')?;
+        let Ok(cached_files) = serde_json::from_str::<Vec<(String, String)>>(entry) else {
+            tracing::warn!("Ignoring invalid schema cache entry: {}", path.display());
+            return None;
+        };
+        if cached_files != schema_files {
+            return None;
+        }
+
+        let index = SchemaIndex::from_cached_files(cached_files);
+        if index.is_none() {
+            tracing::warn!("Ignoring invalid schema cache entry: {}", path.display());
+        }
+        index
+    }
+
+    /// Store the schema files of a built schema
+    ///
+    /// Only call this for schemas that built without errors; the entry is marked valid.
+    pub fn store(&self, schema_files: &[(String, String)]) -> Result<()> {
+        let path = self.entry_path(&Self::key(schema_files));
+        let entry = serde_json::to_string(schema_files).map_err(std::io::Error::from)?;
+        write_cache_file(&path, &format!("{CACHE_MARKER}\n{entry}"))
+    }
+}
+
+/// Write a cache file, via a temporary file so readers never see a partial file
+pub(crate) fn write_cache_file(path: &Path, contents: &str) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    std::fs::write(&tmp_path, contents)?;
+    std::fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn schema_files() -> Vec<(String, String)> {
+        vec![
+            (
+                "schema.graphql".to_string(),
+                "type Query { user: User }\ntype User { id: ID! }".to_string(),
+            ),
+            (
+                "extensions.graphql".to_string(),
+                "extend type User { name: String }".to_string(),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_key_changes_with_content() {
+        let files = schema_files();
+        let mut changed = files.clone();
+        changed.push((
+            "more.graphql".to_string(),
+            "extend type User { email: String }".to_string(),
+        ));
+
+        assert_eq!(SchemaCache::key(&files), SchemaCache::key(&files));
+        assert_ne!(SchemaCache::key(&files), SchemaCache::key(&changed));
+    }
+
+    #[test]
+    fn test_cache_miss_when_empty() {
+        let temp_dir = tempdir().unwrap();
+        let cache = SchemaCache::new(temp_dir.path());
+
+        assert!(cache.load(&schema_files()).is_none());
+    }
+
+    #[test]
+    fn test_cache_hit_skips_rebuild() {
+        let temp_dir = tempdir().unwrap();
+        let cache = SchemaCache::new(temp_dir.path());
+        let files = schema_files();
+
+        cache.store(&files).unwrap();
+
+        let cached = cache.load(&files).expect("expected a cache hit");
+
+        // Definitions still point at the original schema files, not the cache entry
+        let sources: Vec<_> = cached
+            .schema()
+            .sources
+            .values()
+            .map(|source| source.path().display().to_string())
+            .collect();
+        assert!(sources.iter().any(|path| path == "schema.graphql"));
+        assert!(sources.iter().any(|path| path == "extensions.graphql"));
+
+        let fields = cached.get_fields("User").unwrap();
+        assert!(fields.iter().any(|f| f.name == "id"));
+        assert!(fields.iter().any(|f| f.name == "name"));
+    }
+
+    #[test]
+    fn test_cache_ignores_entry_without_marker() {
+        let temp_dir = tempdir().unwrap();
+        let cache = SchemaCache::new(temp_dir.path());
+        let files = schema_files();
+
+        let path = cache.entry_path(&SchemaCache::key(&files));
+        std::fs::write(path, "type Query { user: String }").unwrap();
+
+        assert!(cache.load(&files).is_none());
+    }
+}
@@ -0,0 +1,753 @@
+use crate::SchemaIndex;
+use apollo_compiler::ast::Type;
+use apollo_compiler::schema::{ExtendedType, FieldDefinition, InputValueDefinition};
+use apollo_compiler::Schema;
+use std::collections::BTreeSet;
+
+/// How a schema change affects existing clients, in the categories graphql-inspector uses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ChangeCriticality {
+    /// Existing operations can stop validating, or get responses they don't expect
+    Breaking,
+    /// Existing operations keep working, but clients may see values they don't handle
+    Dangerous,
+    /// Existing operations are unaffected
+    NonBreaking,
+}
+
+impl ChangeCriticality {
+    /// Name of the category in machine-readable output, like `BREAKING`
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Breaking => "BREAKING",
+            Self::Dangerous => "DANGEROUS",
+            Self::NonBreaking => "NON_BREAKING",
+        }
+    }
+}
+
+/// What changed between two versions of a schema
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SchemaChangeKind {
+    TypeRemoved,
+    TypeAdded,
+    TypeKindChanged,
+    FieldRemoved,
+    FieldAdded,
+    FieldTypeChanged,
+    FieldDeprecationAdded,
+    FieldArgumentRemoved,
+    FieldArgumentAdded,
+    FieldArgumentTypeChanged,
+    InputFieldRemoved,
+    InputFieldAdded,
+    InputFieldTypeChanged,
+    EnumValueRemoved,
+    EnumValueAdded,
+    UnionMemberRemoved,
+    UnionMemberAdded,
+    ObjectTypeInterfaceRemoved,
+    ObjectTypeInterfaceAdded,
+    DirectiveRemoved,
+    DirectiveAdded,
+}
+
+impl SchemaChangeKind {
+    /// Name of the change in machine-readable output, as graphql-inspector names it
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::TypeRemoved => "TYPE_REMOVED",
+            Self::TypeAdded => "TYPE_ADDED",
+            Self::TypeKindChanged => "TYPE_KIND_CHANGED",
+            Self::FieldRemoved => "FIELD_REMOVED",
+            Self::FieldAdded => "FIELD_ADDED",
+            Self::FieldTypeChanged => "FIELD_TYPE_CHANGED",
+            Self::FieldDeprecationAdded => "FIELD_DEPRECATION_ADDED",
+            Self::FieldArgumentRemoved => "FIELD_ARGUMENT_REMOVED",
+            Self::FieldArgumentAdded => "FIELD_ARGUMENT_ADDED",
+            Self::FieldArgumentTypeChanged => "FIELD_ARGUMENT_TYPE_CHANGED",
+            Self::InputFieldRemoved => "INPUT_FIELD_REMOVED",
+            Self::InputFieldAdded => "INPUT_FIELD_ADDED",
+            Self::InputFieldTypeChanged => "INPUT_FIELD_TYPE_CHANGED",
+            Self::EnumValueRemoved => "VALUE_REMOVED_FROM_ENUM",
+            Self::EnumValueAdded => "VALUE_ADDED_TO_ENUM",
+            Self::UnionMemberRemoved => "UNION_MEMBER_REMOVED",
+            Self::UnionMemberAdded => "UNION_MEMBER_ADDED",
+            Self::ObjectTypeInterfaceRemoved => "OBJECT_TYPE_INTERFACE_REMOVED",
+            Self::ObjectTypeInterfaceAdded => "OBJECT_TYPE_INTERFACE_ADDED",
+            Self::DirectiveRemoved => "DIRECTIVE_REMOVED",
+            Self::DirectiveAdded => "DIRECTIVE_ADDED",
+        }
+    }
+}
+
+/// A difference between two versions of a schema
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaChange {
+    pub kind: SchemaChangeKind,
+    pub criticality: ChangeCriticality,
+    /// Schema coordinate of what changed, like `User.name`, `Query.user(id:)`, or `@cached`
+    pub path: String,
+    pub message: String,
+}
+
+/// Compare two versions of a schema
+///
+/// Types, fields, arguments, input fields, enum values, union members, implemented
+/// interfaces, and directive definitions are compared. A field's type can be made non-null,
+/// and an argument's or input field's type nullable, without breaking clients; any other
+/// type change is breaking.
+///
+/// Returns breaking changes first, then dangerous, then non-breaking ones, each sorted by
+/// path.
+#[must_use]
+pub fn diff_schemas(old: &SchemaIndex, new: &SchemaIndex) -> Vec<SchemaChange> {
+    let mut differ = SchemaDiffer::default();
+    differ.diff_types(old.schema(), new.schema());
+    differ.diff_directives(old.schema(), new.schema());
+
+    let mut changes = differ.changes;
+    changes.sort_by(|a, b| (a.criticality, &a.path).cmp(&(b.criticality, &b.path)));
+    changes
+}
+
+/// Collects the changes found while comparing two schemas
+#[derive(Default)]
+struct SchemaDiffer {
+    changes: Vec<SchemaChange>,
+}
+
+impl SchemaDiffer {
+    fn push(
+        &mut self,
+        kind: SchemaChangeKind,
+        criticality: ChangeCriticality,
+        path: String,
+        message: String,
+    ) {
+        self.changes.push(SchemaChange {
+            kind,
+            criticality,
+            path,
+            message,
+        });
+    }
+
+    fn diff_types(&mut self, old: &Schema, new: &Schema) {
+        for (name, old_type) in &old.types {
+            let Some(new_type) = new.types.get(name) else {
+                self.push(
+                    SchemaChangeKind::TypeRemoved,
+                    ChangeCriticality::Breaking,
+                    name.to_string(),
+                    format!("Type '{name}' was removed"),
+                );
+                continue;
+            };
+
+            match (old_type, new_type) {
+                (ExtendedType::Object(old_object), ExtendedType::Object(new_object)) => {
+                    self.diff_fields(name, old_type, new_type);
+                    self.diff_members(
+                        name,
+                        old_object
+                            .implements_interfaces
+                            .iter()
+                            .map(ToString::to_string),
+                        new_object
+                            .implements_interfaces
+                            .iter()
+                            .map(ToString::to_string),
+                        SchemaChangeKind::ObjectTypeInterfaceRemoved,
+                        SchemaChangeKind::ObjectTypeInterfaceAdded,
+                    );
+                }
+                (
+                    ExtendedType::Interface(old_interface),
+                    ExtendedType::Interface(new_interface),
+                ) => {
+                    self.diff_fields(name, old_type, new_type);
+                    self.diff_members(
+                        name,
+                        old_interface
+                            .implements_interfaces
+                            .iter()
+                            .map(ToString::to_string),
+                        new_interface
+                            .implements_interfaces
+                            .iter()
+                            .map(ToString::to_string),
+                        SchemaChangeKind::ObjectTypeInterfaceRemoved,
+                        SchemaChangeKind::ObjectTypeInterfaceAdded,
+                    );
+                }
+                (ExtendedType::Union(old_union), ExtendedType::Union(new_union)) => {
+                    self.diff_members(
+                        name,
+                        old_union.members.iter().map(ToString::to_string),
+                        new_union.members.iter().map(ToString::to_string),
+                        SchemaChangeKind::UnionMemberRemoved,
+                        SchemaChangeKind::UnionMemberAdded,
+                    );
+                }
+                (ExtendedType::Enum(old_enum), ExtendedType::Enum(new_enum)) => {
+                    let old_values = old_enum.values.keys().map(ToString::to_string);
+                    let new_values = new_enum.values.keys().map(ToString::to_string);
+                    self.diff_enum_values(name, old_values, new_values);
+                }
+                (ExtendedType::InputObject(_), ExtendedType::InputObject(_)) => {
+                    self.diff_input_fields(name, old_type, new_type);
+                }
+                (ExtendedType::Scalar(_), ExtendedType::Scalar(_)) => {}
+                _ => self.push(
+                    SchemaChangeKind::TypeKindChanged,
+                    ChangeCriticality::Breaking,
+                    name.to_string(),
+                    format!(
+                        "Type '{name}' changed from {} to {}",
+                        kind_name(old_type),
+                        kind_name(new_type)
+                    ),
+                ),
+            }
+        }
+
+        for (name, new_type) in &new.types {
+            if !old.types.contains_key(name) {
+                self.push(
+                    SchemaChangeKind::TypeAdded,
+                    ChangeCriticality::NonBreaking,
+                    name.to_string(),
+                    format!("{} '{name}' was added", capitalized_kind_name(new_type)),
+                );
+            }
+        }
+    }
+
+    fn diff_enum_values(
+        &mut self,
+        enum_name: &str,
+        old: impl Iterator<Item = String>,
+        new: impl Iterator<Item = String>,
+    ) {
+        let old: BTreeSet<String> = old.collect();
+        let new: BTreeSet<String> = new.collect();
+
+        for value in old.difference(&new) {
+            self.push(
+                SchemaChangeKind::EnumValueRemoved,
+                ChangeCriticality::Breaking,
+                format!("{enum_name}.{value}"),
+                format!("Enum value '{enum_name}.{value}' was removed"),
+            );
+        }
+        for value in new.difference(&old) {
+            self.push(
+                SchemaChangeKind::EnumValueAdded,
+                ChangeCriticality::Dangerous,
+                format!("{enum_name}.{value}"),
+                format!("Enum value '{enum_name}.{value}' was added"),
+            );
+        }
+    }
+
+    /// Compare the fields of two versions of an input object type
+    fn diff_input_fields(&mut self, type_name: &str, old: &ExtendedType, new: &ExtendedType) {
+        let (ExtendedType::InputObject(old), ExtendedType::InputObject(new)) = (old, new) else {
+            return;
+        };
+
+        for (field_name, old_field) in &old.fields {
+            let path = format!("{type_name}.{field_name}");
+            match new.fields.get(field_name) {
+                Some(new_field) => self.diff_input_value(
+                    path,
+                    "Input field",
+                    old_field,
+                    new_field,
+                    SchemaChangeKind::InputFieldTypeChanged,
+                ),
+                None => self.push(
+                    SchemaChangeKind::InputFieldRemoved,
+                    ChangeCriticality::Breaking,
+                    path.clone(),
+                    format!("Input field '{path}' was removed"),
+                ),
+            }
+        }
+
+        for (field_name, new_field) in &new.fields {
+            if !old.fields.contains_key(field_name) {
+                self.added_input_value(
+                    format!("{type_name}.{field_name}"),
+                    "input field",
+                    new_field,
+                    SchemaChangeKind::InputFieldAdded,
+                );
+            }
+        }
+    }
+
+    /// Compare the fields of two versions of an object or interface type
+    fn diff_fields(&mut self, type_name: &str, old: &ExtendedType, new: &ExtendedType) {
+        let (old_fields, new_fields) = match (old, new) {
+            (ExtendedType::Object(old), ExtendedType::Object(new)) => (&old.fields, &new.fields),
+            (ExtendedType::Interface(old), ExtendedType::Interface(new)) => {
+                (&old.fields, &new.fields)
+            }
+            _ => return,
+        };
+
+        for (field_name, old_field) in old_fields {
+            let path = format!("{type_name}.{field_name}");
+            match new_fields.get(field_name) {
+                Some(new_field) => self.diff_field(&path, old_field, new_field),
+                None => self.push(
+                    SchemaChangeKind::FieldRemoved,
+                    ChangeCriticality::Breaking,
+                    path.clone(),
+                    format!("Field '{path}' was removed"),
+                ),
+            }
+        }
+
+        for field_name in new_fields.keys() {
+            if !old_fields.contains_key(field_name) {
+                let path = format!("{type_name}.{field_name}");
+                self.push(
+                    SchemaChangeKind::FieldAdded,
+                    ChangeCriticality::NonBreaking,
+                    path.clone(),
+                    format!("Field '{path}' was added"),
+                );
+            }
+        }
+    }
+
+    fn diff_field(&mut self, path: &str, old: &FieldDefinition, new: &FieldDefinition) {
+        if old.ty != new.ty {
+            let criticality = if is_safe_output_type_change(&old.ty, &new.ty) {
+                ChangeCriticality::NonBreaking
+            } else {
+                ChangeCriticality::Breaking
+            };
+            self.push(
+                SchemaChangeKind::FieldTypeChanged,
+                criticality,
+                path.to_string(),
+                format!(
+                    "Field '{path}' changed type from '{}' to '{}'",
+                    old.ty, new.ty
+                ),
+            );
+        }
+
+        if old.directives.get("deprecated").is_none() && new.directives.get("deprecated").is_some()
+        {
+            self.push(
+                SchemaChangeKind::FieldDeprecationAdded,
+                ChangeCriticality::NonBreaking,
+                path.to_string(),
+                format!("Field '{path}' is deprecated"),
+            );
+        }
+
+        for old_argument in &old.arguments {
+            let argument_path = format!("{path}({}:)", old_argument.name);
+            match new
+                .arguments
+                .iter()
+                .find(|argument| argument.name == old_argument.name)
+            {
+                Some(new_argument) => self.diff_input_value(
+                    argument_path,
+                    "Argument",
+                    old_argument,
+                    new_argument,
+                    SchemaChangeKind::FieldArgumentTypeChanged,
+                ),
+                None => self.push(
+                    SchemaChangeKind::FieldArgumentRemoved,
+                    ChangeCriticality::Breaking,
+                    argument_path.clone(),
+                    format!("Argument '{argument_path}' was removed"),
+                ),
+            }
+        }
+
+        for new_argument in &new.arguments {
+            if !old
+                .arguments
+                .iter()
+                .any(|argument| argument.name == new_argument.name)
+            {
+                self.added_input_value(
+                    format!("{path}({}:)", new_argument.name),
+                    "argument",
+                    new_argument,
+                    SchemaChangeKind::FieldArgumentAdded,
+                );
+            }
+        }
+    }
+
+    /// Compare the types of two versions of an argument or input field
+    ///
+    /// `subject` names what's compared in the message, like `Argument`.
+    fn diff_input_value(
+        &mut self,
+        path: String,
+        subject: &str,
+        old: &InputValueDefinition,
+        new: &InputValueDefinition,
+        kind: SchemaChangeKind,
+    ) {
+        if old.ty == new.ty {
+            return;
+        }
+
+        // Inputs accept more values as their type loosens, the reverse of outputs
+        let criticality = if is_safe_output_type_change(&new.ty, &old.ty) {
+            ChangeCriticality::NonBreaking
+        } else {
+            ChangeCriticality::Breaking
+        };
+        let message = format!(
+            "{subject} '{path}' changed type from '{}' to '{}'",
+            old.ty, new.ty
+        );
+        self.push(kind, criticality, path, message);
+    }
+
+    /// Report a new argument or input field, which breaks operations that don't pass it when
+    /// it's required
+    fn added_input_value(
+        &mut self,
+        path: String,
+        subject: &str,
+        new: &InputValueDefinition,
+        kind: SchemaChangeKind,
+    ) {
+        let (criticality, message) = if new.ty.is_non_null() && new.default_value.is_none() {
+            (
+                ChangeCriticality::Breaking,
+                format!("Required {subject} '{path}' was added"),
+            )
+        } else {
+            (
+                ChangeCriticality::Dangerous,
+                format!("Optional {subject} '{path}' was added"),
+            )
+        };
+        self.push(kind, criticality, path, message);
+    }
+
+    /// Compare the union members or implemented interfaces of two versions of a type
+    fn diff_members(
+        &mut self,
+        type_name: &str,
+        old: impl Iterator<Item = String>,
+        new: impl Iterator<Item = String>,
+        removed: SchemaChangeKind,
+        added: SchemaChangeKind,
+    ) {
+        let old: BTreeSet<String> = old.collect();
+        let new: BTreeSet<String> = new.collect();
+        let is_union = removed == SchemaChangeKind::UnionMemberRemoved;
+
+        for member in old.difference(&new) {
+            let message = if is_union {
+                format!("Member '{member}' was removed from union '{type_name}'")
+            } else {
+                format!("Type '{type_name}' no longer implements interface '{member}'")
+            };
+            self.push(
+                removed,
+                ChangeCriticality::Breaking,
+                type_name.to_string(),
+                message,
+            );
+        }
+
+        for member in new.difference(&old) {
+            let message = if is_union {
+                format!("Member '{member}' was added to union '{type_name}'")
+            } else {
+                format!("Type '{type_name}' now implements interface '{member}'")
+            };
+            self.push(
+                added,
+                ChangeCriticality::Dangerous,
+                type_name.to_string(),
+                message,
+            );
+        }
+    }
+
+    fn diff_directives(&mut self, old: &Schema, new: &Schema) {
+        for name in old.directive_definitions.keys() {
+            if !new.directive_definitions.contains_key(name) {
+                self.push(
+                    SchemaChangeKind::DirectiveRemoved,
+                    ChangeCriticality::Breaking,
+                    format!("@{name}"),
+                    format!("Directive '@{name}' was removed"),
+                );
+            }
+        }
+        for name in new.directive_definitions.keys() {
+            if !old.directive_definitions.contains_key(name) {
+                self.push(
+                    SchemaChangeKind::DirectiveAdded,
+                    ChangeCriticality::NonBreaking,
+                    format!("@{name}"),
+                    format!("Directive '@{name}' was added"),
+                );
+            }
+        }
+    }
+}
+
+/// Whether an output type can change from `old` to `new` without breaking clients
+///
+/// Only making a type, or a list's items, non-null is safe, since every value of the new
+/// type is a value clients already handle.
+fn is_safe_output_type_change(old: &Type, new: &Type) -> bool {
+    match (old, new) {
+        (Type::Named(old), Type::Named(new) | Type::NonNullNamed(new))
+        | (Type::NonNullNamed(old), Type::NonNullNamed(new)) => old == new,
+        (Type::List(old), Type::List(new) | Type::NonNullList(new))
+        | (Type::NonNullList(old), Type::NonNullList(new)) => is_safe_output_type_change(old, new),
+        _ => false,
+    }
+}
+
+fn kind_name(ty: &ExtendedType) -> &'static str {
+    match ty {
+        ExtendedType::Object(_) => "object",
+        ExtendedType::Interface(_) => "interface",
+        ExtendedType::Union(_) => "union",
+        ExtendedType::Enum(_) => "enum",
+        ExtendedType::InputObject(_) => "input object",
+        ExtendedType::Scalar(_) => "scalar",
+    }
+}
+
+fn capitalized_kind_name(ty: &ExtendedType) -> &'static str {
+    match ty {
+        ExtendedType::Object(_) => "Object type",
+        ExtendedType::Interface(_) => "Interface",
+        ExtendedType::Union(_) => "Union",
+        ExtendedType::Enum(_) => "Enum",
+        ExtendedType::InputObject(_) => "Input object",
+        ExtendedType::Scalar(_) => "Scalar",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD_SCHEMA: &str = r"
+type Query {
+  user(id: ID!): User
+  users(first: Int): [User!]!
+}
+
+type User implements Node {
+  id: ID!
+  name: String
+  email: String
+  role: Role!
+}
+
+interface Node {
+  id: ID!
+}
+
+enum Role {
+  ADMIN
+  MEMBER
+}
+
+input UserFilter {
+  role: Role!
+}
+";
+
+    fn diff(new: &str) -> Vec<(SchemaChangeKind, ChangeCriticality, String)> {
+        diff_schemas(
+            &SchemaIndex::from_schema(OLD_SCHEMA),
+            &SchemaIndex::from_schema(new),
+        )
+        .into_iter()
+        .map(|change| (change.kind, change.criticality, change.path))
+        .collect()
+    }
+
+    #[test]
+    fn test_identical_schemas_have_no_changes() {
+        assert!(diff(OLD_SCHEMA).is_empty());
+    }
+
+    #[test]
+    fn test_breaking_changes() {
+        let changes = diff(
+            r"
+type Query {
+  user(id: ID!): User
+  users(first: Int, orderBy: String!): [User!]!
+}
+
+type User {
+  id: ID!
+  name: Int
+  role: Role!
+}
+
+interface Node {
+  id: ID!
+}
+
+enum Role {
+  ADMIN
+}
+
+input UserFilter {
+  role: Role!
+}
+",
+        );
+
+        assert_eq!(
+            changes,
+            vec![
+                (
+                    SchemaChangeKind::FieldArgumentAdded,
+                    ChangeCriticality::Breaking,
+                    "Query.users(orderBy:)".to_string()
+                ),
+                (
+                    SchemaChangeKind::EnumValueRemoved,
+                    ChangeCriticality::Breaking,
+                    "Role.MEMBER".to_string()
+                ),
+                (
+                    SchemaChangeKind::ObjectTypeInterfaceRemoved,
+                    ChangeCriticality::Breaking,
+                    "User".to_string()
+                ),
+                (
+                    SchemaChangeKind::FieldRemoved,
+                    ChangeCriticality::Breaking,
+                    "User.email".to_string()
+                ),
+                (
+                    SchemaChangeKind::FieldTypeChanged,
+                    ChangeCriticality::Breaking,
+                    "User.name".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_safe_and_dangerous_changes() {
+        let changes = diff(
+            r#"
+type Query {
+  user(id: ID): User
+  users(first: Int, after: String): [User!]!
+}
+
+type User implements Node {
+  id: ID!
+  name: String!
+  email: String @deprecated(reason: "Use contact")
+  role: Role!
+  contact: String
+}
+
+interface Node {
+  id: ID!
+}
+
+enum Role {
+  ADMIN
+  MEMBER
+  GUEST
+}
+
+input UserFilter {
+  role: Role!
+  name: String
+}
+
+scalar DateTime
+"#,
+        );
+
+        assert_eq!(
+            changes,
+            vec![
+                (
+                    SchemaChangeKind::FieldArgumentAdded,
+                    ChangeCriticality::Dangerous,
+                    "Query.users(after:)".to_string()
+                ),
+                (
+                    SchemaChangeKind::EnumValueAdded,
+                    ChangeCriticality::Dangerous,
+                    "Role.GUEST".to_string()
+                ),
+                (
+                    SchemaChangeKind::InputFieldAdded,
+                    ChangeCriticality::Dangerous,
+                    "UserFilter.name".to_string()
+                ),
+                (
+                    SchemaChangeKind::TypeAdded,
+                    ChangeCriticality::NonBreaking,
+                    "DateTime".to_string()
+                ),
+                (
+                    SchemaChangeKind::FieldArgumentTypeChanged,
+                    ChangeCriticality::NonBreaking,
+                    "Query.user(id:)".to_string()
+                ),
+                (
+                    SchemaChangeKind::FieldAdded,
+                    ChangeCriticality::NonBreaking,
+                    "User.contact".to_string()
+                ),
+                (
+                    SchemaChangeKind::FieldDeprecationAdded,
+                    ChangeCriticality::NonBreaking,
+                    "User.email".to_string()
+                ),
+                (
+                    SchemaChangeKind::FieldTypeChanged,
+                    ChangeCriticality::NonBreaking,
+                    "User.name".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_type_kind_change() {
+        let changes = diff_schemas(
+            &SchemaIndex::from_schema("type Query { node: Node }\ninterface Node { id: ID! }\n"),
+            &SchemaIndex::from_schema("type Query { node: Node }\nunion Node = Query\n"),
+        );
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, SchemaChangeKind::TypeKindChanged);
+        assert_eq!(
+            changes[0].message,
+            "Type 'Node' changed from interface to union"
+        );
+    }
+}
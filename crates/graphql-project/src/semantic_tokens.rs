@@ -0,0 +1,242 @@
+use crate::{LineIndex, Position, PositionEncoding};
+use apollo_parser::{Parser, SyntaxKind, SyntaxTree};
+
+/// Kind of element a semantic token highlights
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SemanticTokenKind {
+    /// Named types, in type conditions, variable types, and type definitions
+    Type,
+    /// Fields and aliases in selections, and field definitions
+    Field,
+    /// Arguments and argument definitions
+    Argument,
+    /// Variables, including the `$`
+    Variable,
+    /// Fragment names in definitions and spreads
+    Fragment,
+    /// Directives, including the `@`
+    Directive,
+    /// Enum values and enum value definitions
+    EnumValue,
+}
+
+/// Highlighted range of a GraphQL document
+///
+/// Tokens never span lines, so a start position and a length are enough.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SemanticToken {
+    pub start: Position,
+    /// Length in the units of the current `PositionEncoding`
+    pub length: usize,
+    pub kind: SemanticTokenKind,
+}
+
+impl SemanticToken {
+    /// Shift this token from a block-relative to a file position
+    ///
+    /// Used for GraphQL extracted from TypeScript/JavaScript, where the block starts at
+    /// `start_line`/`start_column` in the original file.
+    #[must_use]
+    pub const fn offset_by(mut self, start_line: usize, start_column: usize) -> Self {
+        if self.start.line == 0 {
+            self.start.character += start_column;
+        }
+        self.start.line += start_line;
+        self
+    }
+}
+
+/// Semantic token provider
+///
+/// Classifies the names in a document by what they refer to, so editors can highlight
+/// GraphQL without a TextMate grammar, including inside template literals.
+pub struct SemanticTokensProvider {
+    /// Unit `Position::character` columns are counted in
+    position_encoding: PositionEncoding,
+}
+
+impl SemanticTokensProvider {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            position_encoding: PositionEncoding::Utf16,
+        }
+    }
+
+    /// Count `Position::character` columns in `encoding` instead of UTF-16 code units
+    #[must_use]
+    pub const fn with_position_encoding(mut self, encoding: PositionEncoding) -> Self {
+        self.position_encoding = encoding;
+        self
+    }
+
+    /// Get the semantic tokens for a GraphQL document, in document order
+    #[must_use]
+    pub fn semantic_tokens(&self, source: &str) -> Vec<SemanticToken> {
+        let tree = Parser::new(source).parse();
+        self.semantic_tokens_with_ast(source, &tree)
+    }
+
+    /// Get the semantic tokens for a GraphQL document using an already parsed AST
+    #[must_use]
+    pub fn semantic_tokens_with_ast(&self, source: &str, tree: &SyntaxTree) -> Vec<SemanticToken> {
+        let line_index = LineIndex::with_encoding(source, self.position_encoding);
+
+        tree.document()
+            .syntax()
+            .descendants()
+            .filter(|node| node.kind() == SyntaxKind::NAME)
+            .filter_map(|name| {
+                let kind = token_kind(name.parent()?.kind())?;
+                let range = name.text_range();
+                let mut start: usize = range.start().into();
+                let end: usize = range.end().into();
+                let text = source.get(start..end)?;
+                // Names can carry the whitespace and commas that follow them
+                let end = start
+                    + text
+                        .trim_end_matches(|c: char| c.is_whitespace() || c == ',')
+                        .len();
+
+                // Variables and directives are highlighted with their sigil
+                if matches!(
+                    kind,
+                    SemanticTokenKind::Variable | SemanticTokenKind::Directive
+                ) && start > 0
+                    && matches!(source.as_bytes()[start - 1], b'$' | b'@')
+                {
+                    start -= 1;
+                }
+
+                // GraphQL names are ASCII, so bytes are units in every encoding
+                Some(SemanticToken {
+                    start: line_index.offset_to_position(start),
+                    length: end - start,
+                    kind,
+                })
+            })
+            .collect()
+    }
+}
+
+impl Default for SemanticTokensProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Classify a name by the node it belongs to
+const fn token_kind(parent: SyntaxKind) -> Option<SemanticTokenKind> {
+    let kind = match parent {
+        SyntaxKind::NAMED_TYPE
+        | SyntaxKind::OBJECT_TYPE_DEFINITION
+        | SyntaxKind::OBJECT_TYPE_EXTENSION
+        | SyntaxKind::INTERFACE_TYPE_DEFINITION
+        | SyntaxKind::INTERFACE_TYPE_EXTENSION
+        | SyntaxKind::UNION_TYPE_DEFINITION
+        | SyntaxKind::UNION_TYPE_EXTENSION
+        | SyntaxKind::ENUM_TYPE_DEFINITION
+        | SyntaxKind::ENUM_TYPE_EXTENSION
+        | SyntaxKind::INPUT_OBJECT_TYPE_DEFINITION
+        | SyntaxKind::INPUT_OBJECT_TYPE_EXTENSION
+        | SyntaxKind::SCALAR_TYPE_DEFINITION
+        | SyntaxKind::SCALAR_TYPE_EXTENSION => SemanticTokenKind::Type,
+        SyntaxKind::FIELD | SyntaxKind::ALIAS | SyntaxKind::FIELD_DEFINITION => {
+            SemanticTokenKind::Field
+        }
+        SyntaxKind::ARGUMENT | SyntaxKind::INPUT_VALUE_DEFINITION => SemanticTokenKind::Argument,
+        SyntaxKind::VARIABLE => SemanticTokenKind::Variable,
+        SyntaxKind::FRAGMENT_NAME => SemanticTokenKind::Fragment,
+        SyntaxKind::DIRECTIVE | SyntaxKind::DIRECTIVE_DEFINITION => SemanticTokenKind::Directive,
+        SyntaxKind::ENUM_VALUE | SyntaxKind::ENUM_VALUE_DEFINITION => SemanticTokenKind::EnumValue,
+        _ => return None,
+    };
+    Some(kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(
+        line: usize,
+        character: usize,
+        length: usize,
+        kind: SemanticTokenKind,
+    ) -> SemanticToken {
+        SemanticToken {
+            start: Position { line, character },
+            length,
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_classifies_names_in_operations() {
+        let source = r"query GetUser($id: ID!) {
+  me: user(id: $id) @include(if: true) {
+    ...UserFields
+    role(is: ADMIN)
+  }
+}
+";
+
+        let tokens = SemanticTokensProvider::new().semantic_tokens(source);
+
+        assert_eq!(
+            tokens,
+            vec![
+                token(0, 14, 3, SemanticTokenKind::Variable),
+                token(0, 19, 2, SemanticTokenKind::Type),
+                token(1, 2, 2, SemanticTokenKind::Field),
+                token(1, 6, 4, SemanticTokenKind::Field),
+                token(1, 11, 2, SemanticTokenKind::Argument),
+                token(1, 15, 3, SemanticTokenKind::Variable),
+                token(1, 20, 8, SemanticTokenKind::Directive),
+                token(1, 29, 2, SemanticTokenKind::Argument),
+                token(2, 7, 10, SemanticTokenKind::Fragment),
+                token(3, 4, 4, SemanticTokenKind::Field),
+                token(3, 9, 2, SemanticTokenKind::Argument),
+                token(3, 13, 5, SemanticTokenKind::EnumValue),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_classifies_names_in_schema() {
+        let source = "type User @key(fields: \"id\") {\n  posts(first: Int): [Post!]!\n}\n";
+
+        let kinds: Vec<_> = SemanticTokensProvider::new()
+            .semantic_tokens(source)
+            .into_iter()
+            .map(|token| token.kind)
+            .collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                SemanticTokenKind::Type,
+                SemanticTokenKind::Directive,
+                SemanticTokenKind::Argument,
+                SemanticTokenKind::Field,
+                SemanticTokenKind::Argument,
+                SemanticTokenKind::Type,
+                SemanticTokenKind::Type,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_offset_by_maps_block_positions() {
+        let tokens = SemanticTokensProvider::new().semantic_tokens("{ a\n  b }");
+
+        assert_eq!(
+            tokens[0].offset_by(4, 20),
+            token(4, 22, 1, SemanticTokenKind::Field)
+        );
+        assert_eq!(
+            tokens[1].offset_by(4, 20),
+            token(5, 2, 1, SemanticTokenKind::Field)
+        );
+    }
+}
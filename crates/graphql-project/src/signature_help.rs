@@ -0,0 +1,505 @@
+use crate::{LineIndex, OperationType, Position, PositionEncoding, SchemaIndex};
+use apollo_compiler::schema::ExtendedType;
+use apollo_parser::{
+    cst::{self, CstNode},
+    Parser, SyntaxKind, SyntaxNode, SyntaxTree,
+};
+
+/// One parameter of a signature, like `id: ID!`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureParameter {
+    /// Parameter as written in the signature label
+    pub label: String,
+    pub documentation: Option<String>,
+}
+
+/// Signature of the field (or input object) whose arguments the cursor is in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureHelp {
+    /// Full signature, like `user(id: ID!, includeDeleted: Boolean = false)`
+    pub label: String,
+    pub documentation: Option<String>,
+    pub parameters: Vec<SignatureParameter>,
+    /// Index into `parameters` of the argument being written
+    pub active_parameter: Option<usize>,
+}
+
+/// Signature help provider
+///
+/// Shows the argument signature of the field whose argument list contains the cursor. When
+/// the cursor is inside an input object value, the fields of that input object are shown
+/// instead.
+pub struct SignatureHelpProvider {
+    /// Unit `Position::character` columns are counted in
+    position_encoding: PositionEncoding,
+}
+
+impl SignatureHelpProvider {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            position_encoding: PositionEncoding::Utf16,
+        }
+    }
+
+    /// Count `Position::character` columns in `encoding` instead of UTF-16 code units
+    #[must_use]
+    pub const fn with_position_encoding(mut self, encoding: PositionEncoding) -> Self {
+        self.position_encoding = encoding;
+        self
+    }
+
+    /// Get the signature help at a position in a GraphQL document
+    #[must_use]
+    pub fn signature_help(
+        &self,
+        source: &str,
+        position: Position,
+        schema_index: &SchemaIndex,
+    ) -> Option<SignatureHelp> {
+        let tree = Parser::new(source).parse();
+        self.signature_help_with_ast(source, &tree, position, schema_index)
+    }
+
+    /// Get the signature help at a position using an already parsed AST
+    ///
+    /// The AST should match `source`: signature help is requested while typing, so a
+    /// tree cached before the last edit usually won't contain the argument list yet.
+    #[must_use]
+    pub fn signature_help_with_ast(
+        &self,
+        source: &str,
+        tree: &SyntaxTree,
+        position: Position,
+        schema_index: &SchemaIndex,
+    ) -> Option<SignatureHelp> {
+        let offset = LineIndex::with_encoding(source, self.position_encoding)
+            .position_to_offset(position)?;
+
+        for definition in tree.document().definitions() {
+            let (type_name, selection_set) = match definition {
+                cst::Definition::OperationDefinition(operation) => (
+                    schema_index
+                        .root_type(OperationType::of(&operation))
+                        .map(ToString::to_string),
+                    operation.selection_set(),
+                ),
+                cst::Definition::FragmentDefinition(fragment) => (
+                    fragment
+                        .type_condition()
+                        .and_then(|tc| tc.named_type())
+                        .and_then(|nt| nt.name())
+                        .map(|name| name.text().to_string()),
+                    fragment.selection_set(),
+                ),
+                _ => continue,
+            };
+
+            if let (Some(type_name), Some(selection_set)) = (type_name, selection_set) {
+                if contains(selection_set.syntax(), offset) {
+                    return selection_set_signature(
+                        &selection_set,
+                        &type_name,
+                        offset,
+                        schema_index,
+                    );
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl Default for SignatureHelpProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn selection_set_signature(
+    selection_set: &cst::SelectionSet,
+    parent_type: &str,
+    offset: usize,
+    schema_index: &SchemaIndex,
+) -> Option<SignatureHelp> {
+    for selection in selection_set.selections() {
+        match selection {
+            cst::Selection::Field(field) => {
+                if let Some(arguments) = field.arguments() {
+                    if is_inside_parens(&arguments, offset) {
+                        return field_signature(
+                            &field,
+                            &arguments,
+                            parent_type,
+                            offset,
+                            schema_index,
+                        );
+                    }
+                }
+
+                if let Some(nested) = field.selection_set() {
+                    if contains(nested.syntax(), offset) {
+                        let field_name = field.name()?.text().to_string();
+                        let field_info = schema_index
+                            .get_fields(parent_type)?
+                            .into_iter()
+                            .find(|f| f.name == field_name)?;
+                        let base_type = field_info
+                            .type_name
+                            .trim_matches(|c| c == '[' || c == ']' || c == '!');
+                        return selection_set_signature(&nested, base_type, offset, schema_index);
+                    }
+                }
+            }
+            cst::Selection::InlineFragment(inline_fragment) => {
+                if let Some(nested) = inline_fragment.selection_set() {
+                    if contains(nested.syntax(), offset) {
+                        let type_name = inline_fragment
+                            .type_condition()
+                            .and_then(|tc| tc.named_type())
+                            .and_then(|nt| nt.name())
+                            .map_or_else(
+                                || parent_type.to_string(),
+                                |name| name.text().to_string(),
+                            );
+                        return selection_set_signature(&nested, &type_name, offset, schema_index);
+                    }
+                }
+            }
+            cst::Selection::FragmentSpread(_) => {}
+        }
+    }
+
+    None
+}
+
+fn field_signature(
+    field: &cst::Field,
+    arguments: &cst::Arguments,
+    parent_type: &str,
+    offset: usize,
+    schema_index: &SchemaIndex,
+) -> Option<SignatureHelp> {
+    let field_name = field.name()?.text().to_string();
+    let field_info = schema_index
+        .get_fields(parent_type)?
+        .into_iter()
+        .find(|f| f.name == field_name)?;
+
+    // Inside an input object value, the input object's fields are the useful signature
+    for argument in arguments.arguments() {
+        let (Some(name), Some(cst::Value::ObjectValue(object))) =
+            (argument.name(), argument.value())
+        else {
+            continue;
+        };
+        if !is_inside_braces(&object, offset) {
+            continue;
+        }
+        let arg_info = field_info
+            .arguments
+            .iter()
+            .find(|arg| arg.name == name.text())?;
+        return input_object_signature(&object, &arg_info.type_name, offset, schema_index);
+    }
+
+    let parameters: Vec<SignatureParameter> = field_info
+        .arguments
+        .iter()
+        .map(|arg| SignatureParameter {
+            label: parameter_label(&arg.name, &arg.type_name, arg.default_value.as_deref()),
+            documentation: arg.description.clone(),
+        })
+        .collect();
+    if parameters.is_empty() {
+        return None;
+    }
+
+    let written: Vec<(String, SyntaxNode)> = arguments
+        .arguments()
+        .filter_map(|arg| Some((arg.name()?.text().to_string(), arg.syntax().clone())))
+        .collect();
+    let names: Vec<&str> = field_info
+        .arguments
+        .iter()
+        .map(|arg| arg.name.as_str())
+        .collect();
+
+    Some(SignatureHelp {
+        label: signature_label(&field_name, "(", &parameters, ")"),
+        documentation: field_info.description,
+        active_parameter: active_parameter(&names, &written, offset),
+        parameters,
+    })
+}
+
+fn input_object_signature(
+    object: &cst::ObjectValue,
+    type_name: &str,
+    offset: usize,
+    schema_index: &SchemaIndex,
+) -> Option<SignatureHelp> {
+    let base_type = type_name.trim_matches(|c| c == '[' || c == ']' || c == '!');
+    let ExtendedType::InputObject(input) = schema_index.schema().types.get(base_type)? else {
+        return None;
+    };
+
+    // Nested input objects show the innermost one
+    for object_field in object.object_fields() {
+        let (Some(name), Some(cst::Value::ObjectValue(nested))) =
+            (object_field.name(), object_field.value())
+        else {
+            continue;
+        };
+        if is_inside_braces(&nested, offset) {
+            let field = input.fields.get(name.text().as_str())?;
+            return input_object_signature(&nested, &field.ty.to_string(), offset, schema_index);
+        }
+    }
+
+    let parameters: Vec<SignatureParameter> = input
+        .fields
+        .values()
+        .map(|field| SignatureParameter {
+            label: parameter_label(
+                field.name.as_str(),
+                &field.ty.to_string(),
+                field
+                    .default_value
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .as_deref(),
+            ),
+            documentation: field.description.as_ref().map(ToString::to_string),
+        })
+        .collect();
+
+    let written: Vec<(String, SyntaxNode)> = object
+        .object_fields()
+        .filter_map(|field| Some((field.name()?.text().to_string(), field.syntax().clone())))
+        .collect();
+    let names: Vec<&str> = input
+        .fields
+        .keys()
+        .map(apollo_compiler::Name::as_str)
+        .collect();
+
+    Some(SignatureHelp {
+        label: signature_label(base_type, " { ", &parameters, " }"),
+        documentation: input.description.as_ref().map(ToString::to_string),
+        active_parameter: active_parameter(&names, &written, offset),
+        parameters,
+    })
+}
+
+fn parameter_label(name: &str, type_name: &str, default_value: Option<&str>) -> String {
+    default_value.map_or_else(
+        || format!("{name}: {type_name}"),
+        |default_value| format!("{name}: {type_name} = {default_value}"),
+    )
+}
+
+fn signature_label(
+    name: &str,
+    open: &str,
+    parameters: &[SignatureParameter],
+    close: &str,
+) -> String {
+    let parameters: Vec<&str> = parameters.iter().map(|p| p.label.as_str()).collect();
+    format!("{name}{open}{}{close}", parameters.join(", "))
+}
+
+/// The parameter the cursor is on, or else the first one not written yet
+///
+/// GraphQL arguments are passed by name, so in the whitespace after a comma the most
+/// useful parameter to highlight is the next one still missing.
+fn active_parameter(
+    names: &[&str],
+    written: &[(String, SyntaxNode)],
+    offset: usize,
+) -> Option<usize> {
+    let current = written.iter().find(|(_, node)| {
+        let start = usize::from(node.text_range().start());
+        start <= offset && offset <= content_end(node)
+    });
+    if let Some((name, _)) = current {
+        if let Some(index) = names.iter().position(|n| *n == name.as_str()) {
+            return Some(index);
+        }
+    }
+
+    names
+        .iter()
+        .position(|name| !written.iter().any(|(written, _)| written == *name))
+}
+
+/// Whether the offset is after the `(` of an argument list and not after its `)`
+fn is_inside_parens(arguments: &cst::Arguments, offset: usize) -> bool {
+    is_inside_delimiters(
+        arguments.syntax(),
+        SyntaxKind::L_PAREN,
+        SyntaxKind::R_PAREN,
+        offset,
+    )
+}
+
+/// Whether the offset is after the `{` of an object value and not after its `}`
+fn is_inside_braces(object: &cst::ObjectValue, offset: usize) -> bool {
+    is_inside_delimiters(
+        object.syntax(),
+        SyntaxKind::L_CURLY,
+        SyntaxKind::R_CURLY,
+        offset,
+    )
+}
+
+fn is_inside_delimiters(
+    node: &SyntaxNode,
+    open: SyntaxKind,
+    close: SyntaxKind,
+    offset: usize,
+) -> bool {
+    let tokens: Vec<_> = node
+        .children_with_tokens()
+        .filter_map(|child| child.as_token().cloned())
+        .collect();
+    let Some(open) = tokens.iter().find(|token| token.kind() == open) else {
+        return false;
+    };
+    if offset < usize::from(open.text_range().end()) {
+        return false;
+    }
+
+    // While typing, the closing delimiter is often missing
+    tokens
+        .iter()
+        .find(|token| token.kind() == close)
+        .map_or_else(
+            || offset <= usize::from(node.text_range().end()),
+            |close| offset <= usize::from(close.text_range().start()),
+        )
+}
+
+/// End offset of a node, excluding the whitespace and commas the parser attaches after it
+fn content_end(node: &SyntaxNode) -> usize {
+    node.descendants_with_tokens()
+        .filter_map(|child| child.as_token().cloned())
+        .filter(|token| {
+            !matches!(
+                token.kind(),
+                SyntaxKind::WHITESPACE | SyntaxKind::COMMA | SyntaxKind::COMMENT
+            )
+        })
+        .last()
+        .map_or_else(
+            || usize::from(node.text_range().start()),
+            |token| usize::from(token.text_range().end()),
+        )
+}
+
+/// Whether a node's range contains the offset, counting the end as inside
+fn contains(node: &SyntaxNode, offset: usize) -> bool {
+    let range = node.text_range();
+    usize::from(range.start()) <= offset && offset <= usize::from(range.end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_schema() -> SchemaIndex {
+        SchemaIndex::from_schema(
+            r#"
+            type Query {
+                "Look up a user"
+                user("The user's ID" id: ID!, includeDeleted: Boolean = false): User
+                users(filter: UserFilter, first: Int): [User!]!
+                viewer: User
+            }
+
+            type User {
+                id: ID!
+                posts(first: Int = 10, after: String): [Post!]!
+            }
+
+            type Post {
+                title: String!
+            }
+
+            input UserFilter {
+                name: String
+                address: AddressFilter
+            }
+
+            input AddressFilter {
+                city: String
+                country: String = "NL"
+            }
+            "#,
+        )
+    }
+
+    fn help_at(source: &str, marker: &str) -> Option<SignatureHelp> {
+        let offset = source.find(marker).expect("marker should be in source") + marker.len();
+        let position = LineIndex::new(source).offset_to_position(offset);
+        SignatureHelpProvider::new().signature_help(source, position, &create_test_schema())
+    }
+
+    #[test]
+    fn test_field_signature_with_defaults() {
+        let help = help_at(r#"query { user(id: "1", ) { id } }"#, "\"1\", ").unwrap();
+
+        assert_eq!(help.label, "user(id: ID!, includeDeleted: Boolean = false)");
+        assert_eq!(help.documentation.as_deref(), Some("Look up a user"));
+        assert_eq!(
+            help.parameters[0].documentation.as_deref(),
+            Some("The user's ID")
+        );
+        // `id` is written, so the next parameter is active
+        assert_eq!(help.active_parameter, Some(1));
+    }
+
+    #[test]
+    fn test_active_parameter_follows_cursor() {
+        let source = "query { user(includeDeleted: true, id: \"1\") { id } }";
+
+        assert_eq!(
+            help_at(source, "(includ").unwrap().active_parameter,
+            Some(1)
+        );
+        assert_eq!(help_at(source, "id: \"").unwrap().active_parameter, Some(0));
+    }
+
+    #[test]
+    fn test_unclosed_argument_list_in_nested_field() {
+        let help = help_at("query { viewer { posts(", "posts(").unwrap();
+
+        assert_eq!(help.label, "posts(first: Int = 10, after: String)");
+        assert_eq!(help.active_parameter, Some(0));
+    }
+
+    #[test]
+    fn test_input_object_fields() {
+        let source = "query { users(filter: { name: \"a\", address: { } }) { id } }";
+
+        let help = help_at(source, "{ name: \"a").unwrap();
+        assert_eq!(
+            help.label,
+            "UserFilter { name: String, address: AddressFilter }"
+        );
+        assert_eq!(help.active_parameter, Some(0));
+
+        let help = help_at(source, "address: { ").unwrap();
+        assert_eq!(
+            help.label,
+            "AddressFilter { city: String, country: String = \"NL\" }"
+        );
+    }
+
+    #[test]
+    fn test_no_help_outside_arguments() {
+        assert!(help_at("query { user(id: \"1\") { id } }", "{ i").is_none());
+        assert!(help_at("query { viewer() { id } }", "viewer(").is_none());
+    }
+}
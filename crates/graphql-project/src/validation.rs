@@ -1,4 +1,4 @@
-use crate::SchemaIndex;
+use crate::{OperationType, PositionEncoding, SchemaIndex};
 use apollo_compiler::{
     parser::{Parser, SourceOffset},
     validation::{DiagnosticList, Valid},
@@ -7,13 +7,23 @@ use apollo_compiler::{
 
 /// Validation engine for GraphQL documents against a schema
 pub struct Validator {
-    // Stateless validator - all state is in Schema
+    /// Unit diagnostic columns are counted in
+    position_encoding: PositionEncoding,
 }
 
 impl Validator {
     #[must_use]
     pub const fn new() -> Self {
-        Self {}
+        Self {
+            position_encoding: PositionEncoding::Utf16,
+        }
+    }
+
+    /// Count diagnostic columns in `encoding` instead of UTF-16 code units
+    #[must_use]
+    pub const fn with_position_encoding(mut self, encoding: PositionEncoding) -> Self {
+        self.position_encoding = encoding;
+        self
     }
 
     /// Validate a document string against a schema
@@ -166,7 +176,7 @@ impl Validator {
                         let name_str = name.text().to_string();
                         let syntax_node = name.syntax();
                         let offset: usize = syntax_node.text_range().start().into();
-                        let line_col = Self::offset_to_line_col(document, offset);
+                        let line_col = self.offset_to_line_col(document, offset);
 
                         operation_names.entry(name_str).or_default().push(line_col);
                     }
@@ -176,7 +186,7 @@ impl Validator {
                         let name_str = name.text().to_string();
                         let syntax_node = name.syntax();
                         let offset: usize = syntax_node.text_range().start().into();
-                        let line_col = Self::offset_to_line_col(document, offset);
+                        let line_col = self.offset_to_line_col(document, offset);
 
                         fragment_names.entry(name_str).or_default().push(line_col);
                     }
@@ -280,27 +290,12 @@ impl Validator {
         for definition in doc_cst.definitions() {
             if let cst::Definition::OperationDefinition(operation) = definition {
                 // Get the root type name for this operation
-                let root_type_name = match operation.operation_type() {
-                    Some(op_type) if op_type.query_token().is_some() => {
-                        schema_index.schema().schema_definition.query.as_ref()
-                    }
-                    Some(op_type) if op_type.mutation_token().is_some() => {
-                        schema_index.schema().schema_definition.mutation.as_ref()
-                    }
-                    Some(op_type) if op_type.subscription_token().is_some() => schema_index
-                        .schema()
-                        .schema_definition
-                        .subscription
-                        .as_ref(),
-                    None => schema_index.schema().schema_definition.query.as_ref(),
-                    _ => None,
-                };
-
-                if let Some(root_type_name) = root_type_name {
+                if let Some(root_type_name) = schema_index.root_type(OperationType::of(&operation))
+                {
                     if let Some(selection_set) = operation.selection_set() {
-                        Self::check_selection_set_cst(
+                        self.check_selection_set_cst(
                             &selection_set,
-                            root_type_name.as_str(),
+                            root_type_name,
                             schema_index,
                             &mut warnings,
                             document,
@@ -315,6 +310,7 @@ impl Validator {
 
     /// Recursively check a selection set (CST) for deprecated fields
     fn check_selection_set_cst(
+        &self,
         selection_set: &apollo_parser::cst::SelectionSet,
         parent_type_name: &str,
         schema_index: &SchemaIndex,
@@ -339,7 +335,7 @@ impl Validator {
                                     // Get the source location of the field
                                     let syntax_node = field_name.syntax();
                                     let offset: usize = syntax_node.text_range().start().into();
-                                    let line_col = Self::offset_to_line_col(document, offset);
+                                    let line_col = self.offset_to_line_col(document, offset);
 
                                     let range = Range {
                                         start: Position {
@@ -352,42 +348,1648 @@ impl Validator {
                                         },
                                     };
 
-                                    let message =
-                                        format!("Field '{field_name_str}' is deprecated. {reason}");
+                                    let message =
+                                        format!("Field '{field_name_str}' is deprecated. {reason}");
+
+                                    warnings.push(
+                                        Diagnostic::warning(range, message)
+                                            .with_code("deprecated-field")
+                                            .with_source("graphql-validator"),
+                                    );
+                                }
+
+                                // Recursively check nested selections
+                                if let Some(nested_selection_set) = field.selection_set() {
+                                    // Extract the base type name from the field type
+                                    let nested_type = field_info
+                                        .type_name
+                                        .trim_matches(|c| c == '[' || c == ']' || c == '!');
+
+                                    self.check_selection_set_cst(
+                                        &nested_selection_set,
+                                        nested_type,
+                                        schema_index,
+                                        warnings,
+                                        document,
+                                    );
+                                }
+                            }
+                        }
+                    }
+                }
+                cst::Selection::FragmentSpread(_) => {
+                    // TODO: Handle fragment spreads
+                }
+                cst::Selection::InlineFragment(inline_fragment) => {
+                    if let Some(selection_set) = inline_fragment.selection_set() {
+                        // For inline fragments, use the type condition if present
+                        // We need to extract it as a String to avoid lifetime issues
+                        let type_name_owned =
+                            inline_fragment.type_condition().and_then(|type_condition| {
+                                type_condition.named_type().and_then(|named_type| {
+                                    named_type.name().map(|name| name.text().to_string())
+                                })
+                            });
+
+                        let type_name_ref = type_name_owned.as_deref().unwrap_or(parent_type_name);
+
+                        self.check_selection_set_cst(
+                            &selection_set,
+                            type_name_ref,
+                            schema_index,
+                            warnings,
+                            document,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Check the fragment definitions in a document for deprecated fields
+    ///
+    /// The operation check doesn't follow spreads into fragments, which may be defined in
+    /// other files, so each fragment is checked here on its own. Warnings are reported in
+    /// the fragment, with related info pointing at each named operation in `document_index`
+    /// that spreads the fragment, directly or through other fragments.
+    #[must_use]
+    pub fn check_deprecated_fields_in_fragments(
+        &self,
+        document: &str,
+        schema_index: &SchemaIndex,
+        document_index: &crate::DocumentIndex,
+        _file_name: &str,
+    ) -> Vec<crate::Diagnostic> {
+        use crate::diagnostics::{Location, RelatedInfo};
+        use crate::{Position, Range};
+        use apollo_parser::{cst, Parser};
+
+        let mut warnings = Vec::new();
+        let parser = Parser::new(document);
+        let tree = parser.parse();
+
+        if tree.errors().len() > 0 {
+            return warnings;
+        }
+
+        // Built on the first fragment with a deprecated field, since most have none
+        let mut operations_by_fragment = None;
+
+        for definition in tree.document().definitions() {
+            let cst::Definition::FragmentDefinition(fragment) = definition else {
+                continue;
+            };
+            let Some(fragment_name) = fragment
+                .fragment_name()
+                .and_then(|name| name.name())
+                .map(|name| name.text().to_string())
+            else {
+                continue;
+            };
+            let Some(type_name) = fragment
+                .type_condition()
+                .and_then(|tc| tc.named_type())
+                .and_then(|nt| nt.name())
+                .map(|name| name.text().to_string())
+            else {
+                continue;
+            };
+            let Some(selection_set) = fragment.selection_set() else {
+                continue;
+            };
+
+            let mut fragment_warnings = Vec::new();
+            self.check_selection_set_cst(
+                &selection_set,
+                &type_name,
+                schema_index,
+                &mut fragment_warnings,
+                document,
+            );
+            if fragment_warnings.is_empty() {
+                continue;
+            }
+
+            let operations = operations_by_fragment
+                .get_or_insert_with(|| Self::operations_by_fragment(document_index))
+                .get(&fragment_name);
+
+            for mut warning in fragment_warnings {
+                for operation_name in operations.into_iter().flatten() {
+                    let Some(infos) = document_index.get_operations(operation_name) else {
+                        continue;
+                    };
+                    for info in infos {
+                        let uri = if info.file_path.starts_with("file://") {
+                            info.file_path.clone()
+                        } else {
+                            format!("file://{}", info.file_path)
+                        };
+                        warning = warning.with_related_info(RelatedInfo {
+                            message: format!(
+                                "Fragment '{fragment_name}' is used by operation '{operation_name}'"
+                            ),
+                            location: Location {
+                                uri,
+                                range: Range {
+                                    start: Position {
+                                        line: info.line,
+                                        character: info.column,
+                                    },
+                                    end: Position {
+                                        line: info.line,
+                                        character: info.column + operation_name.len(),
+                                    },
+                                },
+                            },
+                        });
+                    }
+                }
+                warnings.push(warning);
+            }
+        }
+
+        warnings
+    }
+
+    /// Map each fragment name to the named operations in the project that spread it
+    ///
+    /// Spreads are followed through fragments, so a fragment spread only by another
+    /// fragment maps to the operations that spread that one.
+    fn operations_by_fragment(
+        document_index: &crate::DocumentIndex,
+    ) -> std::collections::HashMap<String, std::collections::BTreeSet<String>> {
+        use apollo_parser::cst::{self, CstNode};
+        use std::collections::{BTreeSet, HashMap, HashSet};
+
+        fn spread_names(node: &apollo_parser::SyntaxNode) -> Vec<String> {
+            node.descendants()
+                .filter_map(cst::FragmentSpread::cast)
+                .filter_map(|spread| spread.fragment_name()?.name())
+                .map(|name| name.text().to_string())
+                .collect()
+        }
+
+        let trees = document_index
+            .parsed_asts
+            .values()
+            .map(std::sync::Arc::as_ref)
+            .chain(
+                document_index
+                    .extracted_blocks
+                    .values()
+                    .flatten()
+                    .map(|block| block.parsed.as_ref()),
+            );
+
+        let mut fragment_spreads: HashMap<String, Vec<String>> = HashMap::new();
+        let mut operation_spreads: Vec<(String, Vec<String>)> = Vec::new();
+        for tree in trees {
+            for definition in tree.document().definitions() {
+                match definition {
+                    cst::Definition::FragmentDefinition(fragment) => {
+                        if let Some(name) = fragment.fragment_name().and_then(|name| name.name()) {
+                            fragment_spreads
+                                .insert(name.text().to_string(), spread_names(fragment.syntax()));
+                        }
+                    }
+                    cst::Definition::OperationDefinition(operation) => {
+                        if let Some(name) = operation.name() {
+                            operation_spreads
+                                .push((name.text().to_string(), spread_names(operation.syntax())));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut operations_by_fragment: HashMap<String, BTreeSet<String>> = HashMap::new();
+        for (operation_name, spreads) in operation_spreads {
+            let mut visited = HashSet::new();
+            let mut pending = spreads;
+            while let Some(fragment_name) = pending.pop() {
+                if !visited.insert(fragment_name.clone()) {
+                    continue;
+                }
+                if let Some(nested) = fragment_spreads.get(&fragment_name) {
+                    pending.extend(nested.iter().cloned());
+                }
+                operations_by_fragment
+                    .entry(fragment_name)
+                    .or_default()
+                    .insert(operation_name.clone());
+            }
+        }
+
+        operations_by_fragment
+    }
+
+    /// Check for field selections made directly on a union type
+    ///
+    /// Unions have no fields of their own, so only `__typename` may be selected on them
+    /// directly. Any other field has to be selected through an inline fragment on one of
+    /// the union's member types. Returns an error for each offending field, pointing at
+    /// the field name and suggesting an inline fragment.
+    #[must_use]
+    pub fn check_union_field_selections(
+        &self,
+        document: &str,
+        schema_index: &SchemaIndex,
+        _file_name: &str,
+    ) -> Vec<crate::Diagnostic> {
+        use apollo_parser::{cst, Parser};
+
+        let mut errors = Vec::new();
+        let parser = Parser::new(document);
+        let tree = parser.parse();
+
+        if tree.errors().len() > 0 {
+            return errors;
+        }
+
+        let doc_cst = tree.document();
+
+        for definition in doc_cst.definitions() {
+            match definition {
+                cst::Definition::OperationDefinition(operation) => {
+                    if let (Some(root_type_name), Some(selection_set)) = (
+                        schema_index.root_type(OperationType::of(&operation)),
+                        operation.selection_set(),
+                    ) {
+                        self.check_union_selection_set_cst(
+                            &selection_set,
+                            root_type_name,
+                            schema_index,
+                            &mut errors,
+                            document,
+                        );
+                    }
+                }
+                cst::Definition::FragmentDefinition(fragment) => {
+                    let type_name = fragment
+                        .type_condition()
+                        .and_then(|tc| tc.named_type())
+                        .and_then(|nt| nt.name())
+                        .map(|name| name.text().to_string());
+
+                    if let (Some(type_name), Some(selection_set)) =
+                        (type_name, fragment.selection_set())
+                    {
+                        self.check_union_selection_set_cst(
+                            &selection_set,
+                            &type_name,
+                            schema_index,
+                            &mut errors,
+                            document,
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        errors
+    }
+
+    /// Check for operation and fragment names that start with `__`
+    ///
+    /// Names beginning with two underscores are reserved by the GraphQL spec for the
+    /// introspection system. Returns an error on the name token of each offending definition.
+    #[must_use]
+    pub fn check_reserved_names(&self, document: &str, _file_name: &str) -> Vec<crate::Diagnostic> {
+        use crate::{Diagnostic, Position, Range};
+        use apollo_parser::{
+            cst::{self, CstNode},
+            Parser,
+        };
+
+        let mut errors = Vec::new();
+        let parser = Parser::new(document);
+        let tree = parser.parse();
+
+        if tree.errors().len() > 0 {
+            return errors;
+        }
+
+        for definition in tree.document().definitions() {
+            let (kind, name) = match definition {
+                cst::Definition::OperationDefinition(operation) => ("Operation", operation.name()),
+                cst::Definition::FragmentDefinition(fragment) => (
+                    "Fragment",
+                    fragment.fragment_name().and_then(|name| name.name()),
+                ),
+                _ => continue,
+            };
+
+            let Some(name) = name else {
+                continue;
+            };
+            let name_str = name.text();
+            if !name_str.starts_with("__") {
+                continue;
+            }
+
+            let offset: usize = name.syntax().text_range().start().into();
+            let (line, col) = self.offset_to_line_col(document, offset);
+
+            let range = Range {
+                start: Position {
+                    line,
+                    character: col,
+                },
+                end: Position {
+                    line,
+                    character: col + name_str.len(),
+                },
+            };
+
+            errors.push(
+                Diagnostic::error(
+                    range,
+                    format!(
+                        "{kind} name '{name_str}' is invalid: names starting with '__' are reserved for introspection."
+                    ),
+                )
+                .with_code("reserved-name")
+                .with_source("graphql-validator"),
+            );
+        }
+
+        errors
+    }
+
+    /// Check the schema for argument names defined more than once on the same field or directive
+    ///
+    /// Reports an error at each repeated argument, with the first definition as related
+    /// information. Errors carry the schema file they're in, since a schema can span several
+    /// files.
+    #[must_use]
+    pub fn check_duplicate_argument_definitions(
+        &self,
+        schema_index: &SchemaIndex,
+    ) -> Vec<crate::SchemaBuildError> {
+        use apollo_compiler::schema::ExtendedType;
+
+        let schema = schema_index.schema();
+        let mut errors = Vec::new();
+
+        for (type_name, ext_type) in &schema.types {
+            let fields = match ext_type {
+                ExtendedType::Object(obj) => &obj.fields,
+                ExtendedType::Interface(iface) => &iface.fields,
+                _ => continue,
+            };
+            for (field_name, field) in fields {
+                Self::check_duplicate_arguments(
+                    &field.arguments,
+                    &format!("field '{type_name}.{field_name}'"),
+                    schema_index,
+                    &mut errors,
+                );
+            }
+        }
+
+        for (directive_name, directive) in &schema.directive_definitions {
+            Self::check_duplicate_arguments(
+                &directive.arguments,
+                &format!("directive '@{directive_name}'"),
+                schema_index,
+                &mut errors,
+            );
+        }
+
+        errors
+    }
+
+    /// Report the arguments in `arguments` that repeat an earlier argument's name
+    fn check_duplicate_arguments(
+        arguments: &[apollo_compiler::Node<apollo_compiler::schema::InputValueDefinition>],
+        owner: &str,
+        schema_index: &SchemaIndex,
+        errors: &mut Vec<crate::SchemaBuildError>,
+    ) {
+        use crate::diagnostics::{Location, RelatedInfo};
+        use crate::{Diagnostic, Position, Range};
+        use std::collections::HashMap;
+
+        let name_range = |name: &apollo_compiler::Name| {
+            schema_index
+                .name_location(name)
+                .map(|(file_path, line, column)| {
+                    let range = Range {
+                        start: Position {
+                            line,
+                            character: column,
+                        },
+                        end: Position {
+                            line,
+                            character: column + name.len(),
+                        },
+                    };
+                    (file_path, range)
+                })
+        };
+
+        let mut seen: HashMap<&str, &apollo_compiler::Name> = HashMap::new();
+        for argument in arguments {
+            let Some(first) = seen.get(argument.name.as_str()) else {
+                seen.insert(argument.name.as_str(), &argument.name);
+                continue;
+            };
+            let Some((file_path, range)) = name_range(&argument.name) else {
+                continue;
+            };
+
+            let mut error = Diagnostic::error(
+                range,
+                format!(
+                    "Argument '{}' is defined more than once on {owner}.",
+                    argument.name
+                ),
+            )
+            .with_code("unique-argument-definitions")
+            .with_source("graphql-validator");
+            if let Some((first_file_path, first_range)) = name_range(first) {
+                error = error.with_related_info(RelatedInfo {
+                    message: format!("Argument '{}' first defined here", argument.name),
+                    location: Location {
+                        uri: format!("file://{first_file_path}"),
+                        range: first_range,
+                    },
+                });
+            }
+
+            errors.push(crate::SchemaBuildError {
+                file_path: Some(file_path),
+                diagnostic: error,
+            });
+        }
+    }
+
+    /// Check that fragment type conditions name types defined in the schema
+    ///
+    /// Returns an error at the type condition of each fragment on an unknown type, paired
+    /// with the range of the whole fragment definition. While a schema is still being written,
+    /// callers use that range to drop the errors reported for the fields the fragment selects,
+    /// so the missing type is reported once instead of once per field.
+    #[must_use]
+    pub fn check_fragment_type_conditions(
+        &self,
+        document: &str,
+        schema_index: &SchemaIndex,
+        _file_name: &str,
+    ) -> Vec<(crate::Range, crate::Diagnostic)> {
+        use crate::{Diagnostic, Position, Range};
+        use apollo_parser::{
+            cst::{self, CstNode},
+            Parser,
+        };
+
+        let mut errors = Vec::new();
+        let parser = Parser::new(document);
+        let tree = parser.parse();
+
+        if tree.errors().len() > 0 {
+            return errors;
+        }
+
+        for definition in tree.document().definitions() {
+            let cst::Definition::FragmentDefinition(fragment) = definition else {
+                continue;
+            };
+            let Some(type_name) = fragment
+                .type_condition()
+                .and_then(|tc| tc.named_type())
+                .and_then(|nt| nt.name())
+            else {
+                continue;
+            };
+            let type_name_str = type_name.text();
+            if schema_index.get_type(&type_name_str).is_some() {
+                continue;
+            }
+
+            let fragment_name = fragment
+                .fragment_name()
+                .and_then(|name| name.name())
+                .map_or_else(String::new, |name| name.text().to_string());
+
+            let definition_range = fragment.syntax().text_range();
+            let (start_line, start_col) =
+                self.offset_to_line_col(document, definition_range.start().into());
+            let (end_line, end_col) =
+                self.offset_to_line_col(document, definition_range.end().into());
+
+            let offset: usize = type_name.syntax().text_range().start().into();
+            let (line, col) = self.offset_to_line_col(document, offset);
+
+            let range = Range {
+                start: Position {
+                    line,
+                    character: col,
+                },
+                end: Position {
+                    line,
+                    character: col + type_name_str.len(),
+                },
+            };
+
+            errors.push((
+                Range {
+                    start: Position {
+                        line: start_line,
+                        character: start_col,
+                    },
+                    end: Position {
+                        line: end_line,
+                        character: end_col,
+                    },
+                },
+                Diagnostic::error(
+                    range,
+                    format!(
+                        "Unknown type condition: fragment '{fragment_name}' is on '{type_name_str}', which is not defined in the schema."
+                    ),
+                )
+                .with_code("unknown-type-condition")
+                .with_source("graphql-validator"),
+            ));
+        }
+
+        errors
+    }
+
+    /// Check for `@skip` and `@include` on the root fields of subscriptions
+    ///
+    /// A subscription must have exactly one root field, known before any variables are
+    /// applied, so the root selection set can't be conditional. Directives on root fields,
+    /// and on fragment spreads and inline fragments in the root selection set, are reported
+    /// at the directive. Root fields selected through inline fragments are followed.
+    #[must_use]
+    pub fn check_subscription_root_directives(&self, document: &str) -> Vec<crate::Diagnostic> {
+        use apollo_parser::{cst, Parser};
+
+        let mut errors = Vec::new();
+        let parser = Parser::new(document);
+        let tree = parser.parse();
+
+        if tree.errors().len() > 0 {
+            return errors;
+        }
+
+        for definition in tree.document().definitions() {
+            let cst::Definition::OperationDefinition(operation) = definition else {
+                continue;
+            };
+            let is_subscription = operation
+                .operation_type()
+                .is_some_and(|op_type| op_type.subscription_token().is_some());
+            if let (true, Some(selection_set)) = (is_subscription, operation.selection_set()) {
+                self.check_root_selection_directives(&selection_set, document, &mut errors);
+            }
+        }
+
+        errors
+    }
+
+    /// Report `@skip` and `@include` in a subscription's root selection set (CST)
+    fn check_root_selection_directives(
+        &self,
+        selection_set: &apollo_parser::cst::SelectionSet,
+        document: &str,
+        errors: &mut Vec<crate::Diagnostic>,
+    ) {
+        use crate::{Diagnostic, Position, Range};
+        use apollo_parser::cst::{self, CstNode};
+
+        for selection in selection_set.selections() {
+            let (directives, what) = match &selection {
+                cst::Selection::Field(field) => (field.directives(), "root field"),
+                cst::Selection::FragmentSpread(spread) => {
+                    (spread.directives(), "fragment spread on the root type")
+                }
+                cst::Selection::InlineFragment(inline_fragment) => {
+                    if let Some(nested) = inline_fragment.selection_set() {
+                        self.check_root_selection_directives(&nested, document, errors);
+                    }
+                    (
+                        inline_fragment.directives(),
+                        "inline fragment on the root type",
+                    )
+                }
+            };
+
+            for directive in directives.iter().flat_map(cst::Directives::directives) {
+                let Some(name) = directive.name() else {
+                    continue;
+                };
+                let name_str = name.text();
+                if name_str != "skip" && name_str != "include" {
+                    continue;
+                }
+
+                let offset: usize = directive.syntax().text_range().start().into();
+                let (line, col) = self.offset_to_line_col(document, offset);
+                let range = Range {
+                    start: Position {
+                        line,
+                        character: col,
+                    },
+                    end: Position {
+                        line,
+                        character: col + 1 + name_str.len(),
+                    },
+                };
+
+                errors.push(
+                    Diagnostic::error(
+                        range,
+                        format!(
+                            "Subscriptions must have a single, unconditional root field, so @{name_str} can't be used on a {what}."
+                        ),
+                    )
+                    .with_code("subscription-root-directive")
+                    .with_source("graphql-validator"),
+                );
+            }
+        }
+    }
+
+    /// Check literal arguments passed to custom scalars that are mapped to a built-in scalar
+    ///
+    /// Custom scalars are coerced by the server, so apollo-compiler accepts any literal for
+    /// them. When `scalar_mappings` maps a custom scalar to `Int`, `Float`, `String`,
+    /// `Boolean`, or `ID` (via `extensions.scalars`), literals passed to field arguments of
+    /// that scalar must be valid for the built-in scalar. Variables and `null` are always
+    /// accepted, as are custom scalars without a mapping.
+    #[must_use]
+    pub fn check_custom_scalar_arguments(
+        &self,
+        document: &str,
+        schema_index: &SchemaIndex,
+        scalar_mappings: &std::collections::HashMap<String, String>,
+        _file_name: &str,
+    ) -> Vec<crate::Diagnostic> {
+        use apollo_parser::{cst, Parser};
+
+        let mut errors = Vec::new();
+        if scalar_mappings.is_empty() {
+            return errors;
+        }
+
+        let parser = Parser::new(document);
+        let tree = parser.parse();
+
+        if tree.errors().len() > 0 {
+            return errors;
+        }
+
+        for definition in tree.document().definitions() {
+            let (type_name, selection_set) = match definition {
+                cst::Definition::OperationDefinition(operation) => (
+                    schema_index
+                        .root_type(OperationType::of(&operation))
+                        .map(ToString::to_string),
+                    operation.selection_set(),
+                ),
+                cst::Definition::FragmentDefinition(fragment) => (
+                    fragment
+                        .type_condition()
+                        .and_then(|tc| tc.named_type())
+                        .and_then(|nt| nt.name())
+                        .map(|name| name.text().to_string()),
+                    fragment.selection_set(),
+                ),
+                _ => continue,
+            };
+
+            if let (Some(type_name), Some(selection_set)) = (type_name, selection_set) {
+                self.check_scalar_arguments_selection_set_cst(
+                    &selection_set,
+                    &type_name,
+                    schema_index,
+                    scalar_mappings,
+                    &mut errors,
+                    document,
+                );
+            }
+        }
+
+        errors
+    }
+
+    /// Recursively check a selection set (CST) for literals passed to mapped custom scalars
+    fn check_scalar_arguments_selection_set_cst(
+        &self,
+        selection_set: &apollo_parser::cst::SelectionSet,
+        parent_type_name: &str,
+        schema_index: &SchemaIndex,
+        scalar_mappings: &std::collections::HashMap<String, String>,
+        errors: &mut Vec<crate::Diagnostic>,
+        document: &str,
+    ) {
+        use crate::{index::TypeKind, Diagnostic, Position, Range};
+        use apollo_parser::cst::{self, CstNode};
+
+        for selection in selection_set.selections() {
+            match selection {
+                cst::Selection::Field(field) => {
+                    let Some(field_name) = field.name() else {
+                        continue;
+                    };
+                    let Some(field_info) =
+                        schema_index
+                            .get_fields(parent_type_name)
+                            .and_then(|fields| {
+                                fields.into_iter().find(|f| f.name == field_name.text())
+                            })
+                    else {
+                        continue;
+                    };
+
+                    for argument in field.arguments().iter().flat_map(|args| args.arguments()) {
+                        let (Some(arg_name), Some(value)) = (argument.name(), argument.value())
+                        else {
+                            continue;
+                        };
+                        let Some(arg_info) = field_info
+                            .arguments
+                            .iter()
+                            .find(|arg| arg.name == arg_name.text())
+                        else {
+                            continue;
+                        };
+
+                        let scalar_name = arg_info
+                            .type_name
+                            .trim_matches(|c| c == '[' || c == ']' || c == '!');
+                        let is_custom_scalar = schema_index
+                            .get_type(scalar_name)
+                            .is_some_and(|type_info| type_info.kind == TypeKind::Scalar);
+                        let Some(builtin) = scalar_mappings
+                            .get(scalar_name)
+                            .filter(|_| is_custom_scalar)
+                        else {
+                            continue;
+                        };
+
+                        let Some(invalid) = Self::find_invalid_scalar_literal(&value, builtin)
+                        else {
+                            continue;
+                        };
+
+                        let text_range = invalid.syntax().text_range();
+                        let (start_line, start_col) =
+                            self.offset_to_line_col(document, text_range.start().into());
+                        let (end_line, end_col) =
+                            self.offset_to_line_col(document, text_range.end().into());
+
+                        let range = Range {
+                            start: Position {
+                                line: start_line,
+                                character: start_col,
+                            },
+                            end: Position {
+                                line: end_line,
+                                character: end_col,
+                            },
+                        };
+
+                        let message = format!(
+                            "Argument '{}' expects custom scalar '{scalar_name}', which is mapped to '{builtin}', but got {}.",
+                            arg_name.text(),
+                            invalid.syntax().text()
+                        );
+
+                        errors.push(
+                            Diagnostic::error(range, message)
+                                .with_code("invalid-scalar-value")
+                                .with_source("graphql-validator"),
+                        );
+                    }
+
+                    if let Some(nested_selection_set) = field.selection_set() {
+                        let nested_type = field_info
+                            .type_name
+                            .trim_matches(|c| c == '[' || c == ']' || c == '!');
+
+                        self.check_scalar_arguments_selection_set_cst(
+                            &nested_selection_set,
+                            nested_type,
+                            schema_index,
+                            scalar_mappings,
+                            errors,
+                            document,
+                        );
+                    }
+                }
+                cst::Selection::FragmentSpread(_) => {
+                    // Named fragments are checked against their own type condition
+                }
+                cst::Selection::InlineFragment(inline_fragment) => {
+                    if let Some(selection_set) = inline_fragment.selection_set() {
+                        let type_name_owned =
+                            inline_fragment.type_condition().and_then(|type_condition| {
+                                type_condition.named_type().and_then(|named_type| {
+                                    named_type.name().map(|name| name.text().to_string())
+                                })
+                            });
+
+                        let type_name_ref = type_name_owned.as_deref().unwrap_or(parent_type_name);
+
+                        self.check_scalar_arguments_selection_set_cst(
+                            &selection_set,
+                            type_name_ref,
+                            schema_index,
+                            scalar_mappings,
+                            errors,
+                            document,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Find the first literal in `value` that isn't valid for the built-in scalar `builtin`
+    ///
+    /// List values are checked item by item. Returns `None` if every literal is valid, or if
+    /// `builtin` is not a built-in scalar name.
+    fn find_invalid_scalar_literal(
+        value: &apollo_parser::cst::Value,
+        builtin: &str,
+    ) -> Option<apollo_parser::cst::Value> {
+        use apollo_parser::cst::Value;
+
+        if !matches!(builtin, "Int" | "Float" | "String" | "Boolean" | "ID") {
+            return None;
+        }
+
+        let is_valid = match value {
+            Value::Variable(_) | Value::NullValue(_) => true,
+            Value::ListValue(list) => {
+                return list
+                    .values()
+                    .find_map(|item| Self::find_invalid_scalar_literal(&item, builtin));
+            }
+            Value::IntValue(_) => matches!(builtin, "Int" | "Float" | "ID"),
+            Value::FloatValue(_) => builtin == "Float",
+            Value::StringValue(_) => matches!(builtin, "String" | "ID"),
+            Value::BooleanValue(_) => builtin == "Boolean",
+            Value::EnumValue(_) | Value::ObjectValue(_) => false,
+        };
+
+        if is_valid {
+            None
+        } else {
+            Some(value.clone())
+        }
+    }
+
+    /// Check that `@stream` is only applied to list fields
+    ///
+    /// Incremental delivery is opt-in: the check only runs when the schema declares a
+    /// `@stream` directive. The field's return type is resolved from its parent type, and
+    /// `@stream` on a field that doesn't return a list is reported at the directive.
+    #[must_use]
+    pub fn check_stream_on_lists(
+        &self,
+        document: &str,
+        schema_index: &SchemaIndex,
+        _file_name: &str,
+    ) -> Vec<crate::Diagnostic> {
+        use apollo_parser::{cst, Parser};
+
+        let mut errors = Vec::new();
+
+        if schema_index.get_directive("stream").is_none() {
+            return errors;
+        }
+
+        let parser = Parser::new(document);
+        let tree = parser.parse();
+
+        if tree.errors().len() > 0 {
+            return errors;
+        }
+
+        for definition in tree.document().definitions() {
+            let (type_name, selection_set) = match definition {
+                cst::Definition::OperationDefinition(operation) => (
+                    schema_index
+                        .root_type(OperationType::of(&operation))
+                        .map(ToString::to_string),
+                    operation.selection_set(),
+                ),
+                cst::Definition::FragmentDefinition(fragment) => (
+                    fragment
+                        .type_condition()
+                        .and_then(|tc| tc.named_type())
+                        .and_then(|nt| nt.name())
+                        .map(|name| name.text().to_string()),
+                    fragment.selection_set(),
+                ),
+                _ => continue,
+            };
+
+            if let (Some(type_name), Some(selection_set)) = (type_name, selection_set) {
+                self.check_stream_selection_set_cst(
+                    &selection_set,
+                    &type_name,
+                    schema_index,
+                    &mut errors,
+                    document,
+                );
+            }
+        }
+
+        errors
+    }
+
+    /// Recursively check a selection set (CST) for `@stream` on fields that aren't lists
+    fn check_stream_selection_set_cst(
+        &self,
+        selection_set: &apollo_parser::cst::SelectionSet,
+        parent_type_name: &str,
+        schema_index: &SchemaIndex,
+        errors: &mut Vec<crate::Diagnostic>,
+        document: &str,
+    ) {
+        use crate::{Diagnostic, Position, Range};
+        use apollo_parser::cst::{self, CstNode};
+
+        for selection in selection_set.selections() {
+            match selection {
+                cst::Selection::Field(field) => {
+                    let Some(field_name) = field.name() else {
+                        continue;
+                    };
+                    let Some(field_info) =
+                        schema_index
+                            .get_fields(parent_type_name)
+                            .and_then(|fields| {
+                                fields.into_iter().find(|f| f.name == field_name.text())
+                            })
+                    else {
+                        continue;
+                    };
+
+                    let stream = field
+                        .directives()
+                        .iter()
+                        .flat_map(cst::Directives::directives)
+                        .find(|directive| {
+                            directive.name().is_some_and(|name| name.text() == "stream")
+                        });
+                    if let (Some(stream), false) = (stream, field_info.type_name.starts_with('[')) {
+                        let offset: usize = stream.syntax().text_range().start().into();
+                        let (line, col) = self.offset_to_line_col(document, offset);
+                        let range = Range {
+                            start: Position {
+                                line,
+                                character: col,
+                            },
+                            end: Position {
+                                line,
+                                character: col + "@stream".len(),
+                            },
+                        };
+
+                        errors.push(
+                            Diagnostic::error(
+                                range,
+                                format!(
+                                    "@stream can only be used on list fields, but '{parent_type_name}.{}' returns '{}'.",
+                                    field_name.text(),
+                                    field_info.type_name
+                                ),
+                            )
+                            .with_code("stream-on-non-list")
+                            .with_source("graphql-validator"),
+                        );
+                    }
+
+                    if let Some(nested_selection_set) = field.selection_set() {
+                        let nested_type = field_info
+                            .type_name
+                            .trim_matches(|c| c == '[' || c == ']' || c == '!');
+
+                        self.check_stream_selection_set_cst(
+                            &nested_selection_set,
+                            nested_type,
+                            schema_index,
+                            errors,
+                            document,
+                        );
+                    }
+                }
+                cst::Selection::FragmentSpread(_) => {
+                    // Named fragments are checked against their own type condition
+                }
+                cst::Selection::InlineFragment(inline_fragment) => {
+                    if let Some(selection_set) = inline_fragment.selection_set() {
+                        let type_name_owned =
+                            inline_fragment.type_condition().and_then(|type_condition| {
+                                type_condition.named_type().and_then(|named_type| {
+                                    named_type.name().map(|name| name.text().to_string())
+                                })
+                            });
+
+                        let type_name_ref = type_name_owned.as_deref().unwrap_or(parent_type_name);
+
+                        self.check_stream_selection_set_cst(
+                            &selection_set,
+                            type_name_ref,
+                            schema_index,
+                            errors,
+                            document,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Check that each undeclared variable is used consistently with its other usages in the
+    /// same operation
+    ///
+    /// apollo-compiler checks usages of declared variables against their declaration, but
+    /// has no type to check an undeclared variable against. Types are compared by their named
+    /// type and list nesting, and each usage that conflicts with another is reported, with
+    /// related information pointing at the conflicting usages. Usages inside named fragments
+    /// are not followed.
+    #[must_use]
+    pub fn check_variable_usage_types(
+        &self,
+        document: &str,
+        schema_index: &SchemaIndex,
+        file_name: &str,
+    ) -> Vec<crate::Diagnostic> {
+        use crate::diagnostics::{Location, RelatedInfo};
+        use crate::Diagnostic;
+        use apollo_parser::{cst, Parser};
+
+        let mut errors = Vec::new();
+        let parser = Parser::new(document);
+        let tree = parser.parse();
+
+        if tree.errors().len() > 0 {
+            return errors;
+        }
+
+        let uri = if file_name.starts_with("file://") {
+            file_name.to_string()
+        } else {
+            format!("file://{file_name}")
+        };
+
+        for definition in tree.document().definitions() {
+            let cst::Definition::OperationDefinition(operation) = definition else {
+                continue;
+            };
+
+            let declared: std::collections::HashSet<String> = operation
+                .variable_definitions()
+                .iter()
+                .flat_map(cst::VariableDefinitions::variable_definitions)
+                .filter_map(|variable_definition| variable_definition.variable()?.name())
+                .map(|name| name.text().to_string())
+                .collect();
+
+            let usages = self.operation_variable_usages(&operation, schema_index, document);
+            for usage in usages
+                .iter()
+                .filter(|usage| !declared.contains(&usage.name))
+            {
+                let conflicting: Vec<&VariableUsage> = usages
+                    .iter()
+                    .filter(|other| {
+                        other.name == usage.name
+                            && type_shape(&other.expected_type) != type_shape(&usage.expected_type)
+                    })
+                    .collect();
+                if conflicting.is_empty() {
+                    continue;
+                }
+
+                let message = format!(
+                    "Variable '${}' is used where '{}' is expected, which conflicts with its other usages.",
+                    usage.name, usage.expected_type
+                );
+                let mut error = Diagnostic::error(usage.range, message)
+                    .with_code("variable-type-conflict")
+                    .with_source("graphql-validator");
+                for other in conflicting {
+                    error = error.with_related_info(RelatedInfo {
+                        message: format!("Also used where '{}' is expected", other.expected_type),
+                        location: Location {
+                            uri: uri.clone(),
+                            range: other.range,
+                        },
+                    });
+                }
+                errors.push(error);
+            }
+        }
+
+        errors
+    }
+
+    /// Check that operations declare the variables used by the fragments they spread
+    ///
+    /// `fragments` maps the names of fragments defined outside this document to the source
+    /// of their definitions; fragments defined in the document are found directly. Spreads
+    /// are followed through nested fragments, and each variable a spread needs that the
+    /// operation doesn't declare is reported at the spread.
+    #[must_use]
+    pub fn check_fragment_variables(
+        &self,
+        document: &str,
+        fragments: &std::collections::HashMap<String, String>,
+    ) -> Vec<crate::Diagnostic> {
+        use crate::{Diagnostic, Position, Range};
+        use apollo_parser::{
+            cst::{self, CstNode},
+            Parser,
+        };
+        use std::collections::{HashMap, HashSet};
+
+        let mut errors = Vec::new();
+        let tree = Parser::new(document).parse();
+
+        if tree.errors().len() > 0 {
+            return errors;
+        }
+
+        // Variables and spreads of every known fragment, preferring this document's own
+        let mut fragment_usages: HashMap<String, FragmentUsages> = HashMap::new();
+        for fragment_source in fragments.values() {
+            fragment_usages.extend(Self::fragment_usages(&Parser::new(fragment_source).parse()));
+        }
+        fragment_usages.extend(Self::fragment_usages(&tree));
+
+        for definition in tree.document().definitions() {
+            let cst::Definition::OperationDefinition(operation) = definition else {
+                continue;
+            };
+
+            let declared: HashSet<String> = operation
+                .variable_definitions()
+                .iter()
+                .flat_map(cst::VariableDefinitions::variable_definitions)
+                .filter_map(|definition| definition.variable()?.name())
+                .map(|name| name.text().to_string())
+                .collect();
+            let operation_name = operation.name().map_or_else(
+                || "the operation".to_string(),
+                |name| format!("operation '{}'", name.text()),
+            );
+
+            for spread in operation
+                .syntax()
+                .descendants()
+                .filter_map(cst::FragmentSpread::cast)
+            {
+                let Some(fragment_name) = spread.fragment_name() else {
+                    continue;
+                };
+                let Some(name) = fragment_name.name() else {
+                    continue;
+                };
+
+                let mut variables = Vec::new();
+                Self::collect_fragment_variables(
+                    &name.text(),
+                    &fragment_usages,
+                    &mut HashSet::new(),
+                    &mut variables,
+                );
+
+                let start: usize = spread.syntax().text_range().start().into();
+                let end: usize = fragment_name.syntax().text_range().end().into();
+                let (start_line, start_col) = self.offset_to_line_col(document, start);
+                let (end_line, end_col) = self.offset_to_line_col(document, end);
+                let range = Range {
+                    start: Position {
+                        line: start_line,
+                        character: start_col,
+                    },
+                    end: Position {
+                        line: end_line,
+                        character: end_col,
+                    },
+                };
+
+                for variable in variables.iter().filter(|v| !declared.contains(*v)) {
+                    let message = format!(
+                        "Variable '${variable}' is used by fragment '{}' but is not declared by {operation_name}.",
+                        name.text()
+                    );
+                    errors.push(
+                        Diagnostic::error(range, message)
+                            .with_code("undeclared-fragment-variable")
+                            .with_source("graphql-validator"),
+                    );
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// The variables and spreads used by each fragment defined in a document
+    fn fragment_usages(
+        tree: &apollo_parser::SyntaxTree,
+    ) -> std::collections::HashMap<String, FragmentUsages> {
+        use apollo_parser::cst::{self, CstNode};
+
+        tree.document()
+            .definitions()
+            .filter_map(|definition| {
+                let cst::Definition::FragmentDefinition(fragment) = definition else {
+                    return None;
+                };
+                let name = fragment.fragment_name()?.name()?.text().to_string();
+                let variables = fragment
+                    .syntax()
+                    .descendants()
+                    .filter_map(cst::Variable::cast)
+                    .filter_map(|variable| variable.name())
+                    .map(|name| name.text().to_string())
+                    .collect();
+                let spreads = fragment
+                    .syntax()
+                    .descendants()
+                    .filter_map(cst::FragmentSpread::cast)
+                    .filter_map(|spread| spread.fragment_name()?.name())
+                    .map(|name| name.text().to_string())
+                    .collect();
+                Some((name, FragmentUsages { variables, spreads }))
+            })
+            .collect()
+    }
+
+    /// Collect the variables a fragment uses, including through the fragments it spreads
+    ///
+    /// Variables are collected in the order they're first used; unknown fragments and
+    /// cycles are skipped.
+    fn collect_fragment_variables(
+        name: &str,
+        fragment_usages: &std::collections::HashMap<String, FragmentUsages>,
+        visited: &mut std::collections::HashSet<String>,
+        variables: &mut Vec<String>,
+    ) {
+        if !visited.insert(name.to_string()) {
+            return;
+        }
+        let Some(usages) = fragment_usages.get(name) else {
+            return;
+        };
+
+        for variable in &usages.variables {
+            if !variables.contains(variable) {
+                variables.push(variable.clone());
+            }
+        }
+        for spread in &usages.spreads {
+            Self::collect_fragment_variables(spread, fragment_usages, visited, variables);
+        }
+    }
+
+    /// Collect the variables used in an operation, starting from its root type
+    fn operation_variable_usages(
+        &self,
+        operation: &apollo_parser::cst::OperationDefinition,
+        schema_index: &SchemaIndex,
+        document: &str,
+    ) -> Vec<VariableUsage> {
+        let mut usages = Vec::new();
+        if let (Some(root_type_name), Some(selection_set)) = (
+            schema_index.root_type(OperationType::of(operation)),
+            operation.selection_set(),
+        ) {
+            self.collect_variable_usages_cst(
+                &selection_set,
+                root_type_name,
+                schema_index,
+                document,
+                &mut usages,
+            );
+        }
+        usages
+    }
+
+    /// Recursively collect the variables used as argument values in a selection set (CST)
+    fn collect_variable_usages_cst(
+        &self,
+        selection_set: &apollo_parser::cst::SelectionSet,
+        parent_type_name: &str,
+        schema_index: &SchemaIndex,
+        document: &str,
+        usages: &mut Vec<VariableUsage>,
+    ) {
+        use apollo_parser::cst;
+
+        for selection in selection_set.selections() {
+            match selection {
+                cst::Selection::Field(field) => {
+                    self.collect_directive_variable_usages(
+                        field.directives(),
+                        schema_index,
+                        document,
+                        usages,
+                    );
+
+                    let Some(field_name) = field.name() else {
+                        continue;
+                    };
+                    let Some(field_info) =
+                        schema_index
+                            .get_fields(parent_type_name)
+                            .and_then(|fields| {
+                                fields.into_iter().find(|f| f.name == field_name.text())
+                            })
+                    else {
+                        continue;
+                    };
+
+                    for argument in field.arguments().iter().flat_map(|args| args.arguments()) {
+                        let (Some(arg_name), Some(value)) = (argument.name(), argument.value())
+                        else {
+                            continue;
+                        };
+                        if let Some(arg_info) = field_info
+                            .arguments
+                            .iter()
+                            .find(|arg| arg.name == arg_name.text())
+                        {
+                            self.collect_value_variable_usages(
+                                &value,
+                                &arg_info.type_name,
+                                schema_index,
+                                document,
+                                usages,
+                            );
+                        }
+                    }
+
+                    if let Some(nested_selection_set) = field.selection_set() {
+                        let nested_type = field_info
+                            .type_name
+                            .trim_matches(|c| c == '[' || c == ']' || c == '!');
+
+                        self.collect_variable_usages_cst(
+                            &nested_selection_set,
+                            nested_type,
+                            schema_index,
+                            document,
+                            usages,
+                        );
+                    }
+                }
+                cst::Selection::FragmentSpread(fragment_spread) => {
+                    self.collect_directive_variable_usages(
+                        fragment_spread.directives(),
+                        schema_index,
+                        document,
+                        usages,
+                    );
+                }
+                cst::Selection::InlineFragment(inline_fragment) => {
+                    self.collect_directive_variable_usages(
+                        inline_fragment.directives(),
+                        schema_index,
+                        document,
+                        usages,
+                    );
+
+                    if let Some(selection_set) = inline_fragment.selection_set() {
+                        let type_name_owned =
+                            inline_fragment.type_condition().and_then(|type_condition| {
+                                type_condition.named_type().and_then(|named_type| {
+                                    named_type.name().map(|name| name.text().to_string())
+                                })
+                            });
+
+                        let type_name_ref = type_name_owned.as_deref().unwrap_or(parent_type_name);
+
+                        self.collect_variable_usages_cst(
+                            &selection_set,
+                            type_name_ref,
+                            schema_index,
+                            document,
+                            usages,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Collect the variables used as directive argument values
+    fn collect_directive_variable_usages(
+        &self,
+        directives: Option<apollo_parser::cst::Directives>,
+        schema_index: &SchemaIndex,
+        document: &str,
+        usages: &mut Vec<VariableUsage>,
+    ) {
+        for directive in directives.iter().flat_map(|d| d.directives()) {
+            let Some(directive_name) = directive.name() else {
+                continue;
+            };
+            let Some(definition) = schema_index
+                .schema()
+                .directive_definitions
+                .get(directive_name.text().as_str())
+            else {
+                continue;
+            };
+
+            for argument in directive
+                .arguments()
+                .iter()
+                .flat_map(|args| args.arguments())
+            {
+                let (Some(arg_name), Some(value)) = (argument.name(), argument.value()) else {
+                    continue;
+                };
+                if let Some(arg_definition) = definition
+                    .arguments
+                    .iter()
+                    .find(|arg| arg.name.as_str() == arg_name.text().as_str())
+                {
+                    self.collect_value_variable_usages(
+                        &value,
+                        &arg_definition.ty.to_string(),
+                        schema_index,
+                        document,
+                        usages,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Collect the variables in a value, given the type expected for the value
+    ///
+    /// List items and input object fields are followed, with the item or field type as
+    /// the expected type.
+    fn collect_value_variable_usages(
+        &self,
+        value: &apollo_parser::cst::Value,
+        expected_type: &str,
+        schema_index: &SchemaIndex,
+        document: &str,
+        usages: &mut Vec<VariableUsage>,
+    ) {
+        use crate::{Position, Range};
+        use apollo_parser::cst::{CstNode, Value};
+
+        match value {
+            Value::Variable(variable) => {
+                let Some(name) = variable.name() else {
+                    return;
+                };
+                let offset: usize = variable.syntax().text_range().start().into();
+                let (line, col) = self.offset_to_line_col(document, offset);
+                usages.push(VariableUsage {
+                    name: name.text().to_string(),
+                    expected_type: expected_type.to_string(),
+                    range: Range {
+                        start: Position {
+                            line,
+                            character: col,
+                        },
+                        end: Position {
+                            line,
+                            character: col + 1 + name.text().len(),
+                        },
+                    },
+                });
+            }
+            Value::ListValue(list) => {
+                let nullable_type = expected_type.strip_suffix('!').unwrap_or(expected_type);
+                let item_type = nullable_type
+                    .strip_prefix('[')
+                    .and_then(|inner| inner.strip_suffix(']'))
+                    .unwrap_or(nullable_type);
+                for item in list.values() {
+                    self.collect_value_variable_usages(
+                        &item,
+                        item_type,
+                        schema_index,
+                        document,
+                        usages,
+                    );
+                }
+            }
+            Value::ObjectValue(object) => {
+                let input_type = expected_type.trim_matches(|c| c == '[' || c == ']' || c == '!');
+                let Some(fields) = schema_index.get_fields(input_type) else {
+                    return;
+                };
+                for object_field in object.object_fields() {
+                    let (Some(field_name), Some(field_value)) =
+                        (object_field.name(), object_field.value())
+                    else {
+                        continue;
+                    };
+                    if let Some(field) = fields.iter().find(|f| f.name == field_name.text()) {
+                        self.collect_value_variable_usages(
+                            &field_value,
+                            &field.type_name,
+                            schema_index,
+                            document,
+                            usages,
+                        );
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Recursively check a selection set (CST) for direct field selections on unions
+    fn check_union_selection_set_cst(
+        &self,
+        selection_set: &apollo_parser::cst::SelectionSet,
+        parent_type_name: &str,
+        schema_index: &SchemaIndex,
+        errors: &mut Vec<crate::Diagnostic>,
+        document: &str,
+    ) {
+        use crate::{index::TypeKind, Diagnostic, Position, Range};
+        use apollo_parser::cst::{self, CstNode};
+
+        let is_union = schema_index
+            .get_type(parent_type_name)
+            .is_some_and(|type_info| type_info.kind == TypeKind::Union);
 
-                                    warnings.push(
-                                        Diagnostic::warning(range, message)
-                                            .with_code("deprecated-field")
-                                            .with_source("graphql-validator"),
-                                    );
-                                }
+        for selection in selection_set.selections() {
+            match selection {
+                cst::Selection::Field(field) => {
+                    let Some(field_name) = field.name() else {
+                        continue;
+                    };
+                    let field_name_str = field_name.text();
+
+                    if is_union {
+                        if field_name_str != "__typename" {
+                            let offset: usize = field_name.syntax().text_range().start().into();
+                            let (line, col) = self.offset_to_line_col(document, offset);
+
+                            let range = Range {
+                                start: Position {
+                                    line,
+                                    character: col,
+                                },
+                                end: Position {
+                                    line,
+                                    character: col + field_name_str.len(),
+                                },
+                            };
+
+                            let message = format!(
+                                "Cannot select field '{field_name_str}' directly on union type '{parent_type_name}'. Only '__typename' can be selected on a union; use an inline fragment such as '... on <MemberType> {{ {field_name_str} }}' instead."
+                            );
+
+                            errors.push(
+                                Diagnostic::error(range, message)
+                                    .with_code("union-field-selection")
+                                    .with_source("graphql-validator"),
+                            );
+                        }
+                        continue;
+                    }
 
-                                // Recursively check nested selections
-                                if let Some(nested_selection_set) = field.selection_set() {
-                                    // Extract the base type name from the field type
-                                    let nested_type = field_info
-                                        .type_name
-                                        .trim_matches(|c| c == '[' || c == ']' || c == '!');
+                    let Some(nested_selection_set) = field.selection_set() else {
+                        continue;
+                    };
 
-                                    Self::check_selection_set_cst(
-                                        &nested_selection_set,
-                                        nested_type,
-                                        schema_index,
-                                        warnings,
-                                        document,
-                                    );
-                                }
-                            }
-                        }
+                    if let Some(field_info) = schema_index
+                        .get_fields(parent_type_name)
+                        .and_then(|fields| fields.into_iter().find(|f| f.name == field_name_str))
+                    {
+                        let nested_type = field_info
+                            .type_name
+                            .trim_matches(|c| c == '[' || c == ']' || c == '!');
+
+                        self.check_union_selection_set_cst(
+                            &nested_selection_set,
+                            nested_type,
+                            schema_index,
+                            errors,
+                            document,
+                        );
                     }
                 }
                 cst::Selection::FragmentSpread(_) => {
-                    // TODO: Handle fragment spreads
+                    // Named fragments are checked against their own type condition
                 }
                 cst::Selection::InlineFragment(inline_fragment) => {
                     if let Some(selection_set) = inline_fragment.selection_set() {
-                        // For inline fragments, use the type condition if present
-                        // We need to extract it as a String to avoid lifetime issues
                         let type_name_owned =
                             inline_fragment.type_condition().and_then(|type_condition| {
                                 type_condition.named_type().and_then(|named_type| {
@@ -397,11 +1999,11 @@ impl Validator {
 
                         let type_name_ref = type_name_owned.as_deref().unwrap_or(parent_type_name);
 
-                        Self::check_selection_set_cst(
+                        self.check_union_selection_set_cst(
                             &selection_set,
                             type_name_ref,
                             schema_index,
-                            warnings,
+                            errors,
                             document,
                         );
                     }
@@ -411,7 +2013,7 @@ impl Validator {
     }
 
     /// Convert a byte offset to a line and column (0-indexed)
-    fn offset_to_line_col(document: &str, offset: usize) -> (usize, usize) {
+    fn offset_to_line_col(&self, document: &str, offset: usize) -> (usize, usize) {
         let mut line = 0;
         let mut col = 0;
         let mut current_offset = 0;
@@ -425,7 +2027,7 @@ impl Validator {
                 line += 1;
                 col = 0;
             } else {
-                col += 1;
+                col += self.position_encoding.char_len(ch);
             }
 
             current_offset += ch.len_utf8();
@@ -471,6 +2073,73 @@ impl Validator {
     }
 }
 
+/// A variable used as a value, with the type expected where it is used
+struct VariableUsage {
+    name: String,
+    expected_type: String,
+    range: crate::Range,
+}
+
+/// The variables a fragment uses directly and the fragments it spreads
+struct FragmentUsages {
+    variables: Vec<String>,
+    spreads: Vec<String>,
+}
+
+/// A type reference with its non-null markers removed, e.g. `[ID!]!` becomes `[ID]`
+fn type_shape(type_name: &str) -> String {
+    type_name.replace('!', "")
+}
+
+/// Whether a variable of `variable_type` may be used where `location_type` is expected
+///
+/// Follows the spec's `IsVariableUsageAllowed`: a nullable variable can fill a non-null
+/// location only when either side provides a default value.
+pub(crate) fn is_variable_usage_allowed(
+    variable_type: &str,
+    variable_has_default: bool,
+    location_type: &str,
+    location_has_default: bool,
+) -> bool {
+    match (
+        variable_type.strip_suffix('!'),
+        location_type.strip_suffix('!'),
+    ) {
+        (None, Some(nullable_location)) => {
+            (variable_has_default || location_has_default)
+                && are_types_compatible(variable_type, nullable_location)
+        }
+        _ => are_types_compatible(variable_type, location_type),
+    }
+}
+
+/// The item type of a nullable list type reference, e.g. `Int!` for `[Int!]`
+fn list_item(ty: &str) -> Option<&str> {
+    ty.strip_prefix('[')
+        .and_then(|inner| inner.strip_suffix(']'))
+}
+
+/// The spec's `AreTypesCompatible`, over type references written as strings
+fn are_types_compatible(variable_type: &str, location_type: &str) -> bool {
+    match (
+        variable_type.strip_suffix('!'),
+        location_type.strip_suffix('!'),
+    ) {
+        (Some(variable_inner), Some(location_inner)) => {
+            are_types_compatible(variable_inner, location_inner)
+        }
+        (None, Some(_)) => false,
+        (Some(variable_inner), None) => are_types_compatible(variable_inner, location_type),
+        (None, None) => match (list_item(variable_type), list_item(location_type)) {
+            (Some(variable_item), Some(location_item)) => {
+                are_types_compatible(variable_item, location_item)
+            }
+            (None, None) => variable_type == location_type,
+            _ => false,
+        },
+    }
+}
+
 impl Default for Validator {
     fn default() -> Self {
         Self::new()
@@ -738,6 +2407,197 @@ mod tests {
         assert_eq!(warnings[0].severity, crate::Severity::Warning);
     }
 
+    fn create_custom_scalar_schema() -> crate::SchemaIndex {
+        crate::SchemaIndex::from_schema(
+            r"
+            scalar JSON
+            scalar DateTime
+
+            type Query {
+                search(filter: JSON, after: DateTime, dates: [DateTime]): [String]
+            }
+            ",
+        )
+    }
+
+    #[test]
+    fn test_custom_scalar_accepts_any_literal() {
+        let validator = Validator::new();
+        let schema = create_custom_scalar_schema();
+
+        let document = r#"
+            query Search {
+                search(filter: 42, after: "2024-01-01")
+            }
+        "#;
+
+        assert!(
+            validator.validate_document(document, &schema).is_ok(),
+            "An integer should be accepted for a custom JSON scalar"
+        );
+        assert!(validator
+            .check_custom_scalar_arguments(
+                document,
+                &schema,
+                &std::collections::HashMap::new(),
+                "test.graphql"
+            )
+            .is_empty());
+    }
+
+    #[test]
+    fn test_mapped_custom_scalar_checks_literals() {
+        let validator = Validator::new();
+        let schema = create_custom_scalar_schema();
+        let mappings =
+            std::collections::HashMap::from([("DateTime".to_string(), "String".to_string())]);
+
+        let document = r#"
+            query Search($after: DateTime) {
+                search(filter: 42, after: $after, dates: ["2024-01-01", 20240101])
+            }
+        "#;
+
+        let errors =
+            validator.check_custom_scalar_arguments(document, &schema, &mappings, "test.graphql");
+
+        assert_eq!(
+            errors.len(),
+            1,
+            "Only the integer list item should be flagged"
+        );
+        assert_eq!(errors[0].code.as_deref(), Some("invalid-scalar-value"));
+        assert!(errors[0].message.contains("'dates'"));
+        assert!(errors[0].message.contains("20240101"));
+        assert_eq!(errors[0].range.start.line, 2);
+        assert_eq!(errors[0].range.start.character, 72);
+    }
+
+    #[test]
+    fn test_stream_on_non_list_field() {
+        let validator = Validator::new();
+        let schema_sdl = r"
+            directive @stream(label: String, initialCount: Int = 0, if: Boolean! = true) on FIELD
+
+            type Query {
+                user: User
+                users: [User!]!
+            }
+
+            type User {
+                id: ID!
+                name: String!
+                friends: [User]
+            }
+        ";
+        let schema = SchemaIndex::from_schema(schema_sdl);
+
+        let document = r"
+            query Users {
+                users @stream(initialCount: 10) {
+                    name @stream
+                    friends @stream {
+                        id
+                    }
+                }
+            }
+        ";
+
+        let errors = validator.check_stream_on_lists(document, &schema, "test.graphql");
+
+        assert_eq!(errors.len(), 1, "got: {errors:?}");
+        assert_eq!(errors[0].code.as_deref(), Some("stream-on-non-list"));
+        assert_eq!(
+            errors[0].message,
+            "@stream can only be used on list fields, but 'User.name' returns 'String!'."
+        );
+        assert_eq!(errors[0].range.start.line, 3);
+        assert_eq!(errors[0].range.start.character, 25);
+
+        // Without incremental delivery in the schema, there's nothing to check
+        let schema =
+            SchemaIndex::from_schema(&schema_sdl.replace("directive @stream", "directive @other"));
+        assert!(validator
+            .check_stream_on_lists(document, &schema, "test.graphql")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_reserved_fragment_name() {
+        let validator = Validator::new();
+
+        let document = r"
+            fragment __Secret on User {
+                id
+            }
+
+            query GetUser {
+                user {
+                    ...__Secret
+                }
+            }
+        ";
+
+        let errors = validator.check_reserved_names(document, "test.graphql");
+
+        assert_eq!(errors.len(), 1, "Only the fragment should be flagged");
+        assert_eq!(errors[0].severity, crate::Severity::Error);
+        assert_eq!(errors[0].code.as_deref(), Some("reserved-name"));
+        assert!(errors[0].message.contains("'__Secret'"));
+        assert_eq!(errors[0].range.start.line, 1);
+        assert_eq!(errors[0].range.start.character, 21);
+        assert_eq!(errors[0].range.end.character, 29);
+    }
+
+    #[test]
+    fn test_union_field_selected_without_inline_fragment() {
+        let schema = crate::SchemaIndex::from_schema(
+            r"
+            type Query {
+                search: [SearchResult!]!
+            }
+
+            union SearchResult = User | Post
+
+            type User {
+                id: ID!
+                name: String!
+            }
+
+            type Post {
+                id: ID!
+                title: String!
+            }
+            ",
+        );
+
+        let validator = Validator::new();
+
+        let document = r"
+            query Search {
+                search {
+                    __typename
+                    name
+                    ... on Post {
+                        title
+                    }
+                }
+            }
+        ";
+
+        let errors = validator.check_union_field_selections(document, &schema, "test.graphql");
+
+        assert_eq!(errors.len(), 1, "Only 'name' should be flagged");
+        assert_eq!(errors[0].severity, crate::Severity::Error);
+        assert_eq!(errors[0].code.as_deref(), Some("union-field-selection"));
+        assert!(errors[0].message.contains("'name'"));
+        assert!(errors[0].message.contains("SearchResult"));
+        assert!(errors[0].message.contains("inline fragment"));
+        assert_eq!(errors[0].range.start.line, 4);
+        assert_eq!(errors[0].range.start.character, 20);
+        assert_eq!(errors[0].range.end.character, 24);
+    }
+
     #[test]
     fn test_multiple_deprecated_fields() {
         let schema_with_deprecated = crate::SchemaIndex::from_schema(
@@ -865,6 +2725,80 @@ mod tests {
         assert_eq!(warnings.len(), 0, "Should have no warnings");
     }
 
+    #[test]
+    fn test_deprecated_field_in_spread_fragment() {
+        let schema_with_deprecated = crate::SchemaIndex::from_schema(
+            r#"
+            type Query {
+                user(id: ID!): User
+            }
+
+            type User {
+                id: ID!
+                email: String @deprecated(reason: "Use 'emailAddress' instead")
+                emailAddress: String
+            }
+            "#,
+        );
+
+        let operation = "query GetUser {\n  user(id: \"1\") { ...UserDetails }\n}\n";
+        let fragments = "fragment UserDetails on User {\n  id\n  ...UserContact\n}\n\nfragment UserContact on User {\n  email\n}\n";
+
+        let mut document_index = crate::DocumentIndex::new();
+        document_index.cache_ast(
+            "/queries.graphql".to_string(),
+            std::sync::Arc::new(apollo_parser::Parser::new(operation).parse()),
+        );
+        document_index.cache_ast(
+            "/fragments.graphql".to_string(),
+            std::sync::Arc::new(apollo_parser::Parser::new(fragments).parse()),
+        );
+        document_index.add_operation(
+            Some("GetUser".to_string()),
+            crate::OperationInfo {
+                name: Some("GetUser".to_string()),
+                operation_type: crate::OperationType::Query,
+                file_path: "/queries.graphql".to_string(),
+                line: 0,
+                column: 6,
+            },
+        );
+
+        let validator = Validator::new();
+
+        // The operation doesn't select the deprecated field itself
+        assert!(validator
+            .check_deprecated_fields_custom(operation, &schema_with_deprecated, "/queries.graphql")
+            .is_empty());
+
+        let warnings = validator.check_deprecated_fields_in_fragments(
+            fragments,
+            &schema_with_deprecated,
+            &document_index,
+            "/fragments.graphql",
+        );
+
+        assert_eq!(warnings.len(), 1);
+        let warning = &warnings[0];
+        assert_eq!(
+            warning.message,
+            "Field 'email' is deprecated. Use 'emailAddress' instead"
+        );
+        assert_eq!(warning.range.start.line, 6);
+        assert_eq!(warning.range.start.character, 2);
+        assert_eq!(warning.related_info.len(), 1);
+        assert_eq!(
+            warning.related_info[0].message,
+            "Fragment 'UserContact' is used by operation 'GetUser'"
+        );
+        assert_eq!(
+            warning.related_info[0].location.uri,
+            "file:///queries.graphql"
+        );
+        assert_eq!(warning.related_info[0].location.range.start.character, 6);
+        assert_eq!(warning.related_info[0].location.range.end.character, 13);
+    }
+
     #[test]
     fn test_fragment_only_document_is_valid() {
         let validator = Validator::new();
@@ -1111,4 +3045,187 @@ mod tests {
         // Anonymous operations don't have names, so no duplicates should be detected
         assert_eq!(errors.len(), 0, "Anonymous operations should not conflict");
     }
+
+    #[test]
+    fn test_variable_used_at_incompatible_types() {
+        let validator = Validator::new();
+        let schema = SchemaIndex::from_schema(
+            r"
+            type Query {
+                user(id: ID!): User
+                search(term: String!): [User!]!
+            }
+
+            type User {
+                id: ID!
+            }
+        ",
+        );
+
+        let document = r"query Lookup {
+  user(id: $id) {
+    id
+  }
+  search(term: $id) {
+    id
+  }
+}";
+
+        let errors = validator.check_variable_usage_types(document, &schema, "query.graphql");
+        assert_eq!(errors.len(), 2);
+
+        let error = &errors[1];
+        assert_eq!(error.code.as_deref(), Some("variable-type-conflict"));
+        assert_eq!(
+            error.message,
+            "Variable '$id' is used where 'String!' is expected, which conflicts with its other usages."
+        );
+        assert_eq!(error.range.start.line, 4);
+        assert_eq!(error.range.start.character, 15);
+        assert_eq!(error.range.end.character, 18);
+
+        // Points at the usage it conflicts with
+        assert_eq!(error.related_info.len(), 1);
+        assert_eq!(error.related_info[0].location.range.start.line, 1);
+        assert_eq!(error.related_info[0].location.uri, "file://query.graphql");
+
+        // Usages of declared variables are checked by apollo-compiler
+        let document = r"query Lookup($id: ID!) {
+  user(id: $id) { id }
+  search(term: $id) { id }
+}";
+        assert!(validator
+            .check_variable_usage_types(document, &schema, "query.graphql")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_variable_usage_nullability_is_not_a_conflict() {
+        let validator = Validator::new();
+        let schema = SchemaIndex::from_schema(
+            r"
+            type Query {
+                user(id: ID!): User
+                users(ids: [ID!]): [User!]!
+                node(id: ID): User
+            }
+
+            type User {
+                id: ID!
+            }
+        ",
+        );
+
+        let document = r"query Lookup {
+  user(id: $id) { id }
+  node(id: $id) { id }
+  users(ids: [$id]) { id }
+}";
+
+        let errors = validator.check_variable_usage_types(document, &schema, "query.graphql");
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_subscription_root_field_with_include() {
+        let validator = Validator::new();
+
+        let document = r"subscription OnMessage($withMessages: Boolean!) {
+  messageAdded @include(if: $withMessages) {
+    id
+    author @skip(if: false) { id }
+  }
+}";
+        let errors = validator.check_subscription_root_directives(document);
+
+        // Only the root field is flagged, not the nested `author`
+        assert_eq!(errors.len(), 1);
+        let error = &errors[0];
+        assert_eq!(error.code.as_deref(), Some("subscription-root-directive"));
+        assert!(error.message.contains("@include"));
+        assert_eq!(error.range.start.line, 1);
+        assert_eq!(error.range.start.character, 15);
+        assert_eq!(error.range.end.character, 23);
+
+        // Queries may use them at the root
+        let document = r"query Q($skip: Boolean!) { user @skip(if: $skip) { id } }";
+        assert!(validator
+            .check_subscription_root_directives(document)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_fragment_variable_not_declared_by_operation() {
+        let validator = Validator::new();
+
+        // `UserFields` is defined in another file and needs `$limit` through `FriendFields`
+        let fragments = std::collections::HashMap::from([(
+            "UserFields".to_string(),
+            "fragment UserFields on User {\n  id\n  ...FriendFields\n}\n\nfragment FriendFields on User {\n  friends(first: $limit) { id }\n}".to_string(),
+        )]);
+
+        let document = r"query GetUser($id: ID!) {
+  user(id: $id) {
+    ...UserFields
+  }
+}";
+        let errors = validator.check_fragment_variables(document, &fragments);
+
+        assert_eq!(errors.len(), 1);
+        let error = &errors[0];
+        assert_eq!(error.code.as_deref(), Some("undeclared-fragment-variable"));
+        assert_eq!(
+            error.message,
+            "Variable '$limit' is used by fragment 'UserFields' but is not declared by operation 'GetUser'."
+        );
+        assert_eq!(error.range.start.line, 2);
+        assert_eq!(error.range.start.character, 4);
+        assert_eq!(error.range.end.character, 17);
+
+        // Declaring the variable satisfies the fragment
+        let document = r"query GetUser($id: ID!, $limit: Int) {
+  user(id: $id) {
+    ...UserFields
+  }
+}";
+        assert!(validator
+            .check_fragment_variables(document, &fragments)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_argument_definitions() {
+        let validator = Validator::new();
+        let schema = SchemaIndex::from_schema(
+            "type Query {\n  user(id: ID!, id: String): User\n}\n\ntype User {\n  id: ID!\n}\n\ndirective @auth(role: String, role: String) on FIELD_DEFINITION\n",
+        );
+
+        let errors = validator.check_duplicate_argument_definitions(&schema);
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].file_path.as_deref(), Some("schema.graphql"));
+        let field_error = &errors[0].diagnostic;
+        assert_eq!(
+            field_error.message,
+            "Argument 'id' is defined more than once on field 'Query.user'."
+        );
+        assert_eq!(
+            field_error.code.as_deref(),
+            Some("unique-argument-definitions")
+        );
+        assert_eq!(field_error.range.start.line, 1);
+        assert_eq!(field_error.range.start.character, 16);
+        assert_eq!(
+            field_error.related_info[0].location.range.start.character,
+            7
+        );
+
+        let directive_error = &errors[1].diagnostic;
+        assert_eq!(
+            directive_error.message,
+            "Argument 'role' is defined more than once on directive '@auth'."
+        );
+        assert_eq!(directive_error.range.start.line, 8);
+        assert_eq!(directive_error.range.start.character, 30);
+    }
 }
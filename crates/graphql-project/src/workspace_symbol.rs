@@ -0,0 +1,380 @@
+use crate::{DocumentIndex, OperationType, Position, Range, SchemaIndex, TypeKind};
+
+/// Kind of element a workspace symbol represents
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorkspaceSymbolKind {
+    Operation(OperationType),
+    Fragment,
+    Type(TypeKind),
+    /// Field of an object or interface type
+    Field {
+        type_name: String,
+    },
+    Directive,
+}
+
+/// Named definition found by a workspace symbol search
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceSymbol {
+    pub name: String,
+    pub kind: WorkspaceSymbolKind,
+    /// File path where the symbol is defined
+    pub file_path: String,
+    /// Range of the symbol's name
+    pub range: Range,
+}
+
+/// Workspace symbol provider
+///
+/// Searches the operations and fragments in the `DocumentIndex` and the types in the
+/// `SchemaIndex` by name. A query can be narrowed to the fields of one type with a
+/// `User.` prefix, or to directives with an `@` prefix.
+pub struct WorkspaceSymbolProvider;
+
+impl WorkspaceSymbolProvider {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Find every operation, fragment, and schema type whose name matches `query`
+    ///
+    /// Matching is case-insensitive and fuzzy: the query's characters must appear in the
+    /// name in order. Results are ranked exact matches first, then prefix matches, then
+    /// substring matches, then other fuzzy matches, and by name within each rank. An empty
+    /// query matches everything.
+    ///
+    /// A query of `TypeName.rest` only searches the fields of `TypeName` for `rest`, and a
+    /// query of `@rest` only searches directives.
+    #[must_use]
+    pub fn workspace_symbols(
+        &self,
+        query: &str,
+        document_index: &DocumentIndex,
+        schema_index: &SchemaIndex,
+    ) -> Vec<WorkspaceSymbol> {
+        if let Some(directive_query) = query.strip_prefix('@') {
+            return sorted(directive_symbols(
+                &directive_query.to_lowercase(),
+                schema_index,
+            ));
+        }
+        if let Some((type_name, field_query)) = query.split_once('.') {
+            return sorted(field_symbols(
+                type_name,
+                &field_query.to_lowercase(),
+                schema_index,
+            ));
+        }
+
+        let query = query.to_lowercase();
+        let mut symbols: Vec<(u8, WorkspaceSymbol)> = Vec::new();
+
+        for (name, operations) in &document_index.operations {
+            let Some(rank) = match_rank(&query, name) else {
+                continue;
+            };
+            for op in operations {
+                symbols.push((
+                    rank,
+                    WorkspaceSymbol {
+                        name: name.clone(),
+                        kind: WorkspaceSymbolKind::Operation(op.operation_type),
+                        file_path: op.file_path.clone(),
+                        range: name_range(op.line, op.column, name),
+                    },
+                ));
+            }
+        }
+
+        for (name, fragments) in &document_index.fragments {
+            let Some(rank) = match_rank(&query, name) else {
+                continue;
+            };
+            for frag in fragments {
+                symbols.push((
+                    rank,
+                    WorkspaceSymbol {
+                        name: name.clone(),
+                        kind: WorkspaceSymbolKind::Fragment,
+                        file_path: frag.file_path.clone(),
+                        range: name_range(frag.line, frag.column, name),
+                    },
+                ));
+            }
+        }
+
+        for type_info in schema_index.all_types() {
+            let Some(rank) = match_rank(&query, &type_info.name) else {
+                continue;
+            };
+            // Built-in types have no definition in the project's schema files
+            let Some(location) = schema_index.find_type_definition(&type_info.name) else {
+                continue;
+            };
+            symbols.push((
+                rank,
+                WorkspaceSymbol {
+                    range: name_range(location.line, location.column, &type_info.name),
+                    name: type_info.name,
+                    kind: WorkspaceSymbolKind::Type(type_info.kind),
+                    file_path: location.file_path,
+                },
+            ));
+        }
+
+        sorted(symbols)
+    }
+}
+
+impl Default for WorkspaceSymbolProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Fields of `type_name` whose name matches a lowercased `query`
+fn field_symbols(
+    type_name: &str,
+    query: &str,
+    schema_index: &SchemaIndex,
+) -> Vec<(u8, WorkspaceSymbol)> {
+    let Some(fields) = schema_index.get_fields(type_name) else {
+        return Vec::new();
+    };
+
+    fields
+        .into_iter()
+        .filter_map(|field| {
+            let rank = match_rank(query, &field.name)?;
+            // Input object fields have no definition location to jump to
+            let location = schema_index.find_field_definition(type_name, &field.name)?;
+            Some((
+                rank,
+                WorkspaceSymbol {
+                    range: name_range(location.line, location.column, &field.name),
+                    name: field.name,
+                    kind: WorkspaceSymbolKind::Field {
+                        type_name: type_name.to_string(),
+                    },
+                    file_path: location.file_path,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Directives whose name matches a lowercased `query`
+fn directive_symbols(query: &str, schema_index: &SchemaIndex) -> Vec<(u8, WorkspaceSymbol)> {
+    schema_index
+        .all_directives()
+        .into_iter()
+        .filter_map(|directive| {
+            let rank = match_rank(query, &directive.name)?;
+            // Built-in directives have no definition in the project's schema files
+            let location = schema_index.find_directive_definition(&directive.name)?;
+            Some((
+                rank,
+                WorkspaceSymbol {
+                    range: name_range(location.line, location.column, &directive.name),
+                    name: directive.name,
+                    kind: WorkspaceSymbolKind::Directive,
+                    file_path: location.file_path,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Sort ranked symbols by rank, then name and location, dropping the ranks
+fn sorted(mut symbols: Vec<(u8, WorkspaceSymbol)>) -> Vec<WorkspaceSymbol> {
+    symbols.sort_by(|(rank_a, a), (rank_b, b)| {
+        (rank_a, &a.name, &a.file_path, a.range.start.line).cmp(&(
+            rank_b,
+            &b.name,
+            &b.file_path,
+            b.range.start.line,
+        ))
+    });
+
+    symbols.into_iter().map(|(_, symbol)| symbol).collect()
+}
+
+/// Rank how well `name` matches a lowercased `query`, or `None` if it doesn't match
+fn match_rank(query: &str, name: &str) -> Option<u8> {
+    let name = name.to_lowercase();
+
+    if name == query {
+        Some(0)
+    } else if name.starts_with(query) {
+        Some(1)
+    } else if name.contains(query) {
+        Some(2)
+    } else {
+        let mut name_chars = name.chars();
+        query
+            .chars()
+            .all(|query_char| name_chars.any(|name_char| name_char == query_char))
+            .then_some(3)
+    }
+}
+
+const fn name_range(line: usize, column: usize, name: &str) -> Range {
+    Range {
+        start: Position {
+            line,
+            character: column,
+        },
+        end: Position {
+            line,
+            character: column + name.len(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FragmentInfo, OperationInfo};
+
+    fn create_document_index() -> DocumentIndex {
+        let mut index = DocumentIndex::new();
+        index.add_operation(
+            Some("GetUser".to_string()),
+            OperationInfo {
+                name: Some("GetUser".to_string()),
+                operation_type: OperationType::Query,
+                file_path: "/path/to/queries.graphql".to_string(),
+                line: 0,
+                column: 6,
+            },
+        );
+        index.add_fragment(
+            "UserFields".to_string(),
+            FragmentInfo {
+                name: "UserFields".to_string(),
+                type_condition: "User".to_string(),
+                file_path: "/path/to/fragments.graphql".to_string(),
+                line: 2,
+                column: 9,
+            },
+        );
+        index.add_fragment(
+            "PostFields".to_string(),
+            FragmentInfo {
+                name: "PostFields".to_string(),
+                type_condition: "Post".to_string(),
+                file_path: "/path/to/fragments.graphql".to_string(),
+                line: 8,
+                column: 9,
+            },
+        );
+        index
+    }
+
+    #[test]
+    fn test_filters_case_insensitively() {
+        let document_index = create_document_index();
+        let schema_index = SchemaIndex::new();
+
+        let symbols = WorkspaceSymbolProvider::new().workspace_symbols(
+            "userf",
+            &document_index,
+            &schema_index,
+        );
+
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(symbols[0].name, "UserFields");
+        assert_eq!(symbols[0].kind, WorkspaceSymbolKind::Fragment);
+        assert_eq!(symbols[0].file_path, "/path/to/fragments.graphql");
+        assert_eq!(
+            symbols[0].range,
+            Range {
+                start: Position {
+                    line: 2,
+                    character: 9
+                },
+                end: Position {
+                    line: 2,
+                    character: 19
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn test_ranks_exact_and_prefix_matches_first() {
+        let document_index = create_document_index();
+        let schema_index = SchemaIndex::from_schema(
+            "type Query { user: User }\ntype User { id: ID! }\ntype Post { id: ID! }",
+        );
+
+        let symbols = WorkspaceSymbolProvider::new().workspace_symbols(
+            "user",
+            &document_index,
+            &schema_index,
+        );
+
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["User", "UserFields", "GetUser"]);
+        assert_eq!(symbols[0].kind, WorkspaceSymbolKind::Type(TypeKind::Object));
+    }
+
+    #[test]
+    fn test_type_prefix_filters_to_fields() {
+        let document_index = create_document_index();
+        let schema_index = SchemaIndex::from_schema(
+            "type Query { user: User }\ntype User {\n  id: ID!\n  name: String\n}\ntype Post { id: ID! name: String }",
+        );
+
+        let symbols = WorkspaceSymbolProvider::new().workspace_symbols(
+            "User.",
+            &document_index,
+            &schema_index,
+        );
+
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["id", "name"]);
+        assert!(symbols.iter().all(|s| s.kind
+            == WorkspaceSymbolKind::Field {
+                type_name: "User".to_string()
+            }));
+        assert_eq!(
+            symbols[1].range,
+            Range {
+                start: Position {
+                    line: 3,
+                    character: 2
+                },
+                end: Position {
+                    line: 3,
+                    character: 6
+                },
+            }
+        );
+
+        let symbols = WorkspaceSymbolProvider::new().workspace_symbols(
+            "User.na",
+            &document_index,
+            &schema_index,
+        );
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["name"]);
+    }
+
+    #[test]
+    fn test_at_prefix_filters_to_directives() {
+        let document_index = create_document_index();
+        let schema_index = SchemaIndex::from_schema(
+            "directive @auth(role: String) on FIELD_DEFINITION\ndirective @cacheControl on OBJECT\ntype Query { auth: String }",
+        );
+
+        let symbols =
+            WorkspaceSymbolProvider::new().workspace_symbols("@", &document_index, &schema_index);
+
+        // Built-in directives like @deprecated aren't defined in the project's files
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["auth", "cacheControl"]);
+        assert_eq!(symbols[0].kind, WorkspaceSymbolKind::Directive);
+    }
+}
@@ -1,7 +1,7 @@
 #![cfg(not(target_os = "windows"))]
 
 use graphql_config::{DocumentsConfig, ProjectConfig, SchemaConfig};
-use graphql_project::GraphQLProject;
+use graphql_project::{GraphQLProject, SchemaCache};
 use std::fs;
 use std::path::Path;
 use tempfile::TempDir;
@@ -68,6 +68,25 @@ async fn create_test_project() -> (TempDir, GraphQLProject) {
     (temp_dir, project)
 }
 
+/// Helper to create a project with only the given schema and no documents
+async fn create_project_with_schema(schema: &str) -> (TempDir, GraphQLProject) {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let base_path = temp_dir.path();
+    fs::write(base_path.join("schema.graphql"), schema).expect("Failed to write schema");
+
+    let config = ProjectConfig {
+        schema: SchemaConfig::Path(base_path.join("schema.graphql").display().to_string()),
+        documents: None,
+        include: None,
+        exclude: None,
+        extensions: None,
+    };
+    let project = GraphQLProject::new(config).with_base_dir(base_path.to_path_buf());
+    project.load_schema().await.expect("Failed to load schema");
+
+    (temp_dir, project)
+}
+
 #[tokio::test]
 async fn test_validate_document_source_with_operation_no_fragments() {
     let (_temp_dir, project) = create_test_project().await;
@@ -176,6 +195,34 @@ fragment InvalidPostFields on Post {
     );
 }
 
+#[tokio::test]
+async fn test_validate_document_source_standalone_fragment_unknown_type_condition() {
+    let (_temp_dir, project) = create_test_project().await;
+
+    let fragment = r"
+fragment ProductFields on Product {
+  id
+  sku
+  price {
+    amount
+  }
+}
+";
+
+    let diagnostics = project.validate_document_source(fragment, "fragment.graphql");
+
+    // The missing type is reported once, not once per selected field
+    let errors: Vec<_> = diagnostics
+        .iter()
+        .filter(|d| d.severity == graphql_project::Severity::Error)
+        .collect();
+    assert_eq!(errors.len(), 1, "Expected a single error, got: {errors:?}");
+    assert_eq!(errors[0].code.as_deref(), Some("unknown-type-condition"));
+    assert!(errors[0].message.contains("Product"));
+    assert_eq!(errors[0].range.start.line, 1);
+    assert_eq!(errors[0].range.start.character, 26);
+}
+
 #[tokio::test]
 async fn test_validate_document_source_operation_without_fragment_spread() {
     let (_temp_dir, project) = create_test_project().await;
@@ -357,3 +404,548 @@ const GET_USERS = gql`
         "Error should preserve line offset from TypeScript file, got: {diagnostics:?}"
     );
 }
+
+#[tokio::test]
+async fn test_fragments_resolve_within_owning_project() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let base_path = temp_dir.path();
+
+    let schema_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("schema.graphql");
+
+    // Both projects define `UserFields`, on different types
+    for (dir, fragment) in [
+        ("web", "fragment UserFields on User {\n  name\n}\n"),
+        ("admin", "fragment UserFields on Post {\n  title\n}\n"),
+    ] {
+        fs::create_dir(base_path.join(dir)).expect("Failed to create project dir");
+        fs::write(base_path.join(dir).join("fragments.graphql"), fragment)
+            .expect("Failed to write fragment");
+    }
+
+    let config_yaml = format!(
+        r"
+projects:
+  web:
+    schema: {schema}
+    documents: web/**/*.graphql
+  admin:
+    schema: {schema}
+    documents: admin/**/*.graphql
+",
+        schema = schema_path.display()
+    );
+    let config: graphql_config::GraphQLConfig =
+        serde_yaml::from_str(&config_yaml).expect("Failed to parse config");
+    let projects = GraphQLProject::from_config_with_base(&config, base_path)
+        .expect("Failed to create projects");
+    for (_, project) in &projects {
+        project.load_schema().await.expect("Failed to load schema");
+        project.load_documents().expect("Failed to load documents");
+    }
+    let project = |name: &str| {
+        projects
+            .iter()
+            .find(|(project_name, _)| project_name == name)
+            .map(|(_, project)| project)
+            .unwrap()
+    };
+
+    let errors = |project: &GraphQLProject, source: &str, file_name: &str| {
+        project
+            .validate_document_source(source, file_name)
+            .into_iter()
+            .filter(|diagnostic| diagnostic.severity == graphql_project::Severity::Error)
+            .map(|diagnostic| diagnostic.message)
+            .collect::<Vec<_>>()
+    };
+
+    let web_query = "query WebUser {\n  user(id: \"1\") {\n    ...UserFields\n  }\n}\n";
+    let web_file = base_path.join("web").join("query.graphql");
+    assert!(project("web").is_document_file(&web_file));
+    assert!(!project("admin").is_document_file(&web_file));
+    let web_errors = errors(project("web"), web_query, &web_file.display().to_string());
+    assert!(web_errors.is_empty(), "Unexpected errors: {web_errors:?}");
+
+    let admin_query = "query AdminPost {\n  post(id: \"1\") {\n    ...UserFields\n  }\n}\n";
+    let admin_file = base_path.join("admin").join("query.graphql");
+    assert!(project("admin").is_document_file(&admin_file));
+    let admin_errors = errors(
+        project("admin"),
+        admin_query,
+        &admin_file.display().to_string(),
+    );
+    assert!(
+        admin_errors.is_empty(),
+        "Unexpected errors: {admin_errors:?}"
+    );
+
+    // Spreading the web project's fragment in the admin project's context is invalid
+    assert!(!errors(
+        project("admin"),
+        web_query,
+        &admin_file.display().to_string()
+    )
+    .is_empty());
+}
+
+#[tokio::test]
+async fn test_validate_schema_source_reports_errors_in_file() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let base_path = temp_dir.path();
+
+    fs::write(
+        base_path.join("schema.graphql"),
+        "type Query {\n  user: User\n}\n\ntype User {\n  id: ID!\n}\n",
+    )
+    .expect("Failed to write schema");
+    fs::write(
+        base_path.join("post.graphql"),
+        "type Post {\n  author: Author\n  title: String @unknown\n}\n",
+    )
+    .expect("Failed to write schema");
+
+    let config = ProjectConfig {
+        schema: SchemaConfig::Path(base_path.join("*.graphql").display().to_string()),
+        documents: None,
+        include: None,
+        exclude: None,
+        extensions: None,
+    };
+    let project = GraphQLProject::new(config).with_base_dir(base_path.to_path_buf());
+    project.load_schema().await.expect("Failed to load schema");
+
+    let post_file = base_path.join("post.graphql").display().to_string();
+    let diagnostics = project.validate_schema_source(&post_file);
+    assert_eq!(diagnostics.len(), 2, "got: {diagnostics:?}");
+    assert!(diagnostics.iter().any(
+        |diagnostic| diagnostic.message.contains("Author") && diagnostic.range.start.line == 1
+    ));
+    assert!(diagnostics.iter().any(
+        |diagnostic| diagnostic.message.contains("unknown") && diagnostic.range.start.line == 2
+    ));
+
+    let schema_file = base_path.join("schema.graphql").display().to_string();
+    assert!(project.validate_schema_source(&schema_file).is_empty());
+}
+
+#[tokio::test]
+async fn test_update_schema_index_keeps_other_loaded_files() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let base_path = temp_dir.path();
+
+    fs::write(
+        base_path.join("query.graphql"),
+        "type Query {\n  user: User\n}\n",
+    )
+    .expect("Failed to write schema");
+    fs::write(
+        base_path.join("user.graphql"),
+        "type User {\n  id: ID!\n}\n",
+    )
+    .expect("Failed to write schema");
+
+    let config = ProjectConfig {
+        schema: SchemaConfig::Path(base_path.join("*.graphql").display().to_string()),
+        documents: None,
+        include: None,
+        exclude: None,
+        extensions: None,
+    };
+    let project = GraphQLProject::new(config).with_base_dir(base_path.to_path_buf());
+    project.load_schema().await.expect("Failed to load schema");
+
+    // The edit is applied to the files already loaded, so `User` still resolves even
+    // though its file is gone from disk
+    fs::remove_file(base_path.join("user.graphql")).expect("Failed to remove schema");
+    let query_file = base_path.join("query.graphql").display().to_string();
+    project
+        .update_schema_index(
+            &query_file,
+            "type Query {\n  user: User\n  users: [User!]!\n}\n",
+        )
+        .await
+        .expect("Failed to update schema");
+
+    let schema_index = project.get_schema_index();
+    let fields = schema_index
+        .get_fields("Query")
+        .expect("Query should exist");
+    assert!(fields.iter().any(|field| field.name == "users"));
+    assert!(schema_index.get_type("User").is_some());
+    assert!(project.validate_schema_source(&query_file).is_empty());
+}
+
+#[tokio::test]
+async fn test_fragments_resolve_through_import_comments() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let base_path = temp_dir.path();
+
+    let schema_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("schema.graphql");
+    fs::copy(schema_path, base_path.join("schema.graphql")).expect("Failed to copy schema");
+
+    // The fragments are outside the documents glob, and import each other
+    let fragments_dir = base_path.join("fragments");
+    fs::create_dir(&fragments_dir).expect("Failed to create fragments dir");
+    fs::write(
+        fragments_dir.join("user.graphql"),
+        "#import \"./post.graphql\"\n\nfragment UserFields on User {\n  id\n  posts {\n    ...PostFields\n  }\n}\n",
+    )
+    .expect("Failed to write fragment");
+    fs::write(
+        fragments_dir.join("post.graphql"),
+        "#import \"./user.graphql\"\n\nfragment PostFields on Post {\n  id\n  title\n}\n",
+    )
+    .expect("Failed to write fragment");
+
+    let config = ProjectConfig {
+        schema: SchemaConfig::Path(base_path.join("schema.graphql").display().to_string()),
+        documents: Some(DocumentsConfig::Patterns(vec![
+            "operations/*.graphql".to_string()
+        ])),
+        include: None,
+        exclude: None,
+        extensions: None,
+    };
+    let project = GraphQLProject::new(config).with_base_dir(base_path.to_path_buf());
+    project.load_schema().await.expect("Failed to load schema");
+    let _ = project.load_documents();
+
+    let errors = |source: &str| {
+        project
+            .validate_document_source(source, "operations/user.graphql")
+            .into_iter()
+            .filter(|diagnostic| diagnostic.severity == graphql_project::Severity::Error)
+            .map(|diagnostic| diagnostic.message)
+            .collect::<Vec<_>>()
+    };
+
+    let query = "query GetUser {\n  user(id: \"1\") {\n    ...UserFields\n  }\n}\n";
+    let imported = format!("#import \"../fragments/user.graphql\"\n\n{query}");
+    assert!(
+        errors(&imported).is_empty(),
+        "Imported fragments should resolve: {:?}",
+        errors(&imported)
+    );
+
+    // Without the import, the fragment isn't in the project's documents
+    assert!(!errors(query).is_empty());
+}
+
+#[tokio::test]
+async fn test_schema_cache_hit_keeps_schema_file_locations() {
+    // Load the project the way a server restart does, reading the cache if it is there
+    async fn load(config: &ProjectConfig, base_path: &Path) -> GraphQLProject {
+        let project = GraphQLProject::new(config.clone())
+            .with_base_dir(base_path.to_path_buf())
+            .with_schema_cache(SchemaCache::new(base_path.join("cache")));
+        project.load_schema().await.expect("Failed to load schema");
+        project
+    }
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let base_path = temp_dir.path();
+    let schema_path = base_path.join("schema.graphql");
+    // Builds cleanly, so it is cached, but fails validation
+    fs::write(&schema_path, "type Query {\n  user: Missing\n}\n").expect("Failed to write schema");
+
+    let config = ProjectConfig {
+        schema: SchemaConfig::Path(schema_path.display().to_string()),
+        documents: None,
+        include: None,
+        exclude: None,
+        extensions: None,
+    };
+    let built = load(&config, base_path).await;
+    let cached = load(&config, base_path).await;
+    assert!(fs::read_dir(base_path.join("cache"))
+        .expect("Schema should be cached")
+        .next()
+        .is_some());
+
+    // The cached schema still reports errors in the schema file itself
+    let schema_file = schema_path.display().to_string();
+    assert_eq!(
+        cached.validate_schema_source(&schema_file).len(),
+        built.validate_schema_source(&schema_file).len()
+    );
+    assert!(!cached.validate_schema_source(&schema_file).is_empty());
+}
+
+#[tokio::test]
+async fn test_read_only_paths_are_excluded_from_rename() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let base_path = temp_dir.path();
+
+    fs::write(
+        base_path.join("schema.graphql"),
+        "type Query {\n  user: User\n}\n\ntype User {\n  id: ID!\n}\n",
+    )
+    .expect("Failed to write schema");
+    let fragment = "fragment UserFields on User {\n  id\n}\n";
+    let app_query = "query AppUser {\n  user {\n    ...UserFields\n  }\n}\n";
+    let vendor_query = "query VendorUser {\n  user {\n    ...UserFields\n  }\n}\n";
+    fs::create_dir(base_path.join("vendor")).expect("Failed to create vendor dir");
+    let files = [
+        (base_path.join("fragments.graphql"), fragment),
+        (base_path.join("app.graphql"), app_query),
+        (
+            base_path.join("vendor").join("queries.graphql"),
+            vendor_query,
+        ),
+    ];
+    for (path, content) in &files {
+        fs::write(path, content).expect("Failed to write document");
+    }
+
+    let config = ProjectConfig {
+        schema: SchemaConfig::Path(base_path.join("schema.graphql").display().to_string()),
+        documents: Some(DocumentsConfig::Patterns(vec!["**/*.graphql".to_string()])),
+        include: None,
+        exclude: None,
+        extensions: Some(
+            [(
+                "readOnlyPaths".to_string(),
+                serde_json::json!(["vendor/**"]),
+            )]
+            .into_iter()
+            .collect(),
+        ),
+    };
+    let project = GraphQLProject::new(config).with_base_dir(base_path.to_path_buf());
+    project.load_schema().await.expect("Failed to load schema");
+    project.load_documents().expect("Failed to load documents");
+
+    let vendor_file = &files[2].0;
+    assert!(project.is_read_only_file(vendor_file));
+    assert!(project.is_schema_file(vendor_file));
+    assert!(!project.is_document_file(vendor_file));
+    assert!(!project
+        .document_files()
+        .expect("Failed to find documents")
+        .contains(vendor_file));
+
+    // Open documents are keyed by URI, while the loaded documents are indexed by path
+    let all_documents: Vec<(String, String)> = files
+        .iter()
+        .map(|(path, content)| (format!("file://{}", path.display()), (*content).to_string()))
+        .collect();
+    let fragment_file = files[0].0.display().to_string();
+    let locations = project
+        .rename(
+            fragment,
+            graphql_project::Position {
+                line: 0,
+                character: 12,
+            },
+            "UserBits",
+            &all_documents,
+            Some(&fragment_file),
+        )
+        .expect("Rename should be valid")
+        .expect("Fragment should be renameable");
+
+    // One edit for the definition and one for the spread, with no file edited twice
+    assert_eq!(locations.len(), 2, "{locations:?}");
+    let mut edited_files: Vec<_> = locations
+        .iter()
+        .map(|location| location.file_path.trim_start_matches("file://"))
+        .collect();
+    edited_files.sort_unstable();
+    let app_file = files[1].0.display().to_string();
+    assert_eq!(
+        edited_files,
+        vec![app_file.as_str(), fragment_file.as_str()]
+    );
+}
+
+#[tokio::test]
+async fn test_nullable_variable_for_non_null_argument_is_reported_once() {
+    let (_temp_dir, project) = create_project_with_schema(
+        "type Query {\n  user(id: ID!): User\n}\n\ntype User {\n  id: ID!\n}\n",
+    )
+    .await;
+
+    let document = "query Lookup($id: ID) {\n  user(id: $id) {\n    id\n  }\n}\n";
+    let diagnostics = project.validate_document_source(document, "query.graphql");
+    assert_eq!(diagnostics.len(), 1, "got: {diagnostics:?}");
+    assert_eq!(diagnostics[0].range.start.line, 1);
+
+    // A default value makes the nullable variable safe to pass
+    let document = "query Lookup($id: ID = \"1\") {\n  user(id: $id) {\n    id\n  }\n}\n";
+    let diagnostics = project.validate_document_source(document, "query.graphql");
+    assert!(diagnostics.is_empty(), "got: {diagnostics:?}");
+}
+
+#[tokio::test]
+async fn test_input_literal_missing_required_field_is_reported_once() {
+    let (_temp_dir, project) = create_project_with_schema(
+        r#"type Query {
+  user(id: ID!): User
+}
+
+type Mutation {
+  createUser(input: CreateUserInput!): User
+}
+
+type User {
+  id: ID!
+}
+
+input CreateUserInput {
+  name: String!
+  role: String! = "USER"
+  nickname: String
+}
+"#,
+    )
+    .await;
+
+    let document =
+        "mutation CreateUser {\n  createUser(input: { nickname: \"Al\" }) {\n    id\n  }\n}\n";
+    let diagnostics = project.validate_document_source(document, "mutation.graphql");
+    assert_eq!(diagnostics.len(), 1, "got: {diagnostics:?}");
+    assert_eq!(diagnostics[0].range.start.line, 1);
+    assert!(diagnostics[0].message.contains("name"));
+}
+
+#[tokio::test]
+async fn test_mixed_type_list_item_is_reported_once() {
+    let (_temp_dir, project) =
+        create_project_with_schema("type Query {\n  users(ids: [Int!]): [String]\n}\n").await;
+
+    let document = "query Users {\n  users(ids: [1, \"two\", 3])\n}\n";
+    let diagnostics = project.validate_document_source(document, "query.graphql");
+    assert_eq!(diagnostics.len(), 1, "got: {diagnostics:?}");
+    assert_eq!(diagnostics[0].range.start.line, 1);
+}
+
+#[tokio::test]
+async fn test_union_field_selection_is_reported_once() {
+    let (_temp_dir, project) = create_project_with_schema(
+        "type Query {\n  search: [SearchResult!]!\n}\n\nunion SearchResult = User | Post\n\ntype User {\n  id: ID!\n  name: String!\n}\n\ntype Post {\n  id: ID!\n}\n",
+    )
+    .await;
+
+    let document = "query Search {\n  search {\n    __typename\n    name\n  }\n}\n";
+    let diagnostics = project.validate_document_source(document, "query.graphql");
+    assert_eq!(diagnostics.len(), 1, "got: {diagnostics:?}");
+    assert_eq!(
+        diagnostics[0].code.as_deref(),
+        Some("union-field-selection")
+    );
+    assert_eq!(diagnostics[0].range.start.line, 3);
+}
+
+#[tokio::test]
+async fn test_variable_used_at_wrong_type_is_reported_once() {
+    let (_temp_dir, project) = create_project_with_schema(
+        "type Query {\n  user(id: ID!): User\n  search(term: String!): [User!]!\n}\n\ntype User {\n  id: ID!\n}\n",
+    )
+    .await;
+
+    let document =
+        "query Lookup($id: ID!) {\n  user(id: $id) {\n    id\n  }\n  search(term: $id) {\n    id\n  }\n}\n";
+    let diagnostics = project.validate_document_source(document, "query.graphql");
+    assert_eq!(diagnostics.len(), 1, "got: {diagnostics:?}");
+    assert_eq!(diagnostics[0].range.start.line, 4);
+}
+
+#[tokio::test]
+async fn test_undeclared_fragment_variable_is_reported_once() {
+    let (_temp_dir, project) = create_project_with_schema(
+        "type Query {\n  users(limit: Int): [User!]!\n}\n\ntype User {\n  id: ID!\n}\n",
+    )
+    .await;
+
+    let document = "query Users {\n  ...UserList\n}\n\nfragment UserList on Query {\n  users(limit: $limit) {\n    id\n  }\n}\n";
+    let diagnostics = project.validate_document_source(document, "query.graphql");
+    let variable_errors: Vec<_> = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.message.contains("$limit"))
+        .collect();
+    assert_eq!(variable_errors.len(), 1, "got: {diagnostics:?}");
+    assert_eq!(
+        variable_errors[0].code.as_deref(),
+        Some("undeclared-fragment-variable")
+    );
+    assert_eq!(variable_errors[0].range.start.line, 1);
+}
+
+#[tokio::test]
+async fn test_conditional_subscription_root_field_is_reported_once() {
+    let (_temp_dir, project) = create_project_with_schema(
+        "type Query {\n  ping: String\n}\n\ntype Subscription {\n  messageAdded: Message!\n}\n\ntype Message {\n  id: ID!\n}\n",
+    )
+    .await;
+
+    let document = "subscription OnMessage($withMessages: Boolean!) {\n  messageAdded @include(if: $withMessages) {\n    id\n  }\n}\n";
+    let diagnostics = project.validate_document_source(document, "subscription.graphql");
+    assert_eq!(diagnostics.len(), 1, "got: {diagnostics:?}");
+    assert_eq!(
+        diagnostics[0].code.as_deref(),
+        Some("subscription-root-directive")
+    );
+    assert_eq!(diagnostics[0].range.start.line, 1);
+}
+
+#[tokio::test]
+async fn test_config_reload_reextracts_with_new_tag_identifiers() {
+    // Load the project the way the language server and `validate --watch` (re)load a config
+    async fn load(config_path: &Path, base_path: &Path) -> GraphQLProject {
+        let config = graphql_config::load_config(config_path).expect("Failed to load config");
+        let (_, project) = GraphQLProject::from_config_with_base(&config, base_path)
+            .expect("Failed to create projects")
+            .remove(0);
+        project.load_schema().await.expect("Failed to load schema");
+        project.load_documents().expect("Failed to load documents");
+        project
+    }
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let base_path = temp_dir.path();
+
+    let schema_path = base_path.join("schema.graphql");
+    fs::write(
+        &schema_path,
+        "type Query {\n  user: User\n}\n\ntype User {\n  id: ID!\n}\n",
+    )
+    .expect("Failed to write schema");
+    fs::write(
+        base_path.join("component.ts"),
+        "import { query } from './client';\n\nconst GET_USER = query`\n  query GetUser {\n    user {\n      id\n    }\n  }\n`;\n",
+    )
+    .expect("Failed to write component");
+
+    let config_path = base_path.join("graphql.config.yaml");
+    let write_config = |tags: &str| {
+        fs::write(
+            &config_path,
+            format!(
+                "schema: {}\ndocuments: \"**/*.ts\"\nextensions:\n  extractConfig:\n    tagIdentifiers: [{tags}]\n    modules: [\"./client\"]\n",
+                schema_path.display()
+            ),
+        )
+        .expect("Failed to write config");
+    };
+
+    write_config("gql");
+    let project = load(&config_path, base_path).await;
+    let index = project.get_document_index();
+    assert!(index.extracted_blocks.is_empty());
+    assert!(index.operations.is_empty());
+
+    // After the config change, the template tagged with the new identifier is extracted
+    write_config("gql, query");
+    let project = load(&config_path, base_path).await;
+    let index = project.get_document_index();
+    let blocks: Vec<_> = index.extracted_blocks.values().flatten().collect();
+    assert_eq!(blocks.len(), 1);
+    assert!(blocks[0].content.contains("query GetUser"));
+    assert!(index.operations.contains_key("GetUser"));
+}
@@ -49,9 +49,10 @@ export async function activate(context: ExtensionContext) {
         { scheme: "file", language: "typescriptreact" },
         { scheme: "file", language: "javascript" },
         { scheme: "file", language: "javascriptreact" },
+        { scheme: "file", language: "svelte" },
       ],
       synchronize: {
-        fileEvents: workspace.createFileSystemWatcher("**/*.{graphql,gql,ts,tsx,js,jsx}"),
+        fileEvents: workspace.createFileSystemWatcher("**/*.{graphql,gql,ts,tsx,js,jsx,svelte}"),
       },
       outputChannel: outputChannel,
     };